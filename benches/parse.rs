@@ -0,0 +1,61 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dted2::parsers::{dted_file_parser, to_i16, to_i16_slice};
+use dted2::{DTEDData, ElevationSource};
+
+fn parse(c: &mut Criterion) {
+    let content = std::fs::read("tests/test_data.dt2").unwrap();
+    let mut group = c.benchmark_group("parse");
+    group.sample_size(20);
+    group.bench_function("dted_file_parser (level 2)", |b| {
+        b.iter(|| dted_file_parser(black_box(&content)).unwrap())
+    });
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    // the posts of a level 2 record, as converted by the file parser
+    let bytes: Vec<u8> = (0..3601_u32)
+        .flat_map(|i| ((i * 7919) as u16).to_be_bytes())
+        .collect();
+    let mut out = vec![0_i16; bytes.len() / 2];
+    let mut group = c.benchmark_group("decode");
+    group.bench_function("to_i16 (level 2 record)", |b| {
+        b.iter(|| {
+            for (elev, word) in out.iter_mut().zip(black_box(&bytes).chunks_exact(2)) {
+                *elev = to_i16(u16::from_be_bytes([word[0], word[1]]));
+            }
+        })
+    });
+    group.bench_function("to_i16_slice (level 2 record)", |b| {
+        b.iter(|| to_i16_slice(black_box(&bytes), &mut out))
+    });
+    group.finish();
+}
+
+fn query(c: &mut Criterion) {
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    // a 100x100 grid of queries spread over the whole cell
+    let points: Vec<(f64, f64)> = (0..100)
+        .flat_map(|i| (0..100).map(move |j| (42.0 + i as f64 / 100.0, 15.0 + j as f64 / 100.0)))
+        .collect();
+    c.bench_function("elevation_at (10k points)", |b| {
+        b.iter(|| {
+            points
+                .iter()
+                .filter_map(|(lat, lon)| data.elevation_at(*lat, *lon))
+                .map(i64::from)
+                .sum::<i64>()
+        })
+    });
+    c.bench_function("elevation_at_interpolated (10k points)", |b| {
+        b.iter(|| {
+            points
+                .iter()
+                .filter_map(|(lat, lon)| data.elevation_at_interpolated(*lat, *lon))
+                .sum::<f64>()
+        })
+    });
+}
+
+criterion_group!(benches, parse, decode, query);
+criterion_main!(benches);
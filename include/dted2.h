@@ -0,0 +1,160 @@
+#ifndef DTED2_H
+#define DTED2_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// DTED Data
+//
+// This is the main entry point for reading DTED files.
+// Usage consists of either [DTEDData::read], [DTEDData::read_lazy] or [DTEDData::read_header]
+//
+// # Fields
+//
+// * `metadata` - [DTEDMetadata]
+// * `min` - minimum lat/lon
+// * `max` - maximum lat/lon
+// * `data` - data
+// * `coverage` - longitude lines with a data record in the file, see [Coverage]
+typedef struct DTEDData DTEDData;
+
+// Metadata of a DTED file, for C
+//
+// # Fields
+//
+// * `origin_lat` - latitude of the south west corner, in degrees
+// * `origin_lon` - longitude of the south west corner, in degrees
+// * `interval_lat` - interval between the posts along a longitude line, in degrees
+// * `interval_lon` - interval between the longitude lines, in degrees
+// * `lat_count` - number of posts per longitude line
+// * `lon_count` - number of longitude lines
+// * `level` - DTED level (0, 1 or 2), or -1 if it can not be inferred
+typedef struct CDTEDMetadata {
+  double origin_lat;
+  double origin_lon;
+  double interval_lat;
+  double interval_lon;
+  uint16_t lat_count;
+  uint16_t lon_count;
+  int8_t level;
+} CDTEDMetadata;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Read a DTED file
+//
+// # Arguments
+//
+// * `path` - path of the file, as a NUL-terminated UTF-8 string
+//
+// # Returns
+//
+// * Handle to the DTED data, to be freed with [dted2_free], or NULL on error
+//
+// # Safety
+//
+// `path` must be NULL or a valid NUL-terminated string
+struct DTEDData *dted2_open(const char *path);
+
+// Parse DTED data from the bytes of a file
+//
+// # Arguments
+//
+// * `bytes` - the bytes of the file
+// * `len` - number of bytes
+//
+// # Returns
+//
+// * Handle to the DTED data, to be freed with [dted2_free], or NULL on error
+//
+// # Safety
+//
+// `bytes` must be NULL or point to `len` readable bytes
+struct DTEDData *dted2_open_bytes(const uint8_t *bytes, size_t len);
+
+// Free DTED data
+//
+// # Arguments
+//
+// * `data` - handle to the DTED data, or NULL (doing nothing)
+//
+// # Safety
+//
+// `data` must be NULL or a handle returned by [dted2_open] (or
+// [dted2_open_bytes]) not freed yet, and not used once freed
+void dted2_free(struct DTEDData *data);
+
+// Get the message of the last error on the current thread
+//
+// # Returns
+//
+// * NUL-terminated message, valid until the next error on the thread, or
+//   NULL if no error occurred
+const char *dted2_last_error(void);
+
+// Get the metadata of DTED data
+//
+// # Arguments
+//
+// * `data` - handle to the DTED data
+// * `metadata` - [CDTEDMetadata] to fill
+//
+// # Returns
+//
+// * 1 if filled, 0 if either pointer is NULL
+//
+// # Safety
+//
+// `data` must be NULL or a valid handle, and `metadata` NULL or writable
+int dted2_metadata(const struct DTEDData *data, struct CDTEDMetadata *metadata);
+
+// Get the elevation at a lat/lon, of the nearest post, see
+// [ElevationSource::elevation_at]
+//
+// # Arguments
+//
+// * `data` - handle to the DTED data
+// * `lat` - latitude, in degrees
+// * `lon` - longitude, in degrees
+// * `elevation` - elevation to set, in meters
+//
+// # Returns
+//
+// * 1 if set, 0 if out of bounds, void or either pointer is NULL
+//
+// # Safety
+//
+// `data` must be NULL or a valid handle, and `elevation` NULL or writable
+int dted2_elevation_at(const struct DTEDData *data, double lat, double lon, int16_t *elevation);
+
+// Get the elevation at a lat/lon, bilinearly interpolated between the
+// surrounding posts, see [ElevationSource::elevation_at_interpolated]
+//
+// # Arguments
+//
+// * `data` - handle to the DTED data
+// * `lat` - latitude, in degrees
+// * `lon` - longitude, in degrees
+// * `elevation` - elevation to set, in meters
+//
+// # Returns
+//
+// * 1 if set, 0 if out of bounds, void or either pointer is NULL
+//
+// # Safety
+//
+// `data` must be NULL or a valid handle, and `elevation` NULL or writable
+int dted2_elevation_at_interpolated(const struct DTEDData *data,
+                                    double lat,
+                                    double lon,
+                                    double *elevation);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DTED2_H */
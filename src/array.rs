@@ -0,0 +1,121 @@
+//! Contains the [ndarray] views of the elevation grid (requires the `ndarray` feature).
+//!
+//! The arrays are indexed as `[lat_idx, lon_idx]`, from south to north and
+//! from west to east, the views borrowing the column-major [RawDTEDRecords]
+//! without copying.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use ndarray::{Array2, ArrayView2, ArrayViewMut2, ShapeBuilder};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDData, RawDTEDRecords, DTED_VOID_ELEVATION};
+use crate::raster::Raster;
+
+impl RawDTEDRecords {
+    /// View the elevations as a 2D array, indexed as `[lat_idx, lon_idx]`
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevations (in meters), void posts included
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let view = data.data.as_array();
+    /// assert_eq!(view.dim(), (121, 121));
+    /// assert_eq!(Some(view[[5, 3]]), data.data.get(5, 3));
+    /// ```
+    pub fn as_array(&self) -> ArrayView2<'_, i16> {
+        ArrayView2::from_shape(self.shape().f(), &self.elevations)
+            .expect("elevations hold every record")
+    }
+
+    /// View the elevations as a mutable 2D array, indexed as `[lat_idx, lon_idx]`
+    ///
+    /// The checksums of the records are not updated,
+    /// see [RawDTEDRecords::recompute_checksums]
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevations (in meters), void posts included
+    pub fn as_array_mut(&mut self) -> ArrayViewMut2<'_, i16> {
+        let shape = self.shape();
+        ArrayViewMut2::from_shape(shape.f(), &mut self.elevations)
+            .expect("elevations hold every record")
+    }
+
+    /// Shape of the elevation grid, as `(lat, lon)` counts
+    fn shape(&self) -> (usize, usize) {
+        (self.line_len, self.len())
+    }
+}
+
+impl DTEDData {
+    /// Get the elevations as a 2D array of floats, indexed as `[lat_idx, lon_idx]`
+    ///
+    /// # Returns
+    ///
+    /// * Elevations (in meters), with void posts as NaN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let array = data.to_array();
+    /// assert_eq!(array.dim(), (121, 121));
+    /// assert_eq!(array[[5, 3]], data.data.get(5, 3).unwrap() as f64);
+    /// ```
+    pub fn to_array(&self) -> Array2<f64> {
+        self.data.as_array().mapv(|elev| match elev {
+            DTED_VOID_ELEVATION => f64::NAN,
+            elev => elev as f64,
+        })
+    }
+
+    /// Get the mask of the void posts as a 2D array, indexed as `[lat_idx, lon_idx]`
+    ///
+    /// # Returns
+    ///
+    /// * Whether each post is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let mask = data.void_mask_array();
+    /// assert_eq!(mask.dim(), (121, 121));
+    /// assert!(!mask[[5, 3]]);
+    /// ```
+    pub fn void_mask_array(&self) -> Array2<bool> {
+        self.data
+            .as_array()
+            .mapv(|elev| elev == DTED_VOID_ELEVATION)
+    }
+}
+
+impl<T> Raster<T> {
+    /// View the values as a 2D array, indexed as `[lat_idx, lon_idx]`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::terrain::SlopeUnits;
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let slope = data.slope(SlopeUnits::Degrees);
+    /// assert_eq!(slope.as_array().dim(), (121, 121));
+    /// assert_eq!(Some(&slope.as_array()[[5, 3]]), slope.get(5, 3));
+    /// ```
+    pub fn as_array(&self) -> ArrayView2<'_, T> {
+        ArrayView2::from_shape((self.count.lat, self.count.lon).f(), &self.values)
+            .expect("values hold every post")
+    }
+}
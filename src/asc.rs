@@ -0,0 +1,232 @@
+//! Contains the import of ESRI ASCII grids (`.asc`) into a [Raster], to be
+//! resampled into DTED cells with [crate::DTEDBuilder::resample].
+//!
+//! The grid starts with a header of `ncols`, `nrows`, `xllcorner` (or
+//! `xllcenter`), `yllcorner` (or `yllcenter`), `cellsize` (or `dx` and `dy`)
+//! and an optional `nodata_value`, followed by the values of the rows from
+//! north to south, each from west to east. Coordinates are expected in
+//! degrees of longitude (x) and latitude (y). Rasters, such as the elevations
+//! of a cell, are written back the same way.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::collections::HashMap;
+use std::io::{BufWriter, Read, Write};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::primitives::AxisElement;
+use crate::raster::Raster;
+use crate::Error;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// `nodata_value` of the written grids
+pub const ASC_NODATA_VALUE: f64 = -9999.0;
+
+/// Read an ESRI ASCII grid
+///
+/// # Arguments
+///
+/// * `path` - path of the `.asc` file
+///
+/// # Returns
+///
+/// * [Raster] of the values (None where `nodata_value`), or an error if the
+///   grid is malformed
+pub fn read(path: &str) -> Result<Raster<Option<f64>>, Error> {
+    read_from(std::fs::File::open(path)?)
+}
+
+/// Read an ESRI ASCII grid from a reader
+///
+/// # Arguments
+///
+/// * `reader` - reader of an `.asc` file
+///
+/// # Returns
+///
+/// * [Raster] of the values (None where `nodata_value`), or an error if the
+///   grid is malformed
+///
+/// # Examples
+///
+/// ```
+/// use dted2::asc;
+/// use dted2::interpolation::Interpolation;
+/// use dted2::primitives::{Angle, AxisElement};
+/// use dted2::{DTEDBuilder, DTEDLevel};
+///
+/// let grid = "ncols 3\nnrows 2\nxllcorner 15.0\nyllcorner 42.0\ncellsize 0.5\n\
+///             NODATA_value -9999\n1 2 -9999\n4 5 6\n";
+/// let raster = asc::read_from(grid.as_bytes()).unwrap();
+/// assert_eq!(raster.origin, AxisElement::new(42.25, 15.25));
+/// assert_eq!(raster.get(0, 2), Some(&Some(6.0)));
+/// assert_eq!(raster.get(1, 2), Some(&None));
+///
+/// // into a Level 0 cell, resampled at its posts
+/// let origin = AxisElement::new(Angle::new(42, 0, 0.0, false), Angle::new(15, 0, 0.0, false));
+/// let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+///     .resample(&raster, Interpolation::Bilinear)
+///     .build()
+///     .unwrap();
+/// assert_eq!(file.data.get(60, 60), Some(3));
+/// ```
+pub fn read_from<R: Read>(mut reader: R) -> Result<Raster<Option<f64>>, Error> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let mut tokens = content.split_ascii_whitespace().peekable();
+    // --------------------------------------------------
+    // header, keys followed by their value
+    // --------------------------------------------------
+    let mut header = HashMap::new();
+    while let Some(key) = tokens.next_if(|token| token.starts_with(char::is_alphabetic)) {
+        let value = tokens
+            .next()
+            .and_then(|value| value.parse::<f64>().ok())
+            .ok_or_else(|| invalid(format!("missing or invalid value of {}", key)))?;
+        header.insert(key.to_ascii_lowercase(), value);
+    }
+    let field = |key: &str| header.get(key).copied();
+    let required = |key: &str| field(key).ok_or_else(|| invalid(format!("missing {}", key)));
+    let (ncols, nrows) = (required("ncols")?, required("nrows")?);
+    if ncols < 1.0 || nrows < 1.0 || ncols.fract() != 0.0 || nrows.fract() != 0.0 {
+        return Err(invalid(format!("invalid dimensions {}x{}", ncols, nrows)));
+    }
+    let (ncols, nrows) = (ncols as usize, nrows as usize);
+    let (dx, dy) = match (field("cellsize"), field("dx"), field("dy")) {
+        (Some(size), _, _) => (size, size),
+        (None, Some(dx), Some(dy)) => (dx, dy),
+        _ => return Err(invalid("missing cellsize".to_string())),
+    };
+    if dx <= 0.0 || dy <= 0.0 {
+        return Err(invalid(format!("invalid cell size {}x{}", dx, dy)));
+    }
+    // the corners are half a cell from the centers
+    let position = |center: &str, corner: &str, size: f64| match (field(center), field(corner)) {
+        (Some(center), _) => Ok(center),
+        (None, Some(corner)) => Ok(corner + size / 2.0),
+        (None, None) => Err(invalid(format!("missing {} or {}", corner, center))),
+    };
+    let origin = AxisElement::new(
+        position("yllcenter", "yllcorner", dy)?,
+        position("xllcenter", "xllcorner", dx)?,
+    );
+    let no_data = field("nodata_value");
+    // --------------------------------------------------
+    // values, rows from north to south
+    // --------------------------------------------------
+    let values = tokens
+        .map(|token| {
+            token
+                .parse::<f64>()
+                .map_err(|_| invalid(format!("invalid value {}", token)))
+        })
+        .collect::<Result<Vec<f64>, Error>>()?;
+    if values.len() != ncols * nrows {
+        return Err(invalid(format!(
+            "expected {} values, got {}",
+            ncols * nrows,
+            values.len()
+        )));
+    }
+    Ok(Raster::from_fn(
+        origin,
+        AxisElement::new(dy, dx),
+        AxisElement::new(nrows, ncols),
+        |lat_idx, lon_idx| {
+            let value = values[(nrows - 1 - lat_idx) * ncols + lon_idx];
+            match no_data == Some(value) {
+                true => None,
+                false => Some(value),
+            }
+        },
+    ))
+}
+
+/// Write a raster as an ESRI ASCII grid, with the centers of the posts
+/// (`xllcenter` and `yllcenter`)
+///
+/// # Arguments
+///
+/// * `writer` - writer of the `.asc` file
+/// * `raster` - [Raster] of the values, None written as [ASC_NODATA_VALUE]
+///
+/// # Returns
+///
+/// * Nothing, or an [Error::Io] if the grid could not be written
+///
+/// # Examples
+///
+/// ```
+/// use dted2::asc;
+/// use dted2::primitives::AxisElement;
+/// use dted2::raster::Raster;
+///
+/// let raster = Raster::from_fn(
+///     AxisElement::new(42.25, 15.25),
+///     AxisElement::new(0.5, 0.5),
+///     AxisElement::new(2, 3),
+///     |lat_idx, lon_idx| match (lat_idx, lon_idx) {
+///         (1, 2) => None,
+///         _ => Some((lat_idx * 3 + lon_idx) as f64),
+///     },
+/// );
+/// let mut grid = Vec::new();
+/// asc::write(&mut grid, &raster).unwrap();
+/// assert!(String::from_utf8(grid.clone()).unwrap().ends_with("3 4 -9999\n0 1 2\n"));
+/// assert_eq!(asc::read_from(&grid[..]).unwrap(), raster);
+/// ```
+pub fn write<W: Write>(writer: &mut W, raster: &Raster<Option<f64>>) -> Result<(), Error> {
+    let mut writer = BufWriter::new(writer);
+    writeln!(writer, "ncols {}", raster.count.lon)?;
+    writeln!(writer, "nrows {}", raster.count.lat)?;
+    writeln!(writer, "xllcenter {}", raster.origin.lon)?;
+    writeln!(writer, "yllcenter {}", raster.origin.lat)?;
+    match raster.interval.lat == raster.interval.lon {
+        true => writeln!(writer, "cellsize {}", raster.interval.lat)?,
+        false => {
+            writeln!(writer, "dx {}", raster.interval.lon)?;
+            writeln!(writer, "dy {}", raster.interval.lat)?;
+        }
+    }
+    writeln!(writer, "nodata_value {}", ASC_NODATA_VALUE)?;
+    for lat_idx in (0..raster.count.lat).rev() {
+        let row: Vec<String> = (0..raster.count.lon)
+            .map(|lon_idx| match raster.get(lat_idx, lon_idx) {
+                Some(Some(value)) => value.to_string(),
+                _ => ASC_NODATA_VALUE.to_string(),
+            })
+            .collect();
+        writeln!(writer, "{}", row.join(" "))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Malformed ESRI ASCII grid
+fn invalid(message: String) -> Error {
+    Error::InvalidInput(format!("ASC: {}", message))
+}
+
+impl DTEDData {
+    /// Write the elevations as an ESRI ASCII grid, see [write]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the `.asc` file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an [Error::Io] if the file could not be written
+    pub fn write_asc(&self, path: &str) -> Result<(), Error> {
+        write(
+            &mut std::fs::File::create(path)?,
+            &Raster::from_source(self),
+        )
+    }
+}
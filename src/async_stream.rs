@@ -0,0 +1,201 @@
+//! Contains [AsyncDTEDStreamReader], for incrementally parsing DTED from any
+//! [tokio::io::AsyncRead] (requires the `async` feature).
+//!
+//! This is the async counterpart of [crate::stream::DTEDStreamReader].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers;
+use crate::stream::{ParseBuffer, Parsed};
+use crate::Error as DTEDError;
+
+/// Async streaming DTED reader
+///
+/// The header records are parsed when creating the reader, with the data
+/// records then parsed (and their checksums validated) one at a time with
+/// [AsyncDTEDStreamReader::next_record].
+///
+/// # Examples
+///
+/// ```
+/// use dted2::async_stream::AsyncDTEDStreamReader;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let file = tokio::fs::File::open("tests/test_data.dt2").await.unwrap();
+/// let mut reader = AsyncDTEDStreamReader::new(file).await.unwrap();
+/// assert_eq!(reader.metadata().count.lon, 3601);
+/// let first = reader.next_record().await.unwrap().unwrap();
+/// assert_eq!(first.elevations.len(), 3601);
+/// # });
+/// ```
+pub struct AsyncDTEDStreamReader<R: AsyncRead + Unpin> {
+    stream: AsyncStreamBuffer<R>,
+    metadata: DTEDMetadata,
+    remaining: usize,
+}
+impl<R: AsyncRead + Unpin> AsyncDTEDStreamReader<R> {
+    /// Create an async streaming reader, parsing the header records from the stream
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - stream to read the DTED from
+    ///
+    /// # Returns
+    ///
+    /// * [AsyncDTEDStreamReader]: streaming reader, positioned at the first data record
+    pub async fn new(reader: R) -> Result<AsyncDTEDStreamReader<R>, DTEDError> {
+        let mut stream = AsyncStreamBuffer {
+            reader,
+            buffer: ParseBuffer::default(),
+        };
+        let (header, dsi_record, acc_record) = stream
+            .parse(parsers::fixed_length(
+                DTED_DATA_OFFSET,
+                parsers::dted_headers_parser,
+            ))
+            .await?;
+        stream.buffer.set_lat_count(header.count.lat);
+        Ok(AsyncDTEDStreamReader {
+            stream,
+            metadata: DTEDMetadata::from_records(&header, dsi_record, acc_record, ""),
+            remaining: header.count.lon as usize,
+        })
+    }
+
+    /// Get the [DTEDMetadata] parsed from the header records
+    ///
+    /// The filename is empty, as the DTED is read from a stream
+    pub fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    /// Parse the next data record
+    ///
+    /// # Returns
+    ///
+    /// * The next data record, or None once all of them were parsed
+    ///   (or after the first error)
+    pub async fn next_record(&mut self) -> Option<Result<RawDTEDRecord, DTEDError>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let lat_count = self.metadata.count.lat;
+        let record = self
+            .stream
+            .parse(parsers::fixed_length(
+                dted_record_length(lat_count),
+                |input| parsers::parse_dted_record(input, lat_count as usize),
+            ))
+            .await;
+        let index = self.metadata.count.lon as usize - self.remaining;
+        let record = record.and_then(|record| match record.compute_checksum() {
+            computed if computed == record.checksum => Ok(record),
+            computed => Err(DTEDError::BadChecksum {
+                record: index,
+                stored: record.checksum,
+                computed,
+            }),
+        });
+        // stop at the first error, as when reading the whole file
+        self.remaining = match record.is_ok() {
+            true => self.remaining - 1,
+            false => 0,
+        };
+        Some(record)
+    }
+
+    /// Read all of the (remaining) data records into [DTEDData]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data
+    pub async fn read_data(mut self) -> Result<DTEDData, DTEDError> {
+        let mut data =
+            RawDTEDRecords::with_capacity(self.metadata.count.lat as usize, self.remaining);
+        while let Some(record) = self.next_record().await {
+            data.push(record?)?;
+        }
+        Ok(DTEDData::new(self.metadata, data))
+    }
+}
+
+/// Buffer over an async stream, holding the bytes read but not parsed yet
+///
+/// # Fields
+///
+/// * `reader` - stream
+/// * `buffer` - bytes read from the stream
+struct AsyncStreamBuffer<R: AsyncRead + Unpin> {
+    reader: R,
+    buffer: ParseBuffer,
+}
+impl<R: AsyncRead + Unpin> AsyncStreamBuffer<R> {
+    /// Parse the next section of the stream, reading more of it until
+    /// the parser no longer returns [nom::Err::Incomplete]
+    async fn parse<O, F>(&mut self, parser: F) -> Result<O, DTEDError>
+    where
+        F: Fn(&[u8]) -> nom::IResult<&[u8], O>,
+    {
+        loop {
+            match self.buffer.try_parse(&parser)? {
+                Parsed::Done(output) => return Ok(output),
+                Parsed::Needs(needed) => {
+                    let required = self.buffer.reserve(needed);
+                    while !self.buffer.is_filled(required) {
+                        let n = self.reader.read(self.buffer.spare()).await?;
+                        self.buffer.advance(n);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl DTEDData {
+    /// Read a DTED file asynchronously
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let data = DTEDData::read_async("tests/test_data.dt2").await.unwrap();
+    /// assert_eq!(data.metadata.filename, "tests/test_data.dt2");
+    /// # });
+    /// ```
+    pub async fn read_async(path: &str) -> Result<DTEDData, DTEDError> {
+        let file = tokio::fs::File::open(path).await?;
+        let mut data = DTEDData::read_from_async(file).await?;
+        data.metadata.filename = path.to_string();
+        Ok(data)
+    }
+
+    /// Read a DTED file from an async stream, see [AsyncDTEDStreamReader]
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - stream to read the DTED from
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, with an empty filename
+    pub async fn read_from_async<R: AsyncRead + Unpin>(reader: R) -> Result<DTEDData, DTEDError> {
+        AsyncDTEDStreamReader::new(reader).await?.read_data().await
+    }
+}
@@ -0,0 +1,152 @@
+//! Contains the `convert` subcommand, translating between DTED and the other
+//! supported formats, chosen by the extensions of the files:
+//!
+//! * `.dt0`, `.dt1`, `.dt2` - DTED, in and out
+//! * `.hgt` - SRTM HGT, in and out
+//! * `.asc` - ESRI ASCII grid, in and out
+//! * `.tif`, `.tiff` - GeoTIFF, in and out
+//! * `.png` - 16-bit grayscale heightmap, out only (not georeferenced)
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{Arg, ArgMatches, Command};
+use std::fs::File;
+use std::path::Path;
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::primitives::{Angle, AxisElement};
+use dted2::raster::Raster;
+use dted2::{asc, geotiff, hgt, DTEDBuilder, DTEDData, DTEDError, DTEDLevel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Format of a file, from its extension
+enum Format {
+    Dted(DTEDLevel),
+    Hgt,
+    Asc,
+    GeoTiff,
+    Png,
+}
+
+/// Arguments of the subcommand
+pub fn command() -> Command {
+    Command::new("convert")
+        .about("Convert between DTED, HGT, ESRI ASCII grids, GeoTIFF and PNG heightmaps")
+        .arg(
+            Arg::new("input")
+                .help("Input file (.dt0/.dt1/.dt2, .hgt, .asc, .tif/.tiff)")
+                .required(true),
+        )
+        .arg(
+            Arg::new("output")
+                .help("Output file (.dt0/.dt1/.dt2, .hgt, .asc, .tif/.tiff, .png)")
+                .required(true),
+        )
+        .arg(
+            Arg::new("level")
+                .long("level")
+                .help("Level of the output DTED cell (default: from the extension, 2 for HGT)")
+                .value_parser(["0", "1", "2"]),
+        )
+        .arg(
+            Arg::new("origin")
+                .long("origin")
+                .value_name("LAT,LON")
+                .allow_hyphen_values(true)
+                .help("South west corner of the output DTED or HGT cell (default: the cell of the input)"),
+        )
+        .arg(crate::interpolation_arg(
+            "Resampling into the posts of DTED and HGT cells",
+        ))
+}
+
+/// Convert the input into the output
+///
+/// # Returns
+///
+/// * Success, or an error if a file could not be read, converted or written
+pub fn run(matches: &ArgMatches) -> Result<ExitCode, DTEDError> {
+    let input = matches.get_one::<String>("input").unwrap();
+    let output = matches.get_one::<String>("output").unwrap();
+    let level = matches
+        .get_one::<String>("level")
+        .map(|level| level_of(level.as_bytes()[0]));
+    let interpolation = crate::interpolation_of(matches);
+    // --------------------------------------------------
+    // input, as a raster of elevations
+    // --------------------------------------------------
+    let raster = match format_of(input)? {
+        Format::Dted(_) => Raster::from_source(&DTEDData::read(input)?),
+        Format::Hgt => hgt::read(input)?,
+        Format::Asc => asc::read(input)?,
+        Format::GeoTiff => geotiff::read(input)?,
+        Format::Png => {
+            return Err(DTEDError::InvalidInput(format!(
+                "PNG heightmaps are not georeferenced, can not convert {}",
+                input
+            )))
+        }
+    };
+    let origin = match matches.get_one::<String>("origin") {
+        Some(origin) => crate::parse_lat_lon(origin)?,
+        None => AxisElement::new(raster.origin.lat.floor(), raster.origin.lon.floor()),
+    };
+    // into a cell of a level
+    let cell = |level: DTEDLevel| {
+        let origin = AxisElement::new(
+            Angle::from_secs(origin.lat * 3600.0),
+            Angle::from_secs(origin.lon * 3600.0),
+        );
+        DTEDBuilder::for_level(origin, level)
+            .resample(&raster, interpolation)
+            .build()
+    };
+    // --------------------------------------------------
+    // output
+    // --------------------------------------------------
+    match format_of(output)? {
+        Format::Dted(extension) => {
+            cell(level.unwrap_or(extension))?.write(&mut File::create(output)?)?
+        }
+        Format::Hgt => {
+            let file = cell(level.unwrap_or(DTEDLevel::Level2))?;
+            DTEDData::read_from(&file.to_bytes()?[..])?.write_hgt(output)?
+        }
+        Format::Asc => asc::write(&mut File::create(output)?, &raster)?,
+        Format::GeoTiff => geotiff::write(&mut File::create(output)?, &raster)?,
+        Format::Png => std::fs::write(output, raster.to_heightmap_png()?)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Format of a file, from its extension
+fn format_of(path: &str) -> Result<Format, DTEDError> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("dt0" | "dt1" | "dt2") => Ok(Format::Dted(level_of(path.as_bytes()[path.len() - 1]))),
+        Some("hgt") => Ok(Format::Hgt),
+        Some("asc") => Ok(Format::Asc),
+        Some("tif" | "tiff") => Ok(Format::GeoTiff),
+        Some("png") => Ok(Format::Png),
+        _ => Err(DTEDError::InvalidInput(format!(
+            "unknown format of {}",
+            path
+        ))),
+    }
+}
+
+/// Level of its digit
+fn level_of(digit: u8) -> DTEDLevel {
+    match digit {
+        b'0' => DTEDLevel::Level0,
+        b'1' => DTEDLevel::Level1,
+        _ => DTEDLevel::Level2,
+    }
+}
@@ -0,0 +1,105 @@
+//! Contains the `info` subcommand, printing a summary of DTED files.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{Arg, ArgMatches, Command};
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::{DTEDData, DTEDError};
+
+/// Arguments of the subcommand
+pub fn command() -> Command {
+    Command::new("info")
+        .about("Print the origin, extent, level, spacing, producer, accuracy and elevation statistics of DTED files")
+        .arg(
+            Arg::new("files")
+                .help("DTED files")
+                .required(true)
+                .num_args(1..),
+        )
+}
+
+/// Print the summary of each file, going on after the ones that can not be
+/// read
+///
+/// # Returns
+///
+/// * Failure if a file could not be read
+pub fn run(matches: &ArgMatches) -> Result<ExitCode, DTEDError> {
+    let mut code = ExitCode::SUCCESS;
+    for (idx, path) in matches.get_many::<String>("files").unwrap().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        match DTEDData::read(path) {
+            Ok(data) => print_info(path, &data),
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    Ok(code)
+}
+
+/// Print the summary of a file
+fn print_info(path: &str, data: &DTEDData) {
+    let metadata = &data.metadata;
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "unknown".to_string());
+    let stats = data.statistics();
+    println!("{}", path);
+    println!(
+        "  origin:     {:.6}, {:.6}",
+        metadata.origin.lat, metadata.origin.lon
+    );
+    println!(
+        "  extent:     {:.6}, {:.6} to {:.6}, {:.6}",
+        data.min.lat, data.min.lon, data.max.lat, data.max.lon
+    );
+    println!(
+        "  level:      {}",
+        optional(metadata.level().map(|level| level.to_string()))
+    );
+    println!(
+        "  spacing:    {}\" x {}\" (latitude x longitude)",
+        metadata.interval_secs.lat, metadata.interval_secs.lon
+    );
+    println!(
+        "  counts:     {} latitude points x {} longitude lines",
+        metadata.count.lat, metadata.count.lon
+    );
+    println!(
+        "  edition:    {}",
+        optional(metadata.edition().map(|edition| edition.to_string()))
+    );
+    println!(
+        "  producer:   {}",
+        optional(
+            metadata
+                .producer_code()
+                .map(|code| match metadata.producer() {
+                    Some(producer) => format!("{} - {}", code, producer),
+                    None => code.to_string(),
+                })
+        )
+    );
+    println!(
+        "  accuracy:   {}",
+        optional(metadata.accuracy.map(|accuracy| format!("{} m", accuracy)))
+    );
+    println!("  voids:      {:.2}%", data.void_fraction() * 100.0);
+    match (stats.min, stats.max, stats.mean, stats.std_dev) {
+        (Some(min), Some(max), Some(mean), Some(std_dev)) => {
+            println!("  elevations: {} to {} m", min, max);
+            println!(
+                "  mean:       {:.1} m (standard deviation {:.1} m)",
+                mean, std_dev
+            );
+        }
+        _ => println!("  elevations: none"),
+    }
+}
@@ -0,0 +1,102 @@
+//! The `dted2` command line tool (requires the `cli` feature), giving shell
+//! users the readers, converters and analyses of the library.
+//!
+//! Each subcommand lives in its own module, with a `command` describing its
+//! arguments and a `run` executing it, returning the exit status.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{Arg, ArgMatches, Command};
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::interpolation::Interpolation;
+use dted2::primitives::{Angle, AxisElement};
+use dted2::DTEDError;
+
+mod convert;
+mod info;
+mod profile;
+mod render;
+mod sample;
+mod validate;
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+    let result = match matches.subcommand() {
+        Some(("convert", matches)) => convert::run(matches),
+        Some(("info", matches)) => info::run(matches),
+        Some(("profile", matches)) => profile::run(matches),
+        Some(("render", matches)) => render::run(matches),
+        Some(("sample", matches)) => sample::run(matches),
+        Some(("validate", matches)) => validate::run(matches),
+        _ => unreachable!("a subcommand is required"),
+    };
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Command line of the tool, one subcommand per module
+fn command() -> Command {
+    Command::new("dted2")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Read, convert and analyze DTED files")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(info::command())
+        .subcommand(convert::command())
+        .subcommand(sample::command())
+        .subcommand(profile::command())
+        .subcommand(render::command())
+        .subcommand(validate::command())
+}
+
+/// `--interpolation` option, shared by the subcommands
+///
+/// # Arguments
+///
+/// * `help` - what is interpolated
+fn interpolation_arg(help: &'static str) -> Arg {
+    Arg::new("interpolation")
+        .long("interpolation")
+        .help(help)
+        .value_parser(["nearest", "bilinear", "bicubic", "lanczos"])
+        .default_value("bilinear")
+}
+
+/// [Interpolation] method of the `--interpolation` option
+fn interpolation_of(matches: &ArgMatches) -> Interpolation {
+    match matches
+        .get_one::<String>("interpolation")
+        .map(String::as_str)
+    {
+        Some("nearest") => Interpolation::Nearest,
+        Some("bicubic") => Interpolation::Bicubic,
+        Some("lanczos") => Interpolation::Lanczos,
+        _ => Interpolation::Bilinear,
+    }
+}
+
+/// Parse a `LAT,LON` pair, in decimal degrees or DMS notation
+/// (e.g. `34°12'30.5"N,118°15'W`), into degrees
+fn parse_lat_lon(value: &str) -> Result<AxisElement<f64>, DTEDError> {
+    // decimal degrees are kept as given, rather than rounded through arc seconds
+    if let Some((lat, lon)) = value.split_once(',') {
+        if let (Ok(lat), Ok(lon)) = (lat.trim().parse(), lon.trim().parse()) {
+            return Ok(AxisElement::new(lat, lon));
+        }
+    }
+    let pos: AxisElement<Angle> = value.parse()?;
+    Ok(AxisElement::new(
+        pos.lat.to_decimal_degrees(),
+        pos.lon.to_decimal_degrees(),
+    ))
+}
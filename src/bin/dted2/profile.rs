@@ -0,0 +1,110 @@
+//! Contains the `profile` subcommand, sampling the elevations along a line
+//! between two lat/lons, as distance/elevation CSV or an SVG chart.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{value_parser, Arg, ArgMatches, Command};
+use std::path::Path;
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::geodesy::LineType;
+use dted2::profile::{self, ProfileSample};
+use dted2::{DTEDDataset, DTEDError};
+
+/// Arguments of the subcommand
+pub fn command() -> Command {
+    Command::new("profile")
+        .about("Print the elevation profile between two lat/lons, as CSV or an SVG chart")
+        .arg(
+            Arg::new("path")
+                .help("DTED file, or directory of DTED files")
+                .required(true),
+        )
+        .arg(
+            Arg::new("start")
+                .value_name("LAT,LON")
+                .help("Start of the line, in degrees")
+                .allow_hyphen_values(true)
+                .required(true),
+        )
+        .arg(
+            Arg::new("end")
+                .value_name("LAT,LON")
+                .help("End of the line, in degrees")
+                .allow_hyphen_values(true)
+                .required(true),
+        )
+        .arg(
+            Arg::new("samples")
+                .long("samples")
+                .help("Number of samples, including both ends")
+                .value_parser(value_parser!(usize))
+                .default_value("100"),
+        )
+        .arg(
+            Arg::new("line")
+                .long("line")
+                .help("Type of line between the lat/lons")
+                .value_parser(["great-circle", "rhumb"])
+                .default_value("great-circle"),
+        )
+        .arg(
+            Arg::new("svg")
+                .long("svg")
+                .value_name("FILE")
+                .help("Write an SVG chart of the profile to a file, rather than CSV to stdout"),
+        )
+        .arg(crate::interpolation_arg("Interpolation between the posts"))
+}
+
+/// Print a line of `distance,lat,lon,elevation` per sample (the distance in
+/// meters, the elevation empty where not covered or void), or write the chart
+///
+/// # Returns
+///
+/// * Success, or an error if the files or lat/lons could not be read, or the
+///   chart written
+pub fn run(matches: &ArgMatches) -> Result<ExitCode, DTEDError> {
+    let path = matches.get_one::<String>("path").unwrap();
+    let start = crate::parse_lat_lon(matches.get_one::<String>("start").unwrap())?;
+    let end = crate::parse_lat_lon(matches.get_one::<String>("end").unwrap())?;
+    let num_samples = *matches.get_one::<usize>("samples").unwrap();
+    let line = match matches.get_one::<String>("line").unwrap().as_str() {
+        "rhumb" => LineType::Rhumb,
+        _ => LineType::GreatCircle,
+    };
+    let interpolation = crate::interpolation_of(matches);
+    let (start, end) = ((start.lat, start.lon), (end.lat, end.lon));
+    let samples = match Path::new(path).is_dir() {
+        true => {
+            DTEDDataset::from_dir(path)?.profile_with(start, end, num_samples, line, interpolation)
+        }
+        false => {
+            DTEDDataset::read(&[path])?.profile_with(start, end, num_samples, line, interpolation)
+        }
+    };
+    match matches.get_one::<String>("svg") {
+        Some(svg) => std::fs::write(svg, profile::to_svg(&samples, 800, 300))?,
+        None => print_samples(&samples),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Print the samples as CSV
+fn print_samples(samples: &[ProfileSample]) {
+    println!("distance,lat,lon,elevation");
+    for sample in samples {
+        let elevation = sample
+            .elevation
+            .map(|elevation| elevation.to_string())
+            .unwrap_or_default();
+        println!(
+            "{:.3},{},{},{}",
+            sample.distance, sample.lat, sample.lon, elevation
+        );
+    }
+}
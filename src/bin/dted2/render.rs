@@ -0,0 +1,85 @@
+//! Contains the `render` subcommand, rendering a DTED cell into a PNG
+//! quicklook: its shaded relief (grayscale) or its color relief (RGBA,
+//! transparent where void).
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{value_parser, Arg, ArgMatches, Command};
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::terrain::Palette;
+use dted2::{DTEDData, DTEDError};
+
+/// Arguments of the subcommand
+pub fn command() -> Command {
+    Command::new("render")
+        .about("Render a DTED cell as a hillshade or color relief PNG")
+        .arg(Arg::new("input").help("DTED file").required(true))
+        .arg(Arg::new("output").help("PNG file").required(true))
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .help("What to render")
+                .value_parser(["hillshade", "color-relief"])
+                .default_value("hillshade"),
+        )
+        .arg(
+            Arg::new("azimuth")
+                .long("azimuth")
+                .help("Direction of the light of the hillshade, in degrees clockwise from north")
+                .value_parser(value_parser!(f64))
+                .default_value("315"),
+        )
+        .arg(
+            Arg::new("altitude")
+                .long("altitude")
+                .help("Angle of the light of the hillshade above the horizon, in degrees")
+                .value_parser(value_parser!(f64))
+                .default_value("45"),
+        )
+        .arg(
+            Arg::new("z-factor")
+                .long("z-factor")
+                .help("Exaggeration of the elevations of the hillshade")
+                .value_parser(value_parser!(f64))
+                .default_value("1"),
+        )
+        .arg(
+            Arg::new("palette")
+                .long("palette")
+                .help("Colors of the color relief, from the lowest to the highest elevation")
+                .value_parser(["terrain", "grayscale", "viridis"])
+                .default_value("terrain"),
+        )
+}
+
+/// Render the input into the output
+///
+/// # Returns
+///
+/// * Success, or an error if the input could not be read or the output written
+pub fn run(matches: &ArgMatches) -> Result<ExitCode, DTEDError> {
+    let input = matches.get_one::<String>("input").unwrap();
+    let output = matches.get_one::<String>("output").unwrap();
+    let float = |name: &str| *matches.get_one::<f64>(name).unwrap();
+    let data = DTEDData::read(input)?;
+    let png = match matches.get_one::<String>("mode").unwrap().as_str() {
+        "color-relief" => {
+            let palette = match matches.get_one::<String>("palette").unwrap().as_str() {
+                "grayscale" => Palette::Grayscale,
+                "viridis" => Palette::Viridis,
+                _ => Palette::Terrain,
+            };
+            data.color_relief(palette).to_png()?
+        }
+        _ => data
+            .hillshade(float("azimuth"), float("altitude"), float("z-factor"))
+            .to_png()?,
+    };
+    std::fs::write(output, png)?;
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,93 @@
+//! Contains the `sample` subcommand, printing the elevations at lat/lons as
+//! CSV.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{Arg, ArgMatches, Command};
+use std::io::BufRead;
+use std::path::Path;
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::interpolation::Interpolation;
+use dted2::primitives::AxisElement;
+use dted2::{DTEDDataset, DTEDError, ElevationSource};
+
+/// Arguments of the subcommand
+pub fn command() -> Command {
+    Command::new("sample")
+        .about("Print the elevations at lat/lons, as CSV")
+        .arg(
+            Arg::new("path")
+                .help("DTED file, or directory of DTED files")
+                .required(true),
+        )
+        .arg(
+            Arg::new("points")
+                .value_name("LAT,LON")
+                .help("Lat/lons in degrees, after the options (default: LAT,LON lines of CSV on stdin)")
+                .num_args(0..)
+                .allow_hyphen_values(true),
+        )
+        .arg(crate::interpolation_arg("Interpolation between the posts"))
+}
+
+/// Print a line of `lat,lon,elevation` per point, the elevation empty where
+/// not covered or void
+///
+/// # Returns
+///
+/// * Success, or an error if the files or points could not be read
+pub fn run(matches: &ArgMatches) -> Result<ExitCode, DTEDError> {
+    let path = matches.get_one::<String>("path").unwrap();
+    let points = match matches.get_many::<String>("points") {
+        Some(points) => points
+            .map(|point| crate::parse_lat_lon(point))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => read_points(std::io::stdin().lock())?,
+    };
+    let interpolation = crate::interpolation_of(matches);
+    match Path::new(path).is_dir() {
+        true => print_samples(&DTEDDataset::from_dir(path)?, &points, interpolation),
+        false => print_samples(&DTEDDataset::read(&[path])?, &points, interpolation),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Read the points of CSV lines, starting with their latitude and longitude,
+/// skipping a header and empty lines
+fn read_points<R: BufRead>(reader: R) -> Result<Vec<AxisElement<f64>>, DTEDError> {
+    let mut points = Vec::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let lat_lon = line.splitn(3, ',').take(2).collect::<Vec<_>>().join(",");
+        match (crate::parse_lat_lon(&lat_lon), idx) {
+            (Ok(point), _) => points.push(point),
+            (Err(_), 0) => continue,
+            (Err(err), _) => return Err(err),
+        }
+    }
+    Ok(points)
+}
+
+/// Print the elevations of the points
+fn print_samples<S: ElevationSource>(
+    dataset: &DTEDDataset<S>,
+    points: &[AxisElement<f64>],
+    interpolation: Interpolation,
+) {
+    println!("lat,lon,elevation");
+    for point in points {
+        let elevation = dataset
+            .elevation_at_with(point.lat, point.lon, interpolation)
+            .map(|elevation| elevation.to_string())
+            .unwrap_or_default();
+        println!("{},{},{}", point.lat, point.lon, elevation);
+    }
+}
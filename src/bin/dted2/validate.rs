@@ -0,0 +1,215 @@
+//! Contains the `validate` subcommand, checking DTED files against the spec
+//! for archive QA: strict parsing (the DSI and ACC records being required),
+//! the checksum of every data record, and the consistency of the level,
+//! intervals, counts and origin of the UHL and DSI records.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::dted::{DTEDMetadata, SecurityClassification};
+use dted2::primitives::AxisElement;
+use dted2::{DTEDData, DTEDError, DTEDLevel, ParseOptions};
+
+/// Arguments of the subcommand
+pub fn command() -> Command {
+    Command::new("validate")
+        .about("Check DTED files against the spec and verify their checksums")
+        .arg(
+            Arg::new("paths")
+                .help("DTED files, or directories searched for .dt0/.dt1/.dt2 files")
+                .required(true)
+                .num_args(1..),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the results as JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+/// Validate each file, printing its problems
+///
+/// # Returns
+///
+/// * Failure if a file is invalid, or an error if a directory could not be
+///   searched
+pub fn run(matches: &ArgMatches) -> Result<ExitCode, DTEDError> {
+    let mut files = Vec::new();
+    for path in matches.get_many::<String>("paths").unwrap() {
+        match Path::new(path).is_dir() {
+            true => {
+                let mut found = Vec::new();
+                find_files(Path::new(path), &mut found)?;
+                found.sort();
+                files.extend(found);
+            }
+            false => files.push(PathBuf::from(path)),
+        }
+    }
+    let results: Vec<(String, Vec<String>)> = files
+        .iter()
+        .map(|file| {
+            let file = file.to_string_lossy().into_owned();
+            let problems = validate(&file);
+            (file, problems)
+        })
+        .collect();
+    let invalid = results
+        .iter()
+        .filter(|(_, problems)| !problems.is_empty())
+        .count();
+    match matches.get_flag("json") {
+        true => {
+            let files: Vec<_> = results
+                .iter()
+                .map(|(file, problems)| {
+                    json!({"file": file, "valid": problems.is_empty(), "problems": problems})
+                })
+                .collect();
+            let summary = json!({
+                "files": files,
+                "valid": results.len() - invalid,
+                "invalid": invalid,
+            });
+            println!("{}", serde_json::to_string_pretty(&summary).unwrap());
+        }
+        false => {
+            for (file, problems) in &results {
+                match problems.is_empty() {
+                    true => println!("{}: ok", file),
+                    false => {
+                        println!("{}: invalid", file);
+                        for problem in problems {
+                            println!("  {}", problem);
+                        }
+                    }
+                }
+            }
+            println!("{} valid, {} invalid", results.len() - invalid, invalid);
+        }
+    }
+    match invalid {
+        0 => Ok(ExitCode::SUCCESS),
+        _ => Ok(ExitCode::FAILURE),
+    }
+}
+
+/// Find the DTED files within a directory, recursively, not following
+/// symbolic links to directories
+fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), DTEDError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            find_files(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                matches!(
+                    extension.to_ascii_lowercase().as_str(),
+                    "dt0" | "dt1" | "dt2"
+                )
+            })
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Validate a file
+///
+/// # Returns
+///
+/// * The problems found, empty if the file is valid
+fn validate(path: &str) -> Vec<String> {
+    match DTEDData::read_recovering(path, &ParseOptions::strict()) {
+        Ok((data, diagnostics)) => diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .chain(spec_problems(&data.metadata))
+            .collect(),
+        Err(err) => vec![err.to_string()],
+    }
+}
+
+/// Check the metadata against the spec
+///
+/// # Returns
+///
+/// * The problems found, empty if the metadata is consistent
+fn spec_problems(metadata: &DTEDMetadata) -> Vec<String> {
+    let mut problems = Vec::new();
+    if metadata.origin.lat.fract() != 0.0 || metadata.origin.lon.fract() != 0.0 {
+        problems.push(format!(
+            "origin {}, {} is not on whole degrees",
+            metadata.origin.lat, metadata.origin.lon
+        ));
+    }
+    let interval = AxisElement::new(
+        (metadata.interval_secs.lat * 10.0).round() as u16,
+        (metadata.interval_secs.lon * 10.0).round() as u16,
+    );
+    if let Some(dsi) = &metadata.dsi_record {
+        if let SecurityClassification::Other(code) = dsi.security_classification {
+            problems.push(format!("unknown security classification '{}'", code));
+        }
+    }
+    problems.extend(
+        metadata
+            .cross_validate()
+            .iter()
+            .map(|discrepancy| discrepancy.to_string()),
+    );
+    let level = match metadata.level() {
+        Some(level) => level,
+        None => {
+            problems.push("unknown level".to_string());
+            return problems;
+        }
+    };
+    if let Some(dsi) = metadata
+        .dsi_record
+        .as_ref()
+        .filter(|dsi| DTEDLevel::from_product_level(&dsi.product_level) != Some(level))
+    {
+        problems.push(format!(
+            "DSI product level {} differs from the {} intervals",
+            dsi.product_level.trim(),
+            level
+        ));
+    }
+    let zone = metadata.zone();
+    if interval.lat != level.lat_interval_secs_x_10()
+        || interval.lon != level.lon_interval_secs_x_10(zone)
+    {
+        problems.push(format!(
+            "intervals {}\" x {}\" are not the ones of {}",
+            metadata.interval_secs.lat, metadata.interval_secs.lon, level
+        ));
+    }
+    // partial cells have fewer longitude lines
+    let partial = metadata
+        .dsi_record
+        .as_ref()
+        .is_some_and(|dsi| dsi.partial_cell.is_some_and(|partial| partial != 0));
+    if metadata.count.lat != level.lat_count()
+        || (!partial && metadata.count.lon != level.lon_count(zone))
+    {
+        problems.push(format!(
+            "counts {} x {} are not the ones of {}",
+            metadata.count.lat, metadata.count.lon, level
+        ));
+    }
+    problems
+}
@@ -0,0 +1,367 @@
+//! Contains [RawDTEDFileRef], a DTED file borrowed from its bytes, for
+//! transient access without allocating (e.g. over a memory-mapped file).
+//!
+//! The text fields of the [RawDTEDHeaderRef] borrow from the bytes, and the
+//! data records are kept as byte spans ([RawDTEDRecordRef]), their elevations
+//! only being decoded on access. The DSI and ACC records are kept as bytes as
+//! well, parsed into their owned records on demand.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+use nom::{
+    bytes::complete::{tag, take},
+    combinator::recognize,
+    sequence::pair,
+    IResult,
+};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::primitives::{Angle, AxisElement};
+use crate::Error as DTEDError;
+
+#[derive(Debug, Clone, PartialEq)]
+/// DTED User Header Label (UHL), its text fields borrowed from the file, see [RawDTEDHeader]
+///
+/// # Fields
+///
+/// * `origin` - latitude and longitude of the lower left corner of the grid
+/// * `interval_secs_x_10` - data interval in seconds (decimal point is implied after third integer)
+/// * `accuracy`- absolute vertical accuracy in meters
+/// * `security_code` - security classification code
+/// * `unique_ref` - unique reference number
+/// * `count` - number of longitude lines and latitude points
+/// * `multiple_accuracy` - whether the ACC record contains multiple accuracy sub-regions
+pub struct RawDTEDHeaderRef<'a> {
+    pub origin: AxisElement<Angle>,
+    pub interval_secs_x_10: AxisElement<u16>,
+    pub accuracy: Option<u16>,
+    pub security_code: Cow<'a, str>,
+    pub unique_ref: Cow<'a, str>,
+    pub count: AxisElement<u16>,
+    pub multiple_accuracy: bool,
+}
+impl RawDTEDHeaderRef<'_> {
+    /// Convert into a [RawDTEDHeader], copying the text fields
+    pub fn into_owned(self) -> RawDTEDHeader {
+        RawDTEDHeader {
+            origin: self.origin,
+            interval_secs_x_10: self.interval_secs_x_10,
+            accuracy: self.accuracy,
+            security_code: self.security_code.into_owned(),
+            unique_ref: self.unique_ref.into_owned(),
+            count: self.count,
+            multiple_accuracy: self.multiple_accuracy,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// DTED file borrowed from its bytes, see [RawDTEDFile]
+///
+/// # Fields
+///
+/// * `header` - User Header Label
+/// * `dsi_bytes` - bytes of the Data Set Identification record, see [RawDTEDFileRef::dsi_record]
+/// * `acc_bytes` - bytes of the Accuracy Description record, see [RawDTEDFileRef::acc_record]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::borrowed::RawDTEDFileRef;
+/// use dted2::dted::RawDTEDFile;
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let file = RawDTEDFileRef::from_bytes(&content).unwrap();
+/// let owned = RawDTEDFile::from_bytes(&content).unwrap();
+/// assert_eq!(file.header.clone().into_owned(), owned.header);
+/// assert_eq!(file.len(), 121);
+/// assert_eq!(file.get(5, 3), owned.data.get(5, 3));
+/// assert_eq!(file.dsi_record().unwrap(), owned.dsi_record.unwrap());
+/// ```
+pub struct RawDTEDFileRef<'a> {
+    pub header: RawDTEDHeaderRef<'a>,
+    pub dsi_bytes: &'a [u8],
+    pub acc_bytes: &'a [u8],
+    data: &'a [u8],
+    record_len: usize,
+}
+impl<'a> RawDTEDFileRef<'a> {
+    /// Borrow a DTED file from its bytes
+    ///
+    /// Only the UHL is parsed, the DSI and ACC records being checked for their
+    /// sentinels, and the data records to be complete (with their sentinels),
+    /// but no elevation is decoded and the checksums are not validated
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFileRef]: the file, borrowing `content`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::borrowed::RawDTEDFileRef;
+    /// use dted2::Error;
+    ///
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// assert!(matches!(
+    ///     RawDTEDFileRef::from_bytes(&content[..content.len() - 1]),
+    ///     Err(Error::TruncatedFile { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     RawDTEDFileRef::from_bytes(&content[1..]),
+    ///     Err(Error::InvalidSentinel { .. })
+    /// ));
+    /// ```
+    pub fn from_bytes(content: &'a [u8]) -> Result<RawDTEDFileRef<'a>, DTEDError> {
+        let locate = |e| DTEDError::locate(e, content, 0, None);
+        let (rest, header) = parsers::dted_uhl_ref_parser(content).map_err(locate)?;
+        let (rest, dsi_bytes) =
+            sentinel_record(rest, &RecognitionSentinel::DSI, DTED_DSI_RECORD_LENGTH)
+                .map_err(locate)?;
+        let (_, acc_bytes) =
+            sentinel_record(rest, &RecognitionSentinel::ACC, DTED_ACC_RECORD_LENGTH)
+                .map_err(locate)?;
+        // --------------------------------------------------
+        // validate the data records are all there
+        // --------------------------------------------------
+        let record_len = validate_data_records(content, &header.count)?;
+        let data = &content[DTED_DATA_OFFSET..][..record_len * header.count.lon as usize];
+        Ok(RawDTEDFileRef {
+            header,
+            dsi_bytes,
+            acc_bytes,
+            data,
+            record_len,
+        })
+    }
+
+    /// Parse the Data Set Identification record
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDDSIRecord]
+    pub fn dsi_record(&self) -> Result<RawDTEDDSIRecord, DTEDError> {
+        parsers::dted_dsi_parser(self.dsi_bytes)
+            .map(|(_, record)| record)
+            .map_err(|e| DTEDError::locate(e, self.dsi_bytes, DTED_UHL_LENGTH as usize, None))
+    }
+
+    /// Parse the Accuracy Description record
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDAccRecord]
+    pub fn acc_record(&self) -> Result<RawDTEDAccRecord, DTEDError> {
+        let offset = DTED_UHL_LENGTH as usize + DTED_DSI_RECORD_LENGTH;
+        parsers::dted_acc_parser(self.acc_bytes)
+            .map(|(_, record)| record)
+            .map_err(|e| DTEDError::locate(e, self.acc_bytes, offset, None))
+    }
+
+    /// Get the number of data records (longitude lines)
+    pub fn len(&self) -> usize {
+        self.header.count.lon as usize
+    }
+
+    /// Whether there are no data records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a data record
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDRecordRef], or None if out of bounds
+    pub fn record(&self, lon_idx: usize) -> Option<RawDTEDRecordRef<'a>> {
+        self.data
+            .chunks_exact(self.record_len)
+            .nth(lon_idx)
+            .map(|bytes| RawDTEDRecordRef { bytes })
+    }
+
+    /// Iterate over the data records, from west to east
+    pub fn records(&self) -> impl Iterator<Item = RawDTEDRecordRef<'a>> + 'a {
+        self.data
+            .chunks_exact(self.record_len)
+            .map(|bytes| RawDTEDRecordRef { bytes })
+    }
+
+    /// Get the raw elevation of a post, void posts included
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation (in meters), or None if out of bounds
+    pub fn get(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.record(lon_idx)?.get(lat_idx)
+    }
+
+    /// Convert into a [RawDTEDFile], parsing the DSI and ACC records and
+    /// decoding every data record
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile]
+    pub fn to_raw_file(&self) -> Result<RawDTEDFile, DTEDError> {
+        let line_len = self.header.count.lat as usize;
+        let mut elevations = vec![0; line_len * self.len()];
+        let records = self
+            .records()
+            .zip(elevations.chunks_exact_mut(line_len.max(1)))
+            .map(|(record, out)| {
+                record.decode_into(out);
+                record.info()
+            })
+            .collect();
+        Ok(RawDTEDFile {
+            header: self.header.clone().into_owned(),
+            data: RawDTEDRecords {
+                line_len,
+                records,
+                elevations,
+            },
+            dsi_record: Some(self.dsi_record()?),
+            acc_record: Some(self.acc_record()?),
+            coverage: Coverage::full(self.len()),
+        })
+    }
+}
+
+/// Take a header record, checking its sentinel
+fn sentinel_record<'a>(
+    input: &'a [u8],
+    sentinel: &RecognitionSentinel,
+    len: usize,
+) -> IResult<&'a [u8], &'a [u8]> {
+    recognize(pair(
+        tag(sentinel.value()),
+        take(len - sentinel.value().len()),
+    ))(input)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// DTED Data Record borrowed from the bytes of the file, see [RawDTEDRecord]
+///
+/// The elevations are kept as stored (big-endian signed magnitude), and
+/// decoded on access.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::borrowed::RawDTEDFileRef;
+/// use dted2::DTEDData;
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let file = RawDTEDFileRef::from_bytes(&content).unwrap();
+/// let record = file.record(3).unwrap();
+/// assert_eq!(record.len(), 121);
+/// assert_eq!(record.info(), data.data.records[3]);
+/// assert!(record.iter().eq(data.data.column(3).unwrap().iter().copied()));
+/// assert!(record.is_checksum_valid());
+/// ```
+pub struct RawDTEDRecordRef<'a> {
+    bytes: &'a [u8],
+}
+impl<'a> RawDTEDRecordRef<'a> {
+    /// Get the header and checksum of the record
+    pub fn info(&self) -> RawDTEDRecordInfo {
+        parsers::to_record_info(self.bytes)
+    }
+
+    /// Get the bytes of the record, from its sentinel through its checksum
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Get the bytes of the elevations, as stored in the file
+    pub fn elevation_bytes(&self) -> &'a [u8] {
+        &self.bytes[DTED_RECORD_HEADER_LENGTH..self.bytes.len() - DTED_RECORD_CHECKSUM_LENGTH]
+    }
+
+    /// Get the number of elevations (latitude points)
+    pub fn len(&self) -> usize {
+        self.elevation_bytes().len() / 2
+    }
+
+    /// Whether there are no elevations
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the raw elevation of a post, void posts included
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation (in meters), or None if out of bounds
+    pub fn get(&self, lat_idx: usize) -> Option<i16> {
+        self.elevation_bytes()
+            .chunks_exact(2)
+            .nth(lat_idx)
+            .map(|b| parsers::to_i16(u16::from_be_bytes([b[0], b[1]])))
+    }
+
+    /// Iterate over the raw elevations, from south to north
+    pub fn iter(&self) -> impl Iterator<Item = i16> + 'a {
+        self.elevation_bytes()
+            .chunks_exact(2)
+            .map(|b| parsers::to_i16(u16::from_be_bytes([b[0], b[1]])))
+    }
+
+    /// Decode the elevations into a buffer, see [parsers::to_i16_slice]
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Where to write the elevations, decoding as many as fit
+    pub fn decode_into(&self, out: &mut [i16]) {
+        parsers::to_i16_slice(self.elevation_bytes(), out);
+    }
+
+    /// Whether the stored checksum matches the bytes of the record
+    pub fn is_checksum_valid(&self) -> bool {
+        let len = self.bytes.len() - DTED_RECORD_CHECKSUM_LENGTH;
+        parsers::compute_checksum(&self.bytes[..len]) == self.info().checksum
+    }
+
+    /// Convert into a [RawDTEDRecord], decoding the elevations
+    pub fn to_record(&self) -> RawDTEDRecord {
+        let info = self.info();
+        let mut elevations = vec![0; self.len()];
+        self.decode_into(&mut elevations);
+        RawDTEDRecord {
+            blk_count: info.blk_count,
+            lon_count: info.lon_count,
+            lat_count: info.lat_count,
+            elevations,
+            checksum: info.checksum,
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Contains the [DTEDBuilder], used to construct DTED files from arbitrary elevation grids.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::interpolation::Interpolation;
+use crate::primitives::{Angle, AxisElement};
+use crate::raster::Raster;
+use crate::Error as DTEDError;
+
+/// Builder for a spec-compliant [RawDTEDFile] from a grid of elevations
+///
+/// The grid is given as one [Vec] per longitude line (from west to east),
+/// each holding the elevations of that line from south to north, the same
+/// layout as the data records of a DTED file.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDBuilder, DTEDLevel};
+/// use dted2::dted::SecurityClassification;
+/// use dted2::primitives::{Angle, AxisElement};
+///
+/// let origin = AxisElement::new(Angle::new(42, 0, 0.0, false), Angle::new(15, 0, 0.0, true));
+/// let file = DTEDBuilder::new(origin, AxisElement::new(300, 300), AxisElement::new(121, 121))
+///     .elevations(vec![vec![100; 121]; 121])
+///     .level(DTEDLevel::Level0)
+///     .accuracy(25)
+///     .security_classification(SecurityClassification::Unclassified)
+///     .producer_code("USNIMA")
+///     .build()
+///     .unwrap();
+/// assert_eq!(file.data.len(), 121);
+/// assert_eq!(file.dsi_record.unwrap().producer_code, "USNIMA");
+///
+/// let bytes = DTEDBuilder::new(origin, AxisElement::new(300, 300), AxisElement::new(121, 121))
+///     .elevations(vec![vec![100; 120]; 121])
+///     .build();
+/// assert!(bytes.is_err());
+/// ```
+pub struct DTEDBuilder {
+    origin: AxisElement<Angle>,
+    interval_secs_x_10: AxisElement<u16>,
+    count: AxisElement<u16>,
+    elevations: Vec<Vec<i16>>,
+    level: Option<DTEDLevel>,
+    accuracy: Option<u16>,
+    security_classification: SecurityClassification,
+    producer_code: String,
+    edition: Option<u8>,
+}
+impl DTEDBuilder {
+    /// Create a new [DTEDBuilder]
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - latitude and longitude of the lower left corner of the grid
+    /// * `interval_secs_x_10` - data interval in tenths of seconds
+    /// * `count` - number of latitude points and longitude lines
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDBuilder]: builder with an empty grid, no accuracy, and an unclassified security
+    pub fn new(
+        origin: AxisElement<Angle>,
+        interval_secs_x_10: AxisElement<u16>,
+        count: AxisElement<u16>,
+    ) -> DTEDBuilder {
+        DTEDBuilder {
+            origin,
+            interval_secs_x_10,
+            count,
+            elevations: Vec::new(),
+            level: None,
+            accuracy: None,
+            security_classification: SecurityClassification::Unclassified,
+            producer_code: String::new(),
+            edition: None,
+        }
+    }
+
+    /// Create a new [DTEDBuilder] for a 1 degree cell of a [DTEDLevel], with the
+    /// intervals and counts of the level in the [LatitudeZone] of the cell
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - latitude and longitude of the lower left corner of the cell
+    /// * `level` - [DTEDLevel]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDBuilder]: builder validating the grid against the level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::{Angle, AxisElement};
+    /// use dted2::{DTEDBuilder, DTEDLevel};
+    ///
+    /// // posts are twice as far apart in longitude above 50 degrees
+    /// let origin = AxisElement::new(Angle::new(60, 0, 0.0, false), Angle::new(15, 0, 0.0, false));
+    /// let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+    ///     .elevations(vec![vec![100; 121]; 61])
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(file.header.interval_secs_x_10, AxisElement::new(300, 600));
+    /// assert_eq!(file.data.len(), 61);
+    /// ```
+    pub fn for_level(origin: AxisElement<Angle>, level: DTEDLevel) -> DTEDBuilder {
+        let zone = LatitudeZone::of_cell(origin.lat.into());
+        DTEDBuilder::new(
+            origin,
+            AxisElement::new(
+                level.lat_interval_secs_x_10(),
+                level.lon_interval_secs_x_10(zone),
+            ),
+            AxisElement::new(level.lat_count(), level.lon_count(zone)),
+        )
+        .level(level)
+    }
+
+    /// Set the elevation grid, in meters
+    ///
+    /// # Arguments
+    ///
+    /// * `elevations` - one [Vec] per longitude line (west to east), each
+    ///   holding the elevations from south to north
+    pub fn elevations(mut self, elevations: Vec<Vec<i16>>) -> Self {
+        self.elevations = elevations;
+        self
+    }
+
+    /// Set the elevation grid by resampling a [Raster] at every post
+    ///
+    /// Posts outside of the raster (or between void values) are void
+    ///
+    /// # Arguments
+    ///
+    /// * `raster` - elevations, in meters, None if void
+    /// * `interpolation` - [Interpolation] method
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::interpolation::Interpolation;
+    /// use dted2::primitives::{Angle, AxisElement};
+    /// use dted2::raster::Raster;
+    /// use dted2::{DTEDBuilder, DTEDLevel};
+    ///
+    /// // a 1 arc-minute grid over the west half of the cell
+    /// let raster = Raster::from_fn(
+    ///     AxisElement::new(42.0, 15.0),
+    ///     AxisElement::new(1.0 / 60.0, 1.0 / 60.0),
+    ///     AxisElement::new(61, 31),
+    ///     |lat_idx, _| Some(lat_idx as f64 * 10.0),
+    /// );
+    /// let origin = AxisElement::new(Angle::new(42, 0, 0.0, false), Angle::new(15, 0, 0.0, false));
+    /// let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+    ///     .resample(&raster, Interpolation::Bilinear)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(file.data.get(1, 0), Some(5));
+    /// assert_eq!(file.data.get(120, 60), Some(600));
+    /// assert_eq!(file.data.get(120, 61), Some(-32767));
+    /// ```
+    pub fn resample(mut self, raster: &Raster<Option<f64>>, interpolation: Interpolation) -> Self {
+        let origin = AxisElement::new(f64::from(self.origin.lat), f64::from(self.origin.lon));
+        let interval = AxisElement::new(
+            f64::from(self.interval_secs_x_10.lat) / 36_000.0,
+            f64::from(self.interval_secs_x_10.lon) / 36_000.0,
+        );
+        self.elevations = (0..self.count.lon)
+            .map(|lon_idx| {
+                let lon = origin.lon + f64::from(lon_idx) * interval.lon;
+                (0..self.count.lat)
+                    .map(|lat_idx| {
+                        let lat = origin.lat + f64::from(lat_idx) * interval.lat;
+                        match raster.value_at(lat, lon, interpolation) {
+                            Some(elev) => elev.round().clamp(-32766.0, 32767.0) as i16,
+                            None => DTED_VOID_ELEVATION,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        self
+    }
+
+    /// Set the [DTEDLevel] the grid is validated against
+    pub fn level(mut self, level: DTEDLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Set the absolute vertical accuracy, in meters
+    pub fn accuracy(mut self, accuracy: u16) -> Self {
+        self.accuracy = Some(accuracy);
+        self
+    }
+
+    /// Set the [SecurityClassification]
+    pub fn security_classification(mut self, classification: SecurityClassification) -> Self {
+        self.security_classification = classification;
+        self
+    }
+
+    /// Set the producer code (at most 8 characters)
+    pub fn producer_code(mut self, producer_code: &str) -> Self {
+        self.producer_code = producer_code.to_string();
+        self
+    }
+
+    /// Set the data edition number (1 to 99)
+    pub fn edition(mut self, edition: u8) -> Self {
+        self.edition = Some(edition);
+        self
+    }
+
+    /// Validate the builder and construct the [RawDTEDFile]
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile] which can be written with [RawDTEDFile::write],
+    ///   or an error if the grid or metadata are invalid
+    pub fn build(self) -> Result<RawDTEDFile, DTEDError> {
+        self.validate()?;
+        let header = RawDTEDHeader {
+            origin: self.origin,
+            interval_secs_x_10: self.interval_secs_x_10,
+            accuracy: self.accuracy,
+            security_code: self.security_classification.code().to_string(),
+            unique_ref: String::new(),
+            count: self.count,
+            multiple_accuracy: false,
+        };
+        let mut dsi_record = RawDTEDDSIRecord::from_header(&header);
+        dsi_record.security_classification = self.security_classification;
+        dsi_record.producer_code = self.producer_code;
+        dsi_record.edition = self.edition;
+        if let Some(level) = self.level {
+            dsi_record.product_level = level.product_level().to_string();
+        }
+        let acc_record = RawDTEDAccRecord::from_header(&header);
+        let mut data =
+            RawDTEDRecords::with_capacity(self.count.lat as usize, self.elevations.len());
+        for (idx, elevations) in self.elevations.into_iter().enumerate() {
+            let mut record = RawDTEDRecord {
+                blk_count: idx as u32,
+                lon_count: idx as u16,
+                lat_count: 0,
+                elevations,
+                checksum: 0,
+            };
+            record.checksum = record.compute_checksum();
+            data.push(record)?;
+        }
+        Ok(RawDTEDFile {
+            header,
+            coverage: Coverage::full(data.len()),
+            data,
+            dsi_record: Some(dsi_record),
+            acc_record: Some(acc_record),
+        })
+    }
+
+    /// Validate the grid dimensions and metadata
+    fn validate(&self) -> Result<(), DTEDError> {
+        let invalid = |msg: String| Err(DTEDError::InvalidInput(msg));
+        // --------------------------------------------------
+        // grid dimensions
+        // --------------------------------------------------
+        if self.count.lat < 2 || self.count.lon < 2 {
+            return invalid(format!(
+                "at least 2x2 posts are required, got {}x{}",
+                self.count.lat, self.count.lon
+            ));
+        }
+        if self.elevations.len() != self.count.lon as usize {
+            return invalid(format!(
+                "expected {} longitude lines, got {}",
+                self.count.lon,
+                self.elevations.len()
+            ));
+        }
+        if let Some(idx) = self
+            .elevations
+            .iter()
+            .position(|line| line.len() != self.count.lat as usize)
+        {
+            return invalid(format!(
+                "expected {} latitude points in longitude line {}, got {}",
+                self.count.lat,
+                idx,
+                self.elevations[idx].len()
+            ));
+        }
+        if self
+            .elevations
+            .iter()
+            .flatten()
+            .any(|elev| *elev == i16::MIN)
+        {
+            return invalid(format!("{} can not be encoded", i16::MIN));
+        }
+        // --------------------------------------------------
+        // level (1 degree cell, latitude interval of the level,
+        // longitude interval as per the latitude zone of the cell)
+        // --------------------------------------------------
+        if let Some(level) = self.level {
+            let zone = LatitudeZone::of_cell(self.origin.lat.into());
+            let interval = AxisElement::new(
+                level.lat_interval_secs_x_10(),
+                level.lon_interval_secs_x_10(zone),
+            );
+            if self.interval_secs_x_10 != interval {
+                return invalid(format!(
+                    "intervals {:?} do not match {} in latitude zone {:?}",
+                    self.interval_secs_x_10, level, zone
+                ));
+            }
+            let expected = AxisElement::new(level.lat_count(), level.lon_count(zone));
+            if self.count != expected {
+                return invalid(format!(
+                    "{} requires {}x{} posts, got {}x{}",
+                    level, expected.lat, expected.lon, self.count.lat, self.count.lon
+                ));
+            }
+        }
+        // --------------------------------------------------
+        // metadata
+        // --------------------------------------------------
+        if let SecurityClassification::Other(code) = self.security_classification {
+            return invalid(format!("unknown security classification '{}'", code));
+        }
+        if self.producer_code.len() > 8 {
+            return invalid(format!(
+                "producer code \"{}\" is longer than 8 characters",
+                self.producer_code
+            ));
+        }
+        if matches!(self.edition, Some(edition) if edition == 0 || edition > 99) {
+            return invalid("the edition must be between 1 and 99".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! Contains the [Contour] lines of the elevations of a cell, extracted with
+//! marching squares (see [contours]).
+//!
+//! Each square between four posts is crossed by the iso-elevation line where
+//! its edges straddle the level, at the linearly interpolated position along
+//! the edge. Saddles (two diagonal posts above the level, two below) are
+//! resolved by the mean of the four posts. Squares with a void post are
+//! skipped, ending the lines along voids as along the edges of the cell.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::collections::HashMap;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::source::ElevationSource;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Iso-elevation lines of a level
+///
+/// # Fields
+///
+/// * `elevation` - elevation of the level, in meters
+/// * `lines` - polylines of `(lat, lon)` points, the closed ones ending
+///   on their first point
+pub struct Contour {
+    pub elevation: f64,
+    pub lines: Vec<Vec<(f64, f64)>>,
+}
+
+/// Edge between two adjacent posts: `(lat_idx, lon_idx)` of its south or west
+/// post, and whether it runs north (along a longitude line) or east
+type Edge = (usize, usize, bool);
+
+/// Extract the contour lines of every level, at multiples of an interval
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `interval` - elevation between levels, in meters
+///
+/// # Returns
+///
+/// * [Contour]s, from the lowest to the highest level within the elevations,
+///   none if the interval is not positive
+///
+/// # Examples
+///
+/// ```
+/// use dted2::contour;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let contours = contour::contours(&data, 100.0);
+/// assert!(contours.windows(2).all(|c| c[1].elevation - c[0].elevation == 100.0));
+/// assert!(contours.iter().all(|c| c.elevation % 100.0 == 0.0));
+/// ```
+pub fn contours<S: ElevationSource>(source: &S, interval: f64) -> Vec<Contour> {
+    let metadata = source.metadata();
+    let (lat_count, lon_count) = (metadata.count.lat as usize, metadata.count.lon as usize);
+    let z = |lat_idx: usize, lon_idx: usize| source.elevation(lat_idx, lon_idx).map(f64::from);
+    if interval <= 0.0 || !interval.is_finite() {
+        return Vec::new();
+    }
+    let (min, max) = (0..lon_count)
+        .flat_map(|lon_idx| (0..lat_count).filter_map(move |lat_idx| z(lat_idx, lon_idx)))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), z| {
+            (min.min(z), max.max(z))
+        });
+    if min > max {
+        return Vec::new();
+    }
+    let position = |lat_idx: f64, lon_idx: f64| {
+        (
+            metadata.origin.lat + lat_idx * metadata.interval.lat,
+            metadata.origin.lon + lon_idx * metadata.interval.lon,
+        )
+    };
+    ((min / interval).ceil() as i64..=(max / interval).floor() as i64)
+        .map(|level| {
+            let elevation = level as f64 * interval;
+            // crossing of the level along an edge
+            let crossing = |(lat_idx, lon_idx, north): Edge| {
+                let (lat_end, lon_end) = match north {
+                    true => (lat_idx + 1, lon_idx),
+                    false => (lat_idx, lon_idx + 1),
+                };
+                let (z0, z1) = (z(lat_idx, lon_idx)?, z(lat_end, lon_end)?);
+                let t = (elevation - z0) / (z1 - z0);
+                Some(match north {
+                    true => position(lat_idx as f64 + t, lon_idx as f64),
+                    false => position(lat_idx as f64, lon_idx as f64 + t),
+                })
+            };
+            let segments = segments(lat_count, lon_count, elevation, z);
+            let lines = join(&segments)
+                .into_iter()
+                .map(|edges| edges.into_iter().filter_map(crossing).collect())
+                .collect();
+            Contour { elevation, lines }
+        })
+        .collect()
+}
+
+/// Get the segments of the contour lines of a level, from edge to edge of
+/// each square
+///
+/// # Arguments
+///
+/// * `lat_count` - number of latitude points
+/// * `lon_count` - number of longitude lines
+/// * `elevation` - elevation of the level
+/// * `z` - elevation of a post, None if void
+fn segments<F>(lat_count: usize, lon_count: usize, elevation: f64, z: F) -> Vec<(Edge, Edge)>
+where
+    F: Fn(usize, usize) -> Option<f64>,
+{
+    let mut segments = Vec::new();
+    for lon_idx in 0..lon_count.saturating_sub(1) {
+        for lat_idx in 0..lat_count.saturating_sub(1) {
+            // corners counter-clockwise from the south west, each followed
+            // (counter-clockwise) by an edge
+            let corners = [
+                z(lat_idx, lon_idx),
+                z(lat_idx, lon_idx + 1),
+                z(lat_idx + 1, lon_idx + 1),
+                z(lat_idx + 1, lon_idx),
+            ];
+            let Some(corners) = corners.into_iter().collect::<Option<Vec<f64>>>() else {
+                continue;
+            };
+            let edges: [Edge; 4] = [
+                (lat_idx, lon_idx, false),
+                (lat_idx, lon_idx + 1, true),
+                (lat_idx + 1, lon_idx, false),
+                (lat_idx, lon_idx, true),
+            ];
+            let above: Vec<bool> = corners.iter().map(|z| *z >= elevation).collect();
+            let crossed: Vec<Edge> = (0..4)
+                .filter(|idx| above[*idx] != above[(idx + 1) % 4])
+                .map(|idx| edges[idx])
+                .collect();
+            match crossed.len() {
+                2 => segments.push((crossed[0], crossed[1])),
+                4 => {
+                    // saddle, cutting off the corners on the other side of
+                    // the center than their neighbors
+                    let center = corners.iter().sum::<f64>() / 4.0 >= elevation;
+                    segments.extend(
+                        (0..4)
+                            .filter(|idx| above[*idx] != center)
+                            .map(|idx| (edges[(idx + 3) % 4], edges[idx])),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    segments
+}
+
+/// Join segments sharing an edge into polylines
+///
+/// # Arguments
+///
+/// * `segments` - segments between two edges
+///
+/// # Returns
+///
+/// * Edges of each polyline, the open ones first, the closed ones ending on
+///   their first edge
+fn join(segments: &[(Edge, Edge)]) -> Vec<Vec<Edge>> {
+    let mut by_edge: HashMap<Edge, Vec<usize>> = HashMap::new();
+    for (idx, (from, to)) in segments.iter().enumerate() {
+        by_edge.entry(*from).or_default().push(idx);
+        by_edge.entry(*to).or_default().push(idx);
+    }
+    let mut used = vec![false; segments.len()];
+    let mut lines = Vec::new();
+    // open lines start at an edge of a single segment
+    let starts = segments
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, (from, to))| [(idx, *from), (idx, *to)])
+        .filter(|(_, edge)| by_edge[edge].len() == 1)
+        .chain(
+            segments
+                .iter()
+                .enumerate()
+                .map(|(idx, (from, _))| (idx, *from)),
+        );
+    for (start, edge) in starts.collect::<Vec<_>>() {
+        if used[start] {
+            continue;
+        }
+        let mut line = vec![edge];
+        let (mut idx, mut edge) = (start, edge);
+        loop {
+            used[idx] = true;
+            let (from, to) = segments[idx];
+            edge = match from == edge {
+                true => to,
+                false => from,
+            };
+            line.push(edge);
+            match by_edge[&edge].iter().find(|next| !used[**next]) {
+                Some(next) => idx = *next,
+                None => break,
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+impl DTEDData {
+    /// Extract the contour lines of every level, see [contours]
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - elevation between levels, in meters
+    ///
+    /// # Returns
+    ///
+    /// * [Contour]s, from the lowest to the highest level within the elevations
+    pub fn contours(&self, interval: f64) -> Vec<Contour> {
+        contours(self, interval)
+    }
+}
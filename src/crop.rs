@@ -0,0 +1,244 @@
+//! Contains the cropping of a cell to the posts within a bounding box, pulling
+//! a small area of interest out of a (large) cell, either in memory
+//! ([DTEDData::crop]) or as a cell to be written ([RawDTEDFile::crop]).
+//!
+//! The cropped cell keeps the intervals and metadata of the original one,
+//! with its origin moved to the south west post within the box, and its
+//! counts, DSI corners and data records (renumbered from 0, with their
+//! checksums recomputed) cut to the box.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::ops::Range;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{
+    Coverage, DTEDData, DTEDMetadata, RawDTEDDSIRecord, RawDTEDFile, RawDTEDHeader, RawDTEDRecord,
+    RawDTEDRecords,
+};
+use crate::primitives::{Angle, AxisElement, SEC2DEG};
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Posts beyond the bounding box by at most this fraction of the interval are
+/// considered within it, absorbing the rounding of the lat/lon
+const CROP_EPSILON: f64 = 1e-6;
+
+impl DTEDData {
+    /// Crop the cell to the posts within a bounding box
+    ///
+    /// To write the cropped cell as a DTED file, crop the [RawDTEDFile]
+    /// instead, see [RawDTEDFile::crop].
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - south west corner of the box
+    /// * `max` - north east corner of the box
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData] of the posts within the box, or None if there are none
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let crop = data
+    ///     .crop(AxisElement::new(42.5, 15.25), AxisElement::new(42.6, 15.3))
+    ///     .unwrap();
+    /// assert_eq!(crop.min, AxisElement::new(42.5, 15.25));
+    /// assert_eq!(crop.metadata.count, AxisElement::new(361, 181));
+    /// assert_eq!(crop.data.get(0, 0), data.data.get(1800, 900));
+    /// assert!(data
+    ///     .crop(AxisElement::new(40.0, 15.0), AxisElement::new(41.0, 16.0))
+    ///     .is_none());
+    /// ```
+    pub fn crop(&self, min: AxisElement<f64>, max: AxisElement<f64>) -> Option<DTEDData> {
+        let metadata = &self.metadata;
+        let interval_secs_x_10 = AxisElement::new(
+            (metadata.interval_secs.lat * 10.0).round() as u16,
+            (metadata.interval_secs.lon * 10.0).round() as u16,
+        );
+        let posts = post_ranges(metadata.origin, metadata.interval, metadata.count, min, max)?;
+        let origin = crop_origin(metadata.origin_angle, interval_secs_x_10, &posts);
+        let count = AxisElement::new(posts.lat.len() as u16, posts.lon.len() as u16);
+        let metadata = DTEDMetadata {
+            origin: origin.into(),
+            origin_angle: origin,
+            count,
+            dsi_record: metadata
+                .dsi_record
+                .as_ref()
+                .map(|dsi| crop_dsi(dsi, origin, count)),
+            ..metadata.clone()
+        };
+        Some(DTEDData {
+            coverage: crop_coverage(&self.coverage, &posts.lon),
+            ..DTEDData::new(metadata, crop_records(&self.data, &posts))
+        })
+    }
+}
+
+impl RawDTEDFile {
+    /// Crop the file to the posts within a bounding box, see [DTEDData::crop]
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - south west corner of the box
+    /// * `max` - north east corner of the box
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile] of the posts within the box, ready to be written, or
+    ///   None if there are none
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    /// use dted2::primitives::AxisElement;
+    ///
+    /// let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// let crop = file
+    ///     .crop(AxisElement::new(-29.9, -177.5), AxisElement::new(-29.5, -177.0))
+    ///     .unwrap();
+    /// assert_eq!(crop.header.count, AxisElement::new(49, 61));
+    /// assert_eq!(crop.data.get(0, 0), file.data.get(12, 60));
+    ///
+    /// // written and read back
+    /// let read = RawDTEDFile::from_bytes(&crop.to_bytes().unwrap()).unwrap();
+    /// assert_eq!(read, crop);
+    /// ```
+    pub fn crop(&self, min: AxisElement<f64>, max: AxisElement<f64>) -> Option<RawDTEDFile> {
+        let header = &self.header;
+        let interval = header.interval_secs_x_10 / (SEC2DEG * 10.0);
+        let posts = post_ranges(header.origin.into(), interval, header.count, min, max)?;
+        let origin = crop_origin(header.origin, header.interval_secs_x_10, &posts);
+        let count = AxisElement::new(posts.lat.len() as u16, posts.lon.len() as u16);
+        Some(RawDTEDFile {
+            header: RawDTEDHeader {
+                origin,
+                count,
+                ..header.clone()
+            },
+            data: crop_records(&self.data, &posts),
+            dsi_record: self
+                .dsi_record
+                .as_ref()
+                .map(|dsi| crop_dsi(dsi, origin, count)),
+            acc_record: self.acc_record.clone(),
+            coverage: crop_coverage(&self.coverage, &posts.lon),
+        })
+    }
+}
+
+/// Get the indices of the posts within a bounding box
+///
+/// # Arguments
+///
+/// * `origin` - lat/lon of the first (south west) post
+/// * `interval` - lat/lon interval between posts, in degrees
+/// * `count` - number of latitude points and longitude lines
+/// * `min` - south west corner of the box
+/// * `max` - north east corner of the box
+///
+/// # Returns
+///
+/// * Latitude and longitude index ranges, or None if no post is within the box
+pub(crate) fn post_ranges(
+    origin: AxisElement<f64>,
+    interval: AxisElement<f64>,
+    count: AxisElement<u16>,
+    min: AxisElement<f64>,
+    max: AxisElement<f64>,
+) -> Option<AxisElement<Range<usize>>> {
+    let range = |origin: f64, interval: f64, count: u16, min: f64, max: f64| {
+        let first = ((min - origin) / interval - CROP_EPSILON).ceil().max(0.0);
+        let last = ((max - origin) / interval + CROP_EPSILON)
+            .floor()
+            .min(f64::from(count) - 1.0);
+        match first <= last {
+            true => Some(first as usize..last as usize + 1),
+            false => None,
+        }
+    };
+    Some(AxisElement::new(
+        range(origin.lat, interval.lat, count.lat, min.lat, max.lat)?,
+        range(origin.lon, interval.lon, count.lon, min.lon, max.lon)?,
+    ))
+}
+
+/// Get the origin of the cropped posts
+fn crop_origin(
+    origin: AxisElement<Angle>,
+    interval_secs_x_10: AxisElement<u16>,
+    posts: &AxisElement<Range<usize>>,
+) -> AxisElement<Angle> {
+    let offset = |start: usize, interval: u16| start as f64 * f64::from(interval) / 10.0;
+    AxisElement::new(
+        Angle::from_secs(origin.lat.total_secs() + offset(posts.lat.start, interval_secs_x_10.lat)),
+        Angle::from_secs(origin.lon.total_secs() + offset(posts.lon.start, interval_secs_x_10.lon)),
+    )
+}
+
+/// Crop the DSI record, moving its origin and corners and cutting its counts
+fn crop_dsi(
+    dsi: &RawDTEDDSIRecord,
+    origin: AxisElement<Angle>,
+    count: AxisElement<u16>,
+) -> RawDTEDDSIRecord {
+    let extent = AxisElement::new(
+        Angle::from_secs(f64::from(count.lat - 1) * f64::from(dsi.interval_secs_x_10.lat) / 10.0),
+        Angle::from_secs(f64::from(count.lon - 1) * f64::from(dsi.interval_secs_x_10.lon) / 10.0),
+    );
+    RawDTEDDSIRecord {
+        origin,
+        sw_corner: origin,
+        nw_corner: AxisElement::new(origin.lat + extent.lat, origin.lon),
+        ne_corner: origin + extent,
+        se_corner: AxisElement::new(origin.lat, origin.lon + extent.lon),
+        count,
+        ..dsi.clone()
+    }
+}
+
+/// Crop the data records, renumbering them and recomputing their checksums
+fn crop_records(data: &RawDTEDRecords, posts: &AxisElement<Range<usize>>) -> RawDTEDRecords {
+    let mut records = RawDTEDRecords::with_capacity(posts.lat.len(), posts.lon.len());
+    for (lon_idx, column) in posts
+        .lon
+        .clone()
+        .filter_map(|lon| data.column(lon))
+        .enumerate()
+    {
+        let mut record = RawDTEDRecord {
+            blk_count: lon_idx as u32,
+            lon_count: lon_idx as u16,
+            lat_count: 0,
+            elevations: column[posts.lat.clone()].to_vec(),
+            checksum: 0,
+        };
+        record.checksum = record.compute_checksum();
+        records
+            .push(record)
+            .expect("cropped records have the same length");
+    }
+    records
+}
+
+/// Crop the coverage to the cropped longitude lines
+fn crop_coverage(coverage: &Coverage, lons: &Range<usize>) -> Coverage {
+    let start = coverage.lon_range.start.clamp(lons.start, lons.end) - lons.start;
+    let end = coverage.lon_range.end.clamp(lons.start, lons.end) - lons.start;
+    Coverage {
+        lon_range: start..end.max(start),
+        lon_count: lons.len(),
+    }
+}
@@ -0,0 +1,940 @@
+//! Contains [DTEDDataset], answering elevation queries over many DTED cells.
+//!
+//! Each query is routed to the cell covering it, such that a dataset is
+//! queried like a single (larger) cell. Cells are found through a grid index
+//! of whole degree bins, so lookups do not depend on the number of cells.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::cell::OnceCell;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDData, DTEDMetadata};
+use crate::geodesy::LineType;
+#[cfg(feature = "egm96")]
+use crate::geoid::Geoid;
+use crate::interpolation::Interpolation;
+use crate::lazy::LazyDTEDData;
+use crate::options::ParseOptions;
+use crate::primitives::{AxisElement, Elevation};
+use crate::profile::{self, ProfileSample};
+use crate::raster::Raster;
+use crate::reader::DTEDReader;
+use crate::source::ElevationSource;
+use crate::visibility::{self, LineOfSight};
+use crate::Error as DTEDError;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Fractional post indices within this distance of a post are on that post,
+/// when taking posts from a neighboring cell
+const POST_ALIGNMENT_EPSILON: f64 = 1e-6;
+
+/// Dataset of DTED cells
+///
+/// The cells are held by any [ElevationSource], e.g. [DTEDData] (the default)
+/// for fully decoded cells, or [LazyDTEDData] for cells decoded on demand.
+/// Along the edges shared by adjacent cells, queries are answered by the
+/// first cell added.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDData, DTEDDataset, ElevationSource};
+///
+/// let dataset = DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+/// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+/// assert_eq!(dataset.len(), 2);
+/// assert_eq!(dataset.elevation_at(42.52, 15.75), data.elevation_at(42.52, 15.75));
+/// assert!(dataset.elevation_at(-29.5, -177.5).is_some());
+/// assert!(dataset.elevation_at(0.0, 0.0).is_none());
+/// ```
+pub struct DTEDDataset<S: ElevationSource = DTEDData> {
+    cells: Vec<S>,
+    index: HashMap<(i32, i32), Vec<usize>>,
+}
+impl<S: ElevationSource> DTEDDataset<S> {
+    /// Create an empty dataset
+    pub fn new() -> DTEDDataset<S> {
+        DTEDDataset {
+            cells: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Create a dataset from its cells
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - cells, any [ElevationSource]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the cells
+    pub fn from_cells(cells: Vec<S>) -> DTEDDataset<S> {
+        let mut dataset = DTEDDataset::new();
+        cells.into_iter().for_each(|cell| dataset.push(cell));
+        dataset
+    }
+
+    /// Add a cell to the dataset
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - cell, any [ElevationSource]
+    pub fn push(&mut self, cell: S) {
+        let (min, max) = extent(cell.metadata());
+        for bin in bins(min, max) {
+            self.index.entry(bin).or_default().push(self.cells.len());
+        }
+        self.cells.push(cell);
+    }
+
+    /// Get the cells of the dataset, in the order they were added
+    pub fn cells(&self) -> &[S] {
+        &self.cells
+    }
+
+    /// Get the number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the dataset has no cell
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get the bounding box of all of the cells
+    ///
+    /// # Returns
+    ///
+    /// * `(min, max)` lat/lon, or None if the dataset is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    /// use dted2::primitives::AxisElement;
+    ///
+    /// let dataset = DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+    /// assert_eq!(
+    ///     dataset.bounds(),
+    ///     Some((AxisElement::new(-30.0, -178.0), AxisElement::new(43.0, 16.0))),
+    /// );
+    /// ```
+    pub fn bounds(&self) -> Option<(AxisElement<f64>, AxisElement<f64>)> {
+        self.cells
+            .iter()
+            .map(|cell| extent(cell.metadata()))
+            .reduce(|(min, max), (cell_min, cell_max)| {
+                (
+                    AxisElement::new(min.lat.min(cell_min.lat), min.lon.min(cell_min.lon)),
+                    AxisElement::new(max.lat.max(cell_max.lat), max.lon.max(cell_max.lon)),
+                )
+            })
+    }
+
+    /// Get the cell covering a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * The (first) cell covering the lat/lon, or None if not covered
+    pub fn cell_at<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<&S> {
+        self.cell_index_at(lat.into(), lon.into())
+            .map(|idx| &self.cells[idx])
+    }
+
+    /// Get the index of the (first) cell covering a lat/lon
+    fn cell_index_at(&self, lat: f64, lon: f64) -> Option<usize> {
+        self.index
+            .get(&bin(lat, lon))?
+            .iter()
+            .copied()
+            .find(|idx| self.cells[*idx].metadata().get_indices(lat, lon).is_some())
+    }
+
+    /// Get the cells intersecting a bounding box
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - minimum lat/lon
+    /// * `max` - maximum lat/lon
+    ///
+    /// # Returns
+    ///
+    /// * The cells intersecting the bounding box (edges included),
+    ///   in the order they were added
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    /// use dted2::primitives::AxisElement;
+    ///
+    /// let dataset = DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+    /// let cells = dataset.cells_in(AxisElement::new(40.0, 10.0), AxisElement::new(42.5, 15.5));
+    /// assert_eq!(cells.len(), 1);
+    /// assert_eq!(cells[0].metadata.origin.lat, 42.0);
+    /// assert_eq!(dataset.cells_in(AxisElement::new(-90.0, -180.0), AxisElement::new(90.0, 180.0)).len(), 2);
+    /// assert!(dataset.cells_in(AxisElement::new(0.0, 0.0), AxisElement::new(1.0, 1.0)).is_empty());
+    /// ```
+    pub fn cells_in(&self, min: AxisElement<f64>, max: AxisElement<f64>) -> Vec<&S> {
+        let intersects = |cell: &S| {
+            let (cell_min, cell_max) = extent(cell.metadata());
+            cell_min.lat <= max.lat
+                && cell_max.lat >= min.lat
+                && cell_min.lon <= max.lon
+                && cell_max.lon >= min.lon
+        };
+        let bins = bins(min, max);
+        // past as many bins as cells, scanning the cells is cheaper
+        let candidates: BTreeSet<usize> = match bins.len() > self.cells.len() {
+            true => (0..self.cells.len()).collect(),
+            false => bins
+                .filter_map(|bin| self.index.get(&bin))
+                .flatten()
+                .copied()
+                .collect(),
+        };
+        candidates
+            .into_iter()
+            .map(|idx| &self.cells[idx])
+            .filter(|cell| intersects(cell))
+            .collect()
+    }
+
+    /// Get the elevation of the post nearest to a lat/lon,
+    /// see [ElevationSource::elevation_at]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) of the nearest post, or None if not covered
+    ///   or the nearest post is void
+    pub fn elevation_at<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<i16> {
+        let (lat, lon) = (lat.into(), lon.into());
+        self.cell_at(lat, lon)?.elevation_at(lat, lon)
+    }
+
+    /// Get the elevation of the post nearest to a lat/lon, along with the
+    /// absolute vertical accuracy of the cell covering it there,
+    /// see [ElevationSource::elevation_with_accuracy]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) and accuracy (in meters, or None if not
+    ///   available), or None if not covered or the nearest post is void
+    pub fn elevation_with_accuracy<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+    ) -> Option<(i16, Option<u16>)> {
+        let (lat, lon) = (lat.into(), lon.into());
+        self.cell_at(lat, lon)?.elevation_with_accuracy(lat, lon)
+    }
+
+    /// Get the elevations of the posts nearest to many lat/lons,
+    /// see [ElevationSource::elevations_at]
+    ///
+    /// The lat/lons are grouped by cell, with the posts of each cell queried
+    /// in the order they are stored, for locality
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - `(lat, lon)` pairs
+    ///
+    /// # Returns
+    ///
+    /// * Elevations (in meters) in the order of the lat/lons, each None if not
+    ///   covered or the nearest post is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    ///
+    /// let dataset = DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+    /// let points = [(42.52, 15.75), (-29.5, -177.5), (0.0, 0.0)];
+    /// let elevations = dataset.elevations_at(&points);
+    /// assert_eq!(elevations[0], dataset.elevation_at(42.52, 15.75));
+    /// assert_eq!(elevations[1], dataset.elevation_at(-29.5, -177.5));
+    /// assert_eq!(elevations[2], None);
+    /// ```
+    pub fn elevations_at(&self, points: &[(f64, f64)]) -> Vec<Option<i16>> {
+        let mut posts: Vec<(usize, usize, usize, usize)> = points
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (lat, lon))| {
+                let cell = self.cell_index_at(*lat, *lon)?;
+                let (lat_idx, lon_idx) = self.cells[cell].metadata().index_of(*lat, *lon)?;
+                Some((cell, lon_idx, lat_idx, idx))
+            })
+            .collect();
+        posts.sort_unstable();
+        let mut elevations = vec![None; points.len()];
+        for (cell, lon_idx, lat_idx, idx) in posts {
+            elevations[idx] = self.cells[cell].elevation(lat_idx, lon_idx);
+        }
+        elevations
+    }
+
+    /// Get the bilinearly interpolated elevation at a lat/lon,
+    /// see [ElevationSource::elevation_at_interpolated]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if not covered or void
+    pub fn elevation_at_interpolated<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+    ) -> Option<f64> {
+        self.elevation_at_with(lat, lon, Interpolation::Bilinear)
+    }
+
+    /// Get the elevation at a lat/lon, using the given [Interpolation] method,
+    /// see [ElevationSource::elevation_at_with]
+    ///
+    /// Posts beyond the edges of the cell covering the lat/lon are taken from
+    /// the neighboring cells (interpolated if their posts do not line up),
+    /// such that elevations are continuous across cell edges
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if not covered or void
+    pub fn elevation_at_with<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+        method: Interpolation,
+    ) -> Option<f64> {
+        let (lat, lon) = (lat.into(), lon.into());
+        let cell = self.cell_at(lat, lon)?;
+        let (lat_idx, lon_idx) = cell.metadata().get_indices(lat, lon)?;
+        method.interpolate(lat_idx, lon_idx, |lat_idx, lon_idx| {
+            self.post(cell, lat_idx, lon_idx)
+        })
+    }
+
+    /// Get the elevation at a lat/lon as an [Elevation], to be read in
+    /// meters or feet, see [ElevationSource::elevation_at_typed]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * [Elevation] or None if not covered or void
+    pub fn elevation_at_typed<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+        method: Interpolation,
+    ) -> Option<Elevation> {
+        self.elevation_at_with(lat, lon, method)
+            .map(Elevation::from_meters)
+    }
+
+    /// Get the height above the WGS84 ellipsoid at a lat/lon (requires the
+    /// `egm96` feature), see [ElevationSource::elevation_at_ellipsoidal]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `geoid` - [Geoid], e.g. the EGM96 grid
+    ///
+    /// # Returns
+    ///
+    /// * Height (in meters) or None if not covered or void
+    #[cfg(feature = "egm96")]
+    pub fn elevation_at_ellipsoidal(&self, lat: f64, lon: f64, geoid: &Geoid) -> Option<f64> {
+        let elevation = self.elevation_at_interpolated(lat, lon)?;
+        Some(geoid.to_ellipsoidal(lat, lon, elevation))
+    }
+
+    /// Get the elevation profile along the great circle between two lat/lons,
+    /// bilinearly interpolated, see [ElevationSource::profile]
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - `(lat, lon)` of the first sample
+    /// * `end` - `(lat, lon)` of the last sample
+    /// * `num_samples` - number of samples, including both ends
+    ///
+    /// # Returns
+    ///
+    /// * [ProfileSample]s, from the start to the end
+    pub fn profile(
+        &self,
+        start: (f64, f64),
+        end: (f64, f64),
+        num_samples: usize,
+    ) -> Vec<ProfileSample> {
+        self.profile_with(
+            start,
+            end,
+            num_samples,
+            LineType::GreatCircle,
+            Interpolation::Bilinear,
+        )
+    }
+
+    /// Get the elevation profile along a line between two lat/lons,
+    /// see [ElevationSource::profile_with]
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - `(lat, lon)` of the first sample
+    /// * `end` - `(lat, lon)` of the last sample
+    /// * `num_samples` - number of samples, including both ends
+    /// * `line` - [LineType] of the line
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * [ProfileSample]s, from the start to the end
+    pub fn profile_with(
+        &self,
+        start: (f64, f64),
+        end: (f64, f64),
+        num_samples: usize,
+        line: LineType,
+        method: Interpolation,
+    ) -> Vec<ProfileSample> {
+        profile::sample(start, end, num_samples, line, |lat, lon| {
+            self.elevation_at_with(lat, lon, method)
+        })
+    }
+
+    /// Get the line of sight between an observer and a target,
+    /// see [ElevationSource::line_of_sight]
+    ///
+    /// The profile is sampled every half post of the cell of the observer
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - `(lat, lon)` of the observer
+    /// * `observer_height` - height of the observer above the terrain, in meters
+    /// * `target` - `(lat, lon)` of the target
+    /// * `target_height` - height of the target above the terrain, in meters
+    ///
+    /// # Returns
+    ///
+    /// * [LineOfSight], or None if the elevation of the observer or target is unknown
+    pub fn line_of_sight(
+        &self,
+        observer: (f64, f64),
+        observer_height: f64,
+        target: (f64, f64),
+        target_height: f64,
+    ) -> Option<LineOfSight> {
+        let cell = self.cell_at(observer.0, observer.1)?;
+        let distance = LineType::GreatCircle.distance(observer, target);
+        let num_samples = visibility::num_samples(cell.metadata(), distance);
+        let profile = self.profile(observer, target, num_samples);
+        LineOfSight::from_profile(&profile, observer_height, target_height)
+    }
+
+    /// Get the horizon around a point on the terrain,
+    /// see [ElevationSource::horizon_profile]
+    ///
+    /// The terrain is sampled every half post of the cell of the point
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - `(lat, lon)` of the observer, on the terrain
+    /// * `azimuth_step` - angle between azimuths, in degrees
+    /// * `max_range` - range of the terrain masking the horizon, in meters
+    ///
+    /// # Returns
+    ///
+    /// * Masking angle (in degrees above the horizontal) per azimuth, clockwise
+    ///   from north, None if the elevation of the observer or of the terrain
+    ///   in that azimuth is unknown
+    pub fn horizon_profile(
+        &self,
+        point: (f64, f64),
+        azimuth_step: f64,
+        max_range: f64,
+    ) -> Vec<Option<f64>> {
+        let spacing = match self.cell_at(point.0, point.1) {
+            Some(cell) => visibility::sample_spacing(cell.metadata()),
+            None => 0.0,
+        };
+        visibility::horizon(point, azimuth_step, max_range, spacing, |lat, lon| {
+            self.elevation_at_interpolated(lat, lon)
+        })
+    }
+
+    /// Stitch the cells into a single grid of elevations, bilinearly
+    /// interpolated, see [DTEDDataset::mosaic_with]
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - south west corner of the grid, its first post
+    /// * `max` - north east corner of the grid
+    /// * `interval` - lat/lon interval between the posts of the grid, in degrees
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the elevations, None where not covered or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::DTEDDataset;
+    ///
+    /// let dataset = DTEDDataset::read(&["tests/test_data.dt2"]).unwrap();
+    /// let mosaic = dataset.mosaic(
+    ///     AxisElement::new(42.5, 14.5),
+    ///     AxisElement::new(43.0, 15.5),
+    ///     AxisElement::new(0.01, 0.01),
+    /// );
+    /// assert_eq!(mosaic.count, AxisElement::new(51, 101));
+    /// assert_eq!(mosaic.get(0, 0), Some(&None));
+    /// assert_eq!(
+    ///     *mosaic.get(10, 70).unwrap(),
+    ///     dataset.elevation_at_interpolated(42.6, 15.2)
+    /// );
+    /// ```
+    pub fn mosaic(
+        &self,
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+        interval: AxisElement<f64>,
+    ) -> Raster<Option<f64>> {
+        self.mosaic_with(min, max, interval, Interpolation::Bilinear)
+    }
+
+    /// Stitch the cells into a single grid of elevations
+    ///
+    /// Each post of the grid is sampled from the cell covering it, taking
+    /// posts beyond its edges from the neighboring cells (see
+    /// [DTEDDataset::elevation_at_with]), such that the edge posts duplicated
+    /// by adjacent cells appear once and the grid is seamless. At the
+    /// interval of the cells, with the posts lined up, the grid holds the
+    /// posts of the cells as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - south west corner of the grid, its first post
+    /// * `max` - north east corner of the grid
+    /// * `interval` - lat/lon interval between the posts of the grid, in degrees
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the elevations, None where not covered or void
+    pub fn mosaic_with(
+        &self,
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+        interval: AxisElement<f64>,
+        method: Interpolation,
+    ) -> Raster<Option<f64>> {
+        let count = |min: f64, max: f64, interval: f64| match max >= min {
+            true => ((max - min) / interval + POST_ALIGNMENT_EPSILON).floor() as usize + 1,
+            false => 0,
+        };
+        let count = AxisElement::new(
+            count(min.lat, max.lat, interval.lat),
+            count(min.lon, max.lon, interval.lon),
+        );
+        Raster::from_fn(min, interval, count, |lat_idx, lon_idx| {
+            let lat = min.lat + lat_idx as f64 * interval.lat;
+            let lon = min.lon + lon_idx as f64 * interval.lon;
+            let cell = self.cell_at(lat, lon)?;
+            let (lat_idx, lon_idx) = cell.metadata().get_indices(lat, lon)?;
+            // on a post, up to rounding
+            let (lat_post, lon_post) = (lat_idx.round(), lon_idx.round());
+            match (lat_idx - lat_post).abs() < POST_ALIGNMENT_EPSILON
+                && (lon_idx - lon_post).abs() < POST_ALIGNMENT_EPSILON
+            {
+                true => self.post(cell, lat_post as isize, lon_post as isize),
+                false => method.interpolate(lat_idx, lon_idx, |lat_idx, lon_idx| {
+                    self.post(cell, lat_idx, lon_idx)
+                }),
+            }
+        })
+    }
+
+    /// Get the elevation of a post of a cell, taking posts beyond its edges
+    /// from the neighboring cells
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - cell
+    /// * `lat_idx` - latitude index within the cell, possibly out of bounds
+    /// * `lon_idx` - longitude index within the cell, possibly out of bounds
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if not covered or void
+    fn post(&self, cell: &S, lat_idx: isize, lon_idx: isize) -> Option<f64> {
+        let metadata = cell.metadata();
+        if let (Ok(lat), Ok(lon)) = (usize::try_from(lat_idx), usize::try_from(lon_idx)) {
+            if lat < metadata.count.lat as usize && lon < metadata.count.lon as usize {
+                return cell.elevation(lat, lon).map(f64::from);
+            }
+        }
+        let lat = metadata.origin.lat + lat_idx as f64 * metadata.interval.lat;
+        let lon = metadata.origin.lon + lon_idx as f64 * metadata.interval.lon;
+        let neighbor = self.cell_at(lat, lon)?;
+        let (lat_idx, lon_idx) = neighbor.metadata().get_indices(lat, lon)?;
+        match (lat_idx.round(), lon_idx.round()) {
+            (lat_post, lon_post)
+                if (lat_idx - lat_post).abs() < POST_ALIGNMENT_EPSILON
+                    && (lon_idx - lon_post).abs() < POST_ALIGNMENT_EPSILON =>
+            {
+                neighbor
+                    .elevation(lat_post as usize, lon_post as usize)
+                    .map(f64::from)
+            }
+            _ => neighbor.elevation_at_interpolated(lat, lon),
+        }
+    }
+}
+impl<S: ElevationSource> Default for DTEDDataset<S> {
+    fn default() -> DTEDDataset<S> {
+        DTEDDataset::new()
+    }
+}
+impl DTEDDataset<DTEDData> {
+    /// Read DTED files into a dataset
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - paths to the DTED files
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the files, in order
+    pub fn read(paths: &[&str]) -> Result<DTEDDataset<DTEDData>, DTEDError> {
+        DTEDDataset::read_with(paths, &ParseOptions::default())
+    }
+
+    /// Read DTED files into a dataset, as strictly as per the [ParseOptions]
+    ///
+    /// Files are read in turn, any progress callback being invoked for each
+    /// file (see [ParseOptions::on_progress])
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - paths to the DTED files
+    /// * `options` - [ParseOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the files, in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDDataset, ParseOptions};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let files = Arc::new(AtomicUsize::new(0));
+    /// let done = files.clone();
+    /// let options = ParseOptions::default().on_progress(1000, move |progress| {
+    ///     if progress.records == progress.total_records {
+    ///         done.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// });
+    /// let paths = ["tests/test_data.dt2", "tests/test_data_negative.dt2"];
+    /// let dataset = DTEDDataset::read_with(&paths, &options).unwrap();
+    /// assert_eq!(dataset.len(), 2);
+    /// assert_eq!(files.load(Ordering::Relaxed), 2);
+    /// ```
+    pub fn read_with(
+        paths: &[&str],
+        options: &ParseOptions,
+    ) -> Result<DTEDDataset<DTEDData>, DTEDError> {
+        paths
+            .iter()
+            .map(|path| DTEDData::read_with(path, options))
+            .collect::<Result<_, _>>()
+            .map(DTEDDataset::from_cells)
+    }
+}
+impl DTEDDataset<LazyDTEDData> {
+    /// Read DTED files into a dataset, decoding their data records on demand,
+    /// see [LazyDTEDData]
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - paths to the DTED files
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the files, in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    ///
+    /// let dataset = DTEDDataset::read_lazy(&["tests/test_data.dt2"]).unwrap();
+    /// assert!(dataset.elevation_at(42.52, 15.75).is_some());
+    /// assert_eq!(dataset.cells()[0].decoded_records(), 1);
+    /// ```
+    pub fn read_lazy(paths: &[&str]) -> Result<DTEDDataset<LazyDTEDData>, DTEDError> {
+        paths
+            .iter()
+            .map(|path| LazyDTEDData::read(path))
+            .collect::<Result<_, _>>()
+            .map(DTEDDataset::from_cells)
+    }
+}
+
+impl DTEDDataset<DTEDCellHandle> {
+    /// Scan a directory tree for DTED files, reading their header records
+    /// only, see [DTEDCellHandle]
+    ///
+    /// Files are recognized by their names (see [cell_origin]), either
+    /// `n34_w119.dt2` or the classic `w119/n34.dt2` layout, others being ignored.
+    /// Symbolic links to directories are not followed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the directory
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the files, sorted by path
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    ///
+    /// // the test files are not named after their cells
+    /// let dataset = DTEDDataset::from_dir("tests").unwrap();
+    /// assert!(dataset.is_empty());
+    /// assert!(DTEDDataset::from_dir("tests/missing").is_err());
+    /// ```
+    pub fn from_dir(path: &str) -> Result<DTEDDataset<DTEDCellHandle>, DTEDError> {
+        let mut paths = Vec::new();
+        find_cells(Path::new(path), &mut paths)?;
+        paths.sort();
+        paths
+            .iter()
+            .map(|path| match path.to_str() {
+                Some(path) => DTEDCellHandle::open(path),
+                None => Err(DTEDError::InvalidInput(format!(
+                    "non UTF-8 path {:?}",
+                    path
+                ))),
+            })
+            .collect::<Result<_, _>>()
+            .map(DTEDDataset::from_cells)
+    }
+}
+
+/// Handle to a DTED file, whose header records are read upfront, with the
+/// file only opened on the first query and then read by seeking, see [DTEDReader]
+///
+/// Queries through [ElevationSource] report I/O errors as None.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDData, ElevationSource};
+/// use dted2::dataset::DTEDCellHandle;
+///
+/// let handle = DTEDCellHandle::open("tests/test_data.dt2").unwrap();
+/// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+/// assert!(!handle.is_opened());
+/// assert_eq!(handle.elevation_at(42.52, 15.75), data.elevation_at(42.52, 15.75));
+/// assert!(handle.is_opened());
+/// ```
+pub struct DTEDCellHandle {
+    metadata: DTEDMetadata,
+    reader: OnceCell<Option<DTEDReader<File>>>,
+}
+impl DTEDCellHandle {
+    /// Create a handle, reading the header records of a DTED file,
+    /// see [DTEDData::read_metadata]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDCellHandle]: handle to the file
+    pub fn open(path: &str) -> Result<DTEDCellHandle, DTEDError> {
+        Ok(DTEDCellHandle {
+            metadata: DTEDData::read_metadata(path)?,
+            reader: OnceCell::new(),
+        })
+    }
+
+    /// Whether the file was opened (by a query)
+    pub fn is_opened(&self) -> bool {
+        self.reader.get().is_some()
+    }
+
+    /// Get the seeking reader, opening the file on first access
+    fn reader(&self) -> Option<&DTEDReader<File>> {
+        self.reader
+            .get_or_init(|| {
+                let file = File::open(&self.metadata.filename).ok()?;
+                DTEDReader::new(file).ok()
+            })
+            .as_ref()
+    }
+}
+impl ElevationSource for DTEDCellHandle {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.reader()?.raw_elevation(lat_idx, lon_idx)
+    }
+}
+
+/// Get the origin of a DTED cell from the path of its file, following the
+/// DTED naming conventions: either `n34_w119.dt2`, or the classic layout of
+/// `w119/n34.dt2` with a directory per longitude
+///
+/// # Arguments
+///
+/// * `path` - path to the file (`.dt0`, `.dt1` or `.dt2`, in any case)
+///
+/// # Returns
+///
+/// * Origin lat/lon (in degrees), or None if not named after a cell
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dataset::cell_origin;
+/// use dted2::primitives::AxisElement;
+/// use std::path::Path;
+///
+/// assert_eq!(cell_origin(Path::new("n34_w119.dt2")), Some(AxisElement::new(34, -119)));
+/// assert_eq!(cell_origin(Path::new("dted/W119/S34.DT1")), Some(AxisElement::new(-34, -119)));
+/// assert_eq!(cell_origin(Path::new("dted/n34.dt0")), None);
+/// assert_eq!(cell_origin(Path::new("n34_w119.tif")), None);
+/// ```
+pub fn cell_origin(path: &Path) -> Option<AxisElement<i16>> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !matches!(extension.as_str(), "dt0" | "dt1" | "dt2") {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    let (lat, lon) = match stem.split_once('_') {
+        Some((lat, lon)) => (lat.to_string(), lon.to_string()),
+        None => {
+            let dir = path.parent()?.file_name()?.to_str()?.to_ascii_lowercase();
+            (stem, dir)
+        }
+    };
+    Some(AxisElement::new(
+        hemisphere_degrees(&lat, 'n', 's', 90)?,
+        hemisphere_degrees(&lon, 'e', 'w', 180)?,
+    ))
+}
+
+/// Parse signed degrees from a hemisphere letter followed by digits (e.g. `w119`)
+pub(crate) fn hemisphere_degrees(
+    name: &str,
+    positive: char,
+    negative: char,
+    max: i16,
+) -> Option<i16> {
+    let mut chars = name.chars();
+    let sign = match chars.next()? {
+        c if c == positive => 1,
+        c if c == negative => -1,
+        _ => return None,
+    };
+    let digits = chars.as_str();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match digits.parse::<i16>().ok()? {
+        deg if deg <= max => Some(sign * deg),
+        _ => None,
+    }
+}
+
+/// Recursively collect the paths of the files named after a cell, see [cell_origin]
+///
+/// Symbolic links to directories are not followed, which could loop.
+fn find_cells(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), DTEDError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            find_cells(&path, paths)?;
+        } else if cell_origin(&path).is_some() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Grid index bin (whole degrees) of a lat/lon
+fn bin(lat: f64, lon: f64) -> (i32, i32) {
+    (lat.floor() as i32, lon.floor() as i32)
+}
+
+/// Grid index bins overlapping an extent (edges included)
+fn bins(min: AxisElement<f64>, max: AxisElement<f64>) -> impl ExactSizeIterator<Item = (i32, i32)> {
+    let (min, max) = (bin(min.lat, min.lon), bin(max.lat, max.lon));
+    let lon_bins = (max.1 - min.1 + 1).max(0) as usize;
+    let lat_bins = (max.0 - min.0 + 1).max(0) as usize;
+    (0..lat_bins * lon_bins).map(move |idx| {
+        (
+            min.0 + (idx / lon_bins) as i32,
+            min.1 + (idx % lon_bins) as i32,
+        )
+    })
+}
+
+/// Extent of a cell, from its first to its last post
+pub(crate) fn extent(metadata: &DTEDMetadata) -> (AxisElement<f64>, AxisElement<f64>) {
+    let (south, west, north, east) = metadata.bounds();
+    (AxisElement::new(south, west), AxisElement::new(north, east))
+}
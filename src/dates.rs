@@ -0,0 +1,97 @@
+//! Contains the conversion of the `YYMM` dates of the DSI record into
+//! [NaiveDate]s (requires the `chrono` feature).
+//!
+//! The dates only have a month, so they fall on its first day. Years `00` to
+//! `49` are in the 2000s and `50` to `99` in the 1900s, DTED production
+//! having started in the 1970s. The all-zero `0000` encoding (not applicable)
+//! and invalid months are [None].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use chrono::NaiveDate;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::RawDTEDDSIRecord;
+
+/// Convert a `YYMM` date into a [NaiveDate], on the first day of the month
+///
+/// # Arguments
+///
+/// * `yymm` - date, as the number of the `YYMM` field
+///
+/// # Returns
+///
+/// * The date, or None for `0000` (not applicable) or an invalid month
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use dted2::dates::yymm_to_date;
+///
+/// assert_eq!(yymm_to_date(9712), NaiveDate::from_ymd_opt(1997, 12, 1));
+/// assert_eq!(yymm_to_date(205), NaiveDate::from_ymd_opt(2002, 5, 1));
+/// assert_eq!(yymm_to_date(0), None);
+/// assert_eq!(yymm_to_date(9713), None);
+/// ```
+pub fn yymm_to_date(yymm: u16) -> Option<NaiveDate> {
+    if yymm == 0 {
+        return None;
+    }
+    let (yy, month) = (yymm / 100, yymm % 100);
+    let year = if yy < 50 { 2000 + yy } else { 1900 + yy };
+    NaiveDate::from_ymd_opt(year as i32, month as u32, 1)
+}
+
+impl RawDTEDDSIRecord {
+    /// Get the maintenance date, see [yymm_to_date]
+    ///
+    /// # Returns
+    ///
+    /// * The date, or None if blank or not applicable
+    pub fn maintenance_naive_date(&self) -> Option<NaiveDate> {
+        self.maintenance_date.and_then(yymm_to_date)
+    }
+
+    /// Get the match/merge date, see [yymm_to_date]
+    ///
+    /// # Returns
+    ///
+    /// * The date, or None if blank or not applicable
+    pub fn match_merge_naive_date(&self) -> Option<NaiveDate> {
+        self.match_merge_date.and_then(yymm_to_date)
+    }
+
+    /// Get the date of the product specification, see [yymm_to_date]
+    ///
+    /// # Returns
+    ///
+    /// * The date, or None if blank or not applicable
+    pub fn product_spec_naive_date(&self) -> Option<NaiveDate> {
+        self.product_spec_date.and_then(yymm_to_date)
+    }
+
+    /// Get the compilation date, see [yymm_to_date]
+    ///
+    /// # Returns
+    ///
+    /// * The date, or None if blank or not applicable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use dted2::DTEDData;
+    ///
+    /// let metadata = DTEDData::read("tests/test_data.dt2").unwrap().metadata;
+    /// let dsi = metadata.dsi_record.unwrap();
+    /// assert_eq!(dsi.compilation_naive_date(), NaiveDate::from_ymd_opt(2000, 2, 1));
+    /// assert_eq!(dsi.maintenance_naive_date(), None);
+    /// ```
+    pub fn compilation_naive_date(&self) -> Option<NaiveDate> {
+        self.compilation_date.and_then(yymm_to_date)
+    }
+}
@@ -0,0 +1,143 @@
+//! Contains the comparison of two cells post by post ([DTEDData::diff]),
+//! e.g. two editions of the same cell, into a [DTEDDiff] of the differences
+//! and their summary.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::primitives::AxisElement;
+use crate::raster::Raster;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Differences between the posts of two cells of the same grid
+///
+/// # Fields
+///
+/// * `differences` - [Raster] of the difference (other minus this, in
+///   meters) of each post, None where either post is void
+/// * `compared_count` - number of posts non-void in both cells
+/// * `changed_count` - number of posts whose stored values differ, changes
+///   from or to void included
+/// * `void_changed_count` - number of posts void in only one of the cells
+/// * `max_abs_diff` - largest absolute difference (in meters), None if no
+///   post was compared
+/// * `rmse` - root mean square of the differences (in meters), None if no
+///   post was compared
+pub struct DTEDDiff {
+    pub differences: Raster<Option<i32>>,
+    pub compared_count: usize,
+    pub changed_count: usize,
+    pub void_changed_count: usize,
+    pub max_abs_diff: Option<u32>,
+    pub rmse: Option<f64>,
+}
+impl DTEDDiff {
+    /// Whether the cells hold the same values
+    pub fn is_identical(&self) -> bool {
+        self.changed_count == 0
+    }
+}
+
+impl DTEDData {
+    /// Compare the posts with those of another cell of the same grid
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - other [DTEDData], e.g. another edition of the cell
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDiff] of the posts, or an [DTEDError::InvalidInput] if the
+    ///   cells do not have the same origin, intervals and counts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let diff = data.diff(&data).unwrap();
+    /// assert!(diff.is_identical());
+    /// assert_eq!(diff.compared_count, 121 * 121);
+    /// assert_eq!((diff.max_abs_diff, diff.rmse), (Some(0), Some(0.0)));
+    ///
+    /// let other = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(data.diff(&other).is_err());
+    /// ```
+    pub fn diff(&self, other: &DTEDData) -> Result<DTEDDiff, DTEDError> {
+        let (this, that) = (&self.metadata, &other.metadata);
+        let mismatch = |what: &str, this: String, that: String| {
+            Err(DTEDError::InvalidInput(format!(
+                "can not compare cells of different {}: {} and {}",
+                what, this, that
+            )))
+        };
+        let pair =
+            |lat: &dyn std::fmt::Display, lon: &dyn std::fmt::Display| format!("{}, {}", lat, lon);
+        if this.origin_angle != that.origin_angle {
+            return mismatch(
+                "origins",
+                pair(&this.origin.lat, &this.origin.lon),
+                pair(&that.origin.lat, &that.origin.lon),
+            );
+        }
+        if this.interval_secs != that.interval_secs {
+            return mismatch(
+                "intervals",
+                pair(&this.interval_secs.lat, &this.interval_secs.lon),
+                pair(&that.interval_secs.lat, &that.interval_secs.lon),
+            );
+        }
+        if this.count != that.count {
+            return mismatch(
+                "counts",
+                pair(&this.count.lat, &this.count.lon),
+                pair(&that.count.lat, &that.count.lon),
+            );
+        }
+        let count = this.count;
+        let mut diff = DTEDDiff {
+            differences: Raster::from_fn(
+                this.origin,
+                this.interval,
+                AxisElement::new(usize::from(count.lat), usize::from(count.lon)),
+                |lat_idx, lon_idx| {
+                    let (this, that) = (
+                        self.elevation(lat_idx, lon_idx)?,
+                        other.elevation(lat_idx, lon_idx)?,
+                    );
+                    Some(i32::from(that) - i32::from(this))
+                },
+            ),
+            compared_count: 0,
+            changed_count: 0,
+            void_changed_count: 0,
+            max_abs_diff: None,
+            rmse: None,
+        };
+        let mut sum_sq = 0_u64;
+        for lon_idx in 0..usize::from(count.lon) {
+            for lat_idx in 0..usize::from(count.lat) {
+                let (this, that) = (self.post(lat_idx, lon_idx), other.post(lat_idx, lon_idx));
+                diff.changed_count += usize::from(this != that);
+                diff.void_changed_count += usize::from(
+                    this.is_some_and(|post| post.is_void())
+                        != that.is_some_and(|post| post.is_void()),
+                );
+                if let Some(Some(difference)) = diff.differences.get(lat_idx, lon_idx) {
+                    let abs_diff = difference.unsigned_abs();
+                    diff.compared_count += 1;
+                    diff.max_abs_diff = diff.max_abs_diff.max(Some(abs_diff));
+                    sum_sq += u64::from(abs_diff) * u64::from(abs_diff);
+                }
+            }
+        }
+        if diff.compared_count > 0 {
+            diff.rmse = Some((sum_sq as f64 / diff.compared_count as f64).sqrt());
+        }
+        Ok(diff)
+    }
+}
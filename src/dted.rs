@@ -5,25 +5,139 @@
 // --------------------------------------------------
 // external
 // --------------------------------------------------
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "std")]
 use std::io::Read;
+use std::ops::{Index, IndexMut, Range};
 use thisenum::Const;
 
 // --------------------------------------------------
 // local
 // --------------------------------------------------
+#[cfg(feature = "std")]
+use crate::lazy::LazyDTEDData;
+use crate::options::{ParseDiagnostic, ParseOptions, ParseProgress, ProgressCallback};
 use crate::parsers;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::primitives::{self, Angle, AxisElement};
+use crate::producer::Producer;
+use crate::source::ElevationSource;
+#[cfg(feature = "std")]
+use crate::stream::DTEDStreamReader;
+#[cfg(feature = "std")]
+use crate::writers;
 use crate::Error as DTEDError;
+use crate::{ErrorContext, Section};
 
 // --------------------------------------------------
 // constants
 // --------------------------------------------------
 /// User Header Label (UHL) Length
-pub const DT2_UHL_LENGTH: u64 = 80;
+pub const DTED_UHL_LENGTH: u64 = 80;
 /// Data Set Identification (DSI) Record Length
-pub const DT2_DSI_RECORD_LENGTH: usize = 648;
+pub const DTED_DSI_RECORD_LENGTH: usize = 648;
 /// Accuracy Description (ACC) Record Length
-pub const DT2_ACC_RECORD_LENGTH: usize = 2700;
+pub const DTED_ACC_RECORD_LENGTH: usize = 2700;
+/// Data Record header (sentinel, block count, longitude count, latitude count) Length
+pub const DTED_RECORD_HEADER_LENGTH: usize = 8;
+/// Data Record checksum Length
+pub const DTED_RECORD_CHECKSUM_LENGTH: usize = 4;
+/// Offset of the first Data Record, after the UHL, DSI and ACC records
+pub const DTED_DATA_OFFSET: usize =
+    DTED_UHL_LENGTH as usize + DTED_DSI_RECORD_LENGTH + DTED_ACC_RECORD_LENGTH;
+/// Elevation value of a void (null) post
+pub const DTED_VOID_ELEVATION: i16 = -32767;
+/// Maximum number of accuracy sub-regions in an ACC record
+pub const DTED_ACC_MAX_SUBREGIONS: usize = 9;
+/// Length of a single accuracy sub-region within an ACC record
+pub const DTED_ACC_SUBREGION_LENGTH: usize = 284;
+/// Maximum number of coordinates in an accuracy sub-region outline
+pub const DTED_ACC_MAX_SUBREGION_COORDS: usize = 14;
+/// Tolerance (in degrees) of a lat/lon on the outline of an accuracy sub-region
+const SUBREGION_EPSILON: f64 = 1e-9;
+
+// --------------------------------------------------
+// deprecated constants, named after DTED Level 2
+// although they apply to every level
+// --------------------------------------------------
+#[deprecated(note = "use DTED_UHL_LENGTH")]
+pub const DT2_UHL_LENGTH: u64 = DTED_UHL_LENGTH;
+#[deprecated(note = "use DTED_DSI_RECORD_LENGTH")]
+pub const DT2_DSI_RECORD_LENGTH: usize = DTED_DSI_RECORD_LENGTH;
+#[deprecated(note = "use DTED_ACC_RECORD_LENGTH")]
+pub const DT2_ACC_RECORD_LENGTH: usize = DTED_ACC_RECORD_LENGTH;
+#[deprecated(note = "use DTED_RECORD_HEADER_LENGTH")]
+pub const DT2_RECORD_HEADER_LENGTH: usize = DTED_RECORD_HEADER_LENGTH;
+#[deprecated(note = "use DTED_RECORD_CHECKSUM_LENGTH")]
+pub const DT2_RECORD_CHECKSUM_LENGTH: usize = DTED_RECORD_CHECKSUM_LENGTH;
+#[deprecated(note = "use DTED_DATA_OFFSET")]
+pub const DT2_DATA_OFFSET: usize = DTED_DATA_OFFSET;
+#[deprecated(note = "use DTED_VOID_ELEVATION")]
+pub const DT2_VOID_ELEVATION: i16 = DTED_VOID_ELEVATION;
+#[deprecated(note = "use DTED_ACC_MAX_SUBREGIONS")]
+pub const DT2_ACC_MAX_SUBREGIONS: usize = DTED_ACC_MAX_SUBREGIONS;
+#[deprecated(note = "use DTED_ACC_SUBREGION_LENGTH")]
+pub const DT2_ACC_SUBREGION_LENGTH: usize = DTED_ACC_SUBREGION_LENGTH;
+#[deprecated(note = "use DTED_ACC_MAX_SUBREGION_COORDS")]
+pub const DT2_ACC_MAX_SUBREGION_COORDS: usize = DTED_ACC_MAX_SUBREGION_COORDS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A DTED post, which is either an elevation or void (no data)
+///
+/// * Elevation - elevation in meters
+/// * Void - void post, stored as [DTED_VOID_ELEVATION]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{Post, DTED_VOID_ELEVATION};
+///
+/// assert_eq!(Post::from_raw(120), Post::Elevation(120));
+/// assert_eq!(Post::from_raw(DTED_VOID_ELEVATION), Post::Void);
+/// assert_eq!(Post::Void.elevation(), None);
+/// assert_eq!(Post::Void.raw(), DTED_VOID_ELEVATION);
+/// ```
+pub enum Post {
+    Elevation(i16),
+    Void,
+}
+impl Post {
+    /// Create a post from its raw (stored) value
+    pub fn from_raw(raw: i16) -> Post {
+        match raw {
+            DTED_VOID_ELEVATION => Post::Void,
+            elev => Post::Elevation(elev),
+        }
+    }
+
+    /// Get the raw (stored) value of the post
+    pub fn raw(self) -> i16 {
+        match self {
+            Post::Elevation(elev) => elev,
+            Post::Void => DTED_VOID_ELEVATION,
+        }
+    }
+
+    /// Get the elevation (in meters) of the post, or None if void
+    pub fn elevation(self) -> Option<i16> {
+        match self {
+            Post::Elevation(elev) => Some(elev),
+            Post::Void => None,
+        }
+    }
+
+    /// Whether the post is void
+    pub fn is_void(self) -> bool {
+        self == Post::Void
+    }
+}
+impl From<i16> for Post {
+    fn from(raw: i16) -> Post {
+        Post::from_raw(raw)
+    }
+}
 
 #[derive(Const)]
 #[armtype(&[u8])]
@@ -39,7 +153,7 @@ pub const DT2_ACC_RECORD_LENGTH: usize = 2700;
 /// use dted2::dted::RecognitionSentinel;
 ///
 /// assert_eq!(RecognitionSentinel::UHL.value(), b"UHL1");
-/// assert_eq!(RecognitionSentinel::DSI.value(), b"DSIU");
+/// assert_eq!(RecognitionSentinel::DSI.value(), b"DSI");
 /// assert_eq!(RecognitionSentinel::ACC.value(), b"ACC");
 /// assert_eq!(RecognitionSentinel::DATA.value(), &[0xAA]);
 ///
@@ -57,7 +171,7 @@ pub enum RecognitionSentinel {
     #[value = b"UHL1"] // 85 72 76 49
     UHL,
     /// Data Set Identification
-    #[value = b"DSIU"] // 68 83 73 85
+    #[value = b"DSI"] // 68 83 73
     DSI,
     /// Accuracy Description
     #[value = b"ACC"] // 65 67 67
@@ -70,7 +184,299 @@ pub enum RecognitionSentinel {
     NA,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// DTED level, i.e. the resolution of the data
+///
+/// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
+///
+/// * Level0 - 30 arc second latitude interval (about 1 km)
+/// * Level1 - 3 arc second latitude interval (about 100 m)
+/// * Level2 - 1 arc second latitude interval (about 30 m)
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTEDLevel;
+///
+/// assert_eq!(DTEDLevel::from_interval(30), Some(DTEDLevel::Level1));
+/// assert_eq!(DTEDLevel::from_product_level("DTED2"), Some(DTEDLevel::Level2));
+/// assert_eq!(DTEDLevel::Level0.lat_count(), 121);
+/// assert_eq!(DTEDLevel::Level0.to_string(), "DTED0");
+/// ```
+pub enum DTEDLevel {
+    Level0,
+    Level1,
+    Level2,
+}
+impl DTEDLevel {
+    /// Get the level from its latitude interval
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_interval_secs_x_10` - latitude interval in tenths of seconds
+    ///
+    /// # Returns
+    ///
+    /// * The level, or None if the interval is not the one of a level
+    pub fn from_interval(lat_interval_secs_x_10: u16) -> Option<DTEDLevel> {
+        [DTEDLevel::Level0, DTEDLevel::Level1, DTEDLevel::Level2]
+            .into_iter()
+            .find(|level| level.lat_interval_secs_x_10() == lat_interval_secs_x_10)
+    }
+
+    /// Get the level from its number of latitude points per data record
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_count` - number of latitude points
+    ///
+    /// # Returns
+    ///
+    /// * The level, or None if the count is not the one of a level
+    pub fn from_count(lat_count: u16) -> Option<DTEDLevel> {
+        [DTEDLevel::Level0, DTEDLevel::Level1, DTEDLevel::Level2]
+            .into_iter()
+            .find(|level| level.lat_count() == lat_count)
+    }
+
+    /// Get the level from the product level of the DSI record (e.g. `DTED2`)
+    ///
+    /// # Arguments
+    ///
+    /// * `product_level` - product level, see [RawDTEDDSIRecord]
+    ///
+    /// # Returns
+    ///
+    /// * The level, or None if the product level is not recognized
+    pub fn from_product_level(product_level: &str) -> Option<DTEDLevel> {
+        match product_level.trim() {
+            "DTED0" => Some(DTEDLevel::Level0),
+            "DTED1" => Some(DTEDLevel::Level1),
+            "DTED2" => Some(DTEDLevel::Level2),
+            _ => None,
+        }
+    }
+
+    /// Infer the level from the latitude interval and count, falling back
+    /// to the product level of the DSI record, if any
+    ///
+    /// The latitude (rather than longitude) interval and count are used,
+    /// as they do not vary with the latitude of the cell
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_interval_secs_x_10` - latitude interval in tenths of seconds
+    /// * `lat_count` - number of latitude points
+    /// * `product_level` - product level of the DSI record, if any
+    ///
+    /// # Returns
+    ///
+    /// * The level, or None if it can not be inferred
+    pub fn infer(
+        lat_interval_secs_x_10: u16,
+        lat_count: u16,
+        product_level: Option<&str>,
+    ) -> Option<DTEDLevel> {
+        DTEDLevel::from_interval(lat_interval_secs_x_10)
+            .or_else(|| DTEDLevel::from_count(lat_count))
+            .or_else(|| DTEDLevel::from_product_level(product_level?))
+    }
+
+    /// Get the level number (0, 1 or 2)
+    pub fn number(self) -> u8 {
+        match self {
+            DTEDLevel::Level0 => 0,
+            DTEDLevel::Level1 => 1,
+            DTEDLevel::Level2 => 2,
+        }
+    }
+
+    /// Get the latitude interval of the level, in tenths of seconds
+    pub fn lat_interval_secs_x_10(self) -> u16 {
+        match self {
+            DTEDLevel::Level0 => 300,
+            DTEDLevel::Level1 => 30,
+            DTEDLevel::Level2 => 10,
+        }
+    }
+
+    /// Get the number of latitude points per data record of a 1 degree cell
+    pub fn lat_count(self) -> u16 {
+        1 + 36000 / self.lat_interval_secs_x_10()
+    }
+
+    /// Get the longitude interval of the level in a [LatitudeZone], in tenths of seconds
+    pub fn lon_interval_secs_x_10(self, zone: LatitudeZone) -> u16 {
+        self.lat_interval_secs_x_10() * zone.lon_multiplier()
+    }
+
+    /// Get the number of longitude lines (data records) of a 1 degree cell
+    /// in a [LatitudeZone]
+    pub fn lon_count(self, zone: LatitudeZone) -> u16 {
+        1 + 36000 / self.lon_interval_secs_x_10(zone)
+    }
+
+    /// Get the product level of the DSI record (e.g. `DTED2`)
+    pub fn product_level(self) -> &'static str {
+        match self {
+            DTEDLevel::Level0 => "DTED0",
+            DTEDLevel::Level1 => "DTED1",
+            DTEDLevel::Level2 => "DTED2",
+        }
+    }
+}
+impl std::fmt::Display for DTEDLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.product_level())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// DTED latitude zone, in which the longitude interval is a multiple of the
+/// latitude interval, such that posts stay roughly evenly spaced towards the poles
+///
+/// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
+///
+/// * I - 0° to 50° latitude, same longitude interval
+/// * II - 50° to 70° latitude, twice the longitude interval
+/// * III - 70° to 75° latitude, 3 times the longitude interval
+/// * IV - 75° to 80° latitude, 4 times the longitude interval
+/// * V - 80° to 90° latitude, 6 times the longitude interval
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{DTEDLevel, LatitudeZone};
+///
+/// assert_eq!(LatitudeZone::of_cell(42.0), LatitudeZone::I);
+/// assert_eq!(LatitudeZone::of_cell(-51.0), LatitudeZone::II);
+/// assert_eq!(LatitudeZone::of_cell(-50.0), LatitudeZone::I);
+/// assert_eq!(LatitudeZone::of_cell(77.0).lon_multiplier(), 4);
+/// assert_eq!(DTEDLevel::Level2.lon_interval_secs_x_10(LatitudeZone::II), 20);
+/// assert_eq!(DTEDLevel::Level2.lon_count(LatitudeZone::II), 1801);
+/// ```
+pub enum LatitudeZone {
+    I,
+    II,
+    III,
+    IV,
+    V,
+}
+impl LatitudeZone {
+    /// Get the zone of a 1 degree cell, which is the zone of its edge closest
+    /// to the equator
+    ///
+    /// # Arguments
+    ///
+    /// * `origin_lat` - latitude of the origin (south west corner) of the cell
+    ///
+    /// # Returns
+    ///
+    /// * [LatitudeZone] of the cell
+    pub fn of_cell(origin_lat: f64) -> LatitudeZone {
+        let lat = match origin_lat >= 0.0 {
+            true => origin_lat,
+            false => -origin_lat - 1.0,
+        };
+        match lat {
+            lat if lat < 50.0 => LatitudeZone::I,
+            lat if lat < 70.0 => LatitudeZone::II,
+            lat if lat < 75.0 => LatitudeZone::III,
+            lat if lat < 80.0 => LatitudeZone::IV,
+            _ => LatitudeZone::V,
+        }
+    }
+
+    /// Get the multiple of the latitude interval the longitude interval is
+    pub fn lon_multiplier(self) -> u16 {
+        match self {
+            LatitudeZone::I => 1,
+            LatitudeZone::II => 2,
+            LatitudeZone::III => 3,
+            LatitudeZone::IV => 4,
+            LatitudeZone::V => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// DTED security classification, of the DSI record (or the UHL security code)
+///
+/// Classifications are ordered from the least to the most restrictive, an
+/// unknown code being more restrictive than any, such that handling policies
+/// can be enforced by comparison (e.g. `classification <= Restricted`).
+///
+/// * Unclassified - `U`
+/// * Restricted - `R`
+/// * Confidential - `C`
+/// * Secret - `S`
+/// * Other - any other code, as found in the file (displayed as is)
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::SecurityClassification;
+///
+/// let classification = SecurityClassification::from_code('C');
+/// assert_eq!(classification, SecurityClassification::Confidential);
+/// assert_eq!(classification.code(), 'C');
+/// assert!(classification > SecurityClassification::Restricted);
+/// assert!(SecurityClassification::from_code('X') > SecurityClassification::Secret);
+/// ```
+pub enum SecurityClassification {
+    Unclassified,
+    Restricted,
+    Confidential,
+    Secret,
+    Other(char),
+}
+impl SecurityClassification {
+    /// Get the classification of a code
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - `S`, `C`, `R` or `U`, any other being [SecurityClassification::Other]
+    pub fn from_code(code: char) -> SecurityClassification {
+        match code {
+            'U' => SecurityClassification::Unclassified,
+            'R' => SecurityClassification::Restricted,
+            'C' => SecurityClassification::Confidential,
+            'S' => SecurityClassification::Secret,
+            code => SecurityClassification::Other(code),
+        }
+    }
+
+    /// Get the code of the classification, as written in the records
+    pub fn code(self) -> char {
+        match self {
+            SecurityClassification::Unclassified => 'U',
+            SecurityClassification::Restricted => 'R',
+            SecurityClassification::Confidential => 'C',
+            SecurityClassification::Secret => 'S',
+            SecurityClassification::Other(code) => code,
+        }
+    }
+
+    /// Whether the classification is one of the spec (not [SecurityClassification::Other])
+    pub fn is_known(self) -> bool {
+        !matches!(self, SecurityClassification::Other(_))
+    }
+}
+impl std::fmt::Display for SecurityClassification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecurityClassification::Unclassified => write!(f, "UNCLASSIFIED"),
+            SecurityClassification::Restricted => write!(f, "RESTRICTED"),
+            SecurityClassification::Confidential => write!(f, "CONFIDENTIAL"),
+            SecurityClassification::Secret => write!(f, "SECRET"),
+            SecurityClassification::Other(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// DTED User Header Label (UHL)
 ///
 /// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
@@ -82,15 +488,33 @@ pub enum RecognitionSentinel {
 /// * `accuracy`- absolute vertical accuracy in meters (with 90%
 ///   assurance that the linear errors will not exceed this value relative to
 ///   mean sea level)
+/// * `security_code` - security classification code
+/// * `unique_ref` - unique reference number
 /// * `count` - number of longitude lines and latitude points
+/// * `multiple_accuracy` - whether the ACC record contains multiple accuracy sub-regions
 pub struct RawDTEDHeader {
     pub origin: AxisElement<Angle>,
     pub interval_secs_x_10: AxisElement<u16>,
     pub accuracy: Option<u16>,
+    pub security_code: String,
+    pub unique_ref: String,
     pub count: AxisElement<u16>,
+    pub multiple_accuracy: bool,
+}
+impl RawDTEDHeader {
+    /// Get the security classification, of the first character of the security code
+    ///
+    /// # Returns
+    ///
+    /// * [SecurityClassification]: the classification, [SecurityClassification::Other]
+    ///   with a space if the code is blank
+    pub fn security_classification(&self) -> SecurityClassification {
+        SecurityClassification::from_code(self.security_code.chars().next().unwrap_or(' '))
+    }
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// DTED metadata
 ///
 /// # Fields
@@ -104,6 +528,8 @@ pub struct RawDTEDHeader {
 ///   assurance that the linear errors will not exceed this value relative to
 ///   mean sea level)
 /// * `count` - number of longitude lines and latitude points
+/// * `dsi_record` - Data Set Identification record (None if only the UHL was read)
+/// * `acc_record` - Accuracy Description record (None if only the UHL was read)
 pub struct DTEDMetadata {
     pub filename: String,
     pub origin: AxisElement<f64>,
@@ -112,6 +538,8 @@ pub struct DTEDMetadata {
     pub interval_secs: AxisElement<f32>,
     pub accuracy: Option<u16>,
     pub count: AxisElement<u16>,
+    pub dsi_record: Option<RawDTEDDSIRecord>,
+    pub acc_record: Option<RawDTEDAccRecord>,
 }
 impl DTEDMetadata {
     /// Create a [DTEDMetadata] from a [RawDTEDHeader]
@@ -133,239 +561,2480 @@ impl DTEDMetadata {
             interval_secs: raw.interval_secs_x_10 / 10.0,
             accuracy: raw.accuracy,
             count: raw.count,
+            dsi_record: None,
+            acc_record: None,
         }
     }
-}
 
-/// DTED Data
-///
-/// This is the main entry point for reading DTED files.
-/// Usage consists of either [DTEDData::read] or [DTEDData::read_header]
-///
-/// # Fields
-///
-/// * `metadata` - [DTEDMetadata]
-/// * `min` - minimum lat/lon
-/// * `max` - maximum lat/lon
-/// * `data` - data
-pub struct DTEDData {
-    pub metadata: DTEDMetadata,
-    pub min: AxisElement<f64>,
-    pub max: AxisElement<f64>,
-    pub data: Vec<RawDTEDRecord>,
-}
-impl DTEDData {
-    /// Read a DTED file
+    /// Create a [DTEDMetadata] from all of the header records
     ///
     /// # Arguments
     ///
-    /// * `path` (str): Path to the DTED file
+    /// * `raw` - [RawDTEDHeader]
+    /// * `dsi_record` - [RawDTEDDSIRecord]
+    /// * `acc_record` - [RawDTEDAccRecord]
+    /// * `fname` - filename
     ///
     /// # Returns
     ///
-    /// * [DTEDData]: DTED data
+    /// * [DTEDMetadata]: DTED metadata
+    pub fn from_records(
+        raw: &RawDTEDHeader,
+        dsi_record: RawDTEDDSIRecord,
+        acc_record: RawDTEDAccRecord,
+        fname: &str,
+    ) -> DTEDMetadata {
+        DTEDMetadata {
+            dsi_record: Some(dsi_record),
+            acc_record: Some(acc_record),
+            ..DTEDMetadata::from_header(raw, fname)
+        }
+    }
+
+    /// Get the [LatitudeZone] of the cell, see [LatitudeZone::of_cell]
     ///
     /// # Examples
     ///
     /// ```
+    /// use dted2::dted::LatitudeZone;
     /// use dted2::DTEDData;
-    /// assert!(DTEDData::read("tests/test_data.dt2").is_ok());
+    /// let metadata = DTEDData::read_header("tests/test_data.dt2").unwrap();
+    /// assert_eq!(metadata.zone(), LatitudeZone::I);
     /// ```
-    pub fn read(path: &str) -> Result<DTEDData, DTEDError> {
-        let mut file = std::fs::File::open(path)?;
-        let mut content = Vec::new();
-        file.read_to_end(&mut content)?;
-        match parsers::dted_file_parser(&content) {
-            Ok((_, data)) => {
-                let metadata = DTEDMetadata::from_header(&data.header, path);
-                let interval = metadata.interval;
-                let origin_f64: AxisElement<f64> = data.header.origin.into();
-                Ok(DTEDData {
-                    metadata,
-                    min: origin_f64,
-                    max: origin_f64 + ((data.header.count - 1) * interval),
-                    data: data.data,
-                })
-            }
-            Err(e) => match e {
-                nom::Err::Incomplete(e) => Err(e.into()),
-                nom::Err::Error(e) | nom::Err::Failure(e) => Err(e.code.into()),
-            },
-        }
+    pub fn zone(&self) -> LatitudeZone {
+        LatitudeZone::of_cell(self.origin.lat)
     }
 
-    /// Read the header from a DTED file
+    /// Get the [DTEDLevel], see [DTEDLevel::infer]
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `path` (str): Path to the DTED file
+    /// * The level, or None if it can not be inferred
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::DTEDLevel;
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_header("tests/test_data.dt2").unwrap();
+    /// assert_eq!(metadata.level(), Some(DTEDLevel::Level2));
+    /// ```
+    pub fn level(&self) -> Option<DTEDLevel> {
+        DTEDLevel::infer(
+            (self.interval_secs.lat * 10.0).round() as u16,
+            self.count.lat,
+            self.dsi_record
+                .as_ref()
+                .map(|dsi| dsi.product_level.as_str()),
+        )
+    }
+
+    /// Get the producer code of the DSI record
     ///
     /// # Returns
     ///
-    /// * [DTEDMetadata]: DTED metadata
+    /// * The code (e.g. `USNIMA`), or None without a DSI record or if blank
     ///
     /// # Examples
     ///
     /// ```
     /// use dted2::DTEDData;
-    /// assert!(DTEDData::read_header("tests/test_data.dt2").is_ok());
+    /// let metadata = DTEDData::read("tests/test_data.dt2").unwrap().metadata;
+    /// assert_eq!(metadata.producer_code(), Some("USCNIMA"));
     /// ```
-    pub fn read_header(path: &str) -> Result<DTEDMetadata, DTEDError> {
-        let mut file = std::fs::File::open(path)?;
-        let mut content = Vec::new();
-        file.read_to_end(&mut content)?;
-        match parsers::dted_uhl_parser(&content) {
-            Ok((_, header)) => Ok(DTEDMetadata::from_header(&header, path)),
-            Err(e) => match e {
-                nom::Err::Incomplete(e) => Err(e.into()),
-                nom::Err::Error(e) | nom::Err::Failure(e) => Err(e.code.into()),
-            },
-        }
+    pub fn producer_code(&self) -> Option<&str> {
+        self.dsi_record
+            .as_ref()
+            .map(|dsi| dsi.producer_code.as_str())
+            .filter(|code| !code.is_empty())
     }
 
-    /// Get the elevation at a lat/lon
+    /// Get the [Producer] of the producer code, see [Producer::lookup]
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `lat` - latitude
-    /// * `lon` - longitude
+    /// * The producer, or None without a producer code or if its country is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read("tests/test_data.dt2").unwrap().metadata;
+    /// let producer = metadata.producer().unwrap();
+    /// assert_eq!(producer.country, "United States");
+    /// assert_eq!(producer.agency, Some("National Imagery and Mapping Agency"));
+    /// ```
+    pub fn producer(&self) -> Option<Producer> {
+        self.producer_code().and_then(Producer::lookup)
+    }
+
+    /// Get the unique reference number of the DSI record
+    ///
+    /// # Returns
+    ///
+    /// * The reference, or None without a DSI record or if blank
+    pub fn unique_ref(&self) -> Option<&str> {
+        self.dsi_record
+            .as_ref()
+            .map(|dsi| dsi.unique_ref.as_str())
+            .filter(|unique_ref| !unique_ref.is_empty())
+    }
+
+    /// Get the data edition number of the DSI record
     ///
     /// # Returns
     ///
-    /// * Elevation (in meters) or None if out of bounds
+    /// * The edition (1 to 99), or None without a DSI record or if blank
     ///
     /// # Examples
     ///
     /// ```
     /// use dted2::DTEDData;
-    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
-    /// assert!(dted_data.get_elevation(42.52, 15.75).is_some());
-    /// assert!(dted_data.get_elevation(0.0, 0.0).is_none());
+    /// let metadata = DTEDData::read("tests/test_data.dt2").unwrap().metadata;
+    /// assert_eq!(metadata.edition(), Some(1));
     /// ```
-    pub fn get_elevation<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
-        // --------------------------------------------------
-        // get the indices + fractions
-        // --------------------------------------------------
-        let (lat_idx, lon_idx) = self.get_indices(lat, lon)?;
-        let mut lat_int = lat_idx as usize;
-        let mut lon_int = lon_idx as usize;
-        let mut lat_frac = lat_idx - lat_int as f64;
-        let mut lon_frac = lon_idx - lon_int as f64;
-        // --------------------------------------------------
-        // handle the edge case of max lat/lon
-        // --------------------------------------------------
-        if lat_int == self.metadata.count.lat as usize - 1 {
-            lat_int -= 1;
-            lat_frac += 1.0;
-        }
-        if lon_int == self.metadata.count.lon as usize - 1 {
-            lon_int -= 1;
-            lon_frac += 1.0;
-        }
-        // --------------------------------------------------
-        // values for the 4 corners for bilinear interpolation
-        // --------------------------------------------------
-        let elev00 = self.data[lon_int].elevations[lat_int] as f64;
-        let elev01 = self.data[lon_int].elevations[lat_int + 1] as f64;
-        let elev10 = self.data[lon_int + 1].elevations[lat_int] as f64;
-        let elev11 = self.data[lon_int + 1].elevations[lat_int + 1] as f64;
-        // --------------------------------------------------
-        // return interpolated value
-        // --------------------------------------------------
-        let result = 0.0
-            + elev00 * (1.0 - lon_frac) * (1.0 - lat_frac)
-            + elev01 * (1.0 - lon_frac) * lat_frac
-            + elev10 * lon_frac * (1.0 - lat_frac)
-            + elev11 * lon_frac * lat_frac;
-        Some(result)
+    pub fn edition(&self) -> Option<u8> {
+        self.dsi_record.as_ref().and_then(|dsi| dsi.edition)
     }
 
-    /// Get the indices of a lat/lon
+    /// Get the absolute vertical accuracy applicable at a lat/lon
+    ///
+    /// The accuracy of the [AccuracySubregion] containing the lat/lon is
+    /// taken first, then the accuracy of the whole ACC record, and last the
+    /// accuracy of the UHL.
     ///
     /// # Arguments
     ///
-    /// * `lat` - latitude
-    /// * `lon` - longitude
+    /// * `lat` - latitude, in degrees
+    /// * `lon` - longitude, in degrees
     ///
     /// # Returns
     ///
-    /// * `(lat_index, lon_index)` or None if out of bounds
+    /// * The accuracy (in meters, with 90% assurance), or None if not available
     ///
     /// # Examples
     ///
     /// ```
     /// use dted2::DTEDData;
-    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
-    /// assert!(dted_data.get_indices(42.52, 15.75).is_some());
-    /// assert!(dted_data.get_indices(0.0, 0.0).is_none());
+    /// let metadata = DTEDData::read("tests/test_data.dt2").unwrap().metadata;
+    /// assert_eq!(metadata.vertical_accuracy_at(42.5, 15.5), Some(5));
     /// ```
-    pub fn get_indices<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(f64, f64)> {
-        // --------------------------------------------------
-        // check bounds
-        // --------------------------------------------------
-        let lat: f64 = lat.into();
-        let lon: f64 = lon.into();
-        if lat < self.min.lat || lat > self.max.lat || lon < self.min.lon || lon > self.max.lon {
-            return None;
-        }
-        let lat_idx = (lat - self.min.lat) / self.metadata.interval.lat;
-        let lon_idx = (lon - self.min.lon) / self.metadata.interval.lon;
-        Some((lat_idx, lon_idx))
+    pub fn vertical_accuracy_at(&self, lat: f64, lon: f64) -> Option<u16> {
+        let acc = self.acc_record.as_ref();
+        acc.and_then(|acc| acc.subregion_at(lat, lon))
+            .and_then(|subregion| subregion.abs_vertical)
+            .or_else(|| acc.and_then(|acc| acc.abs_vertical))
+            .or(self.accuracy)
     }
-}
 
-/// TODO
-///
-/// DTED Data Set Identification (DSI) Record
-///
-/// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
-pub struct DTEDRecordDSI {
-    /// Security Control and Release Markings
-    pub security_release: Option<String>,
-    /// Security Handling Description
+    /// Get the corners of the cell from its DSI record,
+    /// see [RawDTEDDSIRecord::corners]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDCorners], or None without a DSI record
+    pub fn corners(&self) -> Option<DTEDCorners> {
+        self.dsi_record.as_ref().map(RawDTEDDSIRecord::corners)
+    }
+
+    /// Compare the origin, intervals and counts of the UHL and DSI records,
+    /// which some producers let disagree
+    ///
+    /// # Returns
+    ///
+    /// * The [RecordDiscrepancy]s found, empty if the records agree or
+    ///   without a DSI record
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert!(data.metadata.cross_validate().is_empty());
+    /// ```
+    pub fn cross_validate(&self) -> Vec<RecordDiscrepancy> {
+        let interval_secs_x_10 = AxisElement::new(
+            (self.interval_secs.lat * 10.0).round() as u16,
+            (self.interval_secs.lon * 10.0).round() as u16,
+        );
+        self.dsi_record.as_ref().map_or_else(Vec::new, |dsi| {
+            cross_validate(dsi, &self.origin_angle, interval_secs_x_10, self.count)
+        })
+    }
+
+    /// Get the bounds of the posts, from the origin (south west post) to the
+    /// north east post, `count - 1` intervals away
+    ///
+    /// # Returns
+    ///
+    /// * `(south, west, north, east)`, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(metadata.bounds(), (-30.0, -178.0, -29.0, -177.0));
+    /// ```
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        let last = AxisElement::new(
+            self.count.lat.saturating_sub(1),
+            self.count.lon.saturating_sub(1),
+        );
+        let max = self.origin + (last * self.interval);
+        (self.origin.lat, self.origin.lon, max.lat, max.lon)
+    }
+
+    /// Whether a lat/lon is within the bounds of the posts (edges included),
+    /// see [DTEDMetadata::bounds]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    /// assert!(metadata.contains(-29.5, -177.5));
+    /// assert!(metadata.contains(-29.0, -178.0));
+    /// assert!(!metadata.contains(-28.999, -177.5));
+    /// ```
+    pub fn contains<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> bool {
+        let (lat, lon): (f64, f64) = (lat.into(), lon.into());
+        let (south, west, north, east) = self.bounds();
+        self.count.lat > 0
+            && self.count.lon > 0
+            && (south..=north).contains(&lat)
+            && (west..=east).contains(&lon)
+    }
+
+    /// Get the fractional post indices of a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(lat_index, lon_index)` or None if out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_header("tests/test_data.dt2").unwrap();
+    /// assert_eq!(metadata.get_indices(42.5, 15.25), Some((1800.0, 900.0)));
+    /// assert!(metadata.get_indices(0.0, 0.0).is_none());
+    /// ```
+    pub fn get_indices<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(f64, f64)> {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        if !self.contains(lat, lon) {
+            return None;
+        }
+        let lat_idx = (lat - self.origin.lat) / self.interval.lat;
+        let lon_idx = (lon - self.origin.lon) / self.interval.lon;
+        Some((lat_idx, lon_idx))
+    }
+
+    /// Get the indices of the post nearest to a lat/lon, see [DTEDMetadata::get_indices]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(lat_index, lon_index)` (row and column of the grid) or None if out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_header("tests/test_data.dt2").unwrap();
+    /// assert_eq!(metadata.index_of(42.5, 15.25), Some((1800, 900)));
+    /// assert_eq!(metadata.index_of(42.50001, 15.24999), Some((1800, 900)));
+    /// assert!(metadata.index_of(0.0, 0.0).is_none());
+    /// ```
+    pub fn index_of<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(usize, usize)> {
+        let (lat_idx, lon_idx) = self.get_indices(lat, lon)?;
+        Some((lat_idx.round() as usize, lon_idx.round() as usize))
+    }
+
+    /// Get the lat/lon of a post, the longitude interval accounting for the
+    /// [LatitudeZone] of the cell
+    ///
+    /// Indices beyond the counts are not checked, extrapolating the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index (row of the grid)
+    /// * `lon_idx` - longitude index (column of the grid)
+    ///
+    /// # Returns
+    ///
+    /// * `(lat, lon)` of the post, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_header("tests/test_data.dt2").unwrap();
+    /// assert_eq!(metadata.position_of(1800, 900), (42.5, 15.25));
+    /// assert_eq!(metadata.index_of(42.5, 15.25), Some((1800, 900)));
+    /// ```
+    pub fn position_of(&self, lat_idx: usize, lon_idx: usize) -> (f64, f64) {
+        (
+            self.origin.lat + lat_idx as f64 * self.interval.lat,
+            self.origin.lon + lon_idx as f64 * self.interval.lon,
+        )
+    }
+}
+
+/// DTED Data
+///
+/// This is the main entry point for reading DTED files.
+/// Usage consists of either [DTEDData::read], [DTEDData::read_lazy] or [DTEDData::read_header]
+///
+/// # Fields
+///
+/// * `metadata` - [DTEDMetadata]
+/// * `min` - minimum lat/lon
+/// * `max` - maximum lat/lon
+/// * `data` - data
+/// * `coverage` - longitude lines with a data record in the file, see [Coverage]
+pub struct DTEDData {
+    pub metadata: DTEDMetadata,
+    pub min: AxisElement<f64>,
+    pub max: AxisElement<f64>,
+    pub data: RawDTEDRecords,
+    pub coverage: Coverage,
+}
+impl DTEDData {
+    /// Create [DTEDData] from its metadata and data records
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - [DTEDMetadata]
+    /// * `data` - data records, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, covered by all of the data records
+    pub fn new(metadata: DTEDMetadata, data: RawDTEDRecords) -> DTEDData {
+        let (south, west, north, east) = metadata.bounds();
+        DTEDData {
+            metadata,
+            min: AxisElement::new(south, west),
+            max: AxisElement::new(north, east),
+            coverage: Coverage::full(data.len()),
+            data,
+        }
+    }
+
+    /// Get the extent actually covered by data records, which is less than
+    /// the [DTEDData::min] / [DTEDData::max] of the cell for partial cells
+    ///
+    /// # Returns
+    ///
+    /// * `(min, max)` lat/lon covered, or None if no data record was covered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(data.covered_extent(), Some((data.min, data.max)));
+    /// ```
+    pub fn covered_extent(&self) -> Option<(AxisElement<f64>, AxisElement<f64>)> {
+        let range = &self.coverage.lon_range;
+        if range.is_empty() {
+            return None;
+        }
+        let lon = |idx: usize| self.min.lon + idx as f64 * self.metadata.interval.lon;
+        Some((
+            AxisElement::new(self.min.lat, lon(range.start)),
+            AxisElement::new(self.max.lat, lon(range.end - 1)),
+        ))
+    }
+
+    /// Count the void posts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(data.void_count(), 0);
+    /// assert_eq!(data.void_fraction(), 0.0);
+    /// ```
+    pub fn void_count(&self) -> usize {
+        self.data
+            .elevations
+            .iter()
+            .filter(|&&elev| elev == DTED_VOID_ELEVATION)
+            .count()
+    }
+
+    /// Get the fraction (from 0 to 1) of the posts which are void
+    ///
+    /// # Returns
+    ///
+    /// * Fraction of void posts, 0 if there are no posts at all
+    pub fn void_fraction(&self) -> f64 {
+        match self.data.elevations.len() {
+            0 => 0.0,
+            len => self.void_count() as f64 / len as f64,
+        }
+    }
+
+    /// Iterate over every post with its lat/lon, in the order they are stored:
+    /// one longitude line after the other, from west to east, each from south
+    /// to north
+    ///
+    /// # Returns
+    ///
+    /// * `(lat, lon, elevation)` of each post, the elevation (in meters) being
+    ///   None for void posts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let mut posts = data.posts();
+    /// assert_eq!(posts.next().unwrap().0, -30.0);
+    /// assert_eq!(posts.next().unwrap().0, -30.0 + 30.0 / 3600.0);
+    /// let highest = data
+    ///     .posts()
+    ///     .filter_map(|(lat, lon, elevation)| Some((lat, lon, elevation?)))
+    ///     .max_by_key(|(_, _, elevation)| *elevation);
+    /// assert!(highest.is_some());
+    /// assert_eq!(data.posts().count(), 121 * 121);
+    /// ```
+    pub fn posts(&self) -> impl Iterator<Item = (f64, f64, Option<i16>)> + '_ {
+        self.data
+            .columns()
+            .enumerate()
+            .flat_map(move |(lon_idx, column)| {
+                column.iter().enumerate().map(move |(lat_idx, raw)| {
+                    let (lat, lon) = self.metadata.position_of(lat_idx, lon_idx);
+                    (lat, lon, Post::from_raw(*raw).elevation())
+                })
+            })
+    }
+
+    /// Iterate over every post with its lat/lon in parallel, see
+    /// [DTEDData::posts] (requires the `rayon` feature)
+    ///
+    /// Collecting the iterator keeps the order of [DTEDData::posts].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use rayon::prelude::*;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let posts: Vec<_> = data.par_posts().collect();
+    /// assert_eq!(posts, data.posts().collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_posts(
+        &self,
+    ) -> impl rayon::iter::ParallelIterator<Item = (f64, f64, Option<i16>)> + '_ {
+        use rayon::prelude::*;
+
+        self.data
+            .elevations
+            .par_chunks_exact(self.data.line_len.max(1))
+            .take(self.data.len())
+            .enumerate()
+            .flat_map_iter(move |(lon_idx, column)| {
+                column.iter().enumerate().map(move |(lat_idx, raw)| {
+                    let (lat, lon) = self.metadata.position_of(lat_idx, lon_idx);
+                    (lat, lon, Post::from_raw(*raw).elevation())
+                })
+            })
+    }
+
+    /// Get the mask of the void posts, laid out as [RawDTEDRecords::elevations]
+    /// (one longitude line after the other, from west to east, each from south
+    /// to north), such that the post `(lat_idx, lon_idx)` is at
+    /// `lon_idx * line_len + lat_idx`
+    ///
+    /// # Returns
+    ///
+    /// * Whether each post is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let mask = data.void_mask();
+    /// assert_eq!(mask.len(), 121 * 121);
+    /// assert!(mask.iter().all(|void| !void));
+    /// ```
+    pub fn void_mask(&self) -> Vec<bool> {
+        self.data
+            .elevations
+            .iter()
+            .map(|&elev| elev == DTED_VOID_ELEVATION)
+            .collect()
+    }
+
+    /// Read a DTED file from a stream, see [DTEDStreamReader]
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - stream to read the DTED from
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, with an empty filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let file = std::fs::File::open("tests/test_data.dt2").unwrap();
+    /// assert!(DTEDData::read_from(file).is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from<R: Read>(reader: R) -> Result<DTEDData, DTEDError> {
+        DTEDStreamReader::new(reader)?.read_data()
+    }
+
+    /// Read a DTED file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// assert!(DTEDData::read("tests/test_data.dt2").is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read(path: &str) -> Result<DTEDData, DTEDError> {
+        DTEDData::read_with(path, &ParseOptions::default())
+    }
+
+    /// Read a DTED file, as strictly as per the [ParseOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    /// * `options` - [ParseOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ParseOptions};
+    /// let data = DTEDData::read_with("tests/test_data.dt2", &ParseOptions::lenient()).unwrap();
+    /// assert_eq!(data.metadata.count.lon, 3601);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_with(path: &str, options: &ParseOptions) -> Result<DTEDData, DTEDError> {
+        let file = RawDTEDFile::from_bytes_with(&read_file(path)?, options)?;
+        Ok(DTEDData::from_file(file, path))
+    }
+
+    /// Read a DTED file, recovering from corrupt data records,
+    /// see [RawDTEDFile::recover_from_bytes]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    /// * `options` - [ParseOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, with the corrupt data records void
+    /// * The [ParseDiagnostic]s, one per skipped section
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ParseOptions};
+    /// let (data, diagnostics) =
+    ///     DTEDData::read_recovering("tests/test_data.dt2", &ParseOptions::default()).unwrap();
+    /// assert_eq!(data.metadata.count.lon, 3601);
+    /// assert!(diagnostics.is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_recovering(
+        path: &str,
+        options: &ParseOptions,
+    ) -> Result<(DTEDData, Vec<ParseDiagnostic>), DTEDError> {
+        let (file, diagnostics) = RawDTEDFile::recover_from_bytes(&read_file(path)?, options)?;
+        Ok((DTEDData::from_file(file, path), diagnostics))
+    }
+
+    /// Parse DTED data from the bytes of a file, without any filesystem
+    /// access (e.g. for files uploaded to a browser app)
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file (uncompressed)
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, with an empty filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let data = DTEDData::from_bytes(&content).unwrap();
+    /// assert!(data.data == DTEDData::read("tests/test_data_negative.dt2").unwrap().data);
+    /// assert!(data.metadata.filename.is_empty());
+    /// assert!(DTEDData::from_bytes(&content[..1000]).is_err());
+    /// ```
+    pub fn from_bytes(content: &[u8]) -> Result<DTEDData, DTEDError> {
+        DTEDData::from_bytes_with(content, &ParseOptions::default())
+    }
+
+    /// Parse DTED data from the bytes of a file, as strictly as per the
+    /// [ParseOptions], see [DTEDData::from_bytes]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file (uncompressed)
+    /// * `options` - [ParseOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, with an empty filename
+    pub fn from_bytes_with(content: &[u8], options: &ParseOptions) -> Result<DTEDData, DTEDError> {
+        let file = RawDTEDFile::from_bytes_with(content, options)?;
+        Ok(DTEDData::from_file(file, ""))
+    }
+
+    /// Parse DTED data from the bytes of a file, taking ownership of them,
+    /// see [DTEDData::from_bytes]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file (uncompressed)
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, with an empty filename
+    pub fn from_vec(content: Vec<u8>) -> Result<DTEDData, DTEDError> {
+        DTEDData::from_bytes(&content)
+    }
+
+    /// Create [DTEDData] from a parsed file
+    pub(crate) fn from_file(file: RawDTEDFile, path: &str) -> DTEDData {
+        let metadata = DTEDMetadata {
+            dsi_record: file.dsi_record,
+            acc_record: file.acc_record,
+            ..DTEDMetadata::from_header(&file.header, path)
+        };
+        DTEDData {
+            coverage: file.coverage,
+            ..DTEDData::new(metadata, file.data)
+        }
+    }
+
+    /// Read a DTED file lazily, only decoding its data records on demand,
+    /// see [LazyDTEDData::read]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [LazyDTEDData]: lazily decoded DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let lazy = DTEDData::read_lazy("tests/test_data.dt2").unwrap();
+    /// assert!(lazy.elevation_at(42.52, 15.75).is_some());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_lazy(path: &str) -> Result<LazyDTEDData, DTEDError> {
+        LazyDTEDData::read(path)
+    }
+
+    /// Read the header from a DTED file
+    ///
+    /// Only the UHL header is read from the file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// assert!(DTEDData::read_header("tests/test_data.dt2").is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_header(path: &str) -> Result<DTEDMetadata, DTEDError> {
+        let content = read_prefix(path, DTED_UHL_LENGTH as usize)?;
+        let (_, header) = parsers::dted_uhl_parser(&content)
+            .map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+        Ok(DTEDMetadata::from_header(&header, path))
+    }
+
+    /// Read the metadata from a DTED file, including the DSI and ACC records
+    ///
+    /// Only the header records (UHL, DSI and ACC) are read from the file,
+    /// the elevations are never touched
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_metadata("tests/test_data.dt2").unwrap();
+    /// assert_eq!(metadata.count.lat, 3601);
+    /// assert_eq!(metadata.dsi_record.unwrap().edition, Some(1));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_metadata(path: &str) -> Result<DTEDMetadata, DTEDError> {
+        let content = read_prefix(path, DTED_DATA_OFFSET)?;
+        let (_, (header, dsi_record, acc_record)) = parsers::dted_headers_parser(&content)
+            .map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+        Ok(DTEDMetadata::from_records(
+            &header, dsi_record, acc_record, path,
+        ))
+    }
+
+    /// Get the elevation at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if out of bounds or any of the
+    ///   4 surrounding posts is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.get_elevation(42.52, 15.75).is_some());
+    /// assert!(dted_data.get_elevation(0.0, 0.0).is_none());
+    /// ```
+    pub fn get_elevation<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        // --------------------------------------------------
+        // values for the 4 corners for bilinear interpolation
+        // --------------------------------------------------
+        let (corner, frac) = self.get_cell(lat, lon)?;
+        let elev = |lat_idx, lon_idx| self.data.post(lat_idx, lon_idx)?.elevation();
+        // a single post along an axis is its own neighbor (with fraction 0)
+        let next = AxisElement::new(
+            (corner.lat + 1).min(self.metadata.count.lat as usize - 1),
+            (corner.lon + 1).min(self.metadata.count.lon as usize - 1),
+        );
+        let elev00 = elev(corner.lat, corner.lon)? as f64;
+        let elev01 = elev(next.lat, corner.lon)? as f64;
+        let elev10 = elev(corner.lat, next.lon)? as f64;
+        let elev11 = elev(next.lat, next.lon)? as f64;
+        // --------------------------------------------------
+        // return interpolated value
+        // --------------------------------------------------
+        let result = 0.0
+            + elev00 * (1.0 - frac.lon) * (1.0 - frac.lat)
+            + elev01 * (1.0 - frac.lon) * frac.lat
+            + elev10 * frac.lon * (1.0 - frac.lat)
+            + elev11 * frac.lon * frac.lat;
+        Some(result)
+    }
+
+    /// Get the cell (lower left post indices and fractional position within the
+    /// cell) containing a lat/lon, for interpolation between the 4 corner posts
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(corner, fraction)` or None if out of bounds
+    fn get_cell<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+    ) -> Option<(AxisElement<usize>, AxisElement<f64>)> {
+        // --------------------------------------------------
+        // get the indices + fractions
+        // --------------------------------------------------
+        let (lat_idx, lon_idx) = self.get_indices(lat, lon)?;
+        let mut lat_int = lat_idx as usize;
+        let mut lon_int = lon_idx as usize;
+        let mut lat_frac = lat_idx - lat_int as f64;
+        let mut lon_frac = lon_idx - lon_int as f64;
+        // --------------------------------------------------
+        // handle the edge case of max lat/lon, unless it is the only post
+        // --------------------------------------------------
+        if lat_int > 0 && lat_int == self.metadata.count.lat as usize - 1 {
+            lat_int -= 1;
+            lat_frac += 1.0;
+        }
+        if lon_int > 0 && lon_int == self.metadata.count.lon as usize - 1 {
+            lon_int -= 1;
+            lon_frac += 1.0;
+        }
+        Some((
+            AxisElement::new(lat_int, lon_int),
+            AxisElement::new(lat_frac, lon_frac),
+        ))
+    }
+
+    /// Get the indices of a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(lat_index, lon_index)` or None if out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.get_indices(42.52, 15.75).is_some());
+    /// assert!(dted_data.get_indices(0.0, 0.0).is_none());
+    /// ```
+    pub fn get_indices<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(f64, f64)> {
+        self.metadata.get_indices(lat, lon)
+    }
+}
+impl ElevationSource for DTEDData {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.data.get(lat_idx, lon_idx)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// DTED Data Set Identification (DSI) Record
+///
+/// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
+///
+/// Blank numeric fields are parsed as [None], and text fields
+/// have their space padding removed.
+///
+/// # Fields
+///
+/// * `security_classification` - security classification (of the code `S`, `C`, `R` or `U`)
+/// * `security_control` - security control and release markings (free text, as
+///   they are not enumerated by the spec)
+/// * `security_handling` - security handling description (free text)
+/// * `product_level` - DMA series designator for the product level (e.g. `DTED2`)
+/// * `unique_ref` - unique reference number
+/// * `edition` - data edition number (01 to 99)
+/// * `match_merge_version` - match/merge version (`A` to `Z`)
+/// * `maintenance_date` - maintenance date (`YYMM`)
+/// * `match_merge_date` - match/merge date (`YYMM`)
+/// * `maintenance_code` - maintenance description code
+/// * `producer_code` - producer code (country and agency)
+/// * `product_spec` - product specification
+/// * `product_spec_amendment` - product specification amendment / change number
+/// * `product_spec_date` - date of the product specification (`YYMM`)
+/// * `vertical_datum` - vertical datum (e.g. `MSL`)
+/// * `horizontal_datum` - horizontal datum code (e.g. `WGS84`)
+/// * `collection_system` - digitizing / collection system
+/// * `compilation_date` - compilation date (`YYMM`)
+/// * `origin` - latitude and longitude of the origin of the data
+/// * `sw_corner` - latitude and longitude of the south-west corner
+/// * `nw_corner` - latitude and longitude of the north-west corner
+/// * `ne_corner` - latitude and longitude of the north-east corner
+/// * `se_corner` - latitude and longitude of the south-east corner
+/// * `orientation` - clockwise orientation angle of the data
+/// * `interval_secs_x_10` - data interval in seconds (decimal point is implied after third integer)
+/// * `count` - number of longitude lines and latitude points
+/// * `partial_cell` - partial cell indicator (0 for a complete cell, otherwise percent of coverage)
+pub struct RawDTEDDSIRecord {
+    pub security_classification: SecurityClassification,
+    pub security_control: Option<String>,
     pub security_handling: Option<String>,
-    pub version: String,
-    pub edition: u8,
-    pub mm_version: char,
-    pub maintenance_data: u16,
-    pub mm_date: u16,
-    pub maintenance_code: u16,
-    pub product_specs_desc: String,
-    pub product_specs_code: u8,
-    pub product_specs_date: u16,
-    pub compilation_date: u16,
-    pub lat_origin: Angle,
-    pub lon_origin: Angle,
-    pub lat_sw: Angle,
-    pub lon_sw: Angle,
-    pub lat_nw: Angle,
-    pub lon_nw: Angle,
-    pub lat_ne: Angle,
-    pub lon_ne: Angle,
-    pub lat_se: Angle,
-    pub lon_se: Angle,
-    pub clockwise_orientation: u32,
-    pub lat_interval_s: u16,
-    pub lon_interval_s: u16,
-    pub lat_count: u16,
+    pub product_level: String,
+    pub unique_ref: String,
+    pub edition: Option<u8>,
+    pub match_merge_version: char,
+    pub maintenance_date: Option<u16>,
+    pub match_merge_date: Option<u16>,
+    pub maintenance_code: Option<u16>,
+    pub producer_code: String,
+    pub product_spec: String,
+    pub product_spec_amendment: Option<u8>,
+    pub product_spec_date: Option<u16>,
+    pub vertical_datum: String,
+    pub horizontal_datum: String,
+    pub collection_system: String,
+    pub compilation_date: Option<u16>,
+    pub origin: AxisElement<Angle>,
+    pub sw_corner: AxisElement<Angle>,
+    pub nw_corner: AxisElement<Angle>,
+    pub ne_corner: AxisElement<Angle>,
+    pub se_corner: AxisElement<Angle>,
+    pub orientation: Angle,
+    pub interval_secs_x_10: AxisElement<u16>,
+    pub count: AxisElement<u16>,
+    pub partial_cell: Option<u8>,
+}
+impl RawDTEDDSIRecord {
+    /// Create a minimal, unclassified [RawDTEDDSIRecord] from a [RawDTEDHeader],
+    /// with the corners computed from the origin, interval and count
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - [RawDTEDHeader]
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDDSIRecord]: DSI record agreeing with the header
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDDSIRecord;
+    /// use dted2::primitives::Angle;
+    ///
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let (_, header) = dted2::parsers::dted_uhl_parser(&content).unwrap();
+    /// let dsi = RawDTEDDSIRecord::from_header(&header);
+    /// assert_eq!(dsi.product_level, "DTED0");
+    /// assert_eq!(dsi.ne_corner.lat, Angle::new(29, 0, 0.0, true));
+    /// assert_eq!(dsi.ne_corner.lon, Angle::new(177, 0, 0.0, true));
+    /// ```
+    pub fn from_header(header: &RawDTEDHeader) -> RawDTEDDSIRecord {
+        let origin = header.origin;
+        let extent = AxisElement::new(
+            Angle::from_secs(
+                (header.count.lat.max(1) - 1) as f64 * header.interval_secs_x_10.lat as f64 / 10.0,
+            ),
+            Angle::from_secs(
+                (header.count.lon.max(1) - 1) as f64 * header.interval_secs_x_10.lon as f64 / 10.0,
+            ),
+        );
+        RawDTEDDSIRecord {
+            security_classification: SecurityClassification::Unclassified,
+            security_control: None,
+            security_handling: None,
+            product_level: DTEDLevel::from_interval(header.interval_secs_x_10.lat)
+                .map_or("", DTEDLevel::product_level)
+                .to_string(),
+            unique_ref: header.unique_ref.clone(),
+            edition: None,
+            match_merge_version: ' ',
+            maintenance_date: None,
+            match_merge_date: None,
+            maintenance_code: None,
+            producer_code: String::new(),
+            product_spec: String::new(),
+            product_spec_amendment: None,
+            product_spec_date: None,
+            vertical_datum: "MSL".to_string(),
+            horizontal_datum: "WGS84".to_string(),
+            collection_system: String::new(),
+            compilation_date: None,
+            origin,
+            sw_corner: origin,
+            nw_corner: AxisElement::new(origin.lat + extent.lat, origin.lon),
+            ne_corner: origin + extent,
+            se_corner: AxisElement::new(origin.lat, origin.lon + extent.lon),
+            orientation: Angle::new(0, 0, 0.0, false),
+            interval_secs_x_10: header.interval_secs_x_10,
+            count: header.count,
+            partial_cell: Some(0),
+        }
+    }
+
+    /// Get the corners of the cell, in degrees
+    ///
+    /// Unlike the origin of the UHL, the corners describe the orientation of
+    /// the cell, e.g. for producers not aligning it on the meridians.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::DTEDData;
+    ///
+    /// let metadata = DTEDData::read("tests/test_data_negative.dt2").unwrap().metadata;
+    /// let corners = metadata.dsi_record.unwrap().corners();
+    /// assert_eq!(corners.sw, AxisElement::new(-30.0, -178.0));
+    /// assert_eq!(corners.ne, AxisElement::new(-29.0, -177.0));
+    /// ```
+    pub fn corners(&self) -> DTEDCorners {
+        let degrees = |corner: &AxisElement<Angle>| {
+            AxisElement::new(
+                corner.lat.to_decimal_degrees(),
+                corner.lon.to_decimal_degrees(),
+            )
+        };
+        DTEDCorners {
+            sw: degrees(&self.sw_corner),
+            nw: degrees(&self.nw_corner),
+            ne: degrees(&self.ne_corner),
+            se: degrees(&self.se_corner),
+        }
+    }
+
+    /// Cross-check the origin of the record against the origin of the UHL,
+    /// the former in tenths of seconds being rounded to the whole seconds of
+    /// the latter
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - origin of the UHL, see [RawDTEDHeader::origin]
+    ///
+    /// # Returns
+    ///
+    /// * Whether both origins are the same
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::{Angle, AxisElement};
+    /// use dted2::DTEDData;
+    ///
+    /// let metadata = DTEDData::read("tests/test_data_negative.dt2").unwrap().metadata;
+    /// let mut dsi = metadata.dsi_record.unwrap();
+    /// assert!(dsi.origin_matches(&metadata.origin_angle));
+    /// dsi.origin.lat = Angle::new(30, 0, 0.4, true);
+    /// assert!(dsi.origin_matches(&metadata.origin_angle));
+    /// dsi.origin.lat = Angle::new(29, 0, 0.0, true);
+    /// assert!(!dsi.origin_matches(&metadata.origin_angle));
+    /// ```
+    pub fn origin_matches(&self, origin: &AxisElement<Angle>) -> bool {
+        let matches = |dsi: &Angle, uhl: &Angle| dsi.total_secs().round() == uhl.total_secs();
+        matches(&self.origin.lat, &origin.lat) && matches(&self.origin.lon, &origin.lon)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Corners of a cell, from its DSI record, see [RawDTEDDSIRecord::corners]
+///
+/// # Fields
+///
+/// * `sw` - latitude and longitude of the south-west corner, in degrees
+/// * `nw` - latitude and longitude of the north-west corner, in degrees
+/// * `ne` - latitude and longitude of the north-east corner, in degrees
+/// * `se` - latitude and longitude of the south-east corner, in degrees
+pub struct DTEDCorners {
+    pub sw: AxisElement<f64>,
+    pub nw: AxisElement<f64>,
+    pub ne: AxisElement<f64>,
+    pub se: AxisElement<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Discrepancy between the UHL and DSI records of a file,
+/// see [DTEDMetadata::cross_validate]
+///
+/// * Origin - the origins differ, beyond the rounding of the DSI origin
+///   to whole seconds (see [RawDTEDDSIRecord::origin_matches])
+/// * Interval - the intervals differ, in tenths of seconds
+/// * Count - the counts of posts differ
+pub enum RecordDiscrepancy {
+    Origin {
+        uhl: AxisElement<Angle>,
+        dsi: AxisElement<Angle>,
+    },
+    Interval {
+        uhl: AxisElement<u16>,
+        dsi: AxisElement<u16>,
+    },
+    Count {
+        uhl: AxisElement<u16>,
+        dsi: AxisElement<u16>,
+    },
+}
+impl std::fmt::Display for RecordDiscrepancy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordDiscrepancy::Origin { uhl, dsi } => {
+                write!(f, "DSI origin {} differs from the UHL origin {}", dsi, uhl)
+            }
+            RecordDiscrepancy::Interval { uhl, dsi } => write!(
+                f,
+                "DSI intervals {}\" x {}\" differ from the UHL intervals {}\" x {}\"",
+                dsi.lat as f64 / 10.0,
+                dsi.lon as f64 / 10.0,
+                uhl.lat as f64 / 10.0,
+                uhl.lon as f64 / 10.0
+            ),
+            RecordDiscrepancy::Count { uhl, dsi } => write!(
+                f,
+                "DSI counts {} x {} differ from the UHL counts {} x {}",
+                dsi.lat, dsi.lon, uhl.lat, uhl.lon
+            ),
+        }
+    }
+}
+
+/// Compare a DSI record against the origin, intervals and counts of a UHL
+fn cross_validate(
+    dsi: &RawDTEDDSIRecord,
+    origin: &AxisElement<Angle>,
+    interval_secs_x_10: AxisElement<u16>,
+    count: AxisElement<u16>,
+) -> Vec<RecordDiscrepancy> {
+    let mut discrepancies = Vec::new();
+    if !dsi.origin_matches(origin) {
+        discrepancies.push(RecordDiscrepancy::Origin {
+            uhl: *origin,
+            dsi: dsi.origin,
+        });
+    }
+    if dsi.interval_secs_x_10 != interval_secs_x_10 {
+        discrepancies.push(RecordDiscrepancy::Interval {
+            uhl: interval_secs_x_10,
+            dsi: dsi.interval_secs_x_10,
+        });
+    }
+    if dsi.count != count {
+        discrepancies.push(RecordDiscrepancy::Count {
+            uhl: count,
+            dsi: dsi.count,
+        });
+    }
+    discrepancies
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// DTED Accuracy Description (ACC) Record
+///
+/// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
+///
+/// All accuracies are in meters, with 90% assurance. An accuracy of [None]
+/// means the value is not available (`NA$$` or blank).
+///
+/// # Fields
+///
+/// * `abs_horizontal` - absolute horizontal accuracy
+/// * `abs_vertical` - absolute vertical accuracy
+/// * `rel_horizontal` - relative (point-to-point) horizontal accuracy
+/// * `rel_vertical` - relative (point-to-point) vertical accuracy
+/// * `multiple_accuracy` - multiple accuracy outline flag (0 for none, otherwise
+///   the number of accuracy sub-regions, 2 to 9)
+/// * `subregions` - accuracy sub-regions, each with its outline and accuracies
+pub struct RawDTEDAccRecord {
+    pub abs_horizontal: Option<u16>,
+    pub abs_vertical: Option<u16>,
+    pub rel_horizontal: Option<u16>,
+    pub rel_vertical: Option<u16>,
+    pub multiple_accuracy: u8,
+    pub subregions: Vec<AccuracySubregion>,
+}
+impl RawDTEDAccRecord {
+    /// Create a [RawDTEDAccRecord] from a [RawDTEDHeader], holding only the
+    /// absolute vertical accuracy of the header
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - [RawDTEDHeader]
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDAccRecord]: ACC record agreeing with the header
+    pub fn from_header(header: &RawDTEDHeader) -> RawDTEDAccRecord {
+        RawDTEDAccRecord {
+            abs_horizontal: None,
+            abs_vertical: header.accuracy,
+            rel_horizontal: None,
+            rel_vertical: None,
+            multiple_accuracy: 0,
+            subregions: Vec::new(),
+        }
+    }
+
+    /// Get the accuracy sub-region containing a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude, in degrees
+    /// * `lon` - longitude, in degrees
+    ///
+    /// # Returns
+    ///
+    /// * The first [AccuracySubregion] whose outline contains the lat/lon,
+    ///   or None if none does (or the record has no sub-regions)
+    pub fn subregion_at(&self, lat: f64, lon: f64) -> Option<&AccuracySubregion> {
+        self.subregions
+            .iter()
+            .find(|subregion| subregion.contains(lat, lon))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Accuracy sub-region of a [RawDTEDAccRecord], for cells whose accuracy
+/// varies over their area
+///
+/// All accuracies are in meters, with 90% assurance, [None] meaning not available.
+///
+/// # Fields
+///
+/// * `outline` - polygon outline of the sub-region (latitude and longitude of
+///   each vertex, at most 14, the polygon being implicitly closed)
+/// * `abs_horizontal` - absolute horizontal accuracy
+/// * `abs_vertical` - absolute vertical accuracy
+/// * `rel_horizontal` - relative (point-to-point) horizontal accuracy
+/// * `rel_vertical` - relative (point-to-point) vertical accuracy
+pub struct AccuracySubregion {
+    pub outline: Vec<AxisElement<Angle>>,
+    pub abs_horizontal: Option<u16>,
+    pub abs_vertical: Option<u16>,
+    pub rel_horizontal: Option<u16>,
+    pub rel_vertical: Option<u16>,
+}
+impl AccuracySubregion {
+    /// Get the outline of the sub-region, in degrees
+    pub fn outline_degrees(&self) -> Vec<AxisElement<f64>> {
+        self.outline.iter().map(|vertex| (*vertex).into()).collect()
+    }
+
+    /// Whether the outline contains a lat/lon, points on the outline itself
+    /// being contained
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude, in degrees
+    /// * `lon` - longitude, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::AccuracySubregion;
+    /// use dted2::primitives::{Angle, AxisElement};
+    ///
+    /// let vertex = |lat, lon| AxisElement::new(Angle::new(lat, 0, 0.0, false), Angle::new(lon, 0, 0.0, false));
+    /// let subregion = AccuracySubregion {
+    ///     outline: vec![vertex(42, 15), vertex(43, 15), vertex(42, 16)],
+    ///     abs_horizontal: None,
+    ///     abs_vertical: Some(10),
+    ///     rel_horizontal: None,
+    ///     rel_vertical: None,
+    /// };
+    /// assert!(subregion.contains(42.2, 15.2));
+    /// assert!(subregion.contains(42.5, 15.5));
+    /// assert!(!subregion.contains(42.8, 15.8));
+    /// ```
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        let outline = self.outline_degrees();
+        if outline.len() < 3 {
+            return false;
+        }
+        let mut inside = false;
+        for (idx, a) in outline.iter().enumerate() {
+            let b = &outline[(idx + 1) % outline.len()];
+            // on the edge
+            let cross = (b.lon - a.lon) * (lat - a.lat) - (b.lat - a.lat) * (lon - a.lon);
+            if cross.abs() <= SUBREGION_EPSILON
+                && lat >= a.lat.min(b.lat) - SUBREGION_EPSILON
+                && lat <= a.lat.max(b.lat) + SUBREGION_EPSILON
+                && lon >= a.lon.min(b.lon) - SUBREGION_EPSILON
+                && lon <= a.lon.max(b.lon) + SUBREGION_EPSILON
+            {
+                return true;
+            }
+            // even-odd crossings of a ray towards the east
+            if (a.lat > lat) != (b.lat > lat)
+                && lon < a.lon + (lat - a.lat) * (b.lon - a.lon) / (b.lat - a.lat)
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Longitude lines of a cell actually covered by data records in a file
+///
+/// Partial (e.g. coastal) cells may ship with fewer data records than
+/// advertised by their header, the missing longitude lines being void,
+/// see [crate::ParseOptions]
+///
+/// # Fields
+///
+/// * `lon_range` - indices of the covered longitude lines, from west to east
+/// * `lon_count` - number of longitude lines advertised by the header
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::Coverage;
+///
+/// assert!(!Coverage::full(121).is_partial());
+/// let coverage = Coverage { lon_range: 0..60, lon_count: 121 };
+/// assert!(coverage.is_partial());
+/// ```
+pub struct Coverage {
+    pub lon_range: Range<usize>,
+    pub lon_count: usize,
+}
+impl Coverage {
+    /// Create the coverage of a complete cell
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_count` - number of longitude lines
+    pub fn full(lon_count: usize) -> Coverage {
+        Coverage {
+            lon_range: 0..lon_count,
+            lon_count,
+        }
+    }
+
+    /// Whether some longitude lines are not covered
+    pub fn is_partial(&self) -> bool {
+        self.lon_range != (0..self.lon_count)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A DTED file, as parsed from (or to be written to) bytes
+///
+/// # Fields
+///
+/// * `header` - User Header Label
+/// * `data` - data records, one per longitude line, from west to east
+/// * `dsi_record` - Data Set Identification record
+/// * `acc_record` - Accuracy Description record
+/// * `coverage` - longitude lines with a data record in the file, see [Coverage]
+pub struct RawDTEDFile {
+    pub header: RawDTEDHeader,
+    pub data: RawDTEDRecords,
+    pub dsi_record: Option<RawDTEDDSIRecord>,
+    pub acc_record: Option<RawDTEDAccRecord>,
+    pub coverage: Coverage,
+}
+impl RawDTEDFile {
+    /// Read a DTED file
+    ///
+    /// Unlike [DTEDData::read], the data record checksums are not validated
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile]: the parsed file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    /// let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(file.data.len(), 121);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read(path: &str) -> Result<RawDTEDFile, DTEDError> {
+        RawDTEDFile::from_bytes(&read_file(path)?)
+    }
+
+    /// Parse a DTED file from its bytes
+    ///
+    /// Unlike [DTEDData::read], the data record checksums are not validated
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile]: the parsed file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    /// use dted2::Error;
+    ///
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// assert!(RawDTEDFile::from_bytes(&content).is_ok());
+    /// assert!(matches!(
+    ///     RawDTEDFile::from_bytes(&content[..content.len() - 1]),
+    ///     Err(Error::TruncatedFile { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     RawDTEDFile::from_bytes(&content[1..]),
+    ///     Err(Error::InvalidSentinel { .. })
+    /// ));
+    /// ```
+    pub fn from_bytes(content: &[u8]) -> Result<RawDTEDFile, DTEDError> {
+        RawDTEDFile::from_bytes_with(
+            content,
+            &ParseOptions {
+                verify_checksums: false,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Parse a DTED file from its bytes, as strictly as per the [ParseOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file
+    /// * `options` - [ParseOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile]: the parsed file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{RawDTEDFile, DTED_VOID_ELEVATION};
+    /// use dted2::ParseOptions;
+    ///
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let truncated = &content[..content.len() - 1000];
+    /// assert!(RawDTEDFile::from_bytes_with(truncated, &ParseOptions::default()).is_err());
+    /// let file = RawDTEDFile::from_bytes_with(truncated, &ParseOptions::lenient()).unwrap();
+    /// assert_eq!(file.data.len(), 121);
+    /// assert_eq!(file.data.get(0, 120), Some(DTED_VOID_ELEVATION));
+    /// ```
+    pub fn from_bytes_with(
+        content: &[u8],
+        options: &ParseOptions,
+    ) -> Result<RawDTEDFile, DTEDError> {
+        RawDTEDFile::parse(content, options, false).map(|(file, _)| file)
+    }
+
+    /// Parse a DTED file from its bytes, recovering from corrupt data records
+    ///
+    /// Unlike [RawDTEDFile::from_bytes_with], parsing goes on past a data record
+    /// which is malformed (or has a bad checksum, if verified), its column being
+    /// void instead, with a [ParseDiagnostic] describing what was skipped. Other
+    /// sections (including missing data records) are handled as per the [ParseOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file
+    /// * `options` - [ParseOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile]: the parsed file
+    /// * The [ParseDiagnostic]s, one per skipped section
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{dted_record_length, RawDTEDFile, DTED_DATA_OFFSET, DTED_VOID_ELEVATION};
+    /// use dted2::{ParseOptions, Section};
+    ///
+    /// let mut content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// content[DTED_DATA_OFFSET + 7 * dted_record_length(121)] = 0;
+    /// let (file, diagnostics) =
+    ///     RawDTEDFile::recover_from_bytes(&content, &ParseOptions::default()).unwrap();
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].section, Section::Record(Some(7)));
+    /// assert_eq!(file.data.get(0, 7), Some(DTED_VOID_ELEVATION));
+    /// ```
+    pub fn recover_from_bytes(
+        content: &[u8],
+        options: &ParseOptions,
+    ) -> Result<(RawDTEDFile, Vec<ParseDiagnostic>), DTEDError> {
+        RawDTEDFile::parse(content, options, true)
+    }
+
+    /// Parse a DTED file from its bytes, see [RawDTEDFile::recover_from_bytes]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file
+    /// * `options` - [ParseOptions]
+    /// * `recover` - whether to void corrupt data records, rather than fail
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile]: the parsed file
+    /// * The [ParseDiagnostic]s, one per skipped section
+    fn parse(
+        content: &[u8],
+        options: &ParseOptions,
+        recover: bool,
+    ) -> Result<(RawDTEDFile, Vec<ParseDiagnostic>), DTEDError> {
+        let (input, header) = parsers::dted_uhl_parser(content)
+            .map_err(|e| DTEDError::locate(e, content, 0, None))?;
+        let lat_count = header.count.lat;
+        let locate = |e| DTEDError::locate(e, content, 0, Some(lat_count));
+        if let Some(max) = options.max_records {
+            if header.count.lon as usize > max {
+                return Err(DTEDError::ParseError {
+                    message: format!(
+                        "{} data records exceed the maximum of {}",
+                        header.count.lon, max
+                    ),
+                    context: Some(ErrorContext::new(0, content, Some(lat_count))),
+                });
+            }
+        }
+        let mut diagnostics = Vec::new();
+        // --------------------------------------------------
+        // header records
+        // --------------------------------------------------
+        let (input, dsi_record) = optional_record(
+            input,
+            RecognitionSentinel::DSI,
+            DTED_DSI_RECORD_LENGTH,
+            parsers::dted_dsi_parser,
+            options.require_records,
+        )
+        .map_err(locate)?;
+        let dsi_record = dsi_record
+            .map_err(|e| diagnostics.push(ParseDiagnostic::new(Section::Dsi, locate(e))))
+            .ok();
+        let (input, acc_record) = optional_record(
+            input,
+            RecognitionSentinel::ACC,
+            DTED_ACC_RECORD_LENGTH,
+            parsers::dted_acc_parser,
+            options.require_records,
+        )
+        .map_err(locate)?;
+        let acc_record = acc_record
+            .map_err(|e| diagnostics.push(ParseDiagnostic::new(Section::Acc, locate(e))))
+            .ok();
+        // --------------------------------------------------
+        // data records, the missing ones being void if tolerated
+        // --------------------------------------------------
+        let line_len = lat_count as usize;
+        let lon_count = header.count.lon as usize;
+        let record_len = dted_record_length(lat_count);
+        let available = match options.tolerate_truncated {
+            true => lon_count.min(input.len() / record_len),
+            false => lon_count,
+        };
+        let truncated = |needed| {
+            DTEDError::locate(
+                nom::Err::Incomplete(nom::Needed::new(needed)),
+                &[],
+                content.len(),
+                Some(lat_count),
+            )
+        };
+        let data = match recover {
+            true => {
+                if input.len() < available * record_len {
+                    return Err(truncated(available * record_len - input.len()));
+                }
+                let offset = content.len() - input.len();
+                let mut data = RawDTEDRecords::with_capacity(line_len, lon_count);
+                for (lon_idx, record) in input.chunks_exact(record_len).take(available).enumerate()
+                {
+                    let record = parsers::parse_dted_record(record, line_len)
+                        .map_err(|e| {
+                            DTEDError::locate(
+                                e,
+                                record,
+                                offset + lon_idx * record_len,
+                                Some(lat_count),
+                            )
+                        })
+                        .and_then(|(_, record)| match record.compute_checksum() {
+                            computed
+                                if computed == record.checksum || !options.verify_checksums =>
+                            {
+                                Ok(record)
+                            }
+                            computed => Err(DTEDError::BadChecksum {
+                                record: lon_idx,
+                                stored: record.checksum,
+                                computed,
+                            }),
+                        });
+                    data.push(record.unwrap_or_else(|e| {
+                        diagnostics.push(ParseDiagnostic::new(Section::Record(Some(lon_idx)), e));
+                        RawDTEDRecord::void(lon_idx, line_len)
+                    }))?;
+                    let records = lon_idx + 1;
+                    if let Some(progress) = &options.on_progress {
+                        if records % progress.every() == 0 || records == available {
+                            progress.report(&ParseProgress {
+                                records,
+                                total_records: available,
+                                bytes: offset + records * record_len,
+                                total_bytes: content.len(),
+                            });
+                        }
+                    }
+                }
+                data
+            }
+            false => {
+                let count = AxisElement::new(lat_count, available as u16);
+                let (_, data) = match &options.on_progress {
+                    Some(progress) => records_with_progress(content, input, count, progress),
+                    None => parsers::dted_records_parser(input, count),
+                }
+                .map_err(locate)?;
+                if options.verify_checksums {
+                    data.validate_checksums()?;
+                }
+                data
+            }
+        };
+        let (data, coverage) = match available < lon_count {
+            true => {
+                let (data, coverage, missing) = place_records(data, lon_count)?;
+                for lon_idx in missing {
+                    diagnostics.push(ParseDiagnostic::new(
+                        Section::Record(Some(lon_idx)),
+                        truncated(lon_count * record_len - input.len()),
+                    ));
+                }
+                (data, coverage)
+            }
+            false => (data, Coverage::full(lon_count)),
+        };
+        Ok((
+            RawDTEDFile {
+                header,
+                data,
+                dsi_record,
+                acc_record,
+                coverage,
+            },
+            diagnostics,
+        ))
+    }
+
+    /// Get the [DTEDLevel], see [DTEDLevel::infer]
+    ///
+    /// # Returns
+    ///
+    /// * The level, or None if it can not be inferred
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{DTEDLevel, RawDTEDFile};
+    /// let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(file.level(), Some(DTEDLevel::Level0));
+    /// ```
+    pub fn level(&self) -> Option<DTEDLevel> {
+        DTEDLevel::infer(
+            self.header.interval_secs_x_10.lat,
+            self.header.count.lat,
+            self.dsi_record
+                .as_ref()
+                .map(|dsi| dsi.product_level.as_str()),
+        )
+    }
+
+    /// Compare the origin, intervals and counts of the UHL and DSI records,
+    /// see [DTEDMetadata::cross_validate]
+    ///
+    /// # Returns
+    ///
+    /// * The [RecordDiscrepancy]s found, empty if the records agree or
+    ///   without a DSI record
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{RawDTEDFile, RecordDiscrepancy};
+    /// use dted2::primitives::AxisElement;
+    ///
+    /// let mut file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// assert!(file.cross_validate().is_empty());
+    /// file.dsi_record.as_mut().unwrap().count.lon = 61;
+    /// assert_eq!(
+    ///     file.cross_validate(),
+    ///     vec![RecordDiscrepancy::Count {
+    ///         uhl: AxisElement::new(121, 121),
+    ///         dsi: AxisElement::new(121, 61),
+    ///     }],
+    /// );
+    /// ```
+    pub fn cross_validate(&self) -> Vec<RecordDiscrepancy> {
+        let header = &self.header;
+        self.dsi_record.as_ref().map_or_else(Vec::new, |dsi| {
+            cross_validate(dsi, &header.origin, header.interval_secs_x_10, header.count)
+        })
+    }
+
+    /// Write the file (UHL, DSI, ACC and data records) to any [std::io::Write]
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The destination
+    ///
+    /// # Returns
+    ///
+    /// * An error if writing fails or a field can not be represented
+    #[cfg(feature = "std")]
+    pub fn write<W: std::io::Write>(&self, writer: &mut W) -> Result<(), DTEDError> {
+        Ok(writers::write_dted_file(writer, self)?)
+    }
+
+    /// Serialize the file (UHL, DSI, ACC and data records) to bytes
+    ///
+    /// # Returns
+    ///
+    /// * The bytes of the file, or an error if a field can not be represented
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::parsers::dted_file_parser;
+    ///
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let (_, file) = dted_file_parser(&content).unwrap();
+    /// let bytes = file.to_bytes().unwrap();
+    /// assert_eq!(bytes.len(), content.len());
+    /// assert_eq!(dted_file_parser(&bytes).unwrap().1, file);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DTEDError> {
+        let mut buf = Vec::new();
+        self.write(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Recompute the checksum of every data record,
+    /// replacing any checksum that does not match the record contents
+    ///
+    /// # Returns
+    ///
+    /// * The number of records whose checksum was corrected
+    pub fn recompute_checksums(&mut self) -> usize {
+        self.data.recompute_checksums()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// DTED Data Record
+///
+/// # Fields
+///
+/// * `blk_count` - sequential count of the block within the file
+/// * `lon_count` - longitude count (index of this longitude line)
+/// * `lat_count` - latitude count (index of the first latitude point)
+/// * `elevations` - elevations, in meters, from south to north
+/// * `checksum` - checksum of the record, as stored in the file
+pub struct RawDTEDRecord {
+    pub blk_count: u32,
     pub lon_count: u16,
-    pub partial_cell_flag: f64,
-    pub coverage: f64,
+    pub lat_count: u16,
+    pub elevations: Vec<i16>,
+    pub checksum: u32,
 }
+impl RawDTEDRecord {
+    /// Get the header and checksum of the record, without its elevations
+    pub fn info(&self) -> RawDTEDRecordInfo {
+        RawDTEDRecordInfo {
+            blk_count: self.blk_count,
+            lon_count: self.lon_count,
+            lat_count: self.lat_count,
+            checksum: self.checksum,
+        }
+    }
+
+    /// Compute the checksum of the record from its contents,
+    /// see [RawDTEDRecordInfo::compute_checksum]
+    ///
+    /// # Returns
+    ///
+    /// * The computed checksum
+    pub fn compute_checksum(&self) -> u32 {
+        self.info().compute_checksum(&self.elevations)
+    }
 
-/// TODO
-pub struct DTEDRecordACC {}
+    /// Whether the stored checksum matches the record contents
+    pub fn is_checksum_valid(&self) -> bool {
+        self.checksum == self.compute_checksum()
+    }
 
-pub struct RawDTEDFile {
-    pub header: RawDTEDHeader,
-    pub data: Vec<RawDTEDRecord>,
-    pub dsi_record: Option<u8>,
-    pub acc_record: Option<u8>,
+    /// Create a data record where every post is void, with a valid checksum
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - index of the longitude line, from west to east
+    /// * `line_len` - number of elevations in the record (latitude points)
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDRecord]: the void record
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{RawDTEDRecord, DTED_VOID_ELEVATION};
+    ///
+    /// let record = RawDTEDRecord::void(3, 121);
+    /// assert_eq!(record.lon_count, 3);
+    /// assert!(record.elevations.iter().all(|&e| e == DTED_VOID_ELEVATION));
+    /// assert!(record.is_checksum_valid());
+    /// ```
+    pub fn void(lon_idx: usize, line_len: usize) -> RawDTEDRecord {
+        let mut record = RawDTEDRecord {
+            blk_count: lon_idx as u32,
+            lon_count: lon_idx as u16,
+            lat_count: 0,
+            elevations: vec![DTED_VOID_ELEVATION; line_len],
+            checksum: 0,
+        };
+        record.checksum = record.compute_checksum();
+        record
+    }
 }
 
-pub struct RawDTEDRecord {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// DTED Data Record header and checksum, the elevations of the
+/// record being stored separately (see [RawDTEDRecords])
+///
+/// # Fields
+///
+/// * `blk_count` - sequential count of the block within the file
+/// * `lon_count` - longitude count (index of this longitude line)
+/// * `lat_count` - latitude count (index of the first latitude point)
+/// * `checksum` - checksum of the record, as stored in the file
+pub struct RawDTEDRecordInfo {
     pub blk_count: u32,
     pub lon_count: u16,
     pub lat_count: u16,
+    pub checksum: u32,
+}
+impl RawDTEDRecordInfo {
+    /// Compute the checksum of the record from its contents,
+    /// which is the sum of every byte of the record (as unsigned 8-bit values)
+    /// preceding the checksum itself
+    ///
+    /// # Arguments
+    ///
+    /// * `elevations` - elevations of the record, from south to north
+    ///
+    /// # Returns
+    ///
+    /// * The computed checksum
+    pub fn compute_checksum(&self, elevations: &[i16]) -> u32 {
+        let header = RecognitionSentinel::DATA.value()[0] as u32
+            + parsers::compute_checksum(&self.blk_count.to_be_bytes()[1..])
+            + parsers::compute_checksum(&self.lon_count.to_be_bytes())
+            + parsers::compute_checksum(&self.lat_count.to_be_bytes());
+        elevations.iter().fold(header, |acc, elev| {
+            acc + parsers::compute_checksum(&parsers::to_signed_mag(*elev).to_be_bytes())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// DTED Data Records, with the elevations of every record stored in a single
+/// contiguous buffer (column-major, as in the file: one longitude line after
+/// the other, each from south to north)
+///
+/// Posts are indexed by `(lat_idx, lon_idx)`, the row before the column,
+/// whether with [RawDTEDRecords::get] or `records[(lat_idx, lon_idx)]`.
+///
+/// # Fields
+///
+/// * `line_len` - number of elevations per record (latitude points)
+/// * `records` - header and checksum of each record, from west to east
+/// * `elevations` - elevations of every record, in meters
+///
+/// # Examples
+///
+/// ```
+/// use dted2::DTEDData;
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// assert_eq!(data.data.len(), 121);
+/// assert_eq!(data.data.column(3).unwrap().len(), 121);
+/// assert_eq!(data.data.get(5, 3), Some(data.data.column(3).unwrap()[5]));
+/// assert_eq!(data.data[(5, 3)], data.data.column(3).unwrap()[5]);
+/// assert!(data.data.column(121).is_none());
+/// ```
+pub struct RawDTEDRecords {
+    pub line_len: usize,
+    pub records: Vec<RawDTEDRecordInfo>,
     pub elevations: Vec<i16>,
 }
+impl RawDTEDRecords {
+    /// Create empty records, with room for `lon_count` records
+    ///
+    /// # Arguments
+    ///
+    /// * `line_len` - number of elevations per record (latitude points)
+    /// * `lon_count` - number of records to reserve room for
+    pub fn with_capacity(line_len: usize, lon_count: usize) -> RawDTEDRecords {
+        RawDTEDRecords {
+            line_len,
+            records: Vec::with_capacity(lon_count),
+            elevations: Vec::with_capacity(line_len * lon_count),
+        }
+    }
+
+    /// Number of records
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether there are no records
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Append a record
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - [RawDTEDRecord], which must have `line_len` elevations
+    ///
+    /// # Returns
+    ///
+    /// * An error if the record does not have `line_len` elevations
+    pub fn push(&mut self, record: RawDTEDRecord) -> Result<(), DTEDError> {
+        if record.elevations.len() != self.line_len {
+            return Err(DTEDError::InvalidInput(format!(
+                "record {} has {} elevations, expected {}",
+                self.len(),
+                record.elevations.len(),
+                self.line_len
+            )));
+        }
+        self.records.push(record.info());
+        self.elevations.extend_from_slice(&record.elevations);
+        Ok(())
+    }
+
+    /// Get the elevations of a record
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Elevations from south to north, or None if out of bounds
+    pub fn column(&self, lon_idx: usize) -> Option<&[i16]> {
+        match lon_idx < self.len() {
+            true => Some(&self.elevations[lon_idx * self.line_len..][..self.line_len]),
+            false => None,
+        }
+    }
+
+    /// Get the elevations of a record, mutably
+    ///
+    /// The checksum of the record is not updated,
+    /// see [RawDTEDRecords::recompute_checksums]
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Elevations from south to north, or None if out of bounds
+    pub fn column_mut(&mut self, lon_idx: usize) -> Option<&mut [i16]> {
+        match lon_idx < self.len() {
+            true => Some(&mut self.elevations[lon_idx * self.line_len..][..self.line_len]),
+            false => None,
+        }
+    }
+
+    /// Iterate over the elevations of every record, from west to east
+    pub fn columns(&self) -> impl Iterator<Item = &[i16]> {
+        self.elevations
+            .chunks_exact(self.line_len.max(1))
+            .take(self.len())
+    }
+
+    /// Get the raw elevation of a post, void posts included (see [RawDTEDRecords::post])
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation or None if out of bounds
+    pub fn get(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.column(lon_idx)?.get(lat_idx).copied()
+    }
+
+    /// Get a mutable reference to the raw elevation of a post, leaving the
+    /// checksum of its record to [RawDTEDRecords::recompute_checksums]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation or None if out of bounds
+    pub fn get_mut(&mut self, lat_idx: usize, lon_idx: usize) -> Option<&mut i16> {
+        self.column_mut(lon_idx)?.get_mut(lat_idx)
+    }
+
+    /// Get a [Post], telling void posts apart from elevations
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [Post] or None if out of bounds
+    pub fn post(&self, lat_idx: usize, lon_idx: usize) -> Option<Post> {
+        self.get(lat_idx, lon_idx).map(Post::from_raw)
+    }
+
+    /// Get a (copy of a) record
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDRecord] or None if out of bounds
+    pub fn record(&self, lon_idx: usize) -> Option<RawDTEDRecord> {
+        let info = self.records.get(lon_idx)?;
+        Some(RawDTEDRecord {
+            blk_count: info.blk_count,
+            lon_count: info.lon_count,
+            lat_count: info.lat_count,
+            elevations: self.column(lon_idx)?.to_vec(),
+            checksum: info.checksum,
+        })
+    }
+
+    /// Whether the stored checksum of a record matches its contents
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Whether the checksum is valid (false if out of bounds)
+    pub fn is_checksum_valid(&self, lon_idx: usize) -> bool {
+        match (self.records.get(lon_idx), self.column(lon_idx)) {
+            (Some(info), Some(elevations)) => info.checksum == info.compute_checksum(elevations),
+            _ => false,
+        }
+    }
+
+    /// Validate the checksum of every record
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDError::BadChecksum] for the first record whose stored
+    ///   checksum does not match its contents
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    /// use dted2::Error;
+    ///
+    /// let mut file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// assert!(file.data.validate_checksums().is_ok());
+    /// file.data.records[7].checksum += 1;
+    /// assert!(matches!(
+    ///     file.data.validate_checksums(),
+    ///     Err(Error::BadChecksum { record: 7, .. })
+    /// ));
+    /// ```
+    pub fn validate_checksums(&self) -> Result<(), DTEDError> {
+        for (record, (info, elevations)) in self.records.iter().zip(self.columns()).enumerate() {
+            let computed = info.compute_checksum(elevations);
+            if info.checksum != computed {
+                return Err(DTEDError::BadChecksum {
+                    record,
+                    stored: info.checksum,
+                    computed,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Recompute the checksum of every record,
+    /// replacing any checksum that does not match the record contents
+    ///
+    /// # Returns
+    ///
+    /// * The number of records whose checksum was corrected
+    pub fn recompute_checksums(&mut self) -> usize {
+        let line_len = self.line_len;
+        self.records
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, info)| {
+                let computed =
+                    info.compute_checksum(&self.elevations[idx * line_len..][..line_len]);
+                let repaired = info.checksum != computed;
+                info.checksum = computed;
+                repaired
+            })
+            .filter(|repaired| *repaired)
+            .count()
+    }
+}
+impl Index<(usize, usize)> for RawDTEDRecords {
+    type Output = i16;
+
+    /// Get the raw elevation of the post at `(lat_idx, lon_idx)`: the row
+    /// (latitude index, from south to north) first, then the column
+    /// (longitude index, from west to east), see [RawDTEDRecords::get]
+    ///
+    /// # Panics
+    ///
+    /// If the post is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(Some(data.data[(5, 3)]), data.data.get(5, 3));
+    /// assert_eq!(data.data[(5, 3)], data.data.column(3).unwrap()[5]);
+    /// ```
+    fn index(&self, (lat_idx, lon_idx): (usize, usize)) -> &i16 {
+        let (lat_count, lon_count) = (self.line_len, self.len());
+        self.column(lon_idx)
+            .and_then(|column| column.get(lat_idx))
+            .unwrap_or_else(|| {
+                panic!(
+                    "post ({}, {}) out of bounds of {}x{} posts",
+                    lat_idx, lon_idx, lat_count, lon_count
+                )
+            })
+    }
+}
+impl IndexMut<(usize, usize)> for RawDTEDRecords {
+    /// Get a mutable reference to the raw elevation of the post at
+    /// `(lat_idx, lon_idx)`, see [RawDTEDRecords::get_mut]
+    ///
+    /// # Panics
+    ///
+    /// If the post is out of bounds
+    fn index_mut(&mut self, (lat_idx, lon_idx): (usize, usize)) -> &mut i16 {
+        let (lat_count, lon_count) = (self.line_len, self.len());
+        self.get_mut(lat_idx, lon_idx).unwrap_or_else(|| {
+            panic!(
+                "post ({}, {}) out of bounds of {}x{} posts",
+                lat_idx, lon_idx, lat_count, lon_count
+            )
+        })
+    }
+}
+
+/// Repair the data record checksums of a DTED file in place
+///
+/// Only the checksum fields are rewritten, the elevations and every
+/// other byte of the file are left untouched. The file is only written
+/// if at least one checksum needed correcting
+///
+/// # Arguments
+///
+/// * `path` (str): Path to the DTED file
+///
+/// # Returns
+///
+/// * The number of records whose checksum was corrected
+#[cfg(feature = "std")]
+pub fn repair_checksums(path: &str) -> Result<usize, DTEDError> {
+    let mut content = std::fs::read(path)?;
+    let (_, header) =
+        parsers::dted_uhl_parser(&content).map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+    let record_len = validate_data_records(&content, &header.count)?;
+    let mut repaired = 0;
+    for lon in 0..header.count.lon as usize {
+        let start = DTED_DATA_OFFSET + lon * record_len;
+        let end = start + record_len;
+        let checksum_start = end - DTED_RECORD_CHECKSUM_LENGTH;
+        let computed = parsers::compute_checksum(&content[start..checksum_start]).to_be_bytes();
+        if content[checksum_start..end] != computed {
+            content[checksum_start..end].copy_from_slice(&computed);
+            repaired += 1;
+        }
+    }
+    if repaired > 0 {
+        std::fs::write(path, &content)?;
+    }
+    Ok(repaired)
+}
+
+/// Get the length (in bytes) of a data record, from its header through its checksum
+///
+/// # Arguments
+///
+/// * `lat_count` - number of latitude lines (posts per record)
+///
+/// # Returns
+///
+/// * Record length
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::dted_record_length;
+/// assert_eq!(dted_record_length(3601), 7214);
+/// ```
+pub fn dted_record_length(lat_count: u16) -> usize {
+    DTED_RECORD_HEADER_LENGTH + lat_count as usize * 2 + DTED_RECORD_CHECKSUM_LENGTH
+}
+
+/// Validate that the content of a DTED file holds all of its data records,
+/// each starting with the data sentinel, without decoding them
+///
+/// # Arguments
+///
+/// * `content` - content of the whole DTED file
+/// * `count` - number of lat/lon lines
+///
+/// # Returns
+///
+/// * The record length
+pub(crate) fn validate_data_records(
+    content: &[u8],
+    count: &AxisElement<u16>,
+) -> Result<usize, DTEDError> {
+    let record_len = dted_record_length(count.lat);
+    let end = DTED_DATA_OFFSET + count.lon as usize * record_len;
+    let context = |offset: usize| ErrorContext::new(offset, &content[offset..], Some(count.lat));
+    if end > content.len() {
+        let mut err = DTEDError::from(nom::Needed::new(end - content.len()));
+        err.set_context(context(content.len()));
+        return Err(err);
+    }
+    match (0..count.lon as usize)
+        .map(|lon| DTED_DATA_OFFSET + lon * record_len)
+        .find(|start| content[*start] != RecognitionSentinel::DATA.value()[0])
+    {
+        None => Ok(record_len),
+        Some(start) => Err(DTEDError::InvalidSentinel {
+            context: Some(context(start)),
+        }),
+    }
+}
+
+/// Read (at most) the first bytes of a file
+///
+/// # Arguments
+///
+/// * `path` (str): Path to the file
+/// * `len` - number of bytes to read
+///
+/// # Returns
+///
+/// * The bytes read, fewer than `len` if the file is shorter
+#[cfg(feature = "std")]
+fn read_prefix(path: &str, len: usize) -> Result<Vec<u8>, DTEDError> {
+    let mut content = Vec::with_capacity(len);
+    let file = std::fs::File::open(path)?;
+    #[cfg(feature = "flate2")]
+    let file = crate::gzip::MaybeGzDecoder::new(file)?;
+    file.take(len as u64).read_to_end(&mut content)?;
+    Ok(content)
+}
+
+/// Read a whole file, decompressing it if gzip compressed (with the `flate2`
+/// feature), see [crate::gzip]
+///
+/// # Arguments
+///
+/// * `path` (str): Path to the file
+///
+/// # Returns
+///
+/// * The (decompressed) bytes of the file
+#[cfg(feature = "std")]
+pub(crate) fn read_file(path: &str) -> Result<Vec<u8>, DTEDError> {
+    let content = std::fs::read(path)?;
+    #[cfg(feature = "flate2")]
+    let content = crate::gzip::decompress(content)?;
+    Ok(content)
+}
+
+/// nom error over bytes
+type NomError<'a> = nom::Err<nom::error::Error<&'a [u8]>>;
+
+/// Parse data records in batches, reporting the progress after each batch,
+/// see [parsers::dted_records_parser]
+///
+/// # Arguments
+///
+/// * `content` - content of the whole DTED file
+/// * `input` - A byte slice of `content`, starting at the first data record
+/// * `count` - number of lat/lon lines
+/// * `progress` - [ProgressCallback], its batches being of `every` records
+///
+/// # Returns
+///
+/// * The data records, from west to east
+fn records_with_progress<'a>(
+    content: &[u8],
+    input: &'a [u8],
+    count: AxisElement<u16>,
+    progress: &ProgressCallback,
+) -> nom::IResult<&'a [u8], RawDTEDRecords> {
+    let (line_len, total_records) = (count.lat as usize, count.lon as usize);
+    let mut data = RawDTEDRecords {
+        line_len,
+        records: Vec::with_capacity(total_records),
+        elevations: vec![0; line_len * total_records],
+    };
+    let mut input = input;
+    for start in (0..total_records).step_by(progress.every()) {
+        let batch = progress.every().min(total_records - start);
+        let out = &mut data.elevations[start * line_len..(start + batch) * line_len];
+        let (rest, records) = parsers::dted_records_parser_into(
+            input,
+            AxisElement::new(count.lat, batch as u16),
+            out,
+        )?;
+        data.records.extend(records);
+        input = rest;
+        progress.report(&ParseProgress {
+            records: data.records.len(),
+            total_records,
+            bytes: content.len() - input.len(),
+            total_bytes: content.len(),
+        });
+    }
+    Ok((input, data))
+}
+
+/// Parse a header record (DSI or ACC) which may be skipped over if not required
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+/// * `sentinel` - [RecognitionSentinel] starting the record
+/// * `len` - (fixed) length of the record
+/// * `parser` - parser of the record
+/// * `required` - whether a missing or malformed record is an error
+///
+/// # Returns
+///
+/// * The record, or the error it was skipped for: if missing, the input is
+///   left as is and if malformed, the input is past the record
+fn optional_record<'a, O>(
+    input: &'a [u8],
+    sentinel: RecognitionSentinel,
+    len: usize,
+    parser: impl Fn(&'a [u8]) -> nom::IResult<&'a [u8], O>,
+    required: bool,
+) -> nom::IResult<&'a [u8], Result<O, NomError<'a>>> {
+    match parser(input) {
+        Ok((rest, record)) => Ok((rest, Ok(record))),
+        Err(e) if required => Err(e),
+        Err(e) if !input.starts_with(sentinel.value()) => Ok((input, Err(e))),
+        Err(e) => match input.len() >= len {
+            true => Ok((&input[len..], Err(e))),
+            false => Err(e),
+        },
+    }
+}
+
+/// Place the data records of a short data section at the longitude lines
+/// given by their longitude count, the other longitude lines being void
+///
+/// The longitude counts are clamped such that the records stay in order
+/// and all fit within the cell
+///
+/// # Arguments
+///
+/// * `data` - data records of the file, from west to east
+/// * `lon_count` - number of longitude lines advertised by the header
+///
+/// # Returns
+///
+/// * The data records of every longitude line
+/// * The [Coverage] of the data records
+/// * The indices of the missing longitude lines
+fn place_records(
+    data: RawDTEDRecords,
+    lon_count: usize,
+) -> Result<(RawDTEDRecords, Coverage, Vec<usize>), DTEDError> {
+    let count = data.len();
+    let mut positions = Vec::with_capacity(count);
+    for (idx, info) in data.records.iter().enumerate() {
+        let min = positions.last().map_or(0, |pos| pos + 1);
+        let max = lon_count - (count - idx);
+        positions.push((info.lon_count as usize).clamp(min, max));
+    }
+    let mut placed = RawDTEDRecords::with_capacity(data.line_len, lon_count);
+    let mut missing = Vec::new();
+    let mut records = positions.iter().zip(0..count).peekable();
+    for lon_idx in 0..lon_count {
+        match records.next_if(|(pos, _)| **pos == lon_idx) {
+            Some((_, idx)) => placed.push(data.record(idx).expect("record within data"))?,
+            None => {
+                missing.push(lon_idx);
+                placed.push(RawDTEDRecord::void(lon_idx, data.line_len))?;
+            }
+        }
+    }
+    let lon_range = match (positions.first(), positions.last()) {
+        (Some(first), Some(last)) => *first..last + 1,
+        _ => 0..0,
+    };
+    Ok((
+        placed,
+        Coverage {
+            lon_range,
+            lon_count,
+        },
+        missing,
+    ))
+}
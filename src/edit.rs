@@ -0,0 +1,187 @@
+//! Contains the in-place editing of the posts of a cell, for small
+//! corrections: setting posts one by one or mapping them all, either on the
+//! [RawDTEDRecords] grid (whose checksums are then refreshed with
+//! [RawDTEDRecords::recompute_checksums]) or on a [RawDTEDFile], which keeps
+//! its checksums up to date and writes the edited cell back out.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{Post, RawDTEDFile, RawDTEDRecords};
+use crate::Error as DTEDError;
+
+impl RawDTEDRecords {
+    /// Set a post
+    ///
+    /// The checksum of the record is not updated,
+    /// see [RawDTEDRecords::recompute_checksums]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    /// * `post` - new [Post], e.g. [Post::Void]
+    ///
+    /// # Returns
+    ///
+    /// * Previous [Post], or None if out of bounds (nothing being set)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, Post};
+    ///
+    /// let mut data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let previous = data.data.set_post(10, 20, Post::Void).unwrap();
+    /// assert_eq!(data.data.post(10, 20), Some(Post::Void));
+    /// assert_eq!(data.data.set_post(10, 20, previous), Some(Post::Void));
+    /// assert_eq!(data.data.set_post(121, 0, Post::Void), None);
+    /// ```
+    pub fn set_post(&mut self, lat_idx: usize, lon_idx: usize, post: Post) -> Option<Post> {
+        let raw = self.column_mut(lon_idx)?.get_mut(lat_idx)?;
+        let previous = Post::from_raw(*raw);
+        *raw = post.raw();
+        Some(previous)
+    }
+
+    /// Set the elevation of a post, see [RawDTEDRecords::set_post]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    /// * `elevation` - new elevation, in meters
+    ///
+    /// # Returns
+    ///
+    /// * Previous [Post], or None if out of bounds (nothing being set)
+    pub fn set_elevation(
+        &mut self,
+        lat_idx: usize,
+        lon_idx: usize,
+        elevation: i16,
+    ) -> Option<Post> {
+        self.set_post(lat_idx, lon_idx, Post::Elevation(elevation))
+    }
+
+    /// Replace every post by a function of it
+    ///
+    /// The checksums of the records are not updated,
+    /// see [RawDTEDRecords::recompute_checksums]
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - function of a [Post] returning its replacement
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, Post};
+    ///
+    /// let mut data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let before = data.data.get(10, 20).unwrap();
+    /// // raise the whole cell by 2 m, voids staying void
+    /// data.data.apply(|post| match post {
+    ///     Post::Elevation(elev) => Post::Elevation(elev + 2),
+    ///     Post::Void => Post::Void,
+    /// });
+    /// assert_eq!(data.data.get(10, 20), Some(before + 2));
+    /// ```
+    pub fn apply<F: FnMut(Post) -> Post>(&mut self, mut f: F) {
+        for raw in self.elevations.iter_mut() {
+            *raw = f(Post::from_raw(*raw)).raw();
+        }
+    }
+}
+
+impl RawDTEDFile {
+    /// Set a post, updating the checksum of its record
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    /// * `post` - new [Post], e.g. [Post::Void]
+    ///
+    /// # Returns
+    ///
+    /// * Previous [Post], or None if out of bounds (nothing being set)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    ///
+    /// let mut file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// file.set_elevation(10, 20, 1234).unwrap();
+    /// assert_eq!(file.recompute_checksums(), 0);
+    ///
+    /// // written and read back, its checksums valid
+    /// let read = RawDTEDFile::from_bytes(&file.to_bytes().unwrap()).unwrap();
+    /// assert_eq!(read.data.get(10, 20), Some(1234));
+    /// assert!(read.data.validate_checksums().is_ok());
+    /// ```
+    pub fn set_post(&mut self, lat_idx: usize, lon_idx: usize, post: Post) -> Option<Post> {
+        let previous = self.data.set_post(lat_idx, lon_idx, post)?;
+        self.refresh_checksum(lon_idx);
+        Some(previous)
+    }
+
+    /// Set the elevation of a post, updating the checksum of its record,
+    /// see [RawDTEDFile::set_post]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    /// * `elevation` - new elevation, in meters
+    ///
+    /// # Returns
+    ///
+    /// * Previous [Post], or None if out of bounds (nothing being set)
+    pub fn set_elevation(
+        &mut self,
+        lat_idx: usize,
+        lon_idx: usize,
+        elevation: i16,
+    ) -> Option<Post> {
+        self.set_post(lat_idx, lon_idx, Post::Elevation(elevation))
+    }
+
+    /// Replace every post by a function of it, updating the checksums of the
+    /// records, see [RawDTEDRecords::apply]
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - function of a [Post] returning its replacement
+    pub fn apply<F: FnMut(Post) -> Post>(&mut self, f: F) {
+        self.data.apply(f);
+        self.recompute_checksums();
+    }
+
+    /// Write the file to a path, see [RawDTEDFile::write]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an error if the file could not be written or a field can
+    ///   not be represented
+    pub fn write_dted(&self, path: &str) -> Result<(), DTEDError> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(&mut writer)?;
+        std::io::Write::flush(&mut writer)?;
+        Ok(())
+    }
+
+    /// Recompute the checksum of a record
+    fn refresh_checksum(&mut self, lon_idx: usize) {
+        let line_len = self.data.line_len;
+        let elevations = &self.data.elevations[lon_idx * line_len..][..line_len];
+        if let Some(info) = self.data.records.get_mut(lon_idx) {
+            info.checksum = info.compute_checksum(elevations);
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Contains the C API of the crate (requires the `ffi` feature), for C and
+//! C++ software, declared by the `include/dted2.h` header (generated with
+//! `cbindgen`).
+//!
+//! Files are read into opaque [DTEDData] handles, to be freed with
+//! [dted2_free]. Functions failing return NULL (or 0), with the message of
+//! the error available from [dted2_last_error] on the same thread.
+//!
+//! The shared (or static) library is built with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::source::ElevationSource;
+
+thread_local! {
+    /// Message of the last error on the thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Metadata of a DTED file, for C
+///
+/// # Fields
+///
+/// * `origin_lat` - latitude of the south west corner, in degrees
+/// * `origin_lon` - longitude of the south west corner, in degrees
+/// * `interval_lat` - interval between the posts along a longitude line, in degrees
+/// * `interval_lon` - interval between the longitude lines, in degrees
+/// * `lat_count` - number of posts per longitude line
+/// * `lon_count` - number of longitude lines
+/// * `level` - DTED level (0, 1 or 2), or -1 if it can not be inferred
+#[repr(C)]
+pub struct CDTEDMetadata {
+    pub origin_lat: f64,
+    pub origin_lon: f64,
+    pub interval_lat: f64,
+    pub interval_lon: f64,
+    pub lat_count: u16,
+    pub lon_count: u16,
+    pub level: i8,
+}
+
+/// Read a DTED file
+///
+/// # Arguments
+///
+/// * `path` - path of the file, as a NUL-terminated UTF-8 string
+///
+/// # Returns
+///
+/// * Handle to the DTED data, to be freed with [dted2_free], or NULL on error
+///
+/// # Safety
+///
+/// `path` must be NULL or a valid NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn dted2_open(path: *const c_char) -> *mut DTEDData {
+    if path.is_null() {
+        set_last_error("the path is NULL");
+        return ptr::null_mut();
+    }
+    let read = match CStr::from_ptr(path).to_str() {
+        Ok(path) => DTEDData::read(path).map_err(|e| e.to_string()),
+        Err(_) => Err("the path is not valid UTF-8".to_string()),
+    };
+    into_handle(read)
+}
+
+/// Parse DTED data from the bytes of a file
+///
+/// # Arguments
+///
+/// * `bytes` - the bytes of the file
+/// * `len` - number of bytes
+///
+/// # Returns
+///
+/// * Handle to the DTED data, to be freed with [dted2_free], or NULL on error
+///
+/// # Safety
+///
+/// `bytes` must be NULL or point to `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn dted2_open_bytes(bytes: *const u8, len: usize) -> *mut DTEDData {
+    if bytes.is_null() {
+        set_last_error("the bytes are NULL");
+        return ptr::null_mut();
+    }
+    let content = std::slice::from_raw_parts(bytes, len);
+    into_handle(DTEDData::from_bytes(content).map_err(|e| e.to_string()))
+}
+
+/// Free DTED data
+///
+/// # Arguments
+///
+/// * `data` - handle to the DTED data, or NULL (doing nothing)
+///
+/// # Safety
+///
+/// `data` must be NULL or a handle returned by [dted2_open] (or
+/// [dted2_open_bytes]) not freed yet, and not used once freed
+#[no_mangle]
+pub unsafe extern "C" fn dted2_free(data: *mut DTEDData) {
+    if !data.is_null() {
+        drop(Box::from_raw(data));
+    }
+}
+
+/// Get the message of the last error on the current thread
+///
+/// # Returns
+///
+/// * NUL-terminated message, valid until the next error on the thread, or
+///   NULL if no error occurred
+#[no_mangle]
+pub extern "C" fn dted2_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| {
+        error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.as_ptr())
+    })
+}
+
+/// Get the metadata of DTED data
+///
+/// # Arguments
+///
+/// * `data` - handle to the DTED data
+/// * `metadata` - [CDTEDMetadata] to fill
+///
+/// # Returns
+///
+/// * 1 if filled, 0 if either pointer is NULL
+///
+/// # Safety
+///
+/// `data` must be NULL or a valid handle, and `metadata` NULL or writable
+#[no_mangle]
+pub unsafe extern "C" fn dted2_metadata(
+    data: *const DTEDData,
+    metadata: *mut CDTEDMetadata,
+) -> c_int {
+    let (Some(data), false) = (data.as_ref(), metadata.is_null()) else {
+        set_last_error("the data or metadata is NULL");
+        return 0;
+    };
+    let meta = &data.metadata;
+    metadata.write(CDTEDMetadata {
+        origin_lat: meta.origin.lat,
+        origin_lon: meta.origin.lon,
+        interval_lat: meta.interval.lat,
+        interval_lon: meta.interval.lon,
+        lat_count: meta.count.lat,
+        lon_count: meta.count.lon,
+        level: meta.level().map_or(-1, |level| level.number() as i8),
+    });
+    1
+}
+
+/// Get the elevation at a lat/lon, of the nearest post, see
+/// [ElevationSource::elevation_at]
+///
+/// # Arguments
+///
+/// * `data` - handle to the DTED data
+/// * `lat` - latitude, in degrees
+/// * `lon` - longitude, in degrees
+/// * `elevation` - elevation to set, in meters
+///
+/// # Returns
+///
+/// * 1 if set, 0 if out of bounds, void or either pointer is NULL
+///
+/// # Safety
+///
+/// `data` must be NULL or a valid handle, and `elevation` NULL or writable
+#[no_mangle]
+pub unsafe extern "C" fn dted2_elevation_at(
+    data: *const DTEDData,
+    lat: f64,
+    lon: f64,
+    elevation: *mut i16,
+) -> c_int {
+    write_elevation(data, elevation, |data| data.elevation_at(lat, lon))
+}
+
+/// Get the elevation at a lat/lon, bilinearly interpolated between the
+/// surrounding posts, see [ElevationSource::elevation_at_interpolated]
+///
+/// # Arguments
+///
+/// * `data` - handle to the DTED data
+/// * `lat` - latitude, in degrees
+/// * `lon` - longitude, in degrees
+/// * `elevation` - elevation to set, in meters
+///
+/// # Returns
+///
+/// * 1 if set, 0 if out of bounds, void or either pointer is NULL
+///
+/// # Safety
+///
+/// `data` must be NULL or a valid handle, and `elevation` NULL or writable
+#[no_mangle]
+pub unsafe extern "C" fn dted2_elevation_at_interpolated(
+    data: *const DTEDData,
+    lat: f64,
+    lon: f64,
+    elevation: *mut f64,
+) -> c_int {
+    write_elevation(data, elevation, |data| {
+        data.elevation_at_interpolated(lat, lon)
+    })
+}
+
+/// Record the message of an error on the current thread
+fn set_last_error(message: &str) {
+    // messages do not hold NUL bytes, but make sure of it
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+}
+
+/// Box DTED data into a handle, or record its error and return NULL
+fn into_handle(data: Result<DTEDData, String>) -> *mut DTEDData {
+    match data {
+        Ok(data) => Box::into_raw(Box::new(data)),
+        Err(message) => {
+            set_last_error(&message);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Write an elevation queried from DTED data
+///
+/// # Safety
+///
+/// `data` must be NULL or a valid handle, and `elevation` NULL or writable
+unsafe fn write_elevation<T>(
+    data: *const DTEDData,
+    elevation: *mut T,
+    query: impl FnOnce(&DTEDData) -> Option<T>,
+) -> c_int {
+    let (Some(data), false) = (data.as_ref(), elevation.is_null()) else {
+        set_last_error("the data or elevation is NULL");
+        return 0;
+    };
+    match query(data) {
+        Some(value) => {
+            elevation.write(value);
+            1
+        }
+        None => 0,
+    }
+}
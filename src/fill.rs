@@ -0,0 +1,208 @@
+//! Contains the [VoidFill] strategies, used to fill the void posts of a grid
+//! from the surrounding valid posts (e.g. before meshing or hillshading).
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDData, RawDTEDRecords, DTED_VOID_ELEVATION};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Strategy used to fill void posts, from the valid posts of the original grid
+///
+/// * Nearest - elevation of the nearest valid post
+/// * InverseDistance - inverse distance weighting of the valid posts within
+///   `radius` posts, with weights of `1 / distance^power`
+/// * Bilinear - linear interpolation between the nearest valid posts to the
+///   south and north, and to the west and east, averaged weighted by the
+///   inverse of their spans
+///
+/// Distances are measured in posts. A void post left without any valid post
+/// to fill it from (e.g. beyond the radius) stays void.
+pub enum VoidFill {
+    Nearest,
+    InverseDistance { power: f64, radius: usize },
+    Bilinear,
+}
+impl VoidFill {
+    /// Fill the void posts of data records
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - data records
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDRecords] with the void posts filled, and their checksums recomputed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{RawDTEDRecords, RawDTEDRecord, DTED_VOID_ELEVATION};
+    /// use dted2::fill::VoidFill;
+    ///
+    /// let mut data = RawDTEDRecords::with_capacity(3, 1);
+    /// let mut record = RawDTEDRecord::void(0, 3);
+    /// record.elevations = vec![10, DTED_VOID_ELEVATION, 40];
+    /// data.push(record).unwrap();
+    /// assert_eq!(VoidFill::Nearest.fill(&data).column(0), Some(&[10, 10, 40][..]));
+    /// assert_eq!(VoidFill::Bilinear.fill(&data).column(0), Some(&[10, 25, 40][..]));
+    /// ```
+    pub fn fill(&self, data: &RawDTEDRecords) -> RawDTEDRecords {
+        let grid = Grid(data);
+        let mut filled = data.clone();
+        for lon_idx in 0..data.len() {
+            for lat_idx in 0..data.line_len {
+                if grid.get(lat_idx as isize, lon_idx as isize).is_some() {
+                    continue;
+                }
+                let (lat, lon) = (lat_idx as isize, lon_idx as isize);
+                let elev = match *self {
+                    VoidFill::Nearest => grid.nearest(lat, lon),
+                    VoidFill::InverseDistance { power, radius } => {
+                        grid.inverse_distance(lat, lon, power, radius as isize)
+                    }
+                    VoidFill::Bilinear => grid.bilinear(lat, lon),
+                };
+                if let Some(elev) = elev {
+                    filled[(lat_idx, lon_idx)] = elev;
+                }
+            }
+        }
+        filled.recompute_checksums();
+        filled
+    }
+}
+
+/// Valid posts of data records, by signed indices
+struct Grid<'a>(&'a RawDTEDRecords);
+impl Grid<'_> {
+    /// Get the elevation of a post, None if void or out of bounds
+    fn get(&self, lat_idx: isize, lon_idx: isize) -> Option<i16> {
+        let lat_idx = usize::try_from(lat_idx).ok()?;
+        let lon_idx = usize::try_from(lon_idx).ok()?;
+        match self.0.get(lat_idx, lon_idx)? {
+            DTED_VOID_ELEVATION => None,
+            elev => Some(elev),
+        }
+    }
+
+    /// Largest distance (in posts) between two posts along an axis
+    fn span(&self) -> isize {
+        self.0.line_len.max(self.0.len()) as isize
+    }
+
+    /// Elevation of the nearest valid post, searching square rings of growing radius
+    fn nearest(&self, lat: isize, lon: isize) -> Option<i16> {
+        let mut best: Option<(isize, i16)> = None;
+        for r in 1..=self.span() {
+            // posts of the ring are at least r posts away
+            if best.is_some_and(|(d2, _)| r * r > d2) {
+                break;
+            }
+            for (dlat, dlon) in ring(r) {
+                if let Some(elev) = self.get(lat + dlat, lon + dlon) {
+                    let d2 = dlat * dlat + dlon * dlon;
+                    if best.is_none_or(|(best_d2, _)| d2 < best_d2) {
+                        best = Some((d2, elev));
+                    }
+                }
+            }
+        }
+        best.map(|(_, elev)| elev)
+    }
+
+    /// Inverse distance weighted elevation of the valid posts within a radius
+    fn inverse_distance(&self, lat: isize, lon: isize, power: f64, radius: isize) -> Option<i16> {
+        let mut sum = 0.0;
+        let mut weight = 0.0;
+        for dlat in -radius..=radius {
+            for dlon in -radius..=radius {
+                let d2 = dlat * dlat + dlon * dlon;
+                if d2 == 0 || d2 > radius * radius {
+                    continue;
+                }
+                if let Some(elev) = self.get(lat + dlat, lon + dlon) {
+                    let w = (d2 as f64).sqrt().powf(-power);
+                    sum += elev as f64 * w;
+                    weight += w;
+                }
+            }
+        }
+        match weight > 0.0 {
+            true => Some((sum / weight).round() as i16),
+            false => None,
+        }
+    }
+
+    /// Linear interpolation between the nearest valid posts along each axis
+    fn bilinear(&self, lat: isize, lon: isize) -> Option<i16> {
+        let axes = [
+            self.interpolate(|d| self.get(lat + d, lon)),
+            self.interpolate(|d| self.get(lat, lon + d)),
+        ];
+        let (sum, weight) = axes.iter().flatten().fold((0.0, 0.0), |acc, (elev, span)| {
+            (acc.0 + elev / span, acc.1 + 1.0 / span)
+        });
+        match weight > 0.0 {
+            true => Some((sum / weight).round() as i16),
+            false => None,
+        }
+    }
+
+    /// Linear interpolation between the nearest valid posts on both sides
+    /// of a void post along an axis, given the post at each offset
+    ///
+    /// # Returns
+    ///
+    /// * `(elevation, span)` with the span (in posts) between the valid posts,
+    ///   or None if there is no valid post on either side
+    fn interpolate<F>(&self, post: F) -> Option<(f64, f64)>
+    where
+        F: Fn(isize) -> Option<i16>,
+    {
+        let find = |sign: isize| {
+            (1..=self.span()).find_map(|d| post(sign * d).map(|elev| (d as f64, elev as f64)))
+        };
+        let (before, after) = (find(-1)?, find(1)?);
+        let span = before.0 + after.0;
+        Some((before.1 + (after.1 - before.1) * before.0 / span, span))
+    }
+}
+
+/// Offsets of the posts on the square ring of radius `r` around a post
+fn ring(r: isize) -> impl Iterator<Item = (isize, isize)> {
+    let rows = (-r..=r).flat_map(move |dlon| [(-r, dlon), (r, dlon)]);
+    let cols = (-r + 1..r).flat_map(move |dlat| [(dlat, -r), (dlat, r)]);
+    rows.chain(cols)
+}
+
+impl DTEDData {
+    /// Fill the void posts, see [VoidFill]
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - [VoidFill] strategy
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData] with the void posts filled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::fill::VoidFill;
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let filled = data.fill_voids(VoidFill::InverseDistance { power: 2.0, radius: 3 });
+    /// // without any void post, the grid is left as is
+    /// assert_eq!(data.void_count(), 0);
+    /// assert_eq!(filled.data, data.data);
+    /// ```
+    pub fn fill_voids(&self, strategy: VoidFill) -> DTEDData {
+        DTEDData {
+            coverage: self.coverage.clone(),
+            ..DTEDData::new(self.metadata.clone(), strategy.fill(&self.data))
+        }
+    }
+}
@@ -0,0 +1,442 @@
+//! Contains the conversions between lat/lons and [geo_types] geometries
+//! (requires the `geo-types` feature), the bounding box APIs taking and
+//! returning [Rect]s, and the rasterization of polygons against the posts of
+//! a cell.
+//!
+//! Coordinates are `x` = longitude, `y` = latitude, as throughout the
+//! georust ecosystem.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use geo_types::{Coord, LineString, MultiLineString, Point, Polygon, Rect};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::contour::Contour;
+use crate::dataset::DTEDDataset;
+use crate::dted::{AccuracySubregion, DTEDData, RawDTEDFile};
+use crate::primitives::{Angle, AxisElement};
+use crate::source::ElevationSource;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Vertices and crossings within this fraction of the interval of a post are
+/// on that post, absorbing the rounding of the lat/lon
+const POLYGON_EPSILON: f64 = 1e-6;
+
+impl From<AxisElement<f64>> for Coord<f64> {
+    /// Convert a lat/lon, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use geo_types::{coord, Coord};
+    ///
+    /// let coord = Coord::from(AxisElement::new(42.5, -15.25));
+    /// assert_eq!(coord, coord! { x: -15.25, y: 42.5 });
+    /// assert_eq!(AxisElement::from(coord), AxisElement::new(42.5, -15.25));
+    /// ```
+    fn from(pos: AxisElement<f64>) -> Self {
+        Coord {
+            x: pos.lon,
+            y: pos.lat,
+        }
+    }
+}
+
+impl From<Coord<f64>> for AxisElement<f64> {
+    /// Convert a coordinate into a lat/lon, in degrees
+    fn from(coord: Coord<f64>) -> Self {
+        AxisElement::new(coord.y, coord.x)
+    }
+}
+
+impl From<AxisElement<f64>> for Point<f64> {
+    /// Convert a lat/lon, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use geo_types::Point;
+    ///
+    /// let point = Point::from(AxisElement::new(42.5, -15.25));
+    /// assert_eq!((point.x(), point.y()), (-15.25, 42.5));
+    /// assert_eq!(AxisElement::from(point), AxisElement::new(42.5, -15.25));
+    /// ```
+    fn from(pos: AxisElement<f64>) -> Self {
+        Point(pos.into())
+    }
+}
+
+impl From<Point<f64>> for AxisElement<f64> {
+    /// Convert a point into a lat/lon, in degrees
+    fn from(point: Point<f64>) -> Self {
+        point.0.into()
+    }
+}
+
+impl From<AxisElement<Angle>> for Coord<f64> {
+    /// Convert a lat/lon, in decimal degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::{Angle, AxisElement};
+    /// use geo_types::{coord, Coord};
+    ///
+    /// let pos = AxisElement::new(Angle::new(42, 30, 0.0, false), Angle::new(15, 15, 0.0, true));
+    /// assert_eq!(Coord::from(pos), coord! { x: -15.25, y: 42.5 });
+    /// ```
+    fn from(pos: AxisElement<Angle>) -> Self {
+        Coord {
+            x: pos.lon.to_decimal_degrees(),
+            y: pos.lat.to_decimal_degrees(),
+        }
+    }
+}
+
+impl From<AxisElement<Angle>> for Point<f64> {
+    /// Convert a lat/lon, in decimal degrees
+    fn from(pos: AxisElement<Angle>) -> Self {
+        Point(pos.into())
+    }
+}
+
+impl From<Coord<f64>> for AxisElement<Angle> {
+    /// Convert a coordinate into a lat/lon, see [Angle::from_decimal_degrees]
+    fn from(coord: Coord<f64>) -> Self {
+        AxisElement::new(
+            Angle::from_decimal_degrees(coord.y),
+            Angle::from_decimal_degrees(coord.x),
+        )
+    }
+}
+
+impl From<Point<f64>> for AxisElement<Angle> {
+    /// Convert a point into a lat/lon, see [Angle::from_decimal_degrees]
+    fn from(point: Point<f64>) -> Self {
+        point.0.into()
+    }
+}
+
+/// Convert the south west and north east corners of a bounding box into a [Rect]
+fn to_rect(min: AxisElement<f64>, max: AxisElement<f64>) -> Rect<f64> {
+    Rect::new(Coord::from(min), Coord::from(max))
+}
+
+/// Convert a [Rect] into the south west and north east corners of a bounding box
+fn from_rect(rect: &Rect<f64>) -> (AxisElement<f64>, AxisElement<f64>) {
+    (rect.min().into(), rect.max().into())
+}
+
+impl DTEDData {
+    /// Get the bounding box of the posts, from [DTEDData::min] to [DTEDData::max]
+    ///
+    /// # Returns
+    ///
+    /// * [Rect] of `x` = longitude, `y` = latitude coordinates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use geo_types::coord;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let rect = data.bounding_rect();
+    /// assert_eq!(rect.min(), coord! { x: -178.0, y: -30.0 });
+    /// assert_eq!(rect.max(), coord! { x: -177.0, y: -29.0 });
+    /// ```
+    pub fn bounding_rect(&self) -> Rect<f64> {
+        to_rect(self.min, self.max)
+    }
+
+    /// Get the bounding box actually covered by data records,
+    /// see [DTEDData::covered_extent]
+    ///
+    /// # Returns
+    ///
+    /// * [Rect] of `x` = longitude, `y` = latitude coordinates, or None if no
+    ///   data record was covered
+    pub fn covered_rect(&self) -> Option<Rect<f64>> {
+        self.covered_extent().map(|(min, max)| to_rect(min, max))
+    }
+
+    /// Crop the cell to the posts within a bounding box, see [DTEDData::crop]
+    ///
+    /// # Arguments
+    ///
+    /// * `rect` - [Rect] of `x` = longitude, `y` = latitude coordinates
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData] of the posts within the box, or None if there are none
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::DTEDData;
+    /// use geo_types::{coord, Rect};
+    ///
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let rect = Rect::new(coord! { x: 15.25, y: 42.5 }, coord! { x: 15.3, y: 42.6 });
+    /// let crop = data.crop_rect(&rect).unwrap();
+    /// assert_eq!(crop.metadata.count, AxisElement::new(361, 181));
+    /// assert_eq!(crop.bounding_rect().min(), rect.min());
+    /// ```
+    pub fn crop_rect(&self, rect: &Rect<f64>) -> Option<DTEDData> {
+        let (min, max) = from_rect(rect);
+        self.crop(min, max)
+    }
+}
+
+impl RawDTEDFile {
+    /// Crop the file to the posts within a bounding box, see [RawDTEDFile::crop]
+    ///
+    /// # Arguments
+    ///
+    /// * `rect` - [Rect] of `x` = longitude, `y` = latitude coordinates
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile] of the posts within the box, ready to be written, or
+    ///   None if there are none
+    pub fn crop_rect(&self, rect: &Rect<f64>) -> Option<RawDTEDFile> {
+        let (min, max) = from_rect(rect);
+        self.crop(min, max)
+    }
+}
+
+impl<S: ElevationSource> DTEDDataset<S> {
+    /// Get the bounding box of the cells, see [DTEDDataset::bounds]
+    ///
+    /// # Returns
+    ///
+    /// * [Rect] of `x` = longitude, `y` = latitude coordinates, or None if
+    ///   the dataset is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    /// use geo_types::coord;
+    ///
+    /// let dataset = DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+    /// let rect = dataset.bounding_rect().unwrap();
+    /// assert_eq!(rect.min(), coord! { x: -178.0, y: -30.0 });
+    /// assert_eq!(rect.max(), coord! { x: 16.0, y: 43.0 });
+    /// ```
+    pub fn bounding_rect(&self) -> Option<Rect<f64>> {
+        self.bounds().map(|(min, max)| to_rect(min, max))
+    }
+
+    /// Get the cells intersecting a bounding box, see [DTEDDataset::cells_in]
+    ///
+    /// # Arguments
+    ///
+    /// * `rect` - [Rect] of `x` = longitude, `y` = latitude coordinates
+    ///
+    /// # Returns
+    ///
+    /// * The cells intersecting the bounding box (edges included),
+    ///   in the order they were added
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDDataset;
+    /// use geo_types::{coord, Rect};
+    ///
+    /// let dataset = DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+    /// let rect = Rect::new(coord! { x: 10.0, y: 40.0 }, coord! { x: 15.5, y: 42.5 });
+    /// assert_eq!(dataset.cells_in_rect(&rect).len(), 1);
+    /// ```
+    pub fn cells_in_rect(&self, rect: &Rect<f64>) -> Vec<&S> {
+        let (min, max) = from_rect(rect);
+        self.cells_in(min, max)
+    }
+}
+
+impl From<&Contour> for MultiLineString<f64> {
+    /// Convert the lines of a [Contour]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::contour::Contour;
+    /// use geo_types::MultiLineString;
+    ///
+    /// let contour = Contour {
+    ///     elevation: 100.0,
+    ///     lines: vec![vec![(10.0, 20.0), (10.5, 20.5)]],
+    /// };
+    /// let lines = MultiLineString::from(&contour);
+    /// assert_eq!(lines.0.len(), 1);
+    /// assert_eq!(lines.0[0].0[1].x, 20.5);
+    /// assert_eq!(lines.0[0].0[1].y, 10.5);
+    /// ```
+    fn from(contour: &Contour) -> Self {
+        MultiLineString::new(
+            contour
+                .lines
+                .iter()
+                .map(|line| {
+                    LineString::new(
+                        line.iter()
+                            .map(|(lat, lon)| Coord { x: *lon, y: *lat })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<Contour> for MultiLineString<f64> {
+    /// Convert the lines of a [Contour]
+    fn from(contour: Contour) -> Self {
+        MultiLineString::from(&contour)
+    }
+}
+
+impl Contour {
+    /// Get the lines as a [MultiLineString]
+    ///
+    /// # Returns
+    ///
+    /// * [MultiLineString] of `x` = longitude, `y` = latitude coordinates
+    pub fn to_multi_line_string(&self) -> MultiLineString<f64> {
+        MultiLineString::from(self)
+    }
+}
+
+impl From<&AccuracySubregion> for Polygon<f64> {
+    /// Convert the outline of an [AccuracySubregion], closing it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::AccuracySubregion;
+    /// use dted2::primitives::{Angle, AxisElement};
+    /// use geo_types::Polygon;
+    ///
+    /// let vertex = |lat, lon| AxisElement::new(Angle::new(lat, 0, 0.0, false), Angle::new(lon, 0, 0.0, false));
+    /// let subregion = AccuracySubregion {
+    ///     outline: vec![vertex(42, 15), vertex(43, 15), vertex(42, 16)],
+    ///     abs_horizontal: None,
+    ///     abs_vertical: Some(10),
+    ///     rel_horizontal: None,
+    ///     rel_vertical: None,
+    /// };
+    /// let polygon = Polygon::from(&subregion);
+    /// assert_eq!(polygon.exterior().0.len(), 4);
+    /// assert_eq!(polygon.exterior().0[1].x, 15.0);
+    /// assert_eq!(polygon.exterior().0[1].y, 43.0);
+    /// ```
+    fn from(subregion: &AccuracySubregion) -> Self {
+        Polygon::new(
+            LineString::new(subregion.outline.iter().map(|v| (*v).into()).collect()),
+            vec![],
+        )
+    }
+}
+
+impl DTEDData {
+    /// Rasterize a polygon against the posts, laid out as
+    /// [DTEDData::void_mask], such that the post `(lat_idx, lon_idx)` is at
+    /// `lon_idx * line_len + lat_idx`
+    ///
+    /// A post is within the polygon if it is within its exterior and not
+    /// within any of its interiors (holes). Posts on the boundary of the
+    /// exterior are within it, except where it ends eastward along a
+    /// longitude line (e.g. on the east edge of a box, or an eastmost vertex),
+    /// as the longitude lines cross the edges half-open, while posts on the
+    /// boundary of a hole are in the hole.
+    ///
+    /// # Arguments
+    ///
+    /// * `polygon` - [Polygon] of `x` = longitude, `y` = latitude coordinates
+    ///
+    /// # Returns
+    ///
+    /// * Whether each post is within the polygon
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use geo_types::polygon;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// // a box with a hole, their edges between the posts
+    /// let polygon = polygon!(
+    ///     exterior: [
+    ///         (x: -177.96, y: -29.96),
+    ///         (x: -177.04, y: -29.96),
+    ///         (x: -177.04, y: -29.04),
+    ///         (x: -177.96, y: -29.04),
+    ///     ],
+    ///     interiors: [[
+    ///         (x: -177.61, y: -29.61),
+    ///         (x: -177.39, y: -29.61),
+    ///         (x: -177.39, y: -29.39),
+    ///         (x: -177.61, y: -29.39),
+    ///     ]],
+    /// );
+    /// let mask = data.polygon_mask(&polygon);
+    /// // 111 x 111 posts, less the 27 x 27 posts of the hole
+    /// assert_eq!(mask.iter().filter(|inside| **inside).count(), 111 * 111 - 27 * 27);
+    /// assert!(!mask[0] && mask[5 * 121 + 5] && !mask[60 * 121 + 60]);
+    /// ```
+    pub fn polygon_mask(&self, polygon: &Polygon<f64>) -> Vec<bool> {
+        let metadata = &self.metadata;
+        let (lat_count, lon_count) = (
+            usize::from(metadata.count.lat),
+            usize::from(metadata.count.lon),
+        );
+        let mut mask = vec![false; lat_count * lon_count];
+        // the edges of the rings, in (fractional) post indices snapped to
+        // the posts they are on up to rounding
+        let snap = |index: f64| match (index - index.round()).abs() < POLYGON_EPSILON {
+            true => index.round(),
+            false => index,
+        };
+        let to_index = |coord: Coord<f64>| Coord {
+            x: snap((coord.x - metadata.origin.lon) / metadata.interval.lon),
+            y: snap((coord.y - metadata.origin.lat) / metadata.interval.lat),
+        };
+        let edges: Vec<(Coord<f64>, Coord<f64>)> = std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .flat_map(|ring| ring.lines())
+            .map(|line| (to_index(line.start), to_index(line.end)))
+            .collect();
+        let mut crossings = Vec::new();
+        for (lon_idx, column) in mask.chunks_mut(lat_count.max(1)).enumerate() {
+            // the latitude indices where the rings cross the longitude line,
+            // the posts between every other pair being within (even-odd rule)
+            let lon = lon_idx as f64;
+            crossings.clear();
+            for (start, end) in &edges {
+                if (start.x <= lon) != (end.x <= lon) {
+                    crossings
+                        .push(start.y + (lon - start.x) * (end.y - start.y) / (end.x - start.x));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                let first = snap(pair[0]).ceil().max(0.0);
+                let last = snap(pair[1]).floor().min(lat_count as f64 - 1.0);
+                if first <= last {
+                    column[first as usize..=last as usize].fill(true);
+                }
+            }
+        }
+        mask
+    }
+}
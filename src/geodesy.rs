@@ -0,0 +1,224 @@
+//! Contains the spherical earth computations (distances, intermediate points)
+//! used by the terrain analyses, along [LineType] lines.
+//!
+//! The earth is modeled as a sphere of the WGS84 mean radius, which is within
+//! about 0.5% of the ellipsoidal distances, except for the [to_ecef]
+//! coordinates on the WGS84 ellipsoid.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::f64::consts::{FRAC_PI_4, PI};
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Mean earth radius (of the WGS84 ellipsoid), in meters
+pub const EARTH_RADIUS_M: f64 = 6_371_008.8;
+/// Semi-major axis of the WGS84 ellipsoid, in meters
+pub const WGS84_A: f64 = 6_378_137.0;
+/// Semi-minor axis of the WGS84 ellipsoid, in meters
+pub const WGS84_B: f64 = 6_356_752.314_245_179;
+/// Differences of isometric latitude at or below this value are considered
+/// zero, a rhumb line then following a parallel
+const RHUMB_EPSILON: f64 = 1e-12;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+/// Type of line between two lat/lons
+///
+/// * GreatCircle - shortest line over the earth
+/// * Rhumb - line of constant bearing (loxodrome), longer than the great
+///   circle except along the equator or a meridian
+pub enum LineType {
+    #[default]
+    GreatCircle,
+    Rhumb,
+}
+impl LineType {
+    /// Get the distance between two lat/lons
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - `(lat, lon)` in degrees
+    /// * `end` - `(lat, lon)` in degrees
+    ///
+    /// # Returns
+    ///
+    /// * Distance in meters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::geodesy::{LineType, EARTH_RADIUS_M};
+    ///
+    /// let quarter = std::f64::consts::FRAC_PI_2 * EARTH_RADIUS_M;
+    /// let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+    /// assert!(close(LineType::GreatCircle.distance((0.0, 0.0), (0.0, 90.0)), quarter));
+    /// assert!(close(LineType::Rhumb.distance((0.0, 0.0), (90.0, 0.0)), quarter));
+    /// // across the pole, the rhumb line keeps heading east
+    /// let great_circle = LineType::GreatCircle.distance((60.0, -90.0), (60.0, 90.0));
+    /// assert!(close(great_circle, quarter * 2.0 / 3.0));
+    /// assert!(LineType::Rhumb.distance((60.0, -90.0), (60.0, 90.0)) > great_circle);
+    /// ```
+    pub fn distance(&self, start: (f64, f64), end: (f64, f64)) -> f64 {
+        let (lat1, lon1) = to_radians(start);
+        let (lat2, lon2) = to_radians(end);
+        let angle = match self {
+            LineType::GreatCircle => {
+                let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+                    + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+                2.0 * h.sqrt().min(1.0).asin()
+            }
+            LineType::Rhumb => {
+                let dlat = lat2 - lat1;
+                let dlon = wrap_radians(lon2 - lon1);
+                dlat.hypot(rhumb_stretch(lat1, lat2) * dlon)
+            }
+        };
+        angle * EARTH_RADIUS_M
+    }
+
+    /// Get the point at a fraction of the line between two lat/lons
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - `(lat, lon)` in degrees
+    /// * `end` - `(lat, lon)` in degrees
+    /// * `fraction` - fraction of the distance from the start (0 to 1)
+    ///
+    /// # Returns
+    ///
+    /// * `(lat, lon)` in degrees, the longitude within -180 to 180
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::geodesy::LineType;
+    ///
+    /// let (lat, lon) = LineType::GreatCircle.point((0.0, 10.0), (0.0, 30.0), 0.5);
+    /// assert!(lat.abs() < 1e-9 && (lon - 20.0).abs() < 1e-9);
+    /// // across the antimeridian
+    /// let (_, lon) = LineType::Rhumb.point((10.0, 170.0), (10.0, -170.0), 0.5);
+    /// assert!((lon.abs() - 180.0).abs() < 1e-9);
+    /// ```
+    pub fn point(&self, start: (f64, f64), end: (f64, f64), fraction: f64) -> (f64, f64) {
+        let (lat1, lon1) = to_radians(start);
+        let (lat2, lon2) = to_radians(end);
+        let (lat, lon) = match self {
+            LineType::GreatCircle => {
+                let angle = self.distance(start, end) / EARTH_RADIUS_M;
+                if angle.sin().abs() < f64::EPSILON {
+                    return start;
+                }
+                let a = ((1.0 - fraction) * angle).sin() / angle.sin();
+                let b = (fraction * angle).sin() / angle.sin();
+                let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+                let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+                let z = a * lat1.sin() + b * lat2.sin();
+                (z.atan2(x.hypot(y)), y.atan2(x))
+            }
+            LineType::Rhumb => {
+                let dlon = wrap_radians(lon2 - lon1);
+                let lat = lat1 + fraction * (lat2 - lat1);
+                let dpsi = isometric(lat2) - isometric(lat1);
+                // rhumb lines spiral into the poles, whose longitude is moot
+                let lon = match dpsi.abs() > RHUMB_EPSILON && dpsi.is_finite() {
+                    true => lon1 + dlon * (isometric(lat) - isometric(lat1)) / dpsi,
+                    false => lon1 + fraction * dlon,
+                };
+                (lat, lon)
+            }
+        };
+        (lat.to_degrees(), wrap_radians(lon).to_degrees())
+    }
+}
+
+/// Get the point at a distance and bearing from a lat/lon, along a great circle
+///
+/// # Arguments
+///
+/// * `start` - `(lat, lon)` in degrees
+/// * `bearing` - initial bearing in degrees, clockwise from north
+/// * `distance` - distance in meters
+///
+/// # Returns
+///
+/// * `(lat, lon)` in degrees, the longitude within -180 to 180
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geodesy::{destination, LineType};
+///
+/// let end = destination((45.0, 10.0), 30.0, 100_000.0);
+/// assert!((LineType::GreatCircle.distance((45.0, 10.0), end) - 100_000.0).abs() < 1e-6);
+/// let (lat, lon) = destination((0.0, 179.0), 90.0, 2.0f64.to_radians() * 6_371_008.8);
+/// assert!(lat.abs() < 1e-9 && (lon + 179.0).abs() < 1e-9);
+/// ```
+pub fn destination(start: (f64, f64), bearing: f64, distance: f64) -> (f64, f64) {
+    let (lat1, lon1) = to_radians(start);
+    let bearing = bearing.to_radians();
+    let angle = distance / EARTH_RADIUS_M;
+    let lat = (lat1.sin() * angle.cos() + lat1.cos() * angle.sin() * bearing.cos()).asin();
+    let lon = lon1
+        + (bearing.sin() * angle.sin() * lat1.cos()).atan2(angle.cos() - lat1.sin() * lat.sin());
+    (lat.to_degrees(), wrap_radians(lon).to_degrees())
+}
+
+/// Convert a position above the WGS84 ellipsoid to earth-centered, earth-fixed
+/// (ECEF) coordinates
+///
+/// # Arguments
+///
+/// * `lat` - latitude in degrees
+/// * `lon` - longitude in degrees
+/// * `height` - height above the ellipsoid, in meters
+///
+/// # Returns
+///
+/// * `[x, y, z]` in meters, `x` towards the prime meridian, `z` towards the north pole
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geodesy::{to_ecef, WGS84_A, WGS84_B};
+///
+/// assert_eq!(to_ecef(0.0, 0.0, 100.0), [WGS84_A + 100.0, 0.0, 0.0]);
+/// let [x, y, z] = to_ecef(90.0, 0.0, 0.0);
+/// assert!(x.abs() < 1e-6 && y.abs() < 1e-6 && (z - WGS84_B).abs() < 1e-6);
+/// ```
+pub fn to_ecef(lat: f64, lon: f64, height: f64) -> [f64; 3] {
+    let (lat, lon) = to_radians((lat, lon));
+    let e2 = 1.0 - (WGS84_B * WGS84_B) / (WGS84_A * WGS84_A);
+    let n = WGS84_A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    [
+        (n + height) * lat.cos() * lon.cos(),
+        (n + height) * lat.cos() * lon.sin(),
+        (n * (1.0 - e2) + height) * lat.sin(),
+    ]
+}
+
+/// Convert a lat/lon from degrees to radians
+fn to_radians((lat, lon): (f64, f64)) -> (f64, f64) {
+    (lat.to_radians(), lon.to_radians())
+}
+
+/// Wrap an angle (in radians) within -pi to pi
+fn wrap_radians(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// Isometric latitude (Mercator ordinate) of a latitude, in radians
+fn isometric(lat: f64) -> f64 {
+    (FRAC_PI_4 + lat / 2.0).tan().ln()
+}
+
+/// Ratio of the latitude difference to the isometric latitude difference
+/// of a rhumb line, scaling its longitude difference into a distance
+fn rhumb_stretch(lat1: f64, lat2: f64) -> f64 {
+    let dpsi = isometric(lat2) - isometric(lat1);
+    match dpsi.abs() > RHUMB_EPSILON {
+        true => (lat2 - lat1) / dpsi,
+        false => lat1.cos(),
+    }
+}
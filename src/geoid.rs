@@ -0,0 +1,196 @@
+//! Contains the [Geoid] model (requires the `egm96` feature), converting the
+//! mean sea level (MSL) elevations of DTED to heights above the WGS84
+//! ellipsoid, as used by GNSS, and back.
+//!
+//! The undulations (heights of the geoid above the ellipsoid) are read from
+//! the EGM96 15 arc-minute grid distributed by the NGA as `WW15MGH.DAC`, or
+//! built from any other global grid, and bilinearly interpolated between its
+//! points. The grid is not embedded in the crate, being about 2 MB.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::Error;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Spacing of the points of the EGM96 grid, in degrees
+const EGM96_SPACING: f64 = 0.25;
+/// Number of rows of the EGM96 grid, from 90N to 90S
+const EGM96_ROWS: usize = 721;
+/// Number of columns of the EGM96 grid, from 0E eastward, not repeating 360E
+const EGM96_COLS: usize = 1440;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Global grid of geoid undulations
+///
+/// # Fields
+///
+/// * `spacing` - lat/lon spacing of the points, in degrees, dividing 180
+/// * `undulations` - heights of the geoid above the WGS84 ellipsoid (in
+///   meters), row by row from 90N to 90S, each from 0E eastward up to (but
+///   not repeating) 360E
+pub struct Geoid {
+    spacing: f64,
+    undulations: Vec<f64>,
+}
+impl Geoid {
+    /// Create a geoid from a global grid
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - lat/lon spacing of the points, in degrees, dividing 180
+    /// * `undulations` - heights of the geoid above the WGS84 ellipsoid (in
+    ///   meters), row by row from 90N to 90S, each from 0E eastward up to
+    ///   (but not repeating) 360E
+    ///
+    /// # Returns
+    ///
+    /// * [Geoid], or an [Error::InvalidInput] if the spacing does not divide
+    ///   180 degrees or the grid does not hold as many undulations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::geoid::Geoid;
+    ///
+    /// // a 90 degree grid: the poles, then the equator at 0E, 90E, 180E and 90W
+    /// let geoid = Geoid::new(
+    ///     90.0,
+    ///     vec![14.0, 14.0, 14.0, 14.0, 10.0, 20.0, 30.0, 40.0, -30.0, -30.0, -30.0, -30.0],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(geoid.undulation(0.0, 45.0), 15.0);
+    /// assert_eq!(geoid.undulation(0.0, -45.0), 25.0);
+    /// assert_eq!(geoid.undulation(45.0, 0.0), 12.0);
+    /// assert!(Geoid::new(90.0, vec![0.0; 4]).is_err());
+    /// ```
+    pub fn new(spacing: f64, undulations: Vec<f64>) -> Result<Geoid, Error> {
+        let rows = 180.0 / spacing;
+        if !(spacing > 0.0 && (rows - rows.round()).abs() < 1e-9) {
+            return Err(Error::InvalidInput(format!(
+                "a geoid spacing of {} degrees does not divide 180 degrees",
+                spacing
+            )));
+        }
+        let geoid = Geoid {
+            spacing,
+            undulations,
+        };
+        let (rows, cols) = geoid.dimensions();
+        if geoid.undulations.len() != rows * cols {
+            return Err(Error::InvalidInput(format!(
+                "a {} degree geoid grid needs {}x{} undulations, not {}",
+                spacing,
+                rows,
+                cols,
+                geoid.undulations.len()
+            )));
+        }
+        Ok(geoid)
+    }
+
+    /// Read the EGM96 15 arc-minute grid (`WW15MGH.DAC`)
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the grid file
+    ///
+    /// # Returns
+    ///
+    /// * [Geoid], or an error if the file could not be read or is not the
+    ///   EGM96 grid
+    pub fn read(path: &str) -> Result<Geoid, Error> {
+        Geoid::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Read the EGM96 15 arc-minute grid (`WW15MGH.DAC`) from bytes: 721
+    /// rows of 1440 big-endian 16-bit undulations, in centimeters
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - bytes of the grid file
+    ///
+    /// # Returns
+    ///
+    /// * [Geoid], or an [Error::InvalidInput] if the bytes are not the EGM96
+    ///   grid
+    pub fn from_bytes(bytes: &[u8]) -> Result<Geoid, Error> {
+        if bytes.len() != EGM96_ROWS * EGM96_COLS * 2 {
+            return Err(Error::InvalidInput(format!(
+                "{} bytes are not an EGM96 15 arc-minute grid",
+                bytes.len()
+            )));
+        }
+        let undulations = bytes
+            .chunks_exact(2)
+            .map(|cm| f64::from(i16::from_be_bytes([cm[0], cm[1]])) / 100.0)
+            .collect();
+        Geoid::new(EGM96_SPACING, undulations)
+    }
+
+    /// Get the undulation of the geoid at a lat/lon, bilinearly interpolated
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude, clamped to the poles
+    /// * `lon` - longitude, wrapped around
+    ///
+    /// # Returns
+    ///
+    /// * Height of the geoid above the WGS84 ellipsoid, in meters
+    pub fn undulation(&self, lat: f64, lon: f64) -> f64 {
+        let (rows, cols) = self.dimensions();
+        let row = ((90.0 - lat) / self.spacing).clamp(0.0, (rows - 1) as f64);
+        let col = lon.rem_euclid(360.0) / self.spacing;
+        let (row0, col0) = (
+            (row.floor() as usize).min(rows - 2),
+            col.floor() as usize % cols,
+        );
+        let (row_frac, col_frac) = (row - row0 as f64, col - col.floor());
+        let col1 = (col0 + 1) % cols;
+        let point = |row: usize, col: usize| self.undulations[row * cols + col];
+        let west = point(row0, col0) * (1.0 - row_frac) + point(row0 + 1, col0) * row_frac;
+        let east = point(row0, col1) * (1.0 - row_frac) + point(row0 + 1, col1) * row_frac;
+        west * (1.0 - col_frac) + east * col_frac
+    }
+
+    /// Convert an elevation above mean sea level (e.g. of DTED) into a height
+    /// above the WGS84 ellipsoid
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `elevation` - elevation above mean sea level, in meters
+    ///
+    /// # Returns
+    ///
+    /// * Height above the WGS84 ellipsoid, in meters
+    pub fn to_ellipsoidal(&self, lat: f64, lon: f64, elevation: f64) -> f64 {
+        elevation + self.undulation(lat, lon)
+    }
+
+    /// Convert a height above the WGS84 ellipsoid (e.g. of GNSS) into an
+    /// elevation above mean sea level
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `height` - height above the WGS84 ellipsoid, in meters
+    ///
+    /// # Returns
+    ///
+    /// * Elevation above mean sea level, in meters
+    pub fn to_orthometric(&self, lat: f64, lon: f64, height: f64) -> f64 {
+        height - self.undulation(lat, lon)
+    }
+
+    /// Get the number of rows and columns of the grid
+    fn dimensions(&self) -> (usize, usize) {
+        let rows = (180.0 / self.spacing).round() as usize;
+        (rows + 1, 2 * rows)
+    }
+}
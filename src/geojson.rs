@@ -0,0 +1,141 @@
+//! Contains the [GeoJSON](https://datatracker.ietf.org/doc/html/rfc7946)
+//! serialization of [Contour] lines and cell coverage (requires the `geojson`
+//! feature).
+//!
+//! Positions are `[longitude, latitude]`, as required by GeoJSON, and
+//! polygons wind counter-clockwise.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::contour::Contour;
+use crate::dataset;
+use crate::source::ElevationSource;
+use crate::Error;
+
+/// Serialize contour lines, one `MultiLineString` feature per level
+///
+/// # Arguments
+///
+/// * `contours` - [Contour]s, see [crate::contour::contours]
+///
+/// # Returns
+///
+/// * `FeatureCollection`, each feature with an `elevation` property
+///
+/// # Examples
+///
+/// ```
+/// use dted2::contour::Contour;
+/// use dted2::geojson;
+///
+/// let contour = Contour {
+///     elevation: 100.0,
+///     lines: vec![vec![(10.0, 20.0), (10.5, 20.5)]],
+/// };
+/// let geojson = geojson::contours(&[contour]);
+/// let feature = &geojson["features"][0];
+/// assert_eq!(feature["properties"]["elevation"], 100.0);
+/// assert_eq!(feature["geometry"]["type"], "MultiLineString");
+/// assert_eq!(feature["geometry"]["coordinates"][0][1][0], 20.5);
+/// ```
+pub fn contours(contours: &[Contour]) -> Value {
+    let features = contours.iter().map(|contour| {
+        let lines: Vec<Vec<[f64; 2]>> = contour
+            .lines
+            .iter()
+            .map(|line| line.iter().map(|(lat, lon)| [*lon, *lat]).collect())
+            .collect();
+        json!({
+            "type": "Feature",
+            "geometry": { "type": "MultiLineString", "coordinates": lines },
+            "properties": { "elevation": contour.elevation },
+        })
+    });
+    feature_collection(features)
+}
+
+/// Serialize the coverage of cells, one `Polygon` feature per cell, from its
+/// first to its last post
+///
+/// # Arguments
+///
+/// * `cells` - any [ElevationSource]s, such as [crate::DTEDDataset::cells]
+///
+/// # Returns
+///
+/// * `FeatureCollection`, each feature with the `filename`, `level` (null if
+///   not standard) and `accuracy` (null if unknown) properties of its cell
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geojson;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let geojson = geojson::coverage([&data]);
+/// let feature = &geojson["features"][0];
+/// assert_eq!(feature["properties"]["level"], 0);
+/// assert_eq!(feature["geometry"]["coordinates"][0][0][0], -178.0);
+/// assert_eq!(feature["geometry"]["coordinates"][0][2][1], -29.0);
+/// ```
+pub fn coverage<'a, S, I>(cells: I) -> Value
+where
+    S: ElevationSource + 'a,
+    I: IntoIterator<Item = &'a S>,
+{
+    let features = cells.into_iter().map(|cell| {
+        let metadata = cell.metadata();
+        let (min, max) = dataset::extent(metadata);
+        let ring = [
+            [min.lon, min.lat],
+            [max.lon, min.lat],
+            [max.lon, max.lat],
+            [min.lon, max.lat],
+            [min.lon, min.lat],
+        ];
+        json!({
+            "type": "Feature",
+            "geometry": { "type": "Polygon", "coordinates": [ring] },
+            "properties": {
+                "filename": metadata.filename,
+                "level": metadata.level().map(|level| level.number()),
+                "accuracy": metadata.accuracy,
+            },
+        })
+    });
+    feature_collection(features)
+}
+
+/// Write GeoJSON to a file
+///
+/// # Arguments
+///
+/// * `path` - path of the file, overwritten if it exists
+/// * `geojson` - GeoJSON, see [contours] and [coverage]
+///
+/// # Returns
+///
+/// * Nothing, or an [Error::Io] if the file could not be written
+pub fn write(path: &str, geojson: &Value) -> Result<(), Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, geojson).map_err(std::io::Error::from)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Collect features into a `FeatureCollection`
+fn feature_collection<I: Iterator<Item = Value>>(features: I) -> Value {
+    json!({
+        "type": "FeatureCollection",
+        "features": features.collect::<Vec<_>>(),
+    })
+}
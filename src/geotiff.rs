@@ -0,0 +1,362 @@
+//! Contains the import of single-band GeoTIFF elevation models into a
+//! [Raster] (requires the `geotiff` feature), to be resampled into DTED cells
+//! with [crate::DTEDBuilder::resample].
+//!
+//! Only north-up grids in geographic coordinates (degrees of latitude and
+//! longitude) are supported, georeferenced by a tie point and a pixel scale.
+//! Pixels are treated as posts at their center, or at their corner when the
+//! raster type is `PixelIsPoint`. Rasters, such as the elevations of a
+//! cell, are written as 32-bit float `PixelIsPoint` GeoTIFFs on WGS84, voids
+//! being NaN. With the `proj` feature, UTM rasters are written as projected
+//! `PixelIsArea` GeoTIFFs in the EPSG code of their zone ([write_utm]).
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, Write};
+use tiff::decoder::{Decoder, DecodingResult};
+use tiff::encoder::{colortype::Gray32Float, TiffEncoder};
+use tiff::tags::Tag;
+use tiff::TiffError;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::primitives::AxisElement;
+use crate::raster::Raster;
+#[cfg(feature = "proj")]
+use crate::utm::UtmRaster;
+use crate::Error;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// GeoKey of the model type (`GTModelTypeGeoKey`)
+const MODEL_TYPE_KEY: u16 = 1024;
+/// GeoKey of the raster type (`GTRasterTypeGeoKey`)
+const RASTER_TYPE_KEY: u16 = 1025;
+/// Model type of geographic (lat/lon) coordinates (`ModelTypeGeographic`)
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+/// Raster type of pixels located at their corner (`RasterPixelIsPoint`)
+const RASTER_PIXEL_IS_POINT: u16 = 2;
+/// GeoKeys of the written files: a header of 4 values, then the entries of
+/// (key, location, count, value) of the model type (geographic), raster type
+/// (`PixelIsPoint`) and geographic type (EPSG:4326)
+const WRITTEN_GEO_KEYS: [u16; 16] = [1, 1, 0, 3, 1024, 0, 1, 2, 1025, 0, 1, 2, 2048, 0, 1, 4326];
+/// GeoKey of the projected coordinate system (`ProjectedCSTypeGeoKey`)
+#[cfg(feature = "proj")]
+const PROJECTED_CS_TYPE_KEY: u16 = 3072;
+
+/// Read a GeoTIFF elevation model
+///
+/// # Arguments
+///
+/// * `path` - path of the GeoTIFF file
+///
+/// # Returns
+///
+/// * [Raster] of the elevations (None where NaN or the GDAL no-data value),
+///   or an error if the file is not a supported GeoTIFF
+pub fn read(path: &str) -> Result<Raster<Option<f64>>, Error> {
+    read_from(BufReader::new(File::open(path)?))
+}
+
+/// Read a GeoTIFF elevation model from a reader
+///
+/// # Arguments
+///
+/// * `reader` - reader of a GeoTIFF file
+///
+/// # Returns
+///
+/// * [Raster] of the elevations (None where NaN or the GDAL no-data value),
+///   or an error if the file is not a supported GeoTIFF
+pub fn read_from<R: Read + Seek>(reader: R) -> Result<Raster<Option<f64>>, Error> {
+    let mut decoder = Decoder::new(reader).map_err(from_tiff)?;
+    let (width, height) = decoder.dimensions().map_err(from_tiff)?;
+    let (width, height) = (width as usize, height as usize);
+    let samples = decoder
+        .find_tag_unsigned::<u16>(Tag::SamplesPerPixel)
+        .map_err(from_tiff)?;
+    if samples.unwrap_or(1) != 1 {
+        return invalid("only single-band images are supported");
+    }
+    // --------------------------------------------------
+    // georeferencing
+    // --------------------------------------------------
+    let geo_keys = match decoder
+        .find_tag(Tag::GeoKeyDirectoryTag)
+        .map_err(from_tiff)?
+    {
+        Some(keys) => keys.into_u16_vec().map_err(from_tiff)?,
+        None => Vec::new(),
+    };
+    let geo_key = |key: u16| {
+        // header of 4 values, then entries of (key, location, count, value)
+        geo_keys
+            .get(4..)?
+            .chunks_exact(4)
+            .find(|entry| entry[0] == key && entry[1] == 0)
+            .map(|entry| entry[3])
+    };
+    if geo_key(MODEL_TYPE_KEY).is_some_and(|model| model != MODEL_TYPE_GEOGRAPHIC) {
+        return invalid("only geographic (lat/lon) coordinates are supported");
+    }
+    let (Some(scale), Some(tiepoint)) = (
+        find_f64_vec(&mut decoder, Tag::ModelPixelScaleTag)?,
+        find_f64_vec(&mut decoder, Tag::ModelTiepointTag)?,
+    ) else {
+        return invalid("a pixel scale and tie point are required");
+    };
+    if scale.len() < 2 || tiepoint.len() < 6 || scale[0] <= 0.0 || scale[1] <= 0.0 {
+        return invalid("invalid pixel scale or tie point");
+    }
+    // pixel (column, row) at the (lon, lat) of the tie point
+    let (column, row, lon, lat) = (tiepoint[0], tiepoint[1], tiepoint[3], tiepoint[4]);
+    let center = match geo_key(RASTER_TYPE_KEY) == Some(RASTER_PIXEL_IS_POINT) {
+        true => 0.0,
+        false => 0.5,
+    };
+    let origin = AxisElement::new(
+        lat - (height as f64 - 1.0 + center - row) * scale[1],
+        lon + (center - column) * scale[0],
+    );
+    // --------------------------------------------------
+    // elevations, rows from north to south
+    // --------------------------------------------------
+    let no_data = decoder
+        .find_tag(Tag::GdalNodata)
+        .map_err(from_tiff)?
+        .and_then(|value| value.into_string().ok())
+        .and_then(|value| value.trim_end_matches('\0').trim().parse::<f64>().ok());
+    let values: Vec<f64> = match decoder.read_image().map_err(from_tiff)? {
+        DecodingResult::U8(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::U16(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::U32(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::U64(values) => values.into_iter().map(|v| v as f64).collect(),
+        DecodingResult::F16(values) => values.into_iter().map(|v| v.to_f64()).collect(),
+        DecodingResult::F32(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::F64(values) => values,
+        DecodingResult::I8(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::I16(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::I32(values) => values.into_iter().map(f64::from).collect(),
+        DecodingResult::I64(values) => values.into_iter().map(|v| v as f64).collect(),
+    };
+    if values.len() < width * height {
+        return invalid("the image holds fewer pixels than its dimensions");
+    }
+    Ok(Raster::from_fn(
+        origin,
+        AxisElement::new(scale[1], scale[0]),
+        AxisElement::new(height, width),
+        |lat_idx, lon_idx| {
+            let value = values[(height - 1 - lat_idx) * width + lon_idx];
+            match value.is_nan() || no_data == Some(value) {
+                true => None,
+                false => Some(value),
+            }
+        },
+    ))
+}
+
+/// Write a raster as a GeoTIFF
+///
+/// # Arguments
+///
+/// * `writer` - writer of the GeoTIFF file
+/// * `raster` - [Raster] of the values, None written as NaN
+///
+/// # Returns
+///
+/// * Nothing, or an error if the GeoTIFF could not be written
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geotiff;
+/// use dted2::primitives::AxisElement;
+/// use dted2::raster::Raster;
+/// use std::io::Cursor;
+///
+/// let raster = Raster::from_fn(
+///     AxisElement::new(-10.0, 20.0),
+///     AxisElement::new(0.5, 0.25),
+///     AxisElement::new(3, 5),
+///     |lat_idx, lon_idx| match (lat_idx, lon_idx) {
+///         (2, 0) => None,
+///         _ => Some((lat_idx * 5 + lon_idx) as f64),
+///     },
+/// );
+/// let mut tiff = Cursor::new(Vec::new());
+/// geotiff::write(&mut tiff, &raster).unwrap();
+/// tiff.set_position(0);
+/// assert_eq!(geotiff::read_from(tiff).unwrap(), raster);
+/// ```
+pub fn write<W: Write + Seek>(writer: &mut W, raster: &Raster<Option<f64>>) -> Result<(), Error> {
+    let (width, height) = (raster.count.lon, raster.count.lat);
+    let pixels: Vec<f32> = (0..height)
+        .rev()
+        .flat_map(|lat_idx| (0..width).map(move |lon_idx| (lat_idx, lon_idx)))
+        .map(|(lat_idx, lon_idx)| match raster.get(lat_idx, lon_idx) {
+            Some(Some(value)) => *value as f32,
+            _ => f32::NAN,
+        })
+        .collect();
+    // the north west post, as the pixels are points
+    let north = raster.origin.lat + height.saturating_sub(1) as f64 * raster.interval.lat;
+    write_image(
+        writer,
+        (width, height),
+        &pixels,
+        [raster.interval.lon, raster.interval.lat, 0.0],
+        [0.0, 0.0, 0.0, raster.origin.lon, north, 0.0],
+        &WRITTEN_GEO_KEYS,
+    )
+}
+
+/// Write a UTM raster as a GeoTIFF, in the EPSG code of its zone
+///
+/// # Arguments
+///
+/// * `writer` - writer of the GeoTIFF file
+/// * `raster` - [UtmRaster] of the values, None written as NaN
+///
+/// # Returns
+///
+/// * Nothing, or an error if the GeoTIFF could not be written
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geotiff;
+/// use dted2::interpolation::Interpolation;
+/// use dted2::DTEDData;
+/// use std::io::Cursor;
+/// use tiff::decoder::Decoder;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let raster = data.to_utm(2000.0, Interpolation::Bilinear);
+/// let mut tiff = Cursor::new(Vec::new());
+/// geotiff::write_utm(&mut tiff, &raster).unwrap();
+/// tiff.set_position(0);
+/// let mut decoder = Decoder::new(tiff).unwrap();
+/// assert_eq!(
+///     decoder.dimensions().unwrap(),
+///     (raster.width as u32, raster.height as u32)
+/// );
+/// ```
+#[cfg(feature = "proj")]
+pub fn write_utm<W: Write + Seek>(writer: &mut W, raster: &UtmRaster) -> Result<(), Error> {
+    let pixels: Vec<f32> = raster
+        .values
+        .iter()
+        .map(|value| value.map_or(f32::NAN, |value| value as f32))
+        .collect();
+    // model type (projected), raster type (`PixelIsArea`) and projected
+    // coordinate system (EPSG of the zone)
+    let geo_keys = [
+        1,
+        1,
+        0,
+        3,
+        MODEL_TYPE_KEY,
+        0,
+        1,
+        1,
+        RASTER_TYPE_KEY,
+        0,
+        1,
+        1,
+        PROJECTED_CS_TYPE_KEY,
+        0,
+        1,
+        raster.zone.epsg(),
+    ];
+    write_image(
+        writer,
+        (raster.width, raster.height),
+        &pixels,
+        [raster.pixel_size, raster.pixel_size, 0.0],
+        [0.0, 0.0, 0.0, raster.west, raster.north, 0.0],
+        &geo_keys,
+    )
+}
+
+/// Write the pixels of a single-band 32-bit float GeoTIFF
+///
+/// # Arguments
+///
+/// * `writer` - writer of the GeoTIFF file
+/// * `(width, height)` - dimensions of the image
+/// * `pixels` - pixels, row by row from north to south, NaN where no data
+/// * `scale` - pixel scale
+/// * `tiepoint` - tie point of the first pixel
+/// * `geo_keys` - GeoKey directory
+fn write_image<W: Write + Seek>(
+    writer: &mut W,
+    (width, height): (usize, usize),
+    pixels: &[f32],
+    scale: [f64; 3],
+    tiepoint: [f64; 6],
+    geo_keys: &[u16],
+) -> Result<(), Error> {
+    let mut tiff = TiffEncoder::new(BufWriter::new(writer)).map_err(from_tiff)?;
+    let mut image = tiff
+        .new_image::<Gray32Float>(width as u32, height as u32)
+        .map_err(from_tiff)?;
+    let encoder = image.encoder();
+    encoder
+        .write_tag(Tag::ModelPixelScaleTag, &scale[..])
+        .map_err(from_tiff)?;
+    encoder
+        .write_tag(Tag::ModelTiepointTag, &tiepoint[..])
+        .map_err(from_tiff)?;
+    encoder
+        .write_tag(Tag::GeoKeyDirectoryTag, geo_keys)
+        .map_err(from_tiff)?;
+    encoder
+        .write_tag(Tag::GdalNodata, "nan")
+        .map_err(from_tiff)?;
+    image.write_data(pixels).map_err(from_tiff)
+}
+
+/// Find a tag holding floating point values
+fn find_f64_vec<R: Read + Seek>(
+    decoder: &mut Decoder<R>,
+    tag: Tag,
+) -> Result<Option<Vec<f64>>, Error> {
+    match decoder.find_tag(tag).map_err(from_tiff)? {
+        Some(value) => Ok(Some(value.into_f64_vec().map_err(from_tiff)?)),
+        None => Ok(None),
+    }
+}
+
+/// Invalid (or unsupported) GeoTIFF
+fn invalid<T>(message: &str) -> Result<T, Error> {
+    Err(Error::InvalidInput(format!("GeoTIFF: {}", message)))
+}
+
+/// Convert a TIFF decoding error
+fn from_tiff(err: TiffError) -> Error {
+    match err {
+        TiffError::IoError(err) => Error::Io(err),
+        err => Error::InvalidInput(format!("GeoTIFF: {}", err)),
+    }
+}
+
+impl DTEDData {
+    /// Write the elevations as a GeoTIFF, see [write]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the GeoTIFF file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an error if the file could not be written
+    pub fn write_geotiff(&self, path: &str) -> Result<(), Error> {
+        write(&mut File::create(path)?, &Raster::from_source(self))
+    }
+}
@@ -0,0 +1,252 @@
+//! Contains the row, column and window views of the elevation grid of a cell,
+//! for tiled processing without index arithmetic over the data records.
+//!
+//! The views borrow the posts of the [RawDTEDRecords] without copying them.
+//! Columns (longitude lines) are contiguous slices, as stored in the file,
+//! while rows (latitude points) are strided iterators. Elevations are raw,
+//! void posts being [crate::dted::DTED_VOID_ELEVATION].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::ops::{Index, Range};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::crop::post_ranges;
+use crate::dted::{DTEDData, RawDTEDRecords};
+use crate::primitives::AxisElement;
+
+/// Window over the posts of a cell, within latitude and longitude index ranges
+///
+/// # Examples
+///
+/// ```
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let window = data.window(10..20, 30..35).unwrap();
+/// assert_eq!(window.size(), (10, 5));
+/// assert_eq!(window.get(0, 0), data.data.get(10, 30));
+/// assert_eq!(window[(2, 1)], data.data[(12, 31)]);
+/// assert_eq!(window.columns().count(), 5);
+/// assert_eq!(window.rows().count(), 10);
+/// assert!(window.rows().all(|row| row.count() == 5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridWindow<'a> {
+    records: &'a RawDTEDRecords,
+    lat_range: Range<usize>,
+    lon_range: Range<usize>,
+}
+impl<'a> GridWindow<'a> {
+    /// Create a window over records
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - [RawDTEDRecords]
+    /// * `lat_range` - latitude indices of the window, from south to north
+    /// * `lon_range` - longitude indices of the window, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [GridWindow], or None if a range is empty or beyond the records
+    pub fn new(
+        records: &'a RawDTEDRecords,
+        lat_range: Range<usize>,
+        lon_range: Range<usize>,
+    ) -> Option<GridWindow<'a>> {
+        match !lat_range.is_empty()
+            && !lon_range.is_empty()
+            && lat_range.end <= records.line_len
+            && lon_range.end <= records.len()
+        {
+            true => Some(GridWindow {
+                records,
+                lat_range,
+                lon_range,
+            }),
+            false => None,
+        }
+    }
+
+    /// Get the latitude indices of the window, in the cell
+    pub fn lat_range(&self) -> Range<usize> {
+        self.lat_range.clone()
+    }
+
+    /// Get the longitude indices of the window, in the cell
+    pub fn lon_range(&self) -> Range<usize> {
+        self.lon_range.clone()
+    }
+
+    /// Get the size of the window
+    ///
+    /// # Returns
+    ///
+    /// * `(lat_count, lon_count)`: number of latitude points and longitude lines
+    pub fn size(&self) -> (usize, usize) {
+        (self.lat_range.len(), self.lon_range.len())
+    }
+
+    /// Get the raw elevation of a post of the window
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from the south of the window
+    /// * `lon_idx` - longitude index, from the west of the window
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation, or None if out of the window
+    pub fn get(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        let (lat_count, lon_count) = self.size();
+        match lat_idx < lat_count && lon_idx < lon_count {
+            true => self.records.get(
+                self.lat_range.start + lat_idx,
+                self.lon_range.start + lon_idx,
+            ),
+            false => None,
+        }
+    }
+
+    /// Iterate over the longitude lines of the window, from west to east,
+    /// each a slice of its elevations from south to north
+    pub fn columns(&self) -> impl Iterator<Item = &'a [i16]> + 'a {
+        let (records, lat_range) = (self.records, self.lat_range.clone());
+        self.lon_range
+            .clone()
+            .filter_map(move |lon_idx| Some(&records.column(lon_idx)?[lat_range.clone()]))
+    }
+
+    /// Iterate over the latitude points of the window, from south to north,
+    /// each an iterator over its elevations from west to east
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = i16> + 'a> + 'a {
+        let elevations = &self.records.elevations;
+        let line_len = self.records.line_len;
+        let lon_range = self.lon_range.clone();
+        self.lat_range.clone().map(move |lat_idx| {
+            elevations[lon_range.start * line_len + lat_idx..]
+                .iter()
+                .step_by(line_len)
+                .take(lon_range.len())
+                .copied()
+        })
+    }
+}
+
+impl Index<(usize, usize)> for GridWindow<'_> {
+    type Output = i16;
+
+    /// Get the raw elevation of the post at `(lat_idx, lon_idx)` from the
+    /// south west of the window, see [GridWindow::get]
+    ///
+    /// # Panics
+    ///
+    /// If the post is out of the window
+    fn index(&self, (lat_idx, lon_idx): (usize, usize)) -> &i16 {
+        let (lat_count, lon_count) = self.size();
+        if lat_idx >= lat_count || lon_idx >= lon_count {
+            panic!(
+                "post ({}, {}) out of a window of {}x{} posts",
+                lat_idx, lon_idx, lat_count, lon_count
+            );
+        }
+        &self.records[(
+            self.lat_range.start + lat_idx,
+            self.lon_range.start + lon_idx,
+        )]
+    }
+}
+
+impl DTEDData {
+    /// Iterate over the longitude lines of the cell, from west to east, each a
+    /// slice of its raw elevations from south to north, see [GridWindow::columns]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let column = data.columns().nth(3).unwrap();
+    /// assert_eq!(column.len(), 121);
+    /// assert_eq!(column[5], data.data.get(5, 3).unwrap());
+    /// ```
+    pub fn columns(&self) -> impl Iterator<Item = &[i16]> {
+        self.data.columns()
+    }
+
+    /// Iterate over the latitude points of the cell, from south to north, each
+    /// an iterator over its raw elevations from west to east, see [GridWindow::rows]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let row: Vec<i16> = data.rows().nth(5).unwrap().collect();
+    /// assert_eq!(row.len(), 121);
+    /// assert_eq!(row[3], data.data.get(5, 3).unwrap());
+    /// ```
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = i16> + '_> + '_ {
+        let lon_range = 0..self.data.len();
+        self.window(0..self.data.line_len, lon_range)
+            .into_iter()
+            .flat_map(|window| window.rows())
+    }
+
+    /// Get a window over the posts within index ranges
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_range` - latitude indices, from south to north
+    /// * `lon_range` - longitude indices, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [GridWindow], or None if a range is empty or beyond the cell
+    pub fn window(
+        &self,
+        lat_range: Range<usize>,
+        lon_range: Range<usize>,
+    ) -> Option<GridWindow<'_>> {
+        GridWindow::new(&self.data, lat_range, lon_range)
+    }
+
+    /// Get a window over the posts within a bounding box, see [DTEDData::crop]
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - south west corner of the box
+    /// * `max` - north east corner of the box
+    ///
+    /// # Returns
+    ///
+    /// * [GridWindow], or None if there are no posts within the box
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let window = data
+    ///     .window_in(AxisElement::new(42.5, 15.25), AxisElement::new(42.6, 15.3))
+    ///     .unwrap();
+    /// assert_eq!(window.lat_range(), 1800..2161);
+    /// assert_eq!(window.lon_range(), 900..1081);
+    /// ```
+    pub fn window_in(
+        &self,
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+    ) -> Option<GridWindow<'_>> {
+        let metadata = &self.metadata;
+        let posts = post_ranges(metadata.origin, metadata.interval, metadata.count, min, max)?;
+        self.window(posts.lat, posts.lon)
+    }
+}
@@ -0,0 +1,142 @@
+//! Contains the transparent decompression of gzip compressed DTED (requires
+//! the `flate2` feature), e.g. cells archived as `.dt2.gz`.
+//!
+//! Compressed input is recognized by the gzip magic bytes rather than by the
+//! file extension, such that the readers of files ([crate::DTEDData::read],
+//! [crate::dted::RawDTEDFile::read], [crate::lazy::LazyDTEDData::read], ...)
+//! and of streams ([crate::stream::DTEDStreamReader]) accept both compressed
+//! and uncompressed DTED alike.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use flate2::read::MultiGzDecoder;
+use std::io::{self, Chain, Cursor, Read};
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Magic bytes starting a gzip member
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Whether bytes are gzip compressed, i.e. start with [GZIP_MAGIC]
+///
+/// # Arguments
+///
+/// * `bytes` - (start of the) bytes
+///
+/// # Examples
+///
+/// ```
+/// use dted2::gzip::is_gzip;
+///
+/// assert!(is_gzip(&[0x1f, 0x8b, 0x08]));
+/// assert!(!is_gzip(b"UHL1"));
+/// ```
+pub fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&GZIP_MAGIC)
+}
+
+/// Decompress bytes if gzip compressed, see [is_gzip]
+///
+/// # Arguments
+///
+/// * `bytes` - compressed or uncompressed bytes
+///
+/// # Returns
+///
+/// * The decompressed bytes, or the bytes as is if not compressed
+///
+/// # Examples
+///
+/// ```
+/// use dted2::gzip::decompress;
+/// use flate2::{write::GzEncoder, Compression};
+/// use std::io::Write;
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+/// encoder.write_all(&content).unwrap();
+/// let compressed = encoder.finish().unwrap();
+/// assert!(decompress(compressed).unwrap() == content);
+/// assert!(decompress(content.clone()).unwrap() == content);
+/// ```
+pub fn decompress(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    match is_gzip(&bytes) {
+        true => {
+            let mut content = Vec::with_capacity(bytes.len() * 4);
+            MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut content)?;
+            Ok(content)
+        }
+        false => Ok(bytes),
+    }
+}
+
+/// Stream read as is, or decompressed if gzip compressed
+///
+/// The start of the stream is read upfront to recognize [GZIP_MAGIC], and
+/// then read again through the decompression if need be.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::gzip::MaybeGzDecoder;
+/// use std::io::Read;
+///
+/// let file = std::fs::File::open("tests/test_data_negative.dt2").unwrap();
+/// let mut reader = MaybeGzDecoder::new(file).unwrap();
+/// assert!(!reader.is_compressed());
+/// let mut start = [0; 3];
+/// reader.read_exact(&mut start).unwrap();
+/// assert_eq!(&start, b"UHL");
+/// ```
+pub struct MaybeGzDecoder<R: Read> {
+    inner: Inner<R>,
+}
+
+/// Stream of a [MaybeGzDecoder], the magic bytes read upfront chained back
+enum Inner<R: Read> {
+    Plain(Chain<Cursor<Vec<u8>>, R>),
+    Gzip(MultiGzDecoder<Chain<Cursor<Vec<u8>>, R>>),
+}
+
+impl<R: Read> MaybeGzDecoder<R> {
+    /// Create a reader, recognizing whether the stream is gzip compressed
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - compressed or uncompressed stream
+    ///
+    /// # Returns
+    ///
+    /// * [MaybeGzDecoder], or an error if the start of the stream could not
+    ///   be read
+    pub fn new(mut reader: R) -> io::Result<MaybeGzDecoder<R>> {
+        let mut magic = Vec::with_capacity(GZIP_MAGIC.len());
+        (&mut reader)
+            .take(GZIP_MAGIC.len() as u64)
+            .read_to_end(&mut magic)?;
+        let compressed = is_gzip(&magic);
+        let stream = Cursor::new(magic).chain(reader);
+        Ok(MaybeGzDecoder {
+            inner: match compressed {
+                true => Inner::Gzip(MultiGzDecoder::new(stream)),
+                false => Inner::Plain(stream),
+            },
+        })
+    }
+
+    /// Whether the stream is gzip compressed
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.inner, Inner::Gzip(_))
+    }
+}
+
+impl<R: Read> Read for MaybeGzDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.read(buf),
+            Inner::Gzip(reader) => reader.read(buf),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! Contains the import of SRTM HGT tiles, into a [Raster] or directly into
+//! DTED cells, and the export of cells as HGT tiles.
+//!
+//! An HGT tile covers the one degree cell named after its south west corner
+//! (e.g. `N34W119.hgt`), as a square grid of big-endian 16 bit elevations,
+//! one row after the other from north to south, each from west to east. Void
+//! posts are -32768. Tiles are 3601 posts wide at 1 arc-second (SRTM1), or
+//! 1201 at 3 arc-seconds (SRTM3).
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::builder::DTEDBuilder;
+use crate::dataset::hemisphere_degrees;
+use crate::dted::{DTEDData, DTEDLevel, RawDTEDFile};
+use crate::interpolation::Interpolation;
+use crate::primitives::{Angle, AxisElement};
+use crate::raster::Raster;
+use crate::source::ElevationSource;
+use crate::Error;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Elevation of void posts
+pub const HGT_VOID_ELEVATION: i16 = i16::MIN;
+
+/// Get the origin of an HGT tile from the name of its file (e.g. `N34W119.hgt`)
+///
+/// # Arguments
+///
+/// * `path` - path to the file (`.hgt`, in any case)
+///
+/// # Returns
+///
+/// * Origin lat/lon (in degrees), or None if not named after a tile
+///
+/// # Examples
+///
+/// ```
+/// use dted2::hgt::tile_origin;
+/// use dted2::primitives::AxisElement;
+/// use std::path::Path;
+///
+/// assert_eq!(tile_origin(Path::new("srtm/N34W119.hgt")), Some(AxisElement::new(34, -119)));
+/// assert_eq!(tile_origin(Path::new("s01e005.HGT")), Some(AxisElement::new(-1, 5)));
+/// assert_eq!(tile_origin(Path::new("N34W119.dt2")), None);
+/// ```
+pub fn tile_origin(path: &Path) -> Option<AxisElement<i16>> {
+    if !path.extension()?.to_str()?.eq_ignore_ascii_case("hgt") {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    let split = stem.find(['e', 'w'])?;
+    let (lat, lon) = stem.split_at(split);
+    Some(AxisElement::new(
+        hemisphere_degrees(lat, 'n', 's', 90)?,
+        hemisphere_degrees(lon, 'e', 'w', 180)?,
+    ))
+}
+
+/// Get the name of the HGT file of a tile
+///
+/// # Arguments
+///
+/// * `origin` - lat/lon of the south west corner of the tile, in degrees
+///
+/// # Examples
+///
+/// ```
+/// use dted2::hgt::tile_name;
+/// use dted2::primitives::AxisElement;
+///
+/// assert_eq!(tile_name(AxisElement::new(34, -119)), "N34W119.hgt");
+/// assert_eq!(tile_name(AxisElement::new(-1, 5)), "S01E005.hgt");
+/// ```
+pub fn tile_name(origin: AxisElement<i16>) -> String {
+    format!(
+        "{}{:02}{}{:03}.hgt",
+        match origin.lat < 0 {
+            true => 'S',
+            false => 'N',
+        },
+        origin.lat.unsigned_abs(),
+        match origin.lon < 0 {
+            true => 'W',
+            false => 'E',
+        },
+        origin.lon.unsigned_abs()
+    )
+}
+
+/// Read an HGT tile, located by the name of its file
+///
+/// # Arguments
+///
+/// * `path` - path of the HGT file, see [tile_origin]
+///
+/// # Returns
+///
+/// * [Raster] of the elevations (None where void), or an error if the file
+///   is not named after a tile or is not a square grid
+///
+/// # Examples
+///
+/// ```
+/// use dted2::hgt;
+///
+/// assert!(matches!(hgt::read("tests/test_data.dt2"), Err(dted2::Error::InvalidInput(_))));
+/// ```
+pub fn read(path: &str) -> Result<Raster<Option<f64>>, Error> {
+    let origin = tile_origin(Path::new(path))
+        .ok_or_else(|| Error::InvalidInput(format!("HGT: {} is not named after a tile", path)))?;
+    read_from(std::fs::File::open(path)?, origin)
+}
+
+/// Read an HGT tile from a reader
+///
+/// # Arguments
+///
+/// * `reader` - reader of an HGT file
+/// * `origin` - lat/lon of the south west corner of the tile, in degrees
+///
+/// # Returns
+///
+/// * [Raster] of the elevations (None where void), or an error if the file
+///   is not a square grid
+///
+/// # Examples
+///
+/// ```
+/// use dted2::hgt;
+/// use dted2::primitives::AxisElement;
+///
+/// // a 3x3 tile, rows from north to south
+/// let posts: [i16; 9] = [7, 8, 9, 4, -32768, 6, 1, 2, 3];
+/// let bytes: Vec<u8> = posts.iter().flat_map(|post| post.to_be_bytes()).collect();
+/// let raster = hgt::read_from(&bytes[..], AxisElement::new(34, -119)).unwrap();
+/// assert_eq!(raster.interval, AxisElement::new(0.5, 0.5));
+/// assert_eq!(raster.get(0, 2), Some(&Some(3.0)));
+/// assert_eq!(raster.get(2, 0), Some(&Some(7.0)));
+/// assert_eq!(raster.get(1, 1), Some(&None));
+/// ```
+pub fn read_from<R: Read>(
+    mut reader: R,
+    origin: AxisElement<i16>,
+) -> Result<Raster<Option<f64>>, Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let size = (bytes.len() as f64 / 2.0).sqrt().round() as usize;
+    if size < 2 || bytes.len() != 2 * size * size {
+        return Err(Error::InvalidInput(format!(
+            "HGT: {} bytes is not a square grid of posts",
+            bytes.len()
+        )));
+    }
+    let spacing = 1.0 / (size - 1) as f64;
+    Ok(Raster::from_fn(
+        AxisElement::new(f64::from(origin.lat), f64::from(origin.lon)),
+        AxisElement::new(spacing, spacing),
+        AxisElement::new(size, size),
+        |lat_idx, lon_idx| {
+            let idx = 2 * ((size - 1 - lat_idx) * size + lon_idx);
+            match i16::from_be_bytes([bytes[idx], bytes[idx + 1]]) {
+                HGT_VOID_ELEVATION => None,
+                elev => Some(f64::from(elev)),
+            }
+        },
+    ))
+}
+
+/// Read an HGT tile into a DTED cell of the matching resolution: Level 2 for
+/// SRTM1 tiles and Level 1 for SRTM3 tiles
+///
+/// Above 50 degrees of latitude, where DTED posts are farther apart in
+/// longitude than HGT posts, the tile is resampled bilinearly
+///
+/// # Arguments
+///
+/// * `path` - path of the HGT file, see [tile_origin]
+///
+/// # Returns
+///
+/// * [RawDTEDFile] which can be written with [RawDTEDFile::write], or an
+///   error if the file is not an SRTM1 or SRTM3 tile
+pub fn read_dted(path: &str) -> Result<RawDTEDFile, Error> {
+    let raster = read(path)?;
+    let level = match raster.count.lat {
+        3601 => DTEDLevel::Level2,
+        1201 => DTEDLevel::Level1,
+        size => {
+            return Err(Error::InvalidInput(format!(
+                "HGT: tiles of {} posts have no matching DTED level",
+                size
+            )))
+        }
+    };
+    let angle = |deg: f64| Angle::new(deg.abs() as u16, 0, 0.0, deg < 0.0);
+    let origin = AxisElement::new(angle(raster.origin.lat), angle(raster.origin.lon));
+    DTEDBuilder::for_level(origin, level)
+        .resample(&raster, Interpolation::Bilinear)
+        .build()
+}
+
+/// Read an HGT tile into [DTEDData], see [read_dted]
+///
+/// # Arguments
+///
+/// * `path` - path of the HGT file, see [tile_origin]
+///
+/// # Returns
+///
+/// * [DTEDData], with the path of the HGT file as filename
+pub fn read_data(path: &str) -> Result<DTEDData, Error> {
+    Ok(DTEDData::from_file(read_dted(path)?, path))
+}
+
+/// Write the elevations of a cell as an HGT tile
+///
+/// HGT tiles are square: the grid keeps the latitude points of the cell,
+/// resampling its longitude lines bilinearly (above 50 degrees of latitude)
+/// to as many posts over the same extent
+///
+/// # Arguments
+///
+/// * `writer` - writer of the HGT file
+/// * `source` - any [ElevationSource]
+///
+/// # Returns
+///
+/// * Nothing, or an [Error::Io] if the tile could not be written
+///
+/// # Examples
+///
+/// ```
+/// use dted2::hgt;
+/// use dted2::primitives::AxisElement;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let mut bytes = Vec::new();
+/// hgt::write(&mut bytes, &data).unwrap();
+/// assert_eq!(bytes.len(), 2 * 121 * 121);
+/// let raster = hgt::read_from(&bytes[..], AxisElement::new(-30, -178)).unwrap();
+/// assert_eq!(raster.get(5, 3).unwrap().map(|elev| elev as i16), data.data.get(5, 3));
+/// ```
+pub fn write<S: ElevationSource, W: Write>(writer: &mut W, source: &S) -> Result<(), Error> {
+    let count = source.metadata().count;
+    let (size, lon_count) = (count.lat as usize, count.lon as usize);
+    let mut writer = BufWriter::new(writer);
+    for lat_idx in (0..size).rev() {
+        let row: Vec<u8> = (0..size)
+            .map(|idx| {
+                let lon_idx =
+                    idx as f64 * lon_count.saturating_sub(1) as f64 / (size - 1).max(1) as f64;
+                let elev =
+                    Interpolation::Bilinear.interpolate(lat_idx as f64, lon_idx, |lat, lon| {
+                        match lat >= 0 && lon >= 0 {
+                            true => source.elevation(lat as usize, lon as usize).map(f64::from),
+                            false => None,
+                        }
+                    });
+                match elev {
+                    Some(elev) => elev.round() as i16,
+                    None => HGT_VOID_ELEVATION,
+                }
+            })
+            .flat_map(|elev| elev.to_be_bytes())
+            .collect();
+        writer.write_all(&row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+impl DTEDData {
+    /// Write the elevations as an HGT tile, see [write]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the HGT file, usually named after the tile (see [tile_name])
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an [Error::Io] if the file could not be written
+    pub fn write_hgt(&self, path: &str) -> Result<(), Error> {
+        write(&mut std::fs::File::create(path)?, self)
+    }
+}
@@ -0,0 +1,183 @@
+//! Contains [HttpRangeReader], for reading DTED from a remote server with
+//! HTTP range requests (requires the `http` feature).
+//!
+//! The reader is seekable, such that a [DTEDReader] over it
+//! ([DTEDReader::open_url]) fetches the header records, and then only the
+//! posts queried, rather than downloading whole cells (about 25 MB for
+//! Level 2). Each request fetches a block of at least [HTTP_READ_AHEAD]
+//! bytes, which is kept to answer the next reads within it.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::io::{self, Read, Seek, SeekFrom};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::reader::DTEDReader;
+use crate::Error as DTEDError;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Minimum number of bytes fetched by a range request
+pub const HTTP_READ_AHEAD: usize = 8 * 1024;
+
+/// Seekable reader of a remote file, through HTTP range requests
+///
+/// The server must answer range requests (with `206 Partial Content`),
+/// reads otherwise failing with [io::ErrorKind::Unsupported].
+///
+/// # Fields
+///
+/// * `agent` - HTTP agent, keeping connections alive between requests
+/// * `url` - URL of the file
+/// * `position` - current position within the file
+/// * `len` - length of the file, once known
+/// * `block` - offset and bytes of the last block fetched
+/// * `requests` - number of range requests issued
+pub struct HttpRangeReader {
+    agent: ureq::Agent,
+    url: String,
+    position: u64,
+    len: Option<u64>,
+    block: (u64, Vec<u8>),
+    requests: usize,
+}
+impl HttpRangeReader {
+    /// Create a reader of a remote file, no request being issued until read
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the file
+    ///
+    /// # Returns
+    ///
+    /// * [HttpRangeReader], positioned at the start of the file
+    pub fn new(url: &str) -> HttpRangeReader {
+        HttpRangeReader {
+            agent: ureq::Agent::new(),
+            url: url.to_string(),
+            position: 0,
+            len: None,
+            block: (0, Vec::new()),
+            requests: 0,
+        }
+    }
+
+    /// Get the URL of the file
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the number of range requests issued so far
+    pub fn requests(&self) -> usize {
+        self.requests
+    }
+
+    /// Get the length of the file, fetching its first byte if not known yet
+    fn len(&mut self) -> io::Result<u64> {
+        if self.len.is_none() {
+            self.fetch(0, 1)?;
+        }
+        self.len.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} did not report its length", self.url),
+            )
+        })
+    }
+
+    /// Fetch a range of the file, recording the length of the file
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - offset of the first byte
+    /// * `len` - number of bytes, at least 1
+    ///
+    /// # Returns
+    ///
+    /// * The bytes, fewer than `len` at the end of the file
+    fn fetch(&mut self, start: u64, len: usize) -> io::Result<Vec<u8>> {
+        self.requests += 1;
+        let range = format!("bytes={}-{}", start, start + len as u64 - 1);
+        let response = match self.agent.get(&self.url).set("Range", &range).call() {
+            Ok(response) => response,
+            // the range starts past the end of the file
+            Err(ureq::Error::Status(416, _)) => return Ok(Vec::new()),
+            Err(e) => return Err(io::Error::other(e)),
+        };
+        if response.status() != 206 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} does not support range requests", self.url),
+            ));
+        }
+        // e.g. `bytes 0-8191/25981842`
+        if let Some(total) = response
+            .header("Content-Range")
+            .and_then(|range| range.rsplit_once('/'))
+            .and_then(|(_, total)| total.trim().parse().ok())
+        {
+            self.len = Some(total);
+        }
+        let mut bytes = Vec::with_capacity(len);
+        response
+            .into_reader()
+            .take(len as u64)
+            .read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+impl Read for HttpRangeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let (start, bytes) = &self.block;
+        let cached = self.position >= *start && self.position < start + bytes.len() as u64;
+        if !cached {
+            let bytes = self.fetch(self.position, buf.len().max(HTTP_READ_AHEAD))?;
+            self.block = (self.position, bytes);
+        }
+        let (start, bytes) = &self.block;
+        let available = &bytes[(self.position - start) as usize..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+impl Seek for HttpRangeReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len()?.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+impl DTEDReader<HttpRangeReader> {
+    /// Open a DTED file on a remote server, fetching its header records with
+    /// HTTP range requests, see [HttpRangeReader]
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDReader]: seeking reader, fetching the posts on demand
+    pub fn open_url(url: &str) -> Result<DTEDReader<HttpRangeReader>, DTEDError> {
+        DTEDReader::new(HttpRangeReader::new(url))
+    }
+}
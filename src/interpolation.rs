@@ -0,0 +1,159 @@
+//! Contains the [Interpolation] methods used to query elevations between posts.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Interpolation weights at or below this value are considered zero,
+/// such that a query on a post only depends on that post
+const INTERPOLATION_EPSILON: f64 = 1e-9;
+/// Lanczos kernel size (number of lobes)
+const LANCZOS_A: isize = 3;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+/// Method used to interpolate elevations between posts
+///
+/// * Nearest - elevation of the nearest post
+/// * Bilinear - bilinear interpolation between the 4 surrounding posts
+/// * Bicubic - bicubic convolution (Keys, `a = -0.5`) over the 16 surrounding posts
+/// * Lanczos - Lanczos resampling (`a = 3`) over the 36 surrounding posts
+///
+/// Void (or out of bounds) posts are excluded from [Interpolation::Bilinear],
+/// with the weights of the remaining posts renormalized. As the
+/// [Interpolation::Bicubic] and [Interpolation::Lanczos] kernels have negative
+/// lobes, they fall back to [Interpolation::Bilinear] whenever any post of
+/// their neighborhood is void (or out of bounds).
+pub enum Interpolation {
+    Nearest,
+    #[default]
+    Bilinear,
+    Bicubic,
+    Lanczos,
+}
+impl Interpolation {
+    /// Interpolate an elevation at fractional post indices
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - fractional latitude index
+    /// * `lon_idx` - fractional longitude index
+    /// * `post` - elevation of the post at `(lat_idx, lon_idx)`,
+    ///   None if void or out of bounds
+    ///
+    /// # Returns
+    ///
+    /// * Elevation or None if void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::interpolation::Interpolation;
+    ///
+    /// // elevation = 2 * lat + lon, on a 4x4 grid
+    /// let post = |lat: isize, lon: isize| match (0..4).contains(&lat) && (0..4).contains(&lon) {
+    ///     true => Some((2 * lat + lon) as f64),
+    ///     false => None,
+    /// };
+    /// assert_eq!(Interpolation::Nearest.interpolate(1.4, 1.6, post), Some(4.0));
+    /// assert_eq!(Interpolation::Bilinear.interpolate(1.5, 1.5, post), Some(4.5));
+    /// assert_eq!(Interpolation::Bicubic.interpolate(1.5, 1.5, post), Some(4.5));
+    /// assert_eq!(Interpolation::Bilinear.interpolate(3.0, 3.0, post), Some(9.0));
+    /// assert_eq!(Interpolation::Bilinear.interpolate(4.0, 3.0, post), None);
+    /// ```
+    pub fn interpolate<F>(&self, lat_idx: f64, lon_idx: f64, post: F) -> Option<f64>
+    where
+        F: Fn(isize, isize) -> Option<f64>,
+    {
+        match self {
+            Interpolation::Nearest => post(lat_idx.round() as isize, lon_idx.round() as isize),
+            Interpolation::Bilinear => bilinear(lat_idx, lon_idx, &post),
+            Interpolation::Bicubic => convolve(lat_idx, lon_idx, 2, bicubic_kernel, &post)
+                .or_else(|| bilinear(lat_idx, lon_idx, &post)),
+            Interpolation::Lanczos => convolve(lat_idx, lon_idx, LANCZOS_A, lanczos_kernel, &post)
+                .or_else(|| bilinear(lat_idx, lon_idx, &post)),
+        }
+    }
+}
+
+/// Bilinear interpolation between the 4 surrounding posts,
+/// excluding void posts and renormalizing the remaining weights
+fn bilinear<F>(lat_idx: f64, lon_idx: f64, post: &F) -> Option<f64>
+where
+    F: Fn(isize, isize) -> Option<f64>,
+{
+    let (lat0, lon0) = (lat_idx.floor(), lon_idx.floor());
+    let (lat_frac, lon_frac) = (lat_idx - lat0, lon_idx - lon0);
+    let (lat0, lon0) = (lat0 as isize, lon0 as isize);
+    let posts = [
+        (0, 0, (1.0 - lat_frac) * (1.0 - lon_frac)),
+        (1, 0, lat_frac * (1.0 - lon_frac)),
+        (0, 1, (1.0 - lat_frac) * lon_frac),
+        (1, 1, lat_frac * lon_frac),
+    ];
+    let (sum, weight) = posts
+        .iter()
+        .filter(|(_, _, weight)| *weight > INTERPOLATION_EPSILON)
+        .filter_map(|(lat, lon, weight)| {
+            post(lat0 + lat, lon0 + lon).map(|elev| (elev * weight, *weight))
+        })
+        .fold((0.0, 0.0), |acc, x| (acc.0 + x.0, acc.1 + x.1));
+    match weight > 0.0 {
+        true => Some(sum / weight),
+        false => None,
+    }
+}
+
+/// Separable convolution over the `2 * radius` posts (per axis) surrounding
+/// the fractional indices, None if any of those posts are void
+fn convolve<F>(
+    lat_idx: f64,
+    lon_idx: f64,
+    radius: isize,
+    kernel: fn(f64) -> f64,
+    post: &F,
+) -> Option<f64>
+where
+    F: Fn(isize, isize) -> Option<f64>,
+{
+    let (lat0, lon0) = (lat_idx.floor() as isize, lon_idx.floor() as isize);
+    let mut sum = 0.0;
+    let mut weight = 0.0;
+    for lat in (lat0 - radius + 1)..=(lat0 + radius) {
+        let lat_weight = kernel(lat_idx - lat as f64);
+        for lon in (lon0 - radius + 1)..=(lon0 + radius) {
+            let w = lat_weight * kernel(lon_idx - lon as f64);
+            sum += post(lat, lon)? * w;
+            weight += w;
+        }
+    }
+    Some(sum / weight)
+}
+
+/// Keys cubic convolution kernel, with `a = -0.5`
+fn bicubic_kernel(x: f64) -> f64 {
+    let a = -0.5;
+    let x = x.abs();
+    match x {
+        x if x <= 1.0 => (a + 2.0) * x.powi(3) - (a + 3.0) * x.powi(2) + 1.0,
+        x if x < 2.0 => a * x.powi(3) - 5.0 * a * x.powi(2) + 8.0 * a * x - 4.0 * a,
+        _ => 0.0,
+    }
+}
+
+/// Lanczos kernel, with `a = LANCZOS_A`
+fn lanczos_kernel(x: f64) -> f64 {
+    let a = LANCZOS_A as f64;
+    match x.abs() {
+        x if x < f64::EPSILON => 1.0,
+        x if x < a => {
+            let pi_x = std::f64::consts::PI * x;
+            a * pi_x.sin() * (pi_x / a).sin() / (pi_x * pi_x)
+        }
+        _ => 0.0,
+    }
+}
@@ -0,0 +1,70 @@
+//! Contains the structured JSON dumps of the metadata (requires the `serde`
+//! feature), for inventory systems to ingest the headers of DTED files
+//! without knowing their binary layout.
+//!
+//! The records serialize their fields under the same names as in Rust, the
+//! [crate::primitives::Angle]s as their `deg`, `min`, `sec` and `negative`
+//! fields, and missing (blank) values as `null`.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use serde_json::{json, Value};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDData, RawDTEDFile};
+
+impl RawDTEDFile {
+    /// Dump every field of the UHL, DSI and ACC records as JSON
+    ///
+    /// # Returns
+    ///
+    /// * JSON object of the `uhl`, `dsi` and `acc` records (`null` if not
+    ///   read)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    ///
+    /// let file = RawDTEDFile::read("tests/test_data.dt2").unwrap();
+    /// let json = file.metadata_json();
+    /// assert_eq!(json["uhl"]["origin"]["lat"]["deg"], 42);
+    /// assert_eq!(json["uhl"]["count"], json["dsi"]["count"]);
+    /// assert_eq!(json["dsi"]["product_level"], "DTED2");
+    /// assert!(json["acc"]["subregions"].is_array());
+    /// ```
+    pub fn metadata_json(&self) -> Value {
+        json!({
+            "uhl": self.header,
+            "dsi": self.dsi_record,
+            "acc": self.acc_record,
+        })
+    }
+}
+
+impl DTEDData {
+    /// Dump the metadata as JSON, see [crate::dted::DTEDMetadata]
+    ///
+    /// # Returns
+    ///
+    /// * JSON object of the metadata, with the DSI and ACC records
+    ///   (`dsi_record` and `acc_record`, `null` if only the UHL was read)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let json = data.metadata_json();
+    /// assert_eq!(json["filename"], "tests/test_data.dt2");
+    /// assert_eq!(json["origin"]["lat"], 42.0);
+    /// assert_eq!(json["dsi_record"]["vertical_datum"], "E96");
+    /// ```
+    pub fn metadata_json(&self) -> Value {
+        json!(self.metadata)
+    }
+}
@@ -0,0 +1,174 @@
+//! Contains the export of the posts as an ASPRS LAS point cloud (requires the
+//! `las` feature, and the `laz` feature for compressed LAZ files), for lidar
+//! oriented viewers and tools.
+//!
+//! Each non-void post is a ground point at its longitude (`x`) and latitude
+//! (`y`), in degrees on WGS84 (recorded as EPSG:4326 GeoTIFF keys), and its
+//! elevation (`z`) in meters. Coordinates are stored from the origin of the
+//! cell, to 1e-7 degree (about a centimeter) and a centimeter of elevation.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use las::point::{Classification, Format};
+use las::{Builder, Header, Point, Transform, Vector, Vlr, Writer};
+use std::io::{Seek, Write};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::source::ElevationSource;
+use crate::Error;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Scale of the longitudes and latitudes, in degrees
+const DEGREE_SCALE: f64 = 1e-7;
+/// Scale of the elevations, in meters
+const ELEVATION_SCALE: f64 = 0.01;
+/// User of the GeoTIFF keys record
+const PROJECTION_USER_ID: &str = "LASF_Projection";
+/// Record of the GeoTIFF keys (`GeoKeyDirectoryTag`)
+const GEO_KEY_DIRECTORY_RECORD: u16 = 34735;
+/// GeoTIFF keys of geographic coordinates on WGS84: a header of 4 values,
+/// then the entries of (key, location, count, value) of the model type
+/// (geographic) and geographic type (EPSG:4326)
+const GEO_KEY_DIRECTORY: [u16; 12] = [1, 1, 0, 2, 1024, 0, 1, 2, 2048, 0, 1, 4326];
+
+/// Write the posts as a LAS or LAZ file, compressed if the extension is
+/// `.laz` (with the `laz` feature)
+///
+/// # Arguments
+///
+/// * `path` - path of the `.las` or `.laz` file
+/// * `source` - any [ElevationSource]
+///
+/// # Returns
+///
+/// * Nothing, or an [Error::Io] if the file could not be written
+pub fn write<S: ElevationSource>(path: &str, source: &S) -> Result<(), Error> {
+    let mut writer = Writer::from_path(path, header(source)?).map_err(from_las)?;
+    write_points(&mut writer, source)?;
+    writer.close().map_err(from_las)
+}
+
+/// Write the posts as an (uncompressed) LAS point cloud to a writer
+///
+/// # Arguments
+///
+/// * `writer` - writer of the point cloud
+/// * `source` - any [ElevationSource]
+///
+/// # Returns
+///
+/// * The writer, or an [Error::Io] if the point cloud could not be written
+///
+/// # Examples
+///
+/// ```
+/// use dted2::DTEDData;
+/// use std::io::Cursor;
+///
+/// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+/// let bytes = dted2::las::write_to(Cursor::new(Vec::new()), &data).unwrap();
+///
+/// let mut reader = las::Reader::new(Cursor::new(bytes.into_inner())).unwrap();
+/// let points = reader.read_all().unwrap();
+/// let point = points.points().next().unwrap().unwrap();
+/// assert_eq!((point.x, point.y), (data.min.lon, data.min.lat));
+/// assert_eq!(Some(point.z as i16), data.data.get(0, 0));
+/// ```
+pub fn write_to<S, W>(writer: W, source: &S) -> Result<W, Error>
+where
+    S: ElevationSource,
+    W: 'static + Write + Seek + Send + Sync,
+{
+    let mut writer = Writer::new(writer, header(source)?).map_err(from_las)?;
+    write_points(&mut writer, source)?;
+    writer.into_inner().map_err(from_las)
+}
+
+/// Header of the point cloud of a source, the transforms starting from its
+/// origin
+fn header<S: ElevationSource>(source: &S) -> Result<Header, Error> {
+    let origin = source.metadata().origin;
+    let mut builder = Builder::from((1, 2));
+    builder.point_format = Format::new(0).map_err(from_las)?;
+    builder.generating_software = env!("CARGO_PKG_NAME").to_string();
+    builder.transforms = Vector {
+        x: Transform {
+            scale: DEGREE_SCALE,
+            offset: origin.lon,
+        },
+        y: Transform {
+            scale: DEGREE_SCALE,
+            offset: origin.lat,
+        },
+        z: Transform {
+            scale: ELEVATION_SCALE,
+            offset: 0.0,
+        },
+    };
+    builder.vlrs.push(Vlr {
+        user_id: PROJECTION_USER_ID.to_string(),
+        record_id: GEO_KEY_DIRECTORY_RECORD,
+        description: "GeoTIFF GeoKeyDirectoryTag".to_string(),
+        data: GEO_KEY_DIRECTORY
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect(),
+    });
+    builder.into_header().map_err(from_las)
+}
+
+/// Write a point per non-void post, longitude line by longitude line
+fn write_points<S, W>(writer: &mut Writer<W>, source: &S) -> Result<(), Error>
+where
+    S: ElevationSource,
+    W: 'static + Write + Seek + Send + Sync,
+{
+    let metadata = source.metadata();
+    for lon_idx in 0..metadata.count.lon as usize {
+        for lat_idx in 0..metadata.count.lat as usize {
+            let Some(elev) = source.elevation(lat_idx, lon_idx) else {
+                continue;
+            };
+            let point = Point {
+                x: metadata.origin.lon + lon_idx as f64 * metadata.interval.lon,
+                y: metadata.origin.lat + lat_idx as f64 * metadata.interval.lat,
+                z: f64::from(elev),
+                return_number: 1,
+                number_of_returns: 1,
+                classification: Classification::Ground,
+                ..Default::default()
+            };
+            writer.write_point(point).map_err(from_las)?;
+        }
+    }
+    Ok(())
+}
+
+/// Convert a LAS error
+fn from_las(err: las::Error) -> Error {
+    match err {
+        las::Error::Io(err) => Error::Io(err),
+        err => Error::InvalidInput(format!("LAS: {}", err)),
+    }
+}
+
+impl DTEDData {
+    /// Write the posts as a LAS or LAZ file, see [write]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the `.las` or `.laz` file (with the `laz` feature)
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an [Error::Io] if the file could not be written
+    pub fn write_las(&self, path: &str) -> Result<(), Error> {
+        write(path, self)
+    }
+}
@@ -0,0 +1,148 @@
+//! Contains [LazyDTEDData], DTED data whose records are decoded on demand.
+//!
+//! Only the header records are parsed when reading a file, with each
+//! data record decoded (and cached) the first time one of its posts is
+//! queried, so reading a file for a handful of point queries is fast.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::sync::OnceLock;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+/// Lazily decoded DTED Data
+///
+/// See [ElevationSource] for the available queries.
+///
+/// # Fields
+///
+/// * `metadata` - [DTEDMetadata], including the DSI and ACC records
+pub struct LazyDTEDData {
+    pub metadata: DTEDMetadata,
+    content: Vec<u8>,
+    record_len: usize,
+    records: Vec<OnceLock<Option<RawDTEDRecord>>>,
+}
+impl LazyDTEDData {
+    /// Read a DTED file, only parsing its header records
+    ///
+    /// The data records are validated to be complete, but not decoded
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [LazyDTEDData]: lazily decoded DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// use dted2::lazy::LazyDTEDData;
+    ///
+    /// let lazy = LazyDTEDData::read("tests/test_data.dt2").unwrap();
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert_eq!(lazy.decoded_records(), 0);
+    /// assert_eq!(lazy.elevation_at(42.52, 15.75), data.elevation_at(42.52, 15.75));
+    /// assert_eq!(lazy.decoded_records(), 1);
+    /// ```
+    pub fn read(path: &str) -> Result<LazyDTEDData, DTEDError> {
+        LazyDTEDData::from_content(read_file(path)?, path)
+    }
+
+    /// Create lazily decoded DTED data from the bytes of a file, only
+    /// parsing its header records, without any filesystem access
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file (uncompressed), kept to decode the
+    ///   data records from
+    ///
+    /// # Returns
+    ///
+    /// * [LazyDTEDData]: lazily decoded DTED data, with an empty filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// use dted2::lazy::LazyDTEDData;
+    ///
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let lazy = LazyDTEDData::from_vec(content).unwrap();
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(lazy.elevation(3, 4), data.elevation(3, 4));
+    /// assert!(lazy.metadata.filename.is_empty());
+    /// ```
+    pub fn from_vec(content: Vec<u8>) -> Result<LazyDTEDData, DTEDError> {
+        LazyDTEDData::from_content(content, "")
+    }
+
+    /// Create lazily decoded DTED data from the content of a file, only
+    /// parsing its header records, see [LazyDTEDData::read]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - bytes of the DTED file
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [LazyDTEDData]: lazily decoded DTED data
+    pub(crate) fn from_content(content: Vec<u8>, path: &str) -> Result<LazyDTEDData, DTEDError> {
+        let (_, (header, dsi_record, acc_record)) = parsers::dted_headers_parser(&content)
+            .map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+        let record_len = validate_data_records(&content, &header.count)?;
+        Ok(LazyDTEDData {
+            metadata: DTEDMetadata::from_records(&header, dsi_record, acc_record, path),
+            content,
+            record_len,
+            records: (0..header.count.lon).map(|_| OnceLock::new()).collect(),
+        })
+    }
+
+    /// Get a data record, decoding it on first access
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * The data record, or None if out of bounds or it could not be decoded
+    pub fn record(&self, lon_idx: usize) -> Option<&RawDTEDRecord> {
+        self.records
+            .get(lon_idx)?
+            .get_or_init(|| {
+                let start = DTED_DATA_OFFSET + lon_idx * self.record_len;
+                let input = &self.content[start..start + self.record_len];
+                parsers::parse_dted_record(input, self.metadata.count.lat as usize)
+                    .ok()
+                    .map(|(_, record)| record)
+            })
+            .as_ref()
+    }
+
+    /// Get the number of data records decoded so far
+    pub fn decoded_records(&self) -> usize {
+        self.records.iter().filter(|r| r.get().is_some()).count()
+    }
+}
+impl ElevationSource for LazyDTEDData {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.record(lon_idx)?.elevations.get(lat_idx).copied()
+    }
+}
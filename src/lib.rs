@@ -11,41 +11,367 @@
 //! * _Level 2_: Approximately 30 meters between data points.
 //!
 //! Each level of DTED provides different details suitable for various precision requirements in applications.
+//!
+//! The standard library is required by the `std` feature (on by default), for
+//! file I/O and everything beyond the core byte slice parsers
+//! ([RawDTEDFile::from_bytes](dted::RawDTEDFile::from_bytes)), primitives and
+//! elevation model ([DTEDData], [ElevationSource]). Without it, the crate is
+//! `no_std`, only requiring `alloc`, e.g. for embedded targets.
+#![cfg_attr(not(feature = "std"), no_std)]
 
 // --------------------------------------------------
 // external
 // --------------------------------------------------
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+// the derive macros of `thisenum` name `::std` paths, all within `core`
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(feature = "std")]
 use std::io;
 
 // --------------------------------------------------
 // local
 // --------------------------------------------------
+#[cfg(feature = "ndarray")]
+pub mod array;
+#[cfg(feature = "std")]
+pub mod asc;
+#[cfg(feature = "async")]
+pub mod async_stream;
+pub mod borrowed;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod contour;
+#[cfg(feature = "std")]
+pub mod crop;
+#[cfg(feature = "std")]
+pub mod dataset;
+#[cfg(feature = "chrono")]
+pub mod dates;
+#[cfg(feature = "std")]
+pub mod diff;
 pub mod dted;
+#[cfg(feature = "std")]
+pub mod edit;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod fill;
+#[cfg(feature = "geo-types")]
+pub mod geo;
+#[cfg(feature = "std")]
+pub mod geodesy;
+#[cfg(feature = "egm96")]
+pub mod geoid;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "geotiff")]
+pub mod geotiff;
+#[cfg(feature = "std")]
+pub mod grid;
+#[cfg(feature = "flate2")]
+pub mod gzip;
+#[cfg(feature = "std")]
+pub mod hgt;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod interpolation;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "las")]
+pub mod las;
+#[cfg(feature = "std")]
+pub mod lazy;
+#[cfg(feature = "nalgebra")]
+pub mod matrix;
+#[cfg(feature = "std")]
+pub mod mesh;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "object_store")]
+pub mod object_store;
+pub mod options;
 pub mod parsers;
+#[cfg(feature = "std")]
+pub mod patch;
 pub mod primitives;
-pub use dted::{DTEDData, DTEDMetadata};
+pub mod producer;
+#[cfg(feature = "std")]
+pub mod profile;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod quantized_mesh;
+#[cfg(feature = "std")]
+pub mod raster;
+#[cfg(feature = "std")]
+pub mod reader;
+#[cfg(feature = "std")]
+pub mod resample;
+pub mod source;
+#[cfg(feature = "std")]
+pub mod statistics;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(feature = "std")]
+pub mod terrain;
+#[cfg(feature = "std")]
+pub mod tiles;
+#[cfg(feature = "proj")]
+pub mod utm;
+#[cfg(feature = "std")]
+pub mod visibility;
+#[cfg(feature = "std")]
+pub mod writers;
+#[cfg(feature = "zip")]
+pub mod zip;
+#[cfg(feature = "std")]
+pub use builder::DTEDBuilder;
+#[cfg(feature = "std")]
+pub use dataset::DTEDDataset;
+pub use dted::{DTEDData, DTEDLevel, DTEDMetadata, Post};
+pub use options::{ParseDiagnostic, ParseOptions, ParseProgress};
+pub use source::ElevationSource;
+
+/// Alias of [Error], for glob imports alongside other crates' errors
+pub use Error as DTEDError;
+
+/// The items of the standard prelude from `alloc`, for `no_std` builds
+#[cfg(not(feature = "std"))]
+mod prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
+#[cfg(not(feature = "std"))]
+use prelude::*;
 
-#[derive(Debug)]
-/// DTED parsing error
+#[derive(Debug, thiserror::Error)]
+/// DTED error
+///
+/// Parsing errors carry an [ErrorContext] locating them within the file,
+/// when it is known
 ///
 /// * Io - IO error
-/// * ParseError - parsing error
+/// * InvalidSentinel - a record does not start with its recognition sentinel
+/// * TruncatedFile - the file ends before all of its records
+/// * BadChecksum - the checksum of a data record does not match its contents
+/// * ParseError - any other parsing error (e.g. a malformed field)
+/// * InvalidInput - invalid input provided to construct DTED data
 pub enum Error {
-    Io(io::Error),
-    ParseError(String),
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid recognition sentinel{}", fmt_context(.context))]
+    InvalidSentinel { context: Option<ErrorContext> },
+    #[error("truncated file{}{}", match .needed { Some(n) => format!(", {} more bytes needed", n), None => String::new() }, fmt_context(.context))]
+    TruncatedFile {
+        needed: Option<usize>,
+        context: Option<ErrorContext>,
+    },
+    #[error("bad checksum for data record {record}: stored {stored:#x}, computed {computed:#x}")]
+    BadChecksum {
+        record: usize,
+        stored: u32,
+        computed: u32,
+    },
+    #[error("parsing error: {message}{}", fmt_context(.context))]
+    ParseError {
+        message: String,
+        context: Option<ErrorContext>,
+    },
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
 }
-impl From<io::Error> for Error {
-    fn from(err: io::Error) -> Error {
-        Error::Io(err)
+impl Error {
+    /// Convert a nom error, locating it within the parsed input
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - nom error
+    /// * `input` - input given to the parser
+    /// * `offset` - offset of the input within the file
+    /// * `lat_count` - number of latitude points per data record, if known
+    ///
+    /// # Returns
+    ///
+    /// * [Error] with its [ErrorContext]
+    pub(crate) fn locate(
+        err: nom::Err<nom::error::Error<&[u8]>>,
+        input: &[u8],
+        offset: usize,
+        lat_count: Option<u16>,
+    ) -> Error {
+        let (remaining, mut err) = match err {
+            nom::Err::Incomplete(needed) => (&input[input.len()..], Error::from(needed)),
+            nom::Err::Error(e) | nom::Err::Failure(e) => (e.input, Error::from(e.code)),
+        };
+        let offset = offset + input.len() - remaining.len();
+        err.set_context(ErrorContext::new(offset, remaining, lat_count));
+        err
+    }
+
+    /// Set the [ErrorContext] of a parsing error (no-op for other errors)
+    pub(crate) fn set_context(&mut self, ctx: ErrorContext) {
+        match self {
+            Error::InvalidSentinel { context }
+            | Error::TruncatedFile { context, .. }
+            | Error::ParseError { context, .. } => *context = Some(ctx),
+            _ => {}
+        }
+    }
+
+    /// Get the [ErrorContext] of a parsing error
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::InvalidSentinel { context }
+            | Error::TruncatedFile { context, .. }
+            | Error::ParseError { context, .. } => context.as_ref(),
+            _ => None,
+        }
     }
 }
 impl From<nom::Needed> for Error {
     fn from(err: nom::Needed) -> Error {
-        Error::ParseError(format!("More data needed: {:?}", err))
+        Error::TruncatedFile {
+            needed: match err {
+                nom::Needed::Size(n) => Some(n.get()),
+                nom::Needed::Unknown => None,
+            },
+            context: None,
+        }
     }
 }
 impl From<nom::error::ErrorKind> for Error {
     fn from(err: nom::error::ErrorKind) -> Error {
-        Error::ParseError(format!("Parsing error:{:?}", err))
+        match err {
+            nom::error::ErrorKind::Tag => Error::InvalidSentinel { context: None },
+            nom::error::ErrorKind::Eof => Error::TruncatedFile {
+                needed: None,
+                context: None,
+            },
+            err => Error::ParseError {
+                message: err.description().to_string(),
+                context: None,
+            },
+        }
+    }
+}
+impl From<nom::Err<nom::error::Error<&[u8]>>> for Error {
+    fn from(err: nom::Err<nom::error::Error<&[u8]>>) -> Error {
+        match err {
+            nom::Err::Incomplete(e) => e.into(),
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.code.into(),
+        }
+    }
+}
+
+/// Number of offending bytes kept in an [ErrorContext]
+const ERROR_CONTEXT_BYTES: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Location of a parsing error within a DTED file
+///
+/// # Fields
+///
+/// * `section` - [Section] being parsed
+/// * `offset` - byte offset of the error within the file
+/// * `bytes` - the (first few) offending bytes
+pub struct ErrorContext {
+    pub section: Section,
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+impl ErrorContext {
+    /// Create the context of an error
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - byte offset of the error within the file
+    /// * `remaining` - input remaining from the error onwards
+    /// * `lat_count` - number of latitude points per data record, if known
+    pub(crate) fn new(offset: usize, remaining: &[u8], lat_count: Option<u16>) -> ErrorContext {
+        ErrorContext {
+            section: Section::at(offset, lat_count),
+            offset,
+            bytes: remaining[..remaining.len().min(ERROR_CONTEXT_BYTES)].to_vec(),
+        }
+    }
+}
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "in {} at byte offset {}", self.section, self.offset)?;
+        match self.bytes.is_empty() {
+            true => write!(f, " (end of file)"),
+            false => write!(f, " (bytes: {:02X?})", self.bytes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Section of a DTED file
+///
+/// * Uhl - User Header Label
+/// * Dsi - Data Set Identification record
+/// * Acc - Accuracy Description record
+/// * Record - data record (by index, from west to east), or the
+///   data records if the record length is unknown
+pub enum Section {
+    Uhl,
+    Dsi,
+    Acc,
+    Record(Option<usize>),
+}
+impl Section {
+    /// Get the section at a byte offset within a DTED file
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - byte offset within the file
+    /// * `lat_count` - number of latitude points per data record, if known
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::Section;
+    /// assert_eq!(Section::at(10, None), Section::Uhl);
+    /// assert_eq!(Section::at(3427, None), Section::Acc);
+    /// assert_eq!(Section::at(3428, None), Section::Record(None));
+    /// assert_eq!(Section::at(3428 + 254, Some(121)), Section::Record(Some(1)));
+    /// ```
+    pub fn at(offset: usize, lat_count: Option<u16>) -> Section {
+        let dsi_offset = dted::DTED_UHL_LENGTH as usize;
+        let acc_offset = dsi_offset + dted::DTED_DSI_RECORD_LENGTH;
+        match offset {
+            o if o < dsi_offset => Section::Uhl,
+            o if o < acc_offset => Section::Dsi,
+            o if o < dted::DTED_DATA_OFFSET => Section::Acc,
+            o => Section::Record(lat_count.map(|lat_count| {
+                (o - dted::DTED_DATA_OFFSET) / dted::dted_record_length(lat_count)
+            })),
+        }
+    }
+}
+impl std::fmt::Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Section::Uhl => write!(f, "UHL"),
+            Section::Dsi => write!(f, "DSI record"),
+            Section::Acc => write!(f, "ACC record"),
+            Section::Record(Some(record)) => write!(f, "data record {}", record),
+            Section::Record(None) => write!(f, "data records"),
+        }
+    }
+}
+
+/// Format an optional [ErrorContext], for [Error] messages
+fn fmt_context(context: &Option<ErrorContext>) -> String {
+    match context {
+        Some(context) => format!(" {}", context),
+        None => String::new(),
     }
 }
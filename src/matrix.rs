@@ -0,0 +1,78 @@
+//! Contains the [nalgebra] matrices of the elevation grid (requires the
+//! `nalgebra` feature), e.g. for terrain correlation.
+//!
+//! The matrices have a row per latitude point (from south to north) and a
+//! column per longitude line (from west to east), the element `(lat_idx,
+//! lon_idx)` being a post. As both [nalgebra] and the [RawDTEDRecords] are
+//! column-major, the views borrow the records without copying.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use nalgebra::{DMatrix, DMatrixView, DMatrixViewMut};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDData, RawDTEDRecords, DTED_VOID_ELEVATION};
+
+impl RawDTEDRecords {
+    /// View the elevations as a matrix, the element `(lat_idx, lon_idx)` being a post
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevations (in meters), void posts included
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let view = data.data.as_matrix();
+    /// assert_eq!(view.shape(), (121, 121));
+    /// assert_eq!(Some(view[(5, 3)]), data.data.get(5, 3));
+    /// ```
+    pub fn as_matrix(&self) -> DMatrixView<'_, i16> {
+        let (rows, cols) = (self.line_len, self.len());
+        DMatrixView::from_slice(&self.elevations[..rows * cols], rows, cols)
+    }
+
+    /// View the elevations as a mutable matrix, the element `(lat_idx, lon_idx)`
+    /// being a post
+    ///
+    /// The checksums of the records are not updated,
+    /// see [RawDTEDRecords::recompute_checksums]
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevations (in meters), void posts included
+    pub fn as_matrix_mut(&mut self) -> DMatrixViewMut<'_, i16> {
+        let (rows, cols) = (self.line_len, self.len());
+        DMatrixViewMut::from_slice(&mut self.elevations[..rows * cols], rows, cols)
+    }
+}
+
+impl DTEDData {
+    /// Get the elevations as a matrix of floats, the element `(lat_idx, lon_idx)`
+    /// being a post
+    ///
+    /// # Returns
+    ///
+    /// * Elevations (in meters), with void posts as NaN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let matrix = data.to_dmatrix();
+    /// assert_eq!(matrix.shape(), (121, 121));
+    /// assert_eq!(matrix[(5, 3)], data.data.get(5, 3).unwrap() as f64);
+    /// ```
+    pub fn to_dmatrix(&self) -> DMatrix<f64> {
+        self.data.as_matrix().map(|elev| match elev {
+            DTED_VOID_ELEVATION => f64::NAN,
+            elev => elev as f64,
+        })
+    }
+}
@@ -0,0 +1,308 @@
+//! Contains the triangulation of the posts into a 3D [Mesh], written as
+//! Wavefront OBJ or binary glTF 2.0 (`.glb`) for Blender, game engines and
+//! other 3D viewers.
+//!
+//! Vertices are in meters from the origin of the cell (at zero elevation),
+//! the angular offsets converted to meters as in [crate::terrain], in the
+//! `+Y` up convention of glTF (and of the default Blender OBJ import): `x`
+//! east, `y` up and `z` south. Triangles face up (counter-clockwise seen from
+//! above), and are left out around void posts.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::geodesy::EARTH_RADIUS_M;
+use crate::source::ElevationSource;
+use crate::Error;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Magic of a binary glTF file (`glTF`)
+const GLB_MAGIC: u32 = 0x4654_6c67;
+/// Version of glTF
+const GLB_VERSION: u32 = 2;
+/// Type of the JSON chunk of a binary glTF file (`JSON`)
+const GLB_JSON_CHUNK: u32 = 0x4e4f_534a;
+/// Type of the binary chunk of a binary glTF file (`BIN\0`)
+const GLB_BIN_CHUNK: u32 = 0x004e_4942;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Triangle mesh of the elevations
+///
+/// # Fields
+///
+/// * `vertices` - `[x, y, z]` positions in meters, `x` east, `y` up and `z`
+///   south of the origin of the cell
+/// * `triangles` - indices of the vertices of each triangle,
+///   counter-clockwise seen from above
+pub struct Mesh {
+    pub vertices: Vec<[f64; 3]>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// Triangulate the posts, two triangles per square between four posts
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `step` - decimation, keeping every `step` post along each axis (as
+///   well as the last ones), 1 to keep every post
+///
+/// # Returns
+///
+/// * [Mesh] of the posts, without the triangles of void posts
+///
+/// # Examples
+///
+/// ```
+/// use dted2::mesh;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+/// let full = mesh::mesh(&data, 1);
+/// let decimated = mesh::mesh(&data, 10);
+/// let count = data.metadata.count;
+/// assert!(full.vertices.len() <= count.lat as usize * count.lon as usize);
+/// assert!(decimated.vertices.len() < full.vertices.len() / 50);
+/// // the south west post, at the origin
+/// assert_eq!(full.vertices[0][0], 0.0);
+/// ```
+pub fn mesh<S: ElevationSource>(source: &S, step: usize) -> Mesh {
+    let metadata = source.metadata();
+    let (lat_count, lon_count) = (metadata.count.lat as usize, metadata.count.lon as usize);
+    let indices = |count: usize| -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..count).step_by(step.max(1)).collect();
+        if count > 0 && indices.last() != Some(&(count - 1)) {
+            indices.push(count - 1);
+        }
+        indices
+    };
+    let (lat_indices, lon_indices) = (indices(lat_count), indices(lon_count));
+    // --------------------------------------------------
+    // vertices of the non-void posts, numbered by grid position
+    // --------------------------------------------------
+    let dy = metadata.interval.lat.to_radians() * EARTH_RADIUS_M;
+    let dx = metadata.interval.lon.to_radians() * EARTH_RADIUS_M;
+    let mut mesh = Mesh::default();
+    let mut grid = vec![None; lat_indices.len() * lon_indices.len()];
+    for (col, lon_idx) in lon_indices.iter().enumerate() {
+        for (row, lat_idx) in lat_indices.iter().enumerate() {
+            let Some(elev) = source.elevation(*lat_idx, *lon_idx) else {
+                continue;
+            };
+            let lat = metadata.origin.lat + *lat_idx as f64 * metadata.interval.lat;
+            grid[col * lat_indices.len() + row] = Some(mesh.vertices.len() as u32);
+            mesh.vertices.push([
+                *lon_idx as f64 * dx * lat.to_radians().cos(),
+                f64::from(elev),
+                -(*lat_idx as f64) * dy,
+            ]);
+        }
+    }
+    // --------------------------------------------------
+    // triangles of each square, from its south west corner
+    // --------------------------------------------------
+    let vertex = |row: usize, col: usize| grid[col * lat_indices.len() + row];
+    for col in 0..lon_indices.len().saturating_sub(1) {
+        for row in 0..lat_indices.len().saturating_sub(1) {
+            let (sw, se) = (vertex(row, col), vertex(row, col + 1));
+            let (nw, ne) = (vertex(row + 1, col), vertex(row + 1, col + 1));
+            for triangle in [[sw, se, ne], [sw, ne, nw]] {
+                if let [Some(a), Some(b), Some(c)] = triangle {
+                    mesh.triangles.push([a, b, c]);
+                }
+            }
+        }
+    }
+    mesh
+}
+
+impl Mesh {
+    /// Write the mesh as Wavefront OBJ
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - writer of the OBJ file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an [Error::Io] if the mesh could not be written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::mesh::Mesh;
+    ///
+    /// let mesh = Mesh {
+    ///     vertices: vec![[0.0, 1.0, 0.0], [10.0, 2.0, 0.0], [10.0, 3.0, -10.0]],
+    ///     triangles: vec![[0, 1, 2]],
+    /// };
+    /// let mut obj = Vec::new();
+    /// mesh.write_obj_to(&mut obj).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(obj).unwrap(),
+    ///     "v 0 1 0\nv 10 2 0\nv 10 3 -10\nf 1 2 3\n"
+    /// );
+    /// ```
+    pub fn write_obj_to<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut writer = BufWriter::new(writer);
+        for [x, y, z] in &self.vertices {
+            writeln!(writer, "v {} {} {}", x, y, z)?;
+        }
+        // OBJ indices start at 1
+        for [a, b, c] in &self.triangles {
+            writeln!(writer, "f {} {} {}", a + 1, b + 1, c + 1)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the mesh as a Wavefront OBJ file, see [Mesh::write_obj_to]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the `.obj` file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an [Error::Io] if the file could not be written
+    pub fn write_obj(&self, path: &str) -> Result<(), Error> {
+        self.write_obj_to(&mut File::create(path)?)
+    }
+
+    /// Encode the mesh as binary glTF 2.0, with the positions as 32-bit
+    /// floats and the indices as 32-bit integers in a single buffer
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the `.glb` file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::mesh::Mesh;
+    ///
+    /// let mesh = Mesh {
+    ///     vertices: vec![[0.0, 1.0, 0.0], [10.0, 2.0, 0.0], [10.0, 3.0, -10.0]],
+    ///     triangles: vec![[0, 1, 2]],
+    /// };
+    /// let glb = mesh.to_glb();
+    /// assert_eq!(glb[..4], *b"glTF");
+    /// assert_eq!(glb.len() % 4, 0);
+    /// ```
+    pub fn to_glb(&self) -> Vec<u8> {
+        // --------------------------------------------------
+        // binary buffer, positions then indices
+        // --------------------------------------------------
+        let mut buffer: Vec<u8> = self
+            .vertices
+            .iter()
+            .flatten()
+            .flat_map(|value| (*value as f32).to_le_bytes())
+            .collect();
+        let positions_len = buffer.len();
+        buffer.extend(
+            self.triangles
+                .iter()
+                .flatten()
+                .flat_map(|idx| idx.to_le_bytes()),
+        );
+        let indices_len = buffer.len() - positions_len;
+        // --------------------------------------------------
+        // JSON, the positions requiring their bounds
+        // --------------------------------------------------
+        let bound = |fold: fn(f32, f32) -> f32, init: f32| {
+            let bound = self.vertices.iter().fold([init; 3], |acc, vertex| {
+                [0, 1, 2].map(|axis| fold(acc[axis], vertex[axis] as f32))
+            });
+            match self.vertices.is_empty() {
+                true => [0.0; 3],
+                false => bound,
+            }
+        };
+        let (min, max) = (
+            bound(f32::min, f32::INFINITY),
+            bound(f32::max, f32::NEG_INFINITY),
+        );
+        let json = format!(
+            concat!(
+                r#"{{"asset":{{"version":"2.0","generator":"dted2"}},"#,
+                r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+                r#""meshes":[{{"primitives":[{{"attributes":{{"POSITION":0}},"indices":1}}]}}],"#,
+                r#""buffers":[{{"byteLength":{}}}],"#,
+                r#""bufferViews":[{{"buffer":0,"byteOffset":0,"byteLength":{},"target":34962}},"#,
+                r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":34963}}],"#,
+                r#""accessors":[{{"bufferView":0,"componentType":5126,"count":{},"type":"VEC3","#,
+                r#""min":[{},{},{}],"max":[{},{},{}]}},"#,
+                r#"{{"bufferView":1,"componentType":5125,"count":{},"type":"SCALAR"}}]}}"#
+            ),
+            buffer.len(),
+            positions_len,
+            positions_len,
+            indices_len,
+            self.vertices.len(),
+            min[0],
+            min[1],
+            min[2],
+            max[0],
+            max[1],
+            max[2],
+            self.triangles.len() * 3,
+        );
+        // --------------------------------------------------
+        // chunks, padded to 4 bytes (the JSON with spaces)
+        // --------------------------------------------------
+        let mut json = json.into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        buffer.resize(buffer.len().next_multiple_of(4), 0);
+        let total = 12 + 8 + json.len() + 8 + buffer.len();
+        let mut glb = Vec::with_capacity(total);
+        for value in [GLB_MAGIC, GLB_VERSION, total as u32] {
+            glb.extend(value.to_le_bytes());
+        }
+        for (chunk_type, chunk) in [(GLB_JSON_CHUNK, json), (GLB_BIN_CHUNK, buffer)] {
+            glb.extend((chunk.len() as u32).to_le_bytes());
+            glb.extend(chunk_type.to_le_bytes());
+            glb.extend(chunk);
+        }
+        glb
+    }
+
+    /// Write the mesh as a binary glTF file, see [Mesh::to_glb]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the `.glb` file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an [Error::Io] if the file could not be written
+    pub fn write_glb(&self, path: &str) -> Result<(), Error> {
+        std::fs::write(path, self.to_glb())?;
+        Ok(())
+    }
+}
+
+impl DTEDData {
+    /// Triangulate the posts, see [mesh]
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - decimation, keeping every `step` post along each axis, 1 to
+    ///   keep every post
+    ///
+    /// # Returns
+    ///
+    /// * [Mesh] of the posts, without the triangles of void posts
+    pub fn mesh(&self, step: usize) -> Mesh {
+        mesh(self, step)
+    }
+}
@@ -0,0 +1,230 @@
+//! Contains [MmapDTEDData], a memory-mapped DTED file backend (requires the `mmap` feature).
+//!
+//! Only the header records are parsed when opening a file, with the
+//! elevations read straight out of the mapping on demand, so opening a
+//! file is cheap regardless of its size.
+//!
+//! For read-mostly workloads over huge files, [MmapDTEDData::columns] views
+//! the elevations of each data record in place as [RecordElevations] (the
+//! bytes reinterpreted as `&[u16]` with [bytemuck]), decoded on access.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use memmap2::Mmap;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::borrowed::RawDTEDFileRef;
+use crate::dted::*;
+use crate::parsers;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+/// Memory-mapped DTED Data
+///
+/// The elevations are decoded from the mapped file on each query,
+/// see [ElevationSource] for the available queries.
+///
+/// # Fields
+///
+/// * `metadata` - [DTEDMetadata], including the DSI and ACC records
+pub struct MmapDTEDData {
+    pub metadata: DTEDMetadata,
+    mmap: Mmap,
+    record_len: usize,
+}
+impl MmapDTEDData {
+    /// Open and memory-map a DTED file
+    ///
+    /// The header records are parsed, and the data records are validated
+    /// to be complete, but no elevation is decoded
+    ///
+    /// The file must not be modified while it is mapped, which would
+    /// otherwise change (or invalidate) the elevations read from it
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [MmapDTEDData]: memory-mapped DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// use dted2::mmap::MmapDTEDData;
+    ///
+    /// let mapped = MmapDTEDData::open("tests/test_data.dt2").unwrap();
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert_eq!(mapped.metadata.count, data.metadata.count);
+    /// assert_eq!(mapped.elevation_at(42.52, 15.75), data.elevation_at(42.52, 15.75));
+    /// assert!(mapped.elevation_at(0.0, 0.0).is_none());
+    /// ```
+    pub fn open(path: &str) -> Result<MmapDTEDData, DTEDError> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the mapping is read-only, and the file is documented
+        // to not be modified while mapped
+        let mmap = unsafe { Mmap::map(&file)? };
+        let (_, (header, dsi_record, acc_record)) = parsers::dted_headers_parser(&mmap)
+            .map_err(|e| DTEDError::locate(e, &mmap, 0, None))?;
+        // --------------------------------------------------
+        // validate the data records are all there
+        // --------------------------------------------------
+        let record_len = validate_data_records(&mmap, &header.count)?;
+        Ok(MmapDTEDData {
+            metadata: DTEDMetadata::from_records(&header, dsi_record, acc_record, path),
+            mmap,
+            record_len,
+        })
+    }
+
+    /// Borrow the whole mapped file, see [RawDTEDFileRef]
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFileRef], borrowing the mapping
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::mmap::MmapDTEDData;
+    ///
+    /// let mapped = MmapDTEDData::open("tests/test_data.dt2").unwrap();
+    /// let file = mapped.file_ref().unwrap();
+    /// assert_eq!(file.header.unique_ref, "F02 045");
+    /// assert!(file.records().all(|record| record.is_checksum_valid()));
+    /// ```
+    pub fn file_ref(&self) -> Result<RawDTEDFileRef<'_>, DTEDError> {
+        RawDTEDFileRef::from_bytes(&self.mmap)
+    }
+
+    /// Get the raw bytes of a data record, as stored in the file
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the data record (sentinel through checksum), or None if out of bounds
+    pub fn record_bytes(&self, lon_idx: usize) -> Option<&[u8]> {
+        if lon_idx >= self.metadata.count.lon as usize {
+            return None;
+        }
+        let start = DTED_DATA_OFFSET + lon_idx * self.record_len;
+        Some(&self.mmap[start..start + self.record_len])
+    }
+
+    /// View the elevations of a data record in place, without copying
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [RecordElevations], or None if out of bounds (or if the elevations
+    ///   are not aligned in memory, which they always are in a mapped file)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// use dted2::mmap::MmapDTEDData;
+    ///
+    /// let mapped = MmapDTEDData::open("tests/test_data_negative.dt2").unwrap();
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let column = mapped.column(3).unwrap();
+    /// assert_eq!(column.len(), 121);
+    /// assert_eq!(column.get(5), data.data.get(5, 3));
+    /// assert!(column.iter().eq(data.data.column(3).unwrap().iter().copied()));
+    /// assert!(mapped.column(121).is_none());
+    /// ```
+    pub fn column(&self, lon_idx: usize) -> Option<RecordElevations<'_>> {
+        let len = self.metadata.count.lat as usize * 2;
+        let bytes = &self.record_bytes(lon_idx)?[DTED_RECORD_HEADER_LENGTH..][..len];
+        bytemuck::try_cast_slice(bytes)
+            .ok()
+            .map(|words| RecordElevations { words })
+    }
+
+    /// Iterate over the elevations of every data record in place, from west
+    /// to east, see [MmapDTEDData::column]
+    pub fn columns(&self) -> impl Iterator<Item = RecordElevations<'_>> {
+        (0..self.metadata.count.lon as usize).filter_map(|lon_idx| self.column(lon_idx))
+    }
+}
+
+/// Elevations of a data record, viewed in place in the bytes of the file
+///
+/// The elevations are kept as stored, big-endian signed magnitude words
+/// (see [RecordElevations::words]), and decoded on access.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordElevations<'a> {
+    words: &'a [u16],
+}
+impl<'a> RecordElevations<'a> {
+    /// Get the number of elevations (latitude points)
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether there are no elevations
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Get the raw elevation of a post, void posts included
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation (in meters), or None if out of bounds
+    pub fn get(&self, lat_idx: usize) -> Option<i16> {
+        self.words.get(lat_idx).map(|word| decode(*word))
+    }
+
+    /// Get a [Post], telling void posts apart from elevations
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    ///
+    /// # Returns
+    ///
+    /// * [Post] or None if out of bounds
+    pub fn post(&self, lat_idx: usize) -> Option<Post> {
+        self.get(lat_idx).map(Post::from_raw)
+    }
+
+    /// Iterate over the raw elevations, from south to north
+    pub fn iter(&self) -> impl Iterator<Item = i16> + 'a {
+        self.words.iter().map(|word| decode(*word))
+    }
+
+    /// Get the words as stored, in big-endian signed magnitude, see [parsers::to_i16]
+    pub fn words(&self) -> &'a [u16] {
+        self.words
+    }
+}
+
+/// Decode a word, as stored in the file
+fn decode(word: u16) -> i16 {
+    parsers::to_i16(u16::from_be(word))
+}
+impl ElevationSource for MmapDTEDData {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.column(lon_idx)?.get(lat_idx)
+    }
+}
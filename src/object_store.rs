@@ -0,0 +1,207 @@
+//! Contains the reading of DTED cells from object storage (requires the
+//! `object_store` feature): S3, GCS, Azure, or any other
+//! [ObjectStore](object_store::ObjectStore) implementation, into a
+//! [DTEDDataset] of [ObjectStoreDTEDCell]s rooted at a prefix
+//! ([DTEDDataset::from_object_store]).
+//!
+//! The objects under the prefix named after their cells (see [cell_origin])
+//! are listed, and their header records fetched with range requests upfront,
+//! with an object only fetched on the first query of its cell (or when
+//! prefetched with [ObjectStoreDTEDCell::fetch]), its data records then being
+//! decoded on demand as by [LazyDTEDData].
+//!
+//! The object stores being asynchronous, the (synchronous) queries of a cell
+//! not fetched yet block on the tokio runtime the dataset was created in,
+//! from another thread. Querying from within a `current_thread` runtime
+//! would then never complete, the cells having to be fetched beforehand
+//! ([DTEDDataset::fetch_all]).
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use futures::TryStreamExt;
+use object_store::path::Path as ObjectPath;
+use object_store::ObjectStore;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use tokio::runtime::Handle;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dataset::{cell_origin, DTEDDataset};
+use crate::dted::{DTEDMetadata, DTED_DATA_OFFSET};
+use crate::lazy::LazyDTEDData;
+use crate::parsers;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+/// DTED cell in object storage, whose header records are fetched upfront,
+/// with the object only fetched on the first query (or [ObjectStoreDTEDCell::fetch])
+///
+/// The metadata filename is the location of the object.
+/// Queries through [ElevationSource] report errors as None.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDData, DTEDDataset, ElevationSource};
+/// use object_store::memory::InMemory;
+/// use object_store::path::Path;
+/// use object_store::ObjectStore;
+/// use std::sync::Arc;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let store = Arc::new(InMemory::new());
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let location = Path::from("terrain/dted0/w178/s30.dt0");
+/// store.put(&location, content.into()).await.unwrap();
+///
+/// let dataset = DTEDDataset::from_object_store(store, Some(&Path::from("terrain")))
+///     .await
+///     .unwrap();
+/// let cell = &dataset.cells()[0];
+/// assert_eq!(cell.location(), &location);
+/// assert!(!cell.is_fetched());
+/// dataset.fetch_all().await.unwrap();
+/// assert!(cell.is_fetched());
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// assert_eq!(dataset.elevation_at(-29.5, -177.5), data.elevation_at(-29.5, -177.5));
+/// # });
+/// ```
+pub struct ObjectStoreDTEDCell {
+    metadata: DTEDMetadata,
+    store: Arc<dyn ObjectStore>,
+    location: ObjectPath,
+    runtime: Handle,
+    cell: OnceLock<Option<LazyDTEDData>>,
+}
+impl ObjectStoreDTEDCell {
+    /// Get the location of the object
+    pub fn location(&self) -> &ObjectPath {
+        &self.location
+    }
+
+    /// Whether the object was fetched (by a query or [ObjectStoreDTEDCell::fetch])
+    pub fn is_fetched(&self) -> bool {
+        self.cell.get().is_some()
+    }
+
+    /// Fetch the object, if not fetched yet, such that queries do not block
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an error if the object could not be fetched or parsed
+    pub async fn fetch(&self) -> Result<(), DTEDError> {
+        if !self.is_fetched() {
+            let content = fetch_object(self.store.as_ref(), &self.location).await?;
+            let cell = LazyDTEDData::from_content(content, &self.metadata.filename)?;
+            let _ = self.cell.set(Some(cell));
+        }
+        Ok(())
+    }
+
+    /// Get the fetched cell, fetching the object on first access
+    fn cell(&self) -> Option<&LazyDTEDData> {
+        self.cell
+            .get_or_init(|| {
+                let fetch = || {
+                    self.runtime
+                        .block_on(fetch_object(self.store.as_ref(), &self.location))
+                };
+                // off the current thread, which may be one of the runtime
+                let content = std::thread::scope(|scope| scope.spawn(fetch).join())
+                    .ok()?
+                    .ok()?;
+                LazyDTEDData::from_content(content, &self.metadata.filename).ok()
+            })
+            .as_ref()
+    }
+}
+impl ElevationSource for ObjectStoreDTEDCell {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.cell()?.raw_elevation(lat_idx, lon_idx)
+    }
+}
+
+impl DTEDDataset<ObjectStoreDTEDCell> {
+    /// List the DTED cells in object storage, fetching their header records
+    /// only, see [ObjectStoreDTEDCell]
+    ///
+    /// Objects are recognized by their names (see [cell_origin]), either
+    /// `n34_w119.dt2` or the classic `w119/n34.dt2` layout, others being ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - [ObjectStore], e.g. an S3 bucket
+    /// * `prefix` - prefix to list the objects under, or None for all of them
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the cells, sorted by location, or an error
+    ///   if not running within a tokio runtime
+    pub async fn from_object_store(
+        store: Arc<dyn ObjectStore>,
+        prefix: Option<&ObjectPath>,
+    ) -> Result<DTEDDataset<ObjectStoreDTEDCell>, DTEDError> {
+        let runtime = Handle::try_current().map_err(io::Error::other)?;
+        let mut locations: Vec<ObjectPath> = store
+            .list(prefix)
+            .map_ok(|object| object.location)
+            .try_filter(|location| {
+                std::future::ready(cell_origin(Path::new(location.as_ref())).is_some())
+            })
+            .try_collect()
+            .await
+            .map_err(io::Error::from)?;
+        locations.sort();
+        let mut cells = Vec::with_capacity(locations.len());
+        for location in locations {
+            let content = store
+                .get_range(&location, 0..DTED_DATA_OFFSET as u64)
+                .await
+                .map_err(io::Error::from)?;
+            let (_, (header, dsi_record, acc_record)) = parsers::dted_headers_parser(&content)
+                .map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+            cells.push(ObjectStoreDTEDCell {
+                metadata: DTEDMetadata::from_records(
+                    &header,
+                    dsi_record,
+                    acc_record,
+                    location.as_ref(),
+                ),
+                store: Arc::clone(&store),
+                location,
+                runtime: runtime.clone(),
+                cell: OnceLock::new(),
+            });
+        }
+        Ok(DTEDDataset::from_cells(cells))
+    }
+
+    /// Fetch all of the objects not fetched yet, concurrently,
+    /// see [ObjectStoreDTEDCell::fetch]
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or the first error fetching or parsing an object
+    pub async fn fetch_all(&self) -> Result<(), DTEDError> {
+        futures::future::try_join_all(self.cells().iter().map(ObjectStoreDTEDCell::fetch))
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Fetch the whole content of an object
+async fn fetch_object(
+    store: &dyn ObjectStore,
+    location: &ObjectPath,
+) -> Result<Vec<u8>, DTEDError> {
+    let object = store.get(location).await.map_err(io::Error::from)?;
+    Ok(object.bytes().await.map_err(io::Error::from)?.to_vec())
+}
@@ -0,0 +1,211 @@
+//! Contains the [ParseOptions] controlling how strictly DTED files are parsed,
+//! and the [ParseDiagnostic]s describing what was skipped when recovering.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::{Error as DTEDError, Section};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options controlling how strictly DTED files are parsed,
+/// see [crate::DTEDData::read_with]
+///
+/// The [Default] options are strict, as per the spec.
+///
+/// # Fields
+///
+/// * `verify_checksums` - whether to reject data records whose checksum does not
+///   match their contents
+/// * `require_records` - whether to require the DSI and ACC records. If not required,
+///   a missing record is skipped over, as is a malformed one (starting with its
+///   sentinel, the record length being fixed)
+/// * `tolerate_truncated` - whether to accept a file ending before all of its data
+///   records, the missing (or incomplete) records being void
+/// * `max_records` - maximum number of data records (longitude lines) declared in the
+///   UHL, to guard against allocating for absurd headers
+/// * `on_progress` - callback reporting the progress of parsing, see [ParseOptions::on_progress]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDData, ParseOptions};
+///
+/// let options = ParseOptions {
+///     verify_checksums: false,
+///     ..Default::default()
+/// };
+/// assert!(DTEDData::read_with("tests/test_data.dt2", &options).is_ok());
+/// ```
+pub struct ParseOptions {
+    pub verify_checksums: bool,
+    pub require_records: bool,
+    pub tolerate_truncated: bool,
+    pub max_records: Option<usize>,
+    pub on_progress: Option<ProgressCallback>,
+}
+impl ParseOptions {
+    /// Strict options, as per the spec (same as [Default])
+    pub fn strict() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// Lenient options, accepting whatever can be salvaged from a file:
+    /// unverified checksums, missing DSI / ACC records and truncated files
+    pub fn lenient() -> ParseOptions {
+        ParseOptions {
+            verify_checksums: false,
+            require_records: false,
+            tolerate_truncated: true,
+            max_records: None,
+            on_progress: None,
+        }
+    }
+
+    /// Report the progress of parsing the data records, e.g. to show a progress bar
+    ///
+    /// The callback is invoked every `every` data records parsed, and once all
+    /// of them are, with the [ParseProgress] so far
+    ///
+    /// # Arguments
+    ///
+    /// * `every` - number of data records between invocations (at least 1)
+    /// * `callback` - callback, given the [ParseProgress]
+    ///
+    /// # Returns
+    ///
+    /// * The options, with the callback
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ParseOptions};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let reports = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = reports.clone();
+    /// let options = ParseOptions::default()
+    ///     .on_progress(50, move |progress| sink.lock().unwrap().push(progress.records));
+    /// DTEDData::read_with("tests/test_data_negative.dt2", &options).unwrap();
+    /// assert_eq!(*reports.lock().unwrap(), [50, 100, 121]);
+    /// ```
+    pub fn on_progress(
+        self,
+        every: usize,
+        callback: impl Fn(&ParseProgress) + Send + Sync + 'static,
+    ) -> ParseOptions {
+        ParseOptions {
+            on_progress: Some(ProgressCallback {
+                every: every.max(1),
+                callback: Arc::new(callback),
+            }),
+            ..self
+        }
+    }
+}
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            verify_checksums: true,
+            require_records: true,
+            tolerate_truncated: false,
+            max_records: None,
+            on_progress: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Callback reporting the progress of parsing, see [ParseOptions::on_progress]
+///
+/// Callbacks are compared by identity.
+pub struct ProgressCallback {
+    every: usize,
+    callback: Arc<dyn Fn(&ParseProgress) + Send + Sync>,
+}
+impl ProgressCallback {
+    /// Get the number of data records between invocations
+    pub fn every(&self) -> usize {
+        self.every
+    }
+
+    /// Invoke the callback
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - [ParseProgress] so far
+    pub fn report(&self, progress: &ParseProgress) {
+        (self.callback)(progress)
+    }
+}
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressCallback")
+            .field("every", &self.every)
+            .finish_non_exhaustive()
+    }
+}
+impl PartialEq for ProgressCallback {
+    fn eq(&self, other: &ProgressCallback) -> bool {
+        self.every == other.every && Arc::ptr_eq(&self.callback, &other.callback)
+    }
+}
+impl Eq for ProgressCallback {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Progress of parsing a DTED file, see [ParseOptions::on_progress]
+///
+/// # Fields
+///
+/// * `records` - number of data records parsed
+/// * `total_records` - number of data records to parse
+/// * `bytes` - number of bytes of the file parsed, the header records included
+/// * `total_bytes` - number of bytes of the file
+pub struct ParseProgress {
+    pub records: usize,
+    pub total_records: usize,
+    pub bytes: usize,
+    pub total_bytes: usize,
+}
+impl ParseProgress {
+    /// Get the fraction of the data records parsed, between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        match self.total_records {
+            0 => 1.0,
+            total => self.records as f64 / total as f64,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Diagnostic of a section skipped while parsing, see
+/// [crate::DTEDData::read_recovering]
+///
+/// A skipped DSI or ACC record is None, while a skipped data record is void
+///
+/// # Fields
+///
+/// * `section` - [Section] which was skipped
+/// * `error` - error the section was skipped for
+pub struct ParseDiagnostic {
+    pub section: Section,
+    pub error: DTEDError,
+}
+impl ParseDiagnostic {
+    /// Create a diagnostic
+    pub(crate) fn new(section: Section, error: DTEDError) -> ParseDiagnostic {
+        ParseDiagnostic { section, error }
+    }
+}
+impl std::fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "skipped {}: {}", self.section, self.error)
+    }
+}
@@ -4,21 +4,29 @@
 // --------------------------------------------------
 // external
 // --------------------------------------------------
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
-    combinator::{map, map_res, opt},
+    combinator::{map, map_res, opt, verify},
     multi::count,
-    number::complete::be_u16,
     sequence::{preceded, tuple},
     IResult,
 };
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 use num_traits::{int::PrimInt, Unsigned};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 // --------------------------------------------------
 // local
 // --------------------------------------------------
+use crate::borrowed::RawDTEDHeaderRef;
 use crate::dted::*;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 use crate::primitives::{Angle, AxisElement};
 
 // --------------------------------------------------
@@ -37,22 +45,29 @@ const U16_DATA_MSK: u16 = 0x7FFF;
 ///
 /// # Returns
 ///
-/// An option containing an unsigned integer
+/// An option containing an unsigned integer, or [Option::None] if a byte
+/// is not an ASCII digit or the integer overflows
 ///
 /// # Examples
 ///
 /// ```
 /// use dted2::parsers::to_uint;
 /// assert_eq!(to_uint::<u32>(b"123"), Some(123 as u32));
+/// assert_eq!(to_uint::<u32>(b"1 3"), None);
+/// assert_eq!(to_uint::<u8>(b"256"), None);
 /// ```
 pub fn to_uint<U>(input: &[u8]) -> Option<U>
 where
     U: PrimInt + Unsigned,
 {
-    U::from(input.iter().fold(0_u32, |acc, b| {
-        // assert!(*b >= 0x30 && *b <= 0x39); // is a digit
-        (acc * 10) + (*b - 0x30) as u32
-    }))
+    U::from(
+        input
+            .iter()
+            .try_fold(0_u32, |acc, b| match b.is_ascii_digit() {
+                true => acc.checked_mul(10)?.checked_add((b - b'0') as u32),
+                false => None,
+            })?,
+    )
 }
 
 /// Nom parser that parses `count` number of bytes and returns an unsigned integer
@@ -71,15 +86,21 @@ where
 /// ```
 /// use dted2::parsers::uint_parser;
 /// assert_eq!(uint_parser::<u32>(3)(b"123"), Ok((&b""[..], 123 as u32)));
+/// assert!(uint_parser::<u32>(3)(b"1-3").is_err());
 /// ```
 pub fn uint_parser<U>(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], U>
 where
     U: PrimInt + Unsigned,
 {
     move |input| {
-        map_res(take(count), |bytes: &[u8]| {
-            to_uint::<U>(bytes).ok_or(nom::error::Error::new(input, nom::error::ErrorKind::Digit))
-        })(input)
+        let (rest, bytes) = take(count)(input)?;
+        match to_uint::<U>(bytes) {
+            Some(x) => Ok((rest, x)),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Digit,
+            ))),
+        }
     }
 }
 
@@ -113,6 +134,129 @@ where
     }
 }
 
+/// Nom parser that parses `count` number of bytes and returns an unsigned integer
+/// If all `count` bytes are blank (ASCII spaces), [Option::None] is returned
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// An [Option] containing an unsigned integer, or [Option::None] if the field
+/// is blank
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::opt_uint_parser;
+/// assert_eq!(opt_uint_parser::<u32>(3)(b"123"), Ok((&b""[..], Some(123 as u32))));
+/// assert_eq!(opt_uint_parser::<u32>(3)(b"   4"), Ok((&b"4"[..], None)));
+/// ```
+pub fn opt_uint_parser<U>(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<U>>
+where
+    U: PrimInt + Unsigned,
+{
+    move |input| {
+        let (rest, bytes) = take(count)(input)?;
+        if bytes.iter().all(|b| *b == b' ') {
+            return Ok((rest, None));
+        }
+        uint_parser::<U>(count)(input).map(|(input, x)| (input, Some(x)))
+    }
+}
+
+/// Nom parser that parses `count` number of bytes as ASCII text,
+/// with the space padding removed
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// A [String] with leading and trailing whitespace removed
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::text_parser;
+/// assert_eq!(text_parser(6)(b"WGS84 MSL"), Ok((&b"MSL"[..], "WGS84".to_string())));
+/// ```
+pub fn text_parser(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], String> {
+    move |input| map(text_ref_parser(count), Cow::into_owned)(input)
+}
+
+/// Nom parser that parses `count` number of bytes as ASCII text,
+/// with the space padding removed, borrowing it from the input
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// The text with leading and trailing whitespace removed, borrowed unless it
+/// is not valid UTF-8 (the invalid bytes being replaced, as with [text_parser])
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use dted2::parsers::text_ref_parser;
+/// assert_eq!(text_ref_parser(6)(b"WGS84 MSL"), Ok((&b"MSL"[..], Cow::Borrowed("WGS84"))));
+/// ```
+pub fn text_ref_parser<'a>(count: usize) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], Cow<'a, str>> {
+    move |input| {
+        map(take(count), |bytes: &[u8]| {
+            match String::from_utf8_lossy(bytes) {
+                Cow::Borrowed(text) => Cow::Borrowed(text.trim()),
+                Cow::Owned(text) => Cow::Owned(text.trim().to_string()),
+            }
+        })(input)
+    }
+}
+
+/// Nom parser that parses `count` number of bytes as ASCII text,
+/// with the space padding removed. If the text is blank,
+/// [Option::None] is returned
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// An [Option] containing the [String], or [Option::None] if the field is blank
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::opt_text_parser;
+/// assert_eq!(opt_text_parser(4)(b"AB  "), Ok((&b""[..], Some("AB".to_string()))));
+/// assert_eq!(opt_text_parser(4)(b"    "), Ok((&b""[..], None)));
+/// ```
+pub fn opt_text_parser(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<String>> {
+    move |input| {
+        map(text_parser(count), |text| match text.is_empty() {
+            true => None,
+            false => Some(text),
+        })(input)
+    }
+}
+
+/// Nom parser for a single ASCII character
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::char_parser;
+/// assert_eq!(char_parser(b"U1"), Ok((&b"1"[..], 'U')));
+/// ```
+pub fn char_parser(input: &[u8]) -> IResult<&[u8], char> {
+    map(take(1_usize), |bytes: &[u8]| bytes[0] as char)(input)
+}
+
 /// Parses a byte slice into a [crate::primitives::Angle]
 ///
 /// # Arguments
@@ -133,6 +277,8 @@ where
 /// use dted2::primitives::Angle;
 /// assert_eq!(to_angle(b"12345", 3, 1, 1), Ok((&b""[..], Angle::new(123, 4, 5.0, false))));
 /// assert_eq!(to_angle(b"12345W", 3, 1, 1), Ok((&b""[..], Angle::new(123, 4, 5.0, true))));
+/// assert!(to_angle(b"0426000N", 3, 2, 2).is_err());
+/// assert!(to_angle(b"04201x0N", 3, 2, 2).is_err());
 /// ```
 pub fn to_angle(
     input: &[u8],
@@ -140,6 +286,7 @@ pub fn to_angle(
     num_min: usize,
     num_sec: usize,
 ) -> IResult<&[u8], Angle> {
+    let start = input;
     let (input, (deg, min, sec, sign)) = tuple((
         uint_parser_with_default(num_deg, 0u32),
         uint_parser_with_default(num_min, 0u32),
@@ -153,10 +300,40 @@ pub fn to_angle(
     ))(input)?;
     Ok((
         input,
-        Angle::new(deg as u16, min as u8, sec as f64, sign.unwrap_or(false)),
+        checked_angle(start, deg, min, sec as f64, sign.unwrap_or(false))?,
     ))
 }
 
+/// Create an [Angle] from its parsed fields, failing rather than
+/// panicking if they are out of range
+///
+/// # Arguments
+///
+/// * `input` - input the angle was parsed from, for the error
+/// * `deg` - degrees
+/// * `min` - minutes
+/// * `sec` - seconds
+/// * `negative` - whether the angle is negative
+///
+/// # Returns
+///
+/// The [Angle], or an error if the minutes or seconds are not less than 60
+fn checked_angle(
+    input: &[u8],
+    deg: u32,
+    min: u32,
+    sec: f64,
+    negative: bool,
+) -> Result<Angle, nom::Err<nom::error::Error<&[u8]>>> {
+    match deg <= u16::MAX as u32 && min < 60 && sec < 60.0 {
+        true => Ok(Angle::new(deg as u16, min as u8, sec, negative)),
+        false => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
 /// Nom parser that parses `num_deg`, `num_min`, and `num_sec` number of bytes and returns an angle
 ///
 /// # Arguments
@@ -181,6 +358,82 @@ pub fn angle_parser(
     move |input| to_angle(input, num_deg, num_min, num_sec)
 }
 
+/// Nom parser that parses an angle whose seconds contain a decimal fraction,
+/// such as `DDMMSS.SH`
+///
+/// # Arguments
+///
+/// * `num_deg` - The number of bytes to parse for degrees
+/// * `num_min` - The number of bytes to parse for minutes
+/// * `num_sec` - The number of bytes to parse for whole seconds
+/// * `num_frac` - The number of bytes to parse for the fraction of seconds,
+///   after the decimal point
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+/// use dted2::parsers::decimal_angle_parser;
+/// assert_eq!(decimal_angle_parser(2, 2, 2, 1)(b"420130.5N"), Ok((&b""[..], Angle::new(42, 1, 30.5, false))));
+/// assert_eq!(decimal_angle_parser(3, 2, 2, 1)(b"0150000.0W"), Ok((&b""[..], Angle::new(15, 0, 0.0, true))));
+/// ```
+pub fn decimal_angle_parser(
+    num_deg: usize,
+    num_min: usize,
+    num_sec: usize,
+    num_frac: usize,
+) -> impl Fn(&[u8]) -> IResult<&[u8], Angle> {
+    move |input| {
+        let start = input;
+        let (input, (deg, min, sec, frac, sign)) = tuple((
+            uint_parser_with_default(num_deg, 0u32),
+            uint_parser_with_default(num_min, 0u32),
+            uint_parser_with_default(num_sec, 0u32),
+            preceded(tag("."), uint_parser_with_default(num_frac, 0u32)),
+            opt(alt((
+                map(tag("N"), |_| false),
+                map(tag("S"), |_| true),
+                map(tag("E"), |_| false),
+                map(tag("W"), |_| true),
+            ))),
+        ))(input)?;
+        let sec = sec as f64 + frac as f64 / 10f64.powi(num_frac as i32);
+        Ok((
+            input,
+            checked_angle(start, deg, min, sec, sign.unwrap_or(false))?,
+        ))
+    }
+}
+
+/// Nom parser that fails if the angle parsed by `parser` is beyond `max_deg`
+/// degrees, e.g. a latitude beyond 90 or a longitude beyond 180 degrees
+///
+/// # Arguments
+///
+/// * `max_deg` - The maximum number of degrees, either side of 0
+/// * `parser` - The parser of the angle
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+/// use dted2::parsers::{angle_parser, bounded_angle_parser};
+/// let lat_parser = bounded_angle_parser(90, angle_parser(2, 2, 2));
+/// assert_eq!(lat_parser(b"900000S"), Ok((&b""[..], Angle::new(90, 0, 0.0, true))));
+/// assert!(lat_parser(b"900001N").is_err());
+/// assert!(bounded_angle_parser(180, angle_parser(3, 2, 2))(b"1800030E").is_err());
+/// ```
+pub fn bounded_angle_parser<P>(max_deg: u16, parser: P) -> impl Fn(&[u8]) -> IResult<&[u8], Angle>
+where
+    P: Fn(&[u8]) -> IResult<&[u8], Angle>,
+{
+    move |input| {
+        verify(&parser, |angle: &Angle| {
+            angle.total_secs().abs() <= f64::from(max_deg) * 3600.0
+        })(input)
+    }
+}
+
 /// Parses a byte slice into an unsigned integer,
 /// if the value is not a valid NAN DTED value
 ///
@@ -191,30 +444,43 @@ pub fn angle_parser(
 /// # Returns
 ///
 /// A [Option] containing a unsigned integer. Is None
-/// if the value is a valid NAN value
+/// if the value is a valid NAN value, i.e. the field starts with `NA`, or is
+/// `NA` right justified in padding (zeros or spaces before it, `$` or spaces
+/// after it), as written by some producers (e.g. `00NA`)
 ///
 /// # Examples
 ///
 /// ```
 /// use dted2::parsers::to_nan;
 /// assert_eq!(to_nan::<u32>(b"NA$$", 4), Ok((&b""[..], None)));
+/// assert_eq!(to_nan::<u32>(b"00NA", 4), Ok((&b""[..], None)));
+/// assert_eq!(to_nan::<u32>(b" NA$", 4), Ok((&b""[..], None)));
 /// assert_eq!(to_nan::<u32>(b"12345", 4), Ok((&b"5"[..], Some(1234 as u32))));
+/// assert!(to_nan::<u32>(b"1NA2", 4).is_err());
+/// assert!(to_nan::<u32>(b"0NA1", 4).is_err());
+/// assert!(to_nan::<u32>(b"12$$", 4).is_err());
+/// assert!(to_nan::<u32>(b"NA", 1).is_err());
 /// ```
 pub fn to_nan<U>(input: &[u8], count: usize) -> IResult<&[u8], Option<U>>
 where
     U: PrimInt + Unsigned,
 {
-    match tag::<_, _, nom::error::Error<_>>(RecognitionSentinel::NA.value())(input) {
-        Ok((input, _)) => {
-            let (input, _) = take(count - 2)(input)?;
-            Ok((input, None))
-        }
-        Err(e) => match e {
-            nom::Err::Error(err_input) => {
-                uint_parser::<U>(count)(err_input.input).map(|(input, x)| (input, Some(x)))
-            }
-            _ => Err(e),
-        },
+    let (rest, bytes) = take(count)(input)?;
+    match bytes.starts_with(RecognitionSentinel::NA.value()) || is_right_justified_na(bytes) {
+        true => Ok((rest, None)),
+        false => uint_parser::<U>(count)(input).map(|(input, x)| (input, Some(x))),
+    }
+}
+
+/// Whether a field is `NA` right justified in padding, see [to_nan]
+fn is_right_justified_na(bytes: &[u8]) -> bool {
+    let start = bytes
+        .iter()
+        .position(|b| *b != b'0' && *b != b' ')
+        .unwrap_or(bytes.len());
+    match bytes[start..].strip_prefix(RecognitionSentinel::NA.value()) {
+        Some(after) => after.iter().all(|b| *b == b'$' || *b == b' '),
+        None => false,
     }
 }
 
@@ -238,9 +504,7 @@ where
 /// assert_eq!(nan_parser::<u32>(4)(b"NA$$"), Ok((&b""[..], None)));
 /// assert_eq!(nan_parser::<u32>(4)(b"12345"), Ok((&b"5"[..], Some(1234 as u32))));
 /// ```
-pub fn nan_parser<U>(
-    count: usize,
-) -> impl Fn(&[u8]) -> Result<(&[u8], Option<U>), nom::Err<nom::error::Error<&[u8]>>>
+pub fn nan_parser<U>(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<U>>
 where
     U: PrimInt + Unsigned,
 {
@@ -281,6 +545,152 @@ pub fn to_i16(x: u16) -> i16 {
     (1 - (s << 1)) * v // branchless negation, return (1 - 2s) * v
 }
 
+/// Convert an i16 to a signed magnitude int, the inverse of [to_i16]
+///
+/// # Arguments
+///
+/// * `x` - The i16 to convert, must be greater than [i16::MIN]
+///
+/// # Returns
+///
+/// The signed magnitude int (2 bytes, formatted as u16)
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::{to_i16, to_signed_mag};
+/// assert_eq!(to_signed_mag(0), 0x0000);
+/// assert_eq!(to_signed_mag(3), 0x0003);
+/// assert_eq!(to_signed_mag(-3), 0x8003);
+/// assert_eq!(to_signed_mag(-32767), 0xFFFF);
+/// assert_eq!(to_i16(to_signed_mag(-1234)), -1234);
+/// ```
+pub fn to_signed_mag(x: i16) -> u16 {
+    match x < 0 {
+        true => U16_SIGN_BIT | (x.unsigned_abs() & U16_DATA_MSK),
+        false => x as u16,
+    }
+}
+
+/// Convert big-endian signed magnitude ints to i16s in bulk, as stored in the
+/// data records, see [to_i16]
+///
+/// On x86_64 the ints are converted 16 (AVX2, detected at runtime with the
+/// `std` feature) or 8 (SSE2) at a time, the remainder and other architectures
+/// falling back to [to_i16].
+///
+/// # Arguments
+///
+/// * `input` - A byte slice, 2 bytes per int
+/// * `out` - Where to write the i16s, converting as many ints as fit
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::to_i16_slice;
+/// let mut out = [0; 5];
+/// to_i16_slice(&[0x00, 0x03, 0x80, 0x03, 0x7f, 0xff, 0xff, 0xff, 0x80, 0x00], &mut out);
+/// assert_eq!(out, [3, -3, 32767, -32767, 0]);
+/// ```
+pub fn to_i16_slice(input: &[u8], out: &mut [i16]) {
+    let len = out.len().min(input.len() / 2);
+    let (input, out) = (&input[..len * 2], &mut out[..len]);
+    let done = to_i16_lanes(input, out);
+    for (elev, bytes) in out[done..]
+        .iter_mut()
+        .zip(input[done * 2..].chunks_exact(2))
+    {
+        *elev = to_i16(u16::from_be_bytes([bytes[0], bytes[1]]));
+    }
+}
+
+/// Convert the ints with SIMD, as many as fit in whole vectors
+///
+/// # Returns
+///
+/// The number of ints converted
+#[cfg(target_arch = "x86_64")]
+fn to_i16_lanes(input: &[u8], out: &mut [i16]) -> usize {
+    #[cfg(feature = "std")]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is available
+        return unsafe { to_i16_avx2(input, out) };
+    }
+    to_i16_sse2(input, out)
+}
+#[cfg(not(target_arch = "x86_64"))]
+fn to_i16_lanes(_input: &[u8], _out: &mut [i16]) -> usize {
+    0
+}
+
+/// Convert the ints 8 at a time, SSE2 being part of x86_64
+///
+/// Each lane is byte swapped, then negated if its sign bit is set, as
+/// `(v ^ m) - m` with `m` the sign bit extended to the whole lane.
+#[cfg(target_arch = "x86_64")]
+fn to_i16_sse2(input: &[u8], out: &mut [i16]) -> usize {
+    use core::arch::x86_64::*;
+    let mut count = 0;
+    for (elev, bytes) in out.chunks_exact_mut(8).zip(input.chunks_exact(16)) {
+        // SAFETY: both chunks are 16 bytes, loaded and stored unaligned
+        unsafe {
+            let x = _mm_loadu_si128(bytes.as_ptr() as *const __m128i);
+            let x = _mm_or_si128(_mm_slli_epi16(x, 8), _mm_srli_epi16(x, 8));
+            let m = _mm_srai_epi16(x, 15);
+            let v = _mm_and_si128(x, _mm_set1_epi16(U16_DATA_MSK as i16));
+            let v = _mm_sub_epi16(_mm_xor_si128(v, m), m);
+            _mm_storeu_si128(elev.as_mut_ptr() as *mut __m128i, v);
+        }
+        count += 8;
+    }
+    count
+}
+
+/// Convert the ints 16 at a time, see [to_i16_sse2]
+///
+/// # Safety
+///
+/// The CPU must support AVX2
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn to_i16_avx2(input: &[u8], out: &mut [i16]) -> usize {
+    use core::arch::x86_64::*;
+    let mut count = 0;
+    for (elev, bytes) in out.chunks_exact_mut(16).zip(input.chunks_exact(32)) {
+        // SAFETY: both chunks are 32 bytes, loaded and stored unaligned
+        unsafe {
+            let x = _mm256_loadu_si256(bytes.as_ptr() as *const __m256i);
+            let x = _mm256_or_si256(_mm256_slli_epi16(x, 8), _mm256_srli_epi16(x, 8));
+            let m = _mm256_srai_epi16(x, 15);
+            let v = _mm256_and_si256(x, _mm256_set1_epi16(U16_DATA_MSK as i16));
+            let v = _mm256_sub_epi16(_mm256_xor_si256(v, m), m);
+            _mm256_storeu_si256(elev.as_mut_ptr() as *mut __m256i, v);
+        }
+        count += 16;
+    }
+    count
+}
+
+/// Compute a DTED checksum, the sum of every byte (as unsigned 8-bit values)
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// The checksum of the bytes
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::compute_checksum;
+/// assert_eq!(compute_checksum(&[0xAA, 0x00, 0x01, 0xFF]), 0xAA + 0x01 + 0xFF);
+/// ```
+pub fn compute_checksum(input: &[u8]) -> u32 {
+    input.iter().map(|b| *b as u32).sum()
+}
+
 /// Nom parser for signed magnitude values in DTED
 ///
 /// # Arguments
@@ -328,14 +738,43 @@ pub fn signed_mag_parser(input: &[u8]) -> IResult<&[u8], i16> {
 /// use dted2::parsers::dted_uhl_parser;
 /// use dted2::dted::RecognitionSentinel;
 ///
-/// assert_eq!(dted_uhl_parser(b"UHL11234556E8901234W123456789012UUUXXXXXXXXXXXX123445670XXXXXXXXXXXXXXXXXXXXXXXX"), Ok((&b""[..], RawDTEDHeader {
-///     origin: AxisElement { lat: Angle::new(890, 12, 34.0, true), lon: Angle::new(123, 45, 56.0, false) },
+/// assert_eq!(dted_uhl_parser(b"UHL11234556E0801234S123456789012UUUXXXXXXXXXXXX123445670XXXXXXXXXXXXXXXXXXXXXXXX"), Ok((&b""[..], RawDTEDHeader {
+///     origin: AxisElement { lat: Angle::new(80, 12, 34.0, true), lon: Angle::new(123, 45, 56.0, false) },
 ///     interval_secs_x_10: AxisElement { lat: 5678, lon: 1234 },
 ///     accuracy: Some(9012),
+///     security_code: "UUU".to_string(),
+///     unique_ref: "XXXXXXXXXXXX".to_string(),
 ///     count: AxisElement { lat: 4567, lon: 1234 },
+///     multiple_accuracy: false,
 /// })));
+/// // a latitude of origin beyond 90 degrees
+/// assert!(dted_uhl_parser(b"UHL11234556E8901234S123456789012UUUXXXXXXXXXXXX123445670XXXXXXXXXXXXXXXXXXXXXXXX").is_err());
 /// ```
 pub fn dted_uhl_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeader> {
+    map(dted_uhl_ref_parser, RawDTEDHeaderRef::into_owned)(input)
+}
+
+/// Nom parser for a [RawDTEDHeaderRef], borrowing its text fields from the input
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [RawDTEDHeaderRef] parsed from the byte slice
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::dted_uhl_ref_parser;
+///
+/// let content = std::fs::read("tests/test_data.dt2").unwrap();
+/// let (_, header) = dted_uhl_ref_parser(&content).unwrap();
+/// assert_eq!(header.security_code, "U");
+/// assert_eq!(header.count.lat, 3601);
+/// ```
+pub fn dted_uhl_ref_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeaderRef<'_>> {
     // --------------------------------------------------
     // verify is UHL
     // --------------------------------------------------
@@ -351,54 +790,450 @@ pub fn dted_uhl_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeader> {
             lon_interval_s,
             lat_interval_s,
             accuracy,
-            _,
+            security_code,
+            unique_ref,
             lon_count,
             lat_count,
+            multiple_accuracy,
             _,
         ),
     ) = tuple((
-        angle_parser(3, 2, 2),
-        angle_parser(3, 2, 2),
+        bounded_angle_parser(180, angle_parser(3, 2, 2)),
+        bounded_angle_parser(90, angle_parser(3, 2, 2)),
         uint_parser(4),
         uint_parser(4),
         nan_parser(4),
-        take(15_usize),
+        text_ref_parser(3),
+        text_ref_parser(12),
         uint_parser(4),
         uint_parser(4),
-        take(25_usize),
+        map(char_parser, |c| c == '1'),
+        take(24_usize),
     ))(input)?;
     // --------------------------------------------------
     // return
     // --------------------------------------------------
     Ok((
         input,
-        RawDTEDHeader {
+        RawDTEDHeaderRef {
             origin: AxisElement::new(lat_origin, lon_origin),
             interval_secs_x_10: AxisElement::new(lat_interval_s, lon_interval_s),
             accuracy,
+            security_code,
+            unique_ref,
+            count: AxisElement::new(lat_count, lon_count),
+            multiple_accuracy,
+        },
+    ))
+}
+
+/// Nom parser for a [RawDTEDDSIRecord]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [RawDTEDDSIRecord] parsed from the byte slice
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{SecurityClassification, DTED_UHL_LENGTH};
+/// use dted2::primitives::Angle;
+/// use dted2::parsers::dted_dsi_parser;
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let (_, dsi) = dted_dsi_parser(&content[DTED_UHL_LENGTH as usize..]).unwrap();
+/// assert_eq!(dsi.security_classification, SecurityClassification::Unclassified);
+/// assert_eq!(dsi.product_level, "DTED0");
+/// assert_eq!(dsi.origin.lat, Angle::new(30, 0, 0.0, true));
+/// assert_eq!(dsi.count.lon, 121);
+/// ```
+pub fn dted_dsi_parser(input: &[u8]) -> IResult<&[u8], RawDTEDDSIRecord> {
+    // --------------------------------------------------
+    // verify is DSI
+    // --------------------------------------------------
+    let (input, _) = tag(RecognitionSentinel::DSI.value())(input)?;
+    // --------------------------------------------------
+    // parse security + product identification
+    // --------------------------------------------------
+    let (
+        input,
+        (
+            security_classification,
+            security_control,
+            security_handling,
+            _,
+            product_level,
+            unique_ref,
+            _,
+            edition,
+            match_merge_version,
+            maintenance_date,
+            match_merge_date,
+            maintenance_code,
+            producer_code,
+            _,
+        ),
+    ) = tuple((
+        map(char_parser, SecurityClassification::from_code),
+        opt_text_parser(2),
+        opt_text_parser(27),
+        take(26_usize),
+        text_parser(5),
+        text_parser(15),
+        take(8_usize),
+        opt_uint_parser(2),
+        char_parser,
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+        text_parser(8),
+        take(16_usize),
+    ))(input)?;
+    // --------------------------------------------------
+    // parse product specification + datums
+    // --------------------------------------------------
+    let (
+        input,
+        (
+            product_spec,
+            product_spec_amendment,
+            product_spec_date,
+            vertical_datum,
+            horizontal_datum,
+            collection_system,
+            compilation_date,
+            _,
+        ),
+    ) = tuple((
+        text_parser(9),
+        opt_uint_parser(2),
+        opt_uint_parser(4),
+        text_parser(3),
+        text_parser(5),
+        text_parser(10),
+        opt_uint_parser(4),
+        take(22_usize),
+    ))(input)?;
+    // --------------------------------------------------
+    // parse geographic extents
+    // --------------------------------------------------
+    let (
+        input,
+        (
+            lat_origin,
+            lon_origin,
+            lat_sw,
+            lon_sw,
+            lat_nw,
+            lon_nw,
+            lat_ne,
+            lon_ne,
+            lat_se,
+            lon_se,
+            orientation,
+            lat_interval_s,
+            lon_interval_s,
+            lat_count,
+            lon_count,
+            partial_cell,
+            _,
+        ),
+    ) = tuple((
+        bounded_angle_parser(90, decimal_angle_parser(2, 2, 2, 1)),
+        bounded_angle_parser(180, decimal_angle_parser(3, 2, 2, 1)),
+        bounded_angle_parser(90, angle_parser(2, 2, 2)),
+        bounded_angle_parser(180, angle_parser(3, 2, 2)),
+        bounded_angle_parser(90, angle_parser(2, 2, 2)),
+        bounded_angle_parser(180, angle_parser(3, 2, 2)),
+        bounded_angle_parser(90, angle_parser(2, 2, 2)),
+        bounded_angle_parser(180, angle_parser(3, 2, 2)),
+        bounded_angle_parser(90, angle_parser(2, 2, 2)),
+        bounded_angle_parser(180, angle_parser(3, 2, 2)),
+        decimal_angle_parser(3, 2, 2, 1),
+        uint_parser(4),
+        uint_parser(4),
+        uint_parser(4),
+        uint_parser(4),
+        opt_uint_parser(2),
+        take(357_usize),
+    ))(input)?;
+    // --------------------------------------------------
+    // return
+    // --------------------------------------------------
+    Ok((
+        input,
+        RawDTEDDSIRecord {
+            security_classification,
+            security_control,
+            security_handling,
+            product_level,
+            unique_ref,
+            edition,
+            match_merge_version,
+            maintenance_date,
+            match_merge_date,
+            maintenance_code,
+            producer_code,
+            product_spec,
+            product_spec_amendment,
+            product_spec_date,
+            vertical_datum,
+            horizontal_datum,
+            collection_system,
+            compilation_date,
+            origin: AxisElement::new(lat_origin, lon_origin),
+            sw_corner: AxisElement::new(lat_sw, lon_sw),
+            nw_corner: AxisElement::new(lat_nw, lon_nw),
+            ne_corner: AxisElement::new(lat_ne, lon_ne),
+            se_corner: AxisElement::new(lat_se, lon_se),
+            orientation,
+            interval_secs_x_10: AxisElement::new(lat_interval_s, lon_interval_s),
             count: AxisElement::new(lat_count, lon_count),
+            partial_cell,
+        },
+    ))
+}
+
+/// Nom parser for an accuracy value in the ACC record
+/// Both the `NA$$` sentinel and a blank field are parsed as [Option::None]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// An [Option] containing the accuracy in meters
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::accuracy_parser;
+/// assert_eq!(accuracy_parser(b"0025"), Ok((&b""[..], Some(25))));
+/// assert_eq!(accuracy_parser(b"NA$$"), Ok((&b""[..], None)));
+/// assert_eq!(accuracy_parser(b"    "), Ok((&b""[..], None)));
+/// ```
+pub fn accuracy_parser(input: &[u8]) -> IResult<&[u8], Option<u16>> {
+    let (rest, bytes) = take(4_usize)(input)?;
+    match bytes.iter().all(|b| *b == b' ') {
+        true => Ok((rest, None)),
+        false => nan_parser(4)(input),
+    }
+}
+
+/// Nom parser for a single accuracy sub-region in the ACC record
+///
+/// Only the first `n` coordinates (as given by the 2-byte coordinate count)
+/// of the 14 available slots are part of the outline
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// The [AccuracySubregion], with its outline and accuracies
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+/// use dted2::parsers::dted_acc_subregion_parser;
+///
+/// let mut input = b"03".to_vec();
+/// input.extend_from_slice(b"420000.0N0150000.0E");
+/// input.extend_from_slice(b"430000.0N0150000.0E");
+/// input.extend_from_slice(b"430000.0N0160000.0E");
+/// input.extend_from_slice(&[b' '; 11 * 19]);
+/// input.extend_from_slice(b"0010001000050005");
+/// let (rest, subregion) = dted_acc_subregion_parser(&input).unwrap();
+/// assert!(rest.is_empty());
+/// assert_eq!(subregion.outline.len(), 3);
+/// assert_eq!(subregion.outline[2].lat, Angle::new(43, 0, 0.0, false));
+/// assert_eq!(subregion.outline[2].lon, Angle::new(16, 0, 0.0, false));
+/// assert_eq!(subregion.abs_horizontal, Some(10));
+/// assert_eq!(subregion.abs_vertical, Some(10));
+/// assert_eq!(subregion.rel_vertical, Some(5));
+/// ```
+pub fn dted_acc_subregion_parser(input: &[u8]) -> IResult<&[u8], AccuracySubregion> {
+    let (mut input, num_coords) = uint_parser::<u8>(2)(input)?;
+    let num_coords = (num_coords as usize).min(DTED_ACC_MAX_SUBREGION_COORDS);
+    let mut outline = Vec::with_capacity(num_coords);
+    for _ in 0..num_coords {
+        let (rest, (lat, lon)) = tuple((
+            decimal_angle_parser(2, 2, 2, 1),
+            decimal_angle_parser(3, 2, 2, 1),
+        ))(input)?;
+        outline.push(AxisElement::new(lat, lon));
+        input = rest;
+    }
+    // --------------------------------------------------
+    // skip unused coordinate slots, parse sub-region accuracies
+    // --------------------------------------------------
+    let (input, _) = take((DTED_ACC_MAX_SUBREGION_COORDS - num_coords) * 19)(input)?;
+    let (input, (abs_horizontal, abs_vertical, rel_horizontal, rel_vertical)) = tuple((
+        accuracy_parser,
+        accuracy_parser,
+        accuracy_parser,
+        accuracy_parser,
+    ))(input)?;
+    Ok((
+        input,
+        AccuracySubregion {
+            outline,
+            abs_horizontal,
+            abs_vertical,
+            rel_horizontal,
+            rel_vertical,
+        },
+    ))
+}
+
+/// Nom parser for a [RawDTEDAccRecord]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [RawDTEDAccRecord] parsed from the byte slice
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{DTED_UHL_LENGTH, DTED_DSI_RECORD_LENGTH};
+/// use dted2::parsers::dted_acc_parser;
+///
+/// let content = std::fs::read("tests/test_data.dt2").unwrap();
+/// let offset = DTED_UHL_LENGTH as usize + DTED_DSI_RECORD_LENGTH;
+/// let (_, acc) = dted_acc_parser(&content[offset..]).unwrap();
+/// assert_eq!(acc.abs_horizontal, Some(9));
+/// assert_eq!(acc.abs_vertical, Some(5));
+/// assert_eq!(acc.rel_horizontal, None);
+/// assert_eq!(acc.rel_vertical, Some(5));
+/// assert!(acc.subregions.is_empty());
+/// ```
+pub fn dted_acc_parser(input: &[u8]) -> IResult<&[u8], RawDTEDAccRecord> {
+    // --------------------------------------------------
+    // verify is ACC
+    // --------------------------------------------------
+    let (input, _) = tag(RecognitionSentinel::ACC.value())(input)?;
+    // --------------------------------------------------
+    // parse accuracies
+    // --------------------------------------------------
+    let (input, (abs_horizontal, abs_vertical, rel_horizontal, rel_vertical, _, multiple_accuracy)) =
+        tuple((
+            accuracy_parser,
+            accuracy_parser,
+            accuracy_parser,
+            accuracy_parser,
+            take(36_usize),
+            opt_uint_parser::<u8>(2),
+        ))(input)?;
+    let multiple_accuracy = multiple_accuracy.unwrap_or(0);
+    // --------------------------------------------------
+    // parse sub-regions, skip the rest
+    // --------------------------------------------------
+    let num_subregions = match multiple_accuracy as usize {
+        n if n >= 2 => n.min(DTED_ACC_MAX_SUBREGIONS),
+        _ => 0,
+    };
+    let (input, subregions) = count(dted_acc_subregion_parser, num_subregions)(input)?;
+    let (input, _) =
+        take(DTED_ACC_RECORD_LENGTH - 57 - num_subregions * DTED_ACC_SUBREGION_LENGTH)(input)?;
+    // --------------------------------------------------
+    // return
+    // --------------------------------------------------
+    Ok((
+        input,
+        RawDTEDAccRecord {
+            abs_horizontal,
+            abs_vertical,
+            rel_horizontal,
+            rel_vertical,
+            multiple_accuracy,
+            subregions,
         },
     ))
 }
 
+/// Nom parser for the header records of a DTED file:
+/// the [RawDTEDHeader], [RawDTEDDSIRecord] and [RawDTEDAccRecord]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// The header records, with the remaining input starting at the first data record
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::dted_headers_parser;
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let (rest, (header, dsi, acc)) = dted_headers_parser(&content).unwrap();
+/// assert_eq!(rest[0], 0xAA);
+/// assert_eq!(header.count, dsi.count);
+/// assert_eq!(acc.multiple_accuracy, 0);
+/// ```
+pub fn dted_headers_parser(
+    input: &[u8],
+) -> IResult<&[u8], (RawDTEDHeader, RawDTEDDSIRecord, RawDTEDAccRecord)> {
+    tuple((dted_uhl_parser, dted_dsi_parser, dted_acc_parser))(input)
+}
+
+/// Wraps a (complete) parser of a fixed length section, such that it
+/// returns [nom::Err::Incomplete] until the whole section is available,
+/// for incrementally parsing a stream
+///
+/// # Arguments
+///
+/// * `len` - length (in bytes) of the section
+/// * `parser` - parser of the section
+///
+/// # Returns
+///
+/// A streaming parser of the section
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::{dted_uhl_parser, fixed_length};
+///
+/// let content = std::fs::read("tests/test_data.dt2").unwrap();
+/// let parser = fixed_length(80, dted_uhl_parser);
+/// assert_eq!(parser(&content[..50]), Err(nom::Err::Incomplete(nom::Needed::new(30))));
+/// assert!(parser(&content[..80]).is_ok());
+/// ```
+pub fn fixed_length<O, F>(len: usize, parser: F) -> impl Fn(&[u8]) -> IResult<&[u8], O>
+where
+    F: Fn(&[u8]) -> IResult<&[u8], O>,
+{
+    move |input: &[u8]| {
+        let (rest, section) = nom::bytes::streaming::take(len)(input)?;
+        // errors refer to the input, as if the section was not split off
+        let (_, output) = parser(section).map_err(|e| e.map_input(|i| &input[len - i.len()..]))?;
+        Ok((rest, output))
+    }
+}
+
 pub fn dted_file_parser(input: &[u8]) -> IResult<&[u8], RawDTEDFile> {
     // --------------------------------------------------
     // get headers and header records
     // --------------------------------------------------
-    let (input, (header, _dsi_record, _acc_record)) = tuple((
-        dted_uhl_parser,
-        // TODO: parse DSI record
-        // TODO: parse ACC record
-        take(DT2_DSI_RECORD_LENGTH),
-        take(DT2_ACC_RECORD_LENGTH),
-    ))(input)?;
+    let (input, (header, dsi_record, acc_record)) = dted_headers_parser(input)?;
     // --------------------------------------------------
     // parse the actual data
     // --------------------------------------------------
-    let (input, records) = count(
-        |input| parse_dted_record(input, header.count.lat as usize),
-        header.count.lon as usize,
-    )(input)?;
+    let (input, records) = dted_records_parser(input, header.count)?;
     // --------------------------------------------------
     // return
     // --------------------------------------------------
@@ -406,36 +1241,243 @@ pub fn dted_file_parser(input: &[u8]) -> IResult<&[u8], RawDTEDFile> {
         input,
         RawDTEDFile {
             header,
+            coverage: Coverage::full(records.len()),
             data: records,
-            dsi_record: None,
-            acc_record: None,
+            dsi_record: Some(dsi_record),
+            acc_record: Some(acc_record),
+        },
+    ))
+}
+
+/// Nom parser for all of the data records of a DTED file
+///
+/// With the `rayon` feature, the records are parsed in parallel, as their
+/// boundaries are known from the number of lat/lon lines
+///
+/// # Arguments
+///
+/// * `input` - A byte slice, starting at the first data record
+/// * `count` - number of lat/lon lines
+///
+/// # Returns
+///
+/// The data records, from west to east
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTED_DATA_OFFSET;
+/// use dted2::parsers::{dted_records_parser, dted_uhl_parser};
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let (_, header) = dted_uhl_parser(&content).unwrap();
+/// let (rest, records) = dted_records_parser(&content[DTED_DATA_OFFSET..], header.count).unwrap();
+/// assert!(rest.is_empty());
+/// assert_eq!(records.len(), 121);
+/// assert!(dted_records_parser(&content[DTED_DATA_OFFSET..10000], header.count).is_err());
+/// ```
+pub fn dted_records_parser(
+    input: &[u8],
+    count: AxisElement<u16>,
+) -> IResult<&[u8], RawDTEDRecords> {
+    let line_len = count.lat as usize;
+    let mut elevations = vec![0; line_len * count.lon as usize];
+    let (input, records) = dted_records_parser_into(input, count, &mut elevations)?;
+    Ok((
+        input,
+        RawDTEDRecords {
+            line_len,
+            records,
+            elevations,
         },
     ))
 }
 
-// Parse a DTED record
+/// Nom parser for data records, decoding their elevations into a buffer,
+/// see [dted_records_parser]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice, starting at the first data record to parse
+/// * `count` - number of lat/lon lines to parse
+/// * `elevations` - buffer for the elevations, of `count.lat * count.lon` posts
+///
+/// # Returns
+///
+/// The [RawDTEDRecordInfo] of the records, from west to east
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTED_DATA_OFFSET;
+/// use dted2::parsers::dted_records_parser_into;
+/// use dted2::primitives::AxisElement;
+///
+/// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let mut out = vec![0; 121 * 2];
+/// let (rest, records) =
+///     dted_records_parser_into(&content[DTED_DATA_OFFSET..], AxisElement::new(121, 2), &mut out)
+///         .unwrap();
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(rest.len(), 119 * (8 + 121 * 2 + 4));
+/// ```
+pub fn dted_records_parser_into<'a>(
+    input: &'a [u8],
+    count: AxisElement<u16>,
+    elevations: &mut [i16],
+) -> IResult<&'a [u8], Vec<RawDTEDRecordInfo>> {
+    let line_len = count.lat as usize;
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        let record_len = dted_record_length(count.lat);
+        let len = record_len * count.lon as usize;
+        // when truncated, let the sequential parser report where
+        if input.len() >= len && line_len > 0 {
+            let records = input[..len]
+                .par_chunks(record_len)
+                .zip(elevations.par_chunks_mut(line_len))
+                .enumerate()
+                .map(|(idx, (record, out))| {
+                    parse_dted_record_into(record, out)
+                        .map(|(_, info)| info)
+                        // errors refer to the input, as if the record was not split off
+                        .map_err(|e| e.map_input(|i| &input[(idx + 1) * record_len - i.len()..]))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok((&input[len..], records));
+        }
+    }
+    let mut input = input;
+    let mut records = Vec::with_capacity(count.lon as usize);
+    for out in elevations
+        .chunks_exact_mut(line_len.max(1))
+        .take(count.lon as usize)
+    {
+        let (rest, info) = parse_dted_record_into(input, &mut out[..line_len])?;
+        records.push(info);
+        input = rest;
+    }
+    Ok((input, records))
+}
+
+/// Nom parser for a DTED data record
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+/// * `line_len` - number of elevations in the record (latitude points)
+///
+/// # Returns
+///
+/// A [RawDTEDRecord]
 pub fn parse_dted_record(input: &[u8], line_len: usize) -> IResult<&[u8], RawDTEDRecord> {
-    let (input, (block_byte0, block_rest, lon_count, lat_count, elevations, _)) = tuple((
-        preceded(
-            tag(RecognitionSentinel::DATA.value()),
-            take(1_usize), // starting block byte size, will always be 0
-        ),
-        be_u16,
-        be_u16,
-        be_u16,
-        count(signed_mag_parser, line_len),
-        take(4_usize), // checksum
-    ))(input)?;
-    // --------------------------------------------------
-    // return
-    // --------------------------------------------------
+    let mut elevations = vec![0; line_len];
+    let (input, info) = parse_dted_record_into(input, &mut elevations)?;
     Ok((
         input,
         RawDTEDRecord {
-            blk_count: block_byte0[0] as u32 * 0x10000 + block_rest as u32,
-            lon_count,
-            lat_count,
+            blk_count: info.blk_count,
+            lon_count: info.lon_count,
+            lat_count: info.lat_count,
             elevations,
+            checksum: info.checksum,
         },
     ))
 }
+
+/// Nom parser for a DTED data record, decoding its elevations into a buffer
+///
+/// As the hot path of file parsing, the fields are read at their fixed offsets
+/// rather than with combinators, failing with the same errors: [Tag] without
+/// the sentinel, else [Eof] at the first truncated field.
+///
+/// [Tag]: nom::error::ErrorKind::Tag
+/// [Eof]: nom::error::ErrorKind::Eof
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+/// * `out` - buffer for the elevations, its length being the number of
+///   elevations in the record (latitude points)
+///
+/// # Returns
+///
+/// The [RawDTEDRecordInfo] of the record
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::parse_dted_record_into;
+/// use nom::error::{Error, ErrorKind};
+/// use nom::Err;
+///
+/// let input = [0xAA, 0, 0, 1, 0, 2, 0, 0, 0x80, 0x03, 0, 0x02, 0, 0, 0, 0];
+/// let mut out = [0; 2];
+/// let (_, info) = parse_dted_record_into(&input, &mut out).unwrap();
+/// assert_eq!(out, [-3, 2]);
+/// assert_eq!(info.blk_count, 1);
+/// assert_eq!(info.lon_count, 2);
+///
+/// let err = parse_dted_record_into(&input[..9], &mut out).unwrap_err();
+/// assert_eq!(err, Err::Error(Error::new(&input[8..9], ErrorKind::Eof)));
+/// let err = parse_dted_record_into(&input[1..], &mut out).unwrap_err();
+/// assert_eq!(err, Err::Error(Error::new(&input[1..], ErrorKind::Tag)));
+/// ```
+pub fn parse_dted_record_into<'a>(
+    input: &'a [u8],
+    out: &mut [i16],
+) -> IResult<&'a [u8], RawDTEDRecordInfo> {
+    // --------------------------------------------------
+    // check the record is all there, failing like the field
+    // parsers would: on the sentinel, else at the truncated field
+    // --------------------------------------------------
+    let elevations_end = DTED_RECORD_HEADER_LENGTH + out.len() * 2;
+    let len = elevations_end + DTED_RECORD_CHECKSUM_LENGTH;
+    if input.first() != RecognitionSentinel::DATA.value().first() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Tag,
+        )));
+    }
+    if input.len() < len {
+        let start = [1, 2, 4, 6, DTED_RECORD_HEADER_LENGTH, elevations_end]
+            .into_iter()
+            .zip([2, 4, 6, DTED_RECORD_HEADER_LENGTH, elevations_end, len])
+            .find(|(_, end)| *end > input.len())
+            .map_or(0, |(start, _)| start);
+        return Err(nom::Err::Error(nom::error::Error::new(
+            &input[start..],
+            nom::error::ErrorKind::Eof,
+        )));
+    }
+    // --------------------------------------------------
+    // decode the fields at their offsets
+    // --------------------------------------------------
+    to_i16_slice(&input[DTED_RECORD_HEADER_LENGTH..elevations_end], out);
+    // --------------------------------------------------
+    // return
+    // --------------------------------------------------
+    Ok((&input[len..], to_record_info(&input[..len])))
+}
+
+/// Decode the header and checksum of a whole data record, at their offsets
+///
+/// # Arguments
+///
+/// * `record` - the bytes of the record, from its sentinel through its checksum
+///
+/// # Returns
+///
+/// The [RawDTEDRecordInfo] of the record
+pub(crate) fn to_record_info(record: &[u8]) -> RawDTEDRecordInfo {
+    let be_u16_at = |offset: usize| u16::from_be_bytes([record[offset], record[offset + 1]]);
+    let checksum = &record[record.len() - DTED_RECORD_CHECKSUM_LENGTH..];
+    RawDTEDRecordInfo {
+        // the starting block byte is always 0
+        blk_count: record[1] as u32 * 0x10000 + be_u16_at(2) as u32,
+        lon_count: be_u16_at(4),
+        lat_count: be_u16_at(6),
+        checksum: u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]),
+    }
+}
@@ -0,0 +1,276 @@
+//! Contains the patching of a cell from another overlapping source of
+//! elevations ([PatchSource]): another DTED cell or dataset, or a [Raster]
+//! such as an HGT tile or a GeoTIFF, overwriting the posts within a bounding
+//! box ([DTEDData::patch_from]) or only the void posts
+//! ([DTEDData::fill_voids_from]).
+//!
+//! Each post is sampled from the source at its lat/lon with an
+//! [Interpolation] method, resampling the source when its posts do not line
+//! up with the ones of the cell, and rounded to the meter. Posts where the
+//! source has no data are left as they are.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::ops::Range;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::crop::post_ranges;
+use crate::dataset::DTEDDataset;
+use crate::dted::{DTEDData, Post, RawDTEDFile, RawDTEDRecords, DTED_VOID_ELEVATION};
+use crate::interpolation::Interpolation;
+use crate::primitives::{AxisElement, SEC2DEG};
+use crate::raster::Raster;
+use crate::source::ElevationSource;
+
+/// A source of elevations to patch a cell from, sampled at any lat/lon
+pub trait PatchSource {
+    /// Get the elevation at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters), or None if not covered or void
+    fn sample(&self, lat: f64, lon: f64, method: Interpolation) -> Option<f64>;
+}
+
+impl<S: ElevationSource> PatchSource for S {
+    fn sample(&self, lat: f64, lon: f64, method: Interpolation) -> Option<f64> {
+        self.elevation_at_with(lat, lon, method)
+    }
+}
+
+impl PatchSource for DTEDDataset {
+    fn sample(&self, lat: f64, lon: f64, method: Interpolation) -> Option<f64> {
+        self.elevation_at_with(lat, lon, method)
+    }
+}
+
+impl PatchSource for Raster<Option<f64>> {
+    fn sample(&self, lat: f64, lon: f64, method: Interpolation) -> Option<f64> {
+        self.value_at(lat, lon, method)
+    }
+}
+
+impl DTEDData {
+    /// Overwrite the posts within a bounding box with the elevations of
+    /// another source, recomputing the checksums of the records
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - any [PatchSource]
+    /// * `min` - south west corner of the box
+    /// * `max` - north east corner of the box
+    /// * `method` - [Interpolation] method sampling the source
+    ///
+    /// # Returns
+    ///
+    /// * Number of posts patched
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::interpolation::Interpolation;
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::raster::Raster;
+    /// use dted2::DTEDData;
+    ///
+    /// let mut data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// // a flat 1 arc-minute grid over the north east quarter of the cell
+    /// let raster = Raster::from_fn(
+    ///     AxisElement::new(-29.5, -177.5),
+    ///     AxisElement::new(1.0 / 60.0, 1.0 / 60.0),
+    ///     AxisElement::new(31, 31),
+    ///     |_, _| Some(100.0),
+    /// );
+    /// let (min, max) = (AxisElement::new(-29.5, -177.5), AxisElement::new(-29.0, -177.0));
+    /// let patched = data.patch_from(&raster, min, max, Interpolation::Bilinear);
+    /// assert_eq!(patched, 61 * 61);
+    /// assert_eq!(data.data.get(60, 60), Some(100));
+    /// assert!(data.data.validate_checksums().is_ok());
+    /// ```
+    pub fn patch_from<P: PatchSource>(
+        &mut self,
+        other: &P,
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+        method: Interpolation,
+    ) -> usize {
+        let metadata = &self.metadata;
+        let Some(posts) = post_ranges(metadata.origin, metadata.interval, metadata.count, min, max)
+        else {
+            return 0;
+        };
+        let patched = patch(
+            &mut self.data,
+            (metadata.origin, metadata.interval),
+            &posts,
+            |_| true,
+            other,
+            method,
+        );
+        self.data.recompute_checksums();
+        patched
+    }
+
+    /// Fill the void posts with the elevations of another source,
+    /// recomputing the checksums of the records, see [DTEDData::patch_from]
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - any [PatchSource]
+    /// * `method` - [Interpolation] method sampling the source
+    ///
+    /// # Returns
+    ///
+    /// * Number of void posts filled
+    pub fn fill_voids_from<P: PatchSource>(&mut self, other: &P, method: Interpolation) -> usize {
+        let metadata = &self.metadata;
+        let posts = all_posts(metadata.count);
+        let filled = patch(
+            &mut self.data,
+            (metadata.origin, metadata.interval),
+            &posts,
+            Post::is_void,
+            other,
+            method,
+        );
+        self.data.recompute_checksums();
+        filled
+    }
+}
+
+impl RawDTEDFile {
+    /// Overwrite the posts within a bounding box with the elevations of
+    /// another source, updating the checksums of the records, see
+    /// [DTEDData::patch_from]
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - any [PatchSource]
+    /// * `min` - south west corner of the box
+    /// * `max` - north east corner of the box
+    /// * `method` - [Interpolation] method sampling the source
+    ///
+    /// # Returns
+    ///
+    /// * Number of posts patched
+    pub fn patch_from<P: PatchSource>(
+        &mut self,
+        other: &P,
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+        method: Interpolation,
+    ) -> usize {
+        let grid = self.grid();
+        let Some(posts) = post_ranges(grid.0, grid.1, self.header.count, min, max) else {
+            return 0;
+        };
+        let patched = patch(&mut self.data, grid, &posts, |_| true, other, method);
+        self.recompute_checksums();
+        patched
+    }
+
+    /// Fill the void posts with the elevations of another source, updating
+    /// the checksums of the records, see [DTEDData::fill_voids_from]
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - any [PatchSource]
+    /// * `method` - [Interpolation] method sampling the source
+    ///
+    /// # Returns
+    ///
+    /// * Number of void posts filled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::RawDTEDFile;
+    /// use dted2::interpolation::Interpolation;
+    /// use dted2::{DTEDData, Post};
+    ///
+    /// let mut file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// file.set_post(5, 7, Post::Void);
+    /// assert_eq!(file.fill_voids_from(&data, Interpolation::Nearest), 1);
+    /// assert_eq!(file.data.get(5, 7), data.data.get(5, 7));
+    /// ```
+    pub fn fill_voids_from<P: PatchSource>(&mut self, other: &P, method: Interpolation) -> usize {
+        let grid = self.grid();
+        let posts = all_posts(self.header.count);
+        let filled = patch(&mut self.data, grid, &posts, Post::is_void, other, method);
+        self.recompute_checksums();
+        filled
+    }
+
+    /// Get the origin and interval of the posts, in degrees
+    fn grid(&self) -> (AxisElement<f64>, AxisElement<f64>) {
+        (
+            self.header.origin.into(),
+            self.header.interval_secs_x_10 / (SEC2DEG * 10.0),
+        )
+    }
+}
+
+/// Get the indices of all of the posts
+fn all_posts(count: AxisElement<u16>) -> AxisElement<Range<usize>> {
+    AxisElement::new(0..usize::from(count.lat), 0..usize::from(count.lon))
+}
+
+/// Overwrite posts with the elevations of a source
+///
+/// # Arguments
+///
+/// * `data` - data records
+/// * `(origin, interval)` - lat/lon of the first post, and interval between
+///   posts, in degrees
+/// * `posts` - latitude and longitude index ranges of the posts
+/// * `select` - whether to overwrite a post
+/// * `other` - [PatchSource]
+/// * `method` - [Interpolation] method
+///
+/// # Returns
+///
+/// * Number of posts overwritten
+fn patch<P: PatchSource>(
+    data: &mut RawDTEDRecords,
+    (origin, interval): (AxisElement<f64>, AxisElement<f64>),
+    posts: &AxisElement<Range<usize>>,
+    select: impl Fn(Post) -> bool,
+    other: &P,
+    method: Interpolation,
+) -> usize {
+    let mut patched = 0;
+    for lon_idx in posts.lon.clone() {
+        let lon = origin.lon + lon_idx as f64 * interval.lon;
+        let Some(column) = data.column_mut(lon_idx) else {
+            continue;
+        };
+        for lat_idx in posts.lat.clone() {
+            let Some(raw) = column.get_mut(lat_idx) else {
+                continue;
+            };
+            if !select(Post::from_raw(*raw)) {
+                continue;
+            }
+            let lat = origin.lat + lat_idx as f64 * interval.lat;
+            let elevation = other
+                .sample(lat, lon, method)
+                .map(|elev| elev.round().clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16)
+                .filter(|elev| *elev != DTED_VOID_ELEVATION);
+            if let Some(elevation) = elevation {
+                *raw = elevation;
+                patched += 1;
+            }
+        }
+    }
+    patched
+}
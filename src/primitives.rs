@@ -1,7 +1,11 @@
 //! Contains primitive items used through the crate.
 
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 use num_traits::{FromPrimitive, ToPrimitive};
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Seconds -> Degrees
 pub const SEC2DEG: f64 = 3600.0;
@@ -9,8 +13,11 @@ pub const SEC2DEG: f64 = 3600.0;
 pub const SEC2MIN: f64 = 60.0;
 /// Minutes -> Degrees
 pub const MIN2DEG: f64 = 60.0;
+/// Feet -> Meters (the international foot)
+pub const FT2M: f64 = 0.3048;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// An angle in degrees, minutes, and seconds
 ///
 /// See: [https://en.wikipedia.org/wiki/Geographic_coordinate_system](https://en.wikipedia.org/wiki/Geographic_coordinate_system)
@@ -227,6 +234,86 @@ impl Angle {
             secs_abs
         }
     }
+
+    /// Converts signed decimal degrees to an [Angle],
+    /// negative for south latitudes and west longitudes
+    ///
+    /// # Arguments
+    ///
+    /// * `deg` - The number of degrees, which can be negative
+    ///
+    /// # Returns
+    ///
+    /// The number of degrees, minutes, and seconds as an [Angle].
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `deg` is too large to be represented as an [Angle].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::Angle;
+    ///
+    /// assert_eq!(Angle::from_decimal_degrees(-0.5), Angle::new(0, 30, 0.0, true));
+    /// assert_eq!(Angle::from_decimal_degrees(-118.25).to_decimal_degrees(), -118.25);
+    /// ```
+    pub fn from_decimal_degrees(deg: f64) -> Self {
+        Angle::from_secs(deg * SEC2DEG)
+    }
+
+    /// Computes the signed decimal degrees of the angle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::Angle;
+    ///
+    /// assert_eq!(Angle::new(34, 30, 0.0, false).to_decimal_degrees(), 34.5);
+    /// assert_eq!(Angle::new(0, 15, 0.0, true).to_decimal_degrees(), -0.25);
+    /// ```
+    pub fn to_decimal_degrees(&self) -> f64 {
+        self.total_secs() / SEC2DEG
+    }
+
+    /// Converts signed radians to an [Angle]
+    ///
+    /// # Arguments
+    ///
+    /// * `rad` - The number of radians, which can be negative
+    ///
+    /// # Returns
+    ///
+    /// The number of degrees, minutes, and seconds as an [Angle].
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `rad` is too large to be represented as an [Angle].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::Angle;
+    ///
+    /// let angle = Angle::from_radians(-std::f64::consts::FRAC_PI_2);
+    /// assert_eq!((angle.deg(), angle.min(), angle.is_negative()), (90, 0, true));
+    /// ```
+    pub fn from_radians(rad: f64) -> Self {
+        Angle::from_decimal_degrees(rad.to_degrees())
+    }
+
+    /// Computes the signed radians of the angle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::Angle;
+    ///
+    /// assert_eq!(Angle::new(180, 0, 0.0, true).to_radians(), -std::f64::consts::PI);
+    /// ```
+    pub fn to_radians(&self) -> f64 {
+        self.to_decimal_degrees().to_radians()
+    }
 }
 
 /// Compares two [Angle]s, taking into account that positive zero is the same as negative zero.
@@ -239,10 +326,11 @@ impl Angle {
 /// assert_ne!(Angle::new(1, 1, 1.0, false), Angle::new(1, 1, 1.0, true));
 /// assert_ne!(Angle::new(1, 1, 1.0, false), Angle::new(1, 1, 2.0, false));
 /// assert_eq!(Angle::new(0, 0, 0.0, false), Angle::new(0, 0, 0.0, true));
+/// assert_ne!(Angle::new(0, 30, 0.0, false), Angle::new(0, 30, 0.0, true));
 /// ```
 impl PartialEq for Angle {
     fn eq(&self, other: &Self) -> bool {
-        let is_zero = self.deg == 0 || self.min == 0 || self.sec == 0.0;
+        let is_zero = self.deg == 0 && self.min == 0 && self.sec == 0.0;
 
         (is_zero || self.negative == other.negative)
             && self.deg == other.deg
@@ -325,12 +413,26 @@ where
         Angle::from_secs(self.total_secs() / T::to_f64(&rhs).unwrap_or(1.0))
     }
 }
+/// Negates an [Angle], e.g. from north to south
+///
+/// # Returns
+///
+/// * [Angle]
+impl Neg for Angle {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Angle {
+            negative: !self.negative,
+            ..self
+        }
+    }
+}
 
 /// Converts an [Angle] to degrees of variable precision
 macro_rules! impl_type_from_angle {
     ($($type:ty),*) => {
         $(
-            #[doc = concat!(" Converts an [Angle] (degrees, minutes, seconds) to radians as ")]
+            #[doc = concat!(" Converts an [Angle] (degrees, minutes, seconds) to decimal degrees as ")]
             #[doc = concat!(" a specific numeric type (`", stringify!($type), "`).")]
             #[doc = concat!("")]
             #[doc = concat!(" # Example")]
@@ -374,7 +476,320 @@ impl_type_from_angle!(i64);
 impl_type_from_angle!(i128);
 impl_type_from_angle!(isize);
 
+/// Displays an [Angle] in signed DMS notation, e.g. `-34°12'30.5"`,
+/// the precision applying to the seconds
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+///
+/// let angle = Angle::new(34, 12, 30.5, true);
+/// assert_eq!(angle.to_string(), "-34°12'30.5\"");
+/// assert_eq!(format!("{:.2}", angle), "-34°12'30.50\"");
+/// assert_eq!(format!("{:.0}", Angle::new(0, 59, 59.7, false)), "1°0'0\"");
+/// ```
+impl std::fmt::Display for Angle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative && *self != Angle::from_secs(0.0) {
+            write!(f, "-")?;
+        }
+        fmt_dms(self, f)
+    }
+}
+
+/// Write the unsigned DMS notation of an [Angle], rounding the seconds to the
+/// precision of the formatter, if any, before carrying into the minutes and degrees
+fn fmt_dms(angle: &Angle, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let secs = angle.total_secs().abs();
+    match f.precision() {
+        Some(precision) => {
+            let scale = 10f64.powi(precision as i32);
+            let rounded = Angle::from_secs((secs * scale).round() / scale);
+            write!(
+                f,
+                "{}°{}'{:.*}\"",
+                rounded.deg, rounded.min, precision, rounded.sec
+            )
+        }
+        None => write!(f, "{}°{}'{}\"", angle.deg, angle.min, angle.sec),
+    }
+}
+
+/// An [Angle] displayed in DMS notation with its hemisphere,
+/// e.g. `34°12'30.5"N`, see [Angle::display_lat] and [Angle::display_lon]
+pub struct HemisphereDisplay {
+    angle: Angle,
+    hemispheres: [char; 2],
+}
+impl std::fmt::Display for HemisphereDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_dms(&self.angle, f)?;
+        let negative = self.angle.negative && self.angle != Angle::from_secs(0.0);
+        write!(f, "{}", self.hemispheres[negative as usize])
+    }
+}
+
+impl Angle {
+    /// Display the angle as a latitude, e.g. `34°12'30.5"N`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::Angle;
+    ///
+    /// let lat = Angle::from_decimal_degrees(-34.2);
+    /// assert_eq!(format!("{:.1}", lat.display_lat()), "34°12'0.0\"S");
+    /// ```
+    pub fn display_lat(&self) -> HemisphereDisplay {
+        HemisphereDisplay {
+            angle: *self,
+            hemispheres: LAT_HEMISPHERES,
+        }
+    }
+
+    /// Display the angle as a longitude, e.g. `118°15'0"W`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::Angle;
+    ///
+    /// let lon = Angle::from_decimal_degrees(-118.25);
+    /// assert_eq!(lon.display_lon().to_string(), "118°15'0\"W");
+    /// ```
+    pub fn display_lon(&self) -> HemisphereDisplay {
+        HemisphereDisplay {
+            angle: *self,
+            hemispheres: LON_HEMISPHERES,
+        }
+    }
+}
+
+/// Latitude hemispheres, of positive and negative angles
+const LAT_HEMISPHERES: [char; 2] = ['N', 'S'];
+/// Longitude hemispheres, of positive and negative angles
+const LON_HEMISPHERES: [char; 2] = ['E', 'W'];
+
+/// Parses an [Angle] from DMS or decimal degrees notation
+///
+/// Accepted are up to three components (degrees, minutes and seconds),
+/// separated by whitespace, `°`, `'`, `"`, `′`, `″` or `:`, with only the
+/// last one fractional. Negative angles either have a leading `-` or a
+/// hemisphere letter (`S` or `W`), before or after the components.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+///
+/// let angle: Angle = "34°12'30.5\"S".parse().unwrap();
+/// assert_eq!(angle, Angle::new(34, 12, 30.5, true));
+/// assert_eq!("-34 12 30.5".parse::<Angle>().unwrap(), angle);
+/// assert_eq!("W118:15".parse::<Angle>().unwrap().to_decimal_degrees(), -118.25);
+/// assert_eq!("-118.25".parse::<Angle>().unwrap().to_decimal_degrees(), -118.25);
+/// assert!("34°60'".parse::<Angle>().is_err());
+/// assert!("-34°S".parse::<Angle>().is_err());
+/// ```
+impl std::str::FromStr for Angle {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_angle(s, None)
+    }
+}
+
+/// Parse an [Angle], see [Angle::from_str](std::str::FromStr::from_str)
+///
+/// # Arguments
+///
+/// * `s` - the string
+/// * `hemispheres` - the hemispheres allowed, of positive and negative angles, or None for any
+fn parse_angle(s: &str, hemispheres: Option<[char; 2]>) -> Result<Angle, crate::Error> {
+    let invalid = || crate::Error::InvalidInput(format!("invalid angle {}", s));
+    let is_hemisphere = |c: &char| "NSEWnsew".contains(*c);
+    let s = s.trim();
+    let (hemisphere, body) = match (s.chars().next(), s.chars().last()) {
+        (_, Some(c)) if is_hemisphere(&c) => (Some(c), &s[..s.len() - 1]),
+        (Some(c), _) if is_hemisphere(&c) => (Some(c), &s[1..]),
+        _ => (None, s),
+    };
+    let hemisphere = hemisphere.map(|c| c.to_ascii_uppercase());
+    let body = body.trim();
+    let (sign, body) = match body.strip_prefix(['-', '+']) {
+        Some(rest) => (body.chars().next(), rest),
+        None => (None, body),
+    };
+    if sign.is_some() && hemisphere.is_some() {
+        return Err(invalid());
+    }
+    if let (Some(hemispheres), Some(hemisphere)) = (hemispheres, hemisphere) {
+        if !hemispheres.contains(&hemisphere) {
+            return Err(invalid());
+        }
+    }
+    let is_separator = |c: char| c.is_whitespace() || "°'\"′″:".contains(c);
+    if body
+        .chars()
+        .any(|c| !(c.is_ascii_digit() || c == '.' || is_separator(c)))
+    {
+        return Err(invalid());
+    }
+    let components: Vec<&str> = body.split(is_separator).filter(|c| !c.is_empty()).collect();
+    if components.is_empty()
+        || components.len() > 3
+        || components[..components.len() - 1]
+            .iter()
+            .any(|c| c.contains('.'))
+    {
+        return Err(invalid());
+    }
+    let values = components
+        .iter()
+        .map(|c| c.parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<f64>, _>>()?;
+    if values[1..].iter().any(|value| *value >= 60.0) {
+        return Err(invalid());
+    }
+    let secs = values
+        .iter()
+        .zip([SEC2DEG, SEC2MIN, 1.0])
+        .map(|(value, scale)| value * scale)
+        .sum::<f64>();
+    if secs > (u16::MAX as f64) * SEC2DEG {
+        return Err(invalid());
+    }
+    let negative = sign == Some('-') || matches!(hemisphere, Some('S' | 'W'));
+    Ok(Angle::from_secs(if negative { -secs } else { secs }))
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Unit of an [Elevation]
+///
+/// * Meters - meters, the unit of DTED
+/// * Feet - international feet, e.g. of avionics
+pub enum ElevationUnit {
+    #[default]
+    Meters,
+    Feet,
+}
+impl ElevationUnit {
+    /// Convert a value in meters into the unit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::ElevationUnit;
+    ///
+    /// assert_eq!(ElevationUnit::Feet.from_meters(0.3048), 1.0);
+    /// assert_eq!(ElevationUnit::Feet.to_meters(1000.0), 304.8);
+    /// assert_eq!(ElevationUnit::Meters.from_meters(12.5), 12.5);
+    /// ```
+    pub fn from_meters(self, meters: f64) -> f64 {
+        match self {
+            ElevationUnit::Meters => meters,
+            ElevationUnit::Feet => meters / FT2M,
+        }
+    }
+
+    /// Convert a value in the unit into meters
+    pub fn to_meters(self, value: f64) -> f64 {
+        match self {
+            ElevationUnit::Meters => value,
+            ElevationUnit::Feet => value * FT2M,
+        }
+    }
+
+    /// Get the symbol of the unit (`m` or `ft`)
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ElevationUnit::Meters => "m",
+            ElevationUnit::Feet => "ft",
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// An elevation, stored in meters and read in any [ElevationUnit]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::{Elevation, ElevationUnit};
+///
+/// let elevation = Elevation::from(1000_i16);
+/// assert!((elevation.feet() - 3280.84).abs() < 0.01);
+/// assert_eq!(Elevation::from_feet(1000.0).meters(), 304.8);
+/// assert_eq!(Elevation::new(100.0, ElevationUnit::Feet).to(ElevationUnit::Feet), 100.0);
+/// assert_eq!(elevation - Elevation::from_meters(400.0), Elevation::from_meters(600.0));
+/// assert_eq!(format!("{:.1}", Elevation::from_meters(12.25)), "12.2 m");
+/// ```
+pub struct Elevation {
+    meters: f64,
+}
+impl Elevation {
+    /// Create an elevation from a value in a unit
+    pub fn new(value: f64, unit: ElevationUnit) -> Self {
+        Self {
+            meters: unit.to_meters(value),
+        }
+    }
+
+    /// Create an elevation from a value in meters
+    pub fn from_meters(meters: f64) -> Self {
+        Self { meters }
+    }
+
+    /// Create an elevation from a value in feet
+    pub fn from_feet(feet: f64) -> Self {
+        Self::new(feet, ElevationUnit::Feet)
+    }
+
+    /// Get the elevation in meters
+    pub fn meters(self) -> f64 {
+        self.meters
+    }
+
+    /// Get the elevation in feet
+    pub fn feet(self) -> f64 {
+        self.to(ElevationUnit::Feet)
+    }
+
+    /// Get the elevation in a unit
+    pub fn to(self, unit: ElevationUnit) -> f64 {
+        unit.from_meters(self.meters)
+    }
+}
+impl From<i16> for Elevation {
+    /// Elevation of a post, in meters
+    fn from(meters: i16) -> Self {
+        Self::from_meters(f64::from(meters))
+    }
+}
+impl Add for Elevation {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_meters(self.meters + rhs.meters)
+    }
+}
+impl Sub for Elevation {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_meters(self.meters - rhs.meters)
+    }
+}
+/// Displays the elevation in meters, e.g. `12.5 m`, honoring the precision
+impl std::fmt::Display for Elevation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.meters, f)?;
+        write!(f, " {}", ElevationUnit::Meters.symbol())
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// An Axis element
 ///
 /// # Fields
@@ -390,6 +805,55 @@ impl<T> AxisElement<T> {
         Self { lat, lon }
     }
 }
+/// Displays a lat/lon in DMS notation with hemispheres, e.g.
+/// `34°12'30.5"N, 118°15'0"W`, the precision applying to the seconds
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::{Angle, AxisElement};
+///
+/// let pos = AxisElement::new(Angle::new(34, 12, 30.5, false), Angle::new(118, 15, 0.0, true));
+/// assert_eq!(pos.to_string(), "34°12'30.5\"N, 118°15'0\"W");
+/// assert_eq!(pos.to_string().parse::<AxisElement<Angle>>().unwrap(), pos);
+/// ```
+impl std::fmt::Display for AxisElement<Angle> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.lat.display_lat(), f)?;
+        write!(f, ", ")?;
+        std::fmt::Display::fmt(&self.lon.display_lon(), f)
+    }
+}
+/// Parses a `LAT,LON` pair of [Angle]s, each in DMS or decimal degrees
+/// notation (see [Angle::from_str](std::str::FromStr::from_str)), with the
+/// latitude within ±90° and the longitude within ±180°, and hemisphere
+/// letters matching their axes
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::{Angle, AxisElement};
+///
+/// let pos: AxisElement<Angle> = "34°30'N, 118°15'W".parse().unwrap();
+/// assert_eq!(AxisElement::<f64>::from(pos), AxisElement::new(34.5, -118.25));
+/// assert_eq!("34.5,-118.25".parse::<AxisElement<Angle>>().unwrap(), pos);
+/// assert!("118°15'W, 34°30'N".parse::<AxisElement<Angle>>().is_err());
+/// assert!("91, 0".parse::<AxisElement<Angle>>().is_err());
+/// ```
+impl std::str::FromStr for AxisElement<Angle> {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::Error::InvalidInput(format!("invalid LAT,LON {}", s));
+        let (lat, lon) = s.split_once(',').ok_or_else(invalid)?;
+        let lat = parse_angle(lat, Some(LAT_HEMISPHERES))?;
+        let lon = parse_angle(lon, Some(LON_HEMISPHERES))?;
+        if lat.total_secs().abs() > 90.0 * SEC2DEG || lon.total_secs().abs() > 180.0 * SEC2DEG {
+            return Err(invalid());
+        }
+        Ok(AxisElement::new(lat, lon))
+    }
+}
 /// Adds a [AxisElement]<[Angle]> to another [AxisElement]<[Angle]>
 ///
 /// # Returns
@@ -815,4 +1279,96 @@ mod test {
         assert_eq!(i128::from(angle), -123);
         assert_eq!(isize::from(angle), -123);
     }
+
+    #[test]
+    /// Test [Angle] decimal degrees and radians round trips, and arithmetic, of negative angles
+    fn angle_negative_round_trips() {
+        for deg in [
+            -179.5, -90.0, -34.25, -0.5, -0.001, 0.0, 0.001, 12.75, 179.5,
+        ] {
+            let angle = Angle::from_decimal_degrees(deg);
+            assert_eq!(angle.is_negative(), deg < 0.0);
+            assert!((angle.to_decimal_degrees() - deg).abs() < 1e-12);
+            assert!(
+                (Angle::from_radians(angle.to_radians()).to_decimal_degrees() - deg).abs() < 1e-9
+            );
+            assert_eq!(-(-angle), angle);
+        }
+
+        // crossing zero, south of the equator
+        let south = Angle::from_decimal_degrees(-0.5);
+        assert_eq!(south, Angle::new(0, 30, 0.0, true));
+        assert_eq!(
+            south + Angle::from_decimal_degrees(0.75),
+            Angle::new(0, 15, 0.0, false)
+        );
+        assert_eq!(
+            south - Angle::from_decimal_degrees(0.25),
+            Angle::new(0, 45, 0.0, true)
+        );
+        assert_eq!(south * 2, Angle::new(1, 0, 0.0, true));
+        assert_eq!(south / -2, Angle::new(0, 15, 0.0, false));
+        assert_eq!(-south, Angle::new(0, 30, 0.0, false));
+        assert_ne!(-south, south);
+    }
+
+    #[test]
+    /// Test [Angle] parsing of DMS and decimal degrees notations, and display round trips
+    fn angle_parse_display() {
+        let angle = Angle::new(34, 12, 30.5, true);
+        for s in [
+            "-34°12'30.5\"",
+            "34°12'30.5\"S",
+            "S 34°12'30.5\"",
+            "34° 12′ 30.5″ s",
+            "-34:12:30.5",
+            "  -34 12 30.5  ",
+        ] {
+            assert_eq!(s.parse::<Angle>().unwrap(), angle, "{s}");
+        }
+        assert_eq!(
+            "34".parse::<Angle>().unwrap(),
+            Angle::new(34, 0, 0.0, false)
+        );
+        assert_eq!(
+            "+34°12'".parse::<Angle>().unwrap(),
+            Angle::new(34, 12, 0.0, false)
+        );
+        assert_eq!(
+            "0.5W".parse::<Angle>().unwrap(),
+            Angle::new(0, 30, 0.0, true)
+        );
+        for s in [
+            "",
+            "N",
+            "-",
+            "34.5°12'",
+            "34°12'60\"",
+            "34°60'",
+            "-34S",
+            "34°12'30.5\"X",
+            "34 12 30 15",
+            "1e3",
+            "99999999",
+        ] {
+            assert!(s.parse::<Angle>().is_err(), "{s}");
+        }
+
+        assert_eq!(angle.to_string().parse::<Angle>().unwrap(), angle);
+        assert_eq!(angle.display_lat().to_string(), "34°12'30.5\"S");
+        assert_eq!(angle.display_lon().to_string(), "34°12'30.5\"W");
+        assert_eq!(Angle::new(0, 0, 0.0, true).to_string(), "0°0'0\"");
+        assert_eq!(
+            Angle::new(0, 0, 0.0, true).display_lat().to_string(),
+            "0°0'0\"N"
+        );
+
+        let pos: AxisElement<Angle> = "34°12'30.5\"S, 118°15'W".parse().unwrap();
+        assert_eq!(pos.lat, angle);
+        assert_eq!(pos.lon.to_decimal_degrees(), -118.25);
+        assert_eq!(pos.to_string().parse::<AxisElement<Angle>>().unwrap(), pos);
+        for s in ["34S", "34E, 118W", "34S, 118N", "90.5, 0", "0, 180.5"] {
+            assert!(s.parse::<AxisElement<Angle>>().is_err(), "{s}");
+        }
+    }
 }
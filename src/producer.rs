@@ -0,0 +1,104 @@
+//! Contains the [Producer] lookup, naming the country and agency of the
+//! producer code of a DSI record for provenance reporting.
+//!
+//! Producer codes start with the FIPS 10-4 code of the country, followed by
+//! an abbreviation of the agency (e.g. `USNIMA`).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Producer of a DTED file, of its producer code
+///
+/// # Fields
+///
+/// * `country` - name of the country, of the FIPS 10-4 code starting the producer code
+/// * `agency` - name of the agency, or None if not in the lookup table
+pub struct Producer {
+    pub country: &'static str,
+    pub agency: Option<&'static str>,
+}
+impl Producer {
+    /// Look up the producer of a producer code
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - producer code, as in the DSI record (space padding is ignored)
+    ///
+    /// # Returns
+    ///
+    /// * The [Producer], or None if the country of the code is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::producer::Producer;
+    ///
+    /// let producer = Producer::lookup("USNIMA").unwrap();
+    /// assert_eq!(producer.country, "United States");
+    /// assert_eq!(producer.agency, Some("National Imagery and Mapping Agency"));
+    /// assert_eq!(Producer::lookup("UKXYZ").unwrap().agency, None);
+    /// assert_eq!(Producer::lookup("ZZXYZ"), None);
+    /// ```
+    pub fn lookup(code: &str) -> Option<Producer> {
+        let code = code.trim();
+        let country = COUNTRIES
+            .iter()
+            .find(|(prefix, _)| code.get(..2) == Some(*prefix))?
+            .1;
+        let agency = AGENCIES
+            .iter()
+            .find(|(agency_code, _)| *agency_code == code)
+            .map(|(_, agency)| *agency);
+        Some(Producer { country, agency })
+    }
+}
+impl std::fmt::Display for Producer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.agency {
+            Some(agency) => write!(f, "{} ({})", agency, self.country),
+            None => write!(f, "{}", self.country),
+        }
+    }
+}
+
+/// FIPS 10-4 codes of the countries known to produce DTED
+const COUNTRIES: [(&str, &str); 25] = [
+    ("AS", "Australia"),
+    ("AU", "Austria"),
+    ("BE", "Belgium"),
+    ("CA", "Canada"),
+    ("DA", "Denmark"),
+    ("EI", "Ireland"),
+    ("FI", "Finland"),
+    ("FR", "France"),
+    ("GM", "Germany"),
+    ("GR", "Greece"),
+    ("IS", "Israel"),
+    ("IT", "Italy"),
+    ("JA", "Japan"),
+    ("KS", "South Korea"),
+    ("NL", "Netherlands"),
+    ("NO", "Norway"),
+    ("NZ", "New Zealand"),
+    ("PL", "Poland"),
+    ("PO", "Portugal"),
+    ("SP", "Spain"),
+    ("SW", "Sweden"),
+    ("SZ", "Switzerland"),
+    ("TU", "Turkey"),
+    ("UK", "United Kingdom"),
+    ("US", "United States"),
+];
+
+/// Producer codes of the known agencies
+const AGENCIES: [(&str, &str); 7] = [
+    ("USDMA", "Defense Mapping Agency"),
+    ("USDMAAC", "Defense Mapping Agency Aerospace Center"),
+    (
+        "USDMAHTC",
+        "Defense Mapping Agency Hydrographic/Topographic Center",
+    ),
+    ("USNIMA", "National Imagery and Mapping Agency"),
+    ("USCNIMA", "National Imagery and Mapping Agency"),
+    ("USNGA", "National Geospatial-Intelligence Agency"),
+    ("USUSGS", "U.S. Geological Survey"),
+];
@@ -0,0 +1,183 @@
+//! Contains the [ProfileSample]s of elevation profiles, sampling the terrain
+//! along a line between two lat/lons (see [crate::ElevationSource::profile]).
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::geodesy::LineType;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Sample of an elevation profile
+///
+/// # Fields
+///
+/// * `distance` - distance from the start of the profile, in meters
+/// * `lat` - latitude
+/// * `lon` - longitude
+/// * `elevation` - elevation (in meters), None if not covered or void
+pub struct ProfileSample {
+    pub distance: f64,
+    pub lat: f64,
+    pub lon: f64,
+    pub elevation: Option<f64>,
+}
+
+/// Sample the elevations along a line, at evenly spaced distances
+///
+/// # Arguments
+///
+/// * `start` - `(lat, lon)` of the first sample
+/// * `end` - `(lat, lon)` of the last sample
+/// * `num_samples` - number of samples, including both ends
+/// * `line` - [LineType] of the line
+/// * `elevation` - elevation at a lat/lon
+///
+/// # Returns
+///
+/// * [ProfileSample]s, from the start to the end
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geodesy::LineType;
+/// use dted2::profile;
+///
+/// let samples = profile::sample((0.0, 0.0), (1.0, 0.0), 3, LineType::GreatCircle, |lat, _| {
+///     Some(lat * 100.0)
+/// });
+/// assert_eq!(samples.len(), 3);
+/// assert!((samples[1].lat - 0.5).abs() < 1e-9);
+/// assert!((samples[1].elevation.unwrap() - 50.0).abs() < 1e-6);
+/// assert!((samples[2].distance - 111_195.0).abs() < 1.0);
+/// ```
+pub fn sample<F>(
+    start: (f64, f64),
+    end: (f64, f64),
+    num_samples: usize,
+    line: LineType,
+    elevation: F,
+) -> Vec<ProfileSample>
+where
+    F: Fn(f64, f64) -> Option<f64>,
+{
+    let distance = line.distance(start, end);
+    (0..num_samples)
+        .map(|idx| {
+            let fraction = match num_samples {
+                1 => 0.0,
+                n => idx as f64 / (n - 1) as f64,
+            };
+            let (lat, lon) = match fraction {
+                f if f <= 0.0 => start,
+                f if f >= 1.0 => end,
+                f => line.point(start, end, f),
+            };
+            ProfileSample {
+                distance: fraction * distance,
+                lat,
+                lon,
+                elevation: elevation(lat, lon),
+            }
+        })
+        .collect()
+}
+
+/// Render a profile as an SVG line chart of the elevation over the distance
+///
+/// The line is broken where the elevation is missing, and the axes are
+/// labeled with the total distance (in kilometers) and the elevation range
+/// (in meters).
+///
+/// # Arguments
+///
+/// * `samples` - [ProfileSample]s, such as of [sample]
+/// * `width` - width of the chart, in pixels
+/// * `height` - height of the chart, in pixels
+///
+/// # Returns
+///
+/// * SVG document
+///
+/// # Examples
+///
+/// ```
+/// use dted2::geodesy::LineType;
+/// use dted2::profile;
+///
+/// let samples = profile::sample((0.0, 0.0), (1.0, 0.0), 5, LineType::GreatCircle, |lat, _| {
+///     (lat != 0.5).then_some(lat * 100.0)
+/// });
+/// let svg = profile::to_svg(&samples, 640, 240);
+/// assert!(svg.starts_with("<svg"));
+/// // broken at the missing elevation
+/// assert_eq!(svg.matches("<polyline").count(), 2);
+/// ```
+pub fn to_svg(samples: &[ProfileSample], width: u32, height: u32) -> String {
+    const MARGIN: f64 = 40.0;
+    let (width_f, height_f) = (f64::from(width), f64::from(height));
+    let distance = samples.last().map_or(0.0, |sample| sample.distance);
+    let (min, max) = samples
+        .iter()
+        .filter_map(|sample| sample.elevation)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), elev| {
+            (min.min(elev), max.max(elev))
+        });
+    let (min, max) = match min <= max {
+        true => (min, max),
+        false => (0.0, 0.0),
+    };
+    // chart coordinates of a sample
+    let x = |distance_m: f64| match distance > 0.0 {
+        true => MARGIN + distance_m / distance * (width_f - 2.0 * MARGIN),
+        false => MARGIN,
+    };
+    let y = |elevation: f64| match max > min {
+        true => height_f - MARGIN - (elevation - min) / (max - min) * (height_f - 2.0 * MARGIN),
+        false => height_f / 2.0,
+    };
+    let lines: String = samples
+        .split(|sample| sample.elevation.is_none())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let points: Vec<String> = line
+                .iter()
+                .map(|sample| {
+                    format!(
+                        "{:.1},{:.1}",
+                        x(sample.distance),
+                        y(sample.elevation.unwrap())
+                    )
+                })
+                .collect();
+            format!(
+                "<polyline fill=\"none\" stroke=\"steelblue\" stroke-width=\"1.5\" points=\"{}\"/>\n",
+                points.join(" ")
+            )
+        })
+        .collect();
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            "<rect width=\"{w}\" height=\"{h}\" fill=\"white\"/>\n",
+            "<path fill=\"none\" stroke=\"black\" d=\"M{m},{m} V{bottom} H{right}\"/>\n",
+            "{lines}",
+            "<g font-family=\"sans-serif\" font-size=\"10\">\n",
+            "<text x=\"{m}\" y=\"{m_top}\">{max:.0} m</text>\n",
+            "<text x=\"{m}\" y=\"{label}\">{min:.0} m</text>\n",
+            "<text x=\"{right}\" y=\"{label}\" text-anchor=\"end\">{km:.2} km</text>\n",
+            "</g>\n",
+            "</svg>\n",
+        ),
+        w = width,
+        h = height,
+        m = MARGIN,
+        m_top = MARGIN - 5.0,
+        bottom = height_f - MARGIN,
+        right = width_f - MARGIN,
+        label = height_f - MARGIN + 15.0,
+        lines = lines,
+        max = max,
+        min = min,
+        km = distance / 1000.0,
+    )
+}
@@ -0,0 +1,268 @@
+//! Contains the Python bindings of the crate (requires the `python` feature),
+//! built with [pyo3] into a `dted2` extension module.
+//!
+//! The module exposes [DtedFile] (a [DTEDData]) and [DtedDataset] (a
+//! [DTEDDataset]), with their metadata as dicts and the vectorized queries
+//! returning numpy arrays, void or uncovered posts as NaN. Errors are raised as
+//! `dted2.DtedError`.
+//!
+//! The module is built and installed with [maturin](https://www.maturin.rs)
+//! from the root of the repository (see `pyproject.toml`), e.g.
+//! `maturin develop --release`, and then used as:
+//!
+//! ```python
+//! import dted2
+//!
+//! cell = dted2.DtedFile("n34_w119.dt2")
+//! print(cell.metadata["origin"], cell.elevation_at(34.5, -118.5))
+//! grid = cell.to_numpy()
+//! ```
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use numpy::{AllowTypeChange, IntoPyArray, PyArray1, PyArray2, PyArrayLike1};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dataset::DTEDDataset;
+use crate::dted::{DTEDData, DTEDMetadata};
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+create_exception!(
+    dted2,
+    DtedError,
+    PyException,
+    "Error reading or parsing DTED"
+);
+
+impl From<DTEDError> for PyErr {
+    fn from(e: DTEDError) -> PyErr {
+        DtedError::new_err(e.to_string())
+    }
+}
+
+/// DTED file, read in full, as `dted2.DtedFile(path)`
+#[pyclass(name = "DtedFile", module = "dted2", frozen)]
+pub struct DtedFile {
+    data: DTEDData,
+}
+#[pymethods]
+impl DtedFile {
+    /// Read a DTED file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the file
+    #[new]
+    fn new(path: &str) -> PyResult<DtedFile> {
+        Ok(DtedFile {
+            data: DTEDData::read(path)?,
+        })
+    }
+
+    /// Parse DTED from the bytes of a file, as `DtedFile.from_bytes(content)`
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the bytes of the file
+    #[staticmethod]
+    fn from_bytes(content: &[u8]) -> PyResult<DtedFile> {
+        Ok(DtedFile {
+            data: DTEDData::from_bytes(content)?,
+        })
+    }
+
+    /// Metadata of the file, as a dict, see [metadata_dict]
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        metadata_dict(py, &self.data.metadata)
+    }
+
+    /// Elevation of the nearest post, or None if out of bounds or void
+    fn elevation_at(&self, lat: f64, lon: f64) -> Option<i16> {
+        self.data.elevation_at(lat, lon)
+    }
+
+    /// Bilinearly interpolated elevation, or None if out of bounds or void
+    fn elevation_at_interpolated(&self, lat: f64, lon: f64) -> Option<f64> {
+        self.data.elevation_at_interpolated(lat, lon)
+    }
+
+    /// Elevations at lat/lons, as a float array with NaN if out of bounds or
+    /// void, as `elevations(lats, lons, interpolated=False)`
+    #[pyo3(signature = (lats, lons, interpolated = false))]
+    fn elevations<'py>(
+        &self,
+        py: Python<'py>,
+        lats: PyArrayLike1<'py, f64, AllowTypeChange>,
+        lons: PyArrayLike1<'py, f64, AllowTypeChange>,
+        interpolated: bool,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let points = points(&lats, &lons)?;
+        let elevations: Vec<Option<f64>> = if interpolated {
+            points
+                .iter()
+                .map(|(lat, lon)| self.data.elevation_at_interpolated(*lat, *lon))
+                .collect()
+        } else {
+            let elevations = self.data.elevations_at(&points);
+            elevations.into_iter().map(|e| e.map(f64::from)).collect()
+        };
+        Ok(nan_array(py, elevations))
+    }
+
+    /// Elevation grid, as a float array indexed as `[lat_idx, lon_idx]`,
+    /// with void posts as NaN, see [DTEDData::to_array]
+    fn to_numpy<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.data.to_array().into_pyarray(py)
+    }
+
+    fn __repr__(&self) -> String {
+        let meta = &self.data.metadata;
+        format!(
+            "DtedFile(origin=({}, {}), count=({}, {}))",
+            meta.origin.lat, meta.origin.lon, meta.count.lat, meta.count.lon
+        )
+    }
+}
+
+/// Dataset of DTED files, read in full, as `dted2.DtedDataset(paths)`
+#[pyclass(name = "DtedDataset", module = "dted2", frozen)]
+pub struct DtedDataset {
+    dataset: DTEDDataset<DTEDData>,
+}
+#[pymethods]
+impl DtedDataset {
+    /// Read the DTED files of a dataset
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - paths of the files
+    #[new]
+    fn new(paths: Vec<String>) -> PyResult<DtedDataset> {
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        Ok(DtedDataset {
+            dataset: DTEDDataset::read(&paths)?,
+        })
+    }
+
+    /// Metadata of the cells, as a list of dicts, see [metadata_dict]
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.dataset
+            .cells()
+            .iter()
+            .map(|cell| metadata_dict(py, &cell.metadata))
+            .collect()
+    }
+
+    /// Bounds of the dataset, as `((min_lat, min_lon), (max_lat, max_lon))`,
+    /// or None if empty
+    #[getter]
+    fn bounds(&self) -> Option<((f64, f64), (f64, f64))> {
+        self.dataset
+            .bounds()
+            .map(|(min, max)| ((min.lat, min.lon), (max.lat, max.lon)))
+    }
+
+    /// Elevation of the nearest post, or None if not covered or void
+    fn elevation_at(&self, lat: f64, lon: f64) -> Option<i16> {
+        self.dataset.elevation_at(lat, lon)
+    }
+
+    /// Bilinearly interpolated elevation, or None if not covered or void
+    fn elevation_at_interpolated(&self, lat: f64, lon: f64) -> Option<f64> {
+        self.dataset.elevation_at_interpolated(lat, lon)
+    }
+
+    /// Elevations at lat/lons, as a float array with NaN if not covered or
+    /// void, as `elevations(lats, lons, interpolated=False)`
+    #[pyo3(signature = (lats, lons, interpolated = false))]
+    fn elevations<'py>(
+        &self,
+        py: Python<'py>,
+        lats: PyArrayLike1<'py, f64, AllowTypeChange>,
+        lons: PyArrayLike1<'py, f64, AllowTypeChange>,
+        interpolated: bool,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let points = points(&lats, &lons)?;
+        let elevations: Vec<Option<f64>> = if interpolated {
+            points
+                .iter()
+                .map(|(lat, lon)| self.dataset.elevation_at_interpolated(*lat, *lon))
+                .collect()
+        } else {
+            let elevations = self.dataset.elevations_at(&points);
+            elevations.into_iter().map(|e| e.map(f64::from)).collect()
+        };
+        Ok(nan_array(py, elevations))
+    }
+
+    fn __len__(&self) -> usize {
+        self.dataset.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DtedDataset(cells={})", self.dataset.len())
+    }
+}
+
+/// The `dted2` Python module
+#[pymodule]
+pub fn dted2(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<DtedFile>()?;
+    m.add_class::<DtedDataset>()?;
+    m.add("DtedError", m.py().get_type::<DtedError>())?;
+    Ok(())
+}
+
+/// Convert metadata into a dict, of:
+///
+/// * `filename` - name of the file
+/// * `origin` - `(lat, lon)` of the south west corner, in degrees
+/// * `interval` - `(lat, lon)` intervals between posts, in degrees
+/// * `count` - `(lat, lon)` counts of posts
+/// * `accuracy` - absolute vertical accuracy, in meters, or None
+/// * `level` - DTED level (0, 1 or 2), or None if it can not be inferred
+fn metadata_dict<'py>(py: Python<'py>, meta: &DTEDMetadata) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("filename", &meta.filename)?;
+    dict.set_item("origin", (meta.origin.lat, meta.origin.lon))?;
+    dict.set_item("interval", (meta.interval.lat, meta.interval.lon))?;
+    dict.set_item("count", (meta.count.lat, meta.count.lon))?;
+    dict.set_item("accuracy", meta.accuracy)?;
+    dict.set_item("level", meta.level().map(|level| level.number()))?;
+    Ok(dict)
+}
+
+/// Zip latitudes and longitudes into points, which must be as many
+fn points(
+    lats: &PyArrayLike1<'_, f64, AllowTypeChange>,
+    lons: &PyArrayLike1<'_, f64, AllowTypeChange>,
+) -> PyResult<Vec<(f64, f64)>> {
+    let (lats, lons) = (lats.as_array(), lons.as_array());
+    if lats.len() != lons.len() {
+        return Err(PyValueError::new_err(format!(
+            "{} latitudes but {} longitudes",
+            lats.len(),
+            lons.len()
+        )));
+    }
+    Ok(lats.iter().copied().zip(lons.iter().copied()).collect())
+}
+
+/// Convert elevations into a float array, with None as NaN
+fn nan_array(py: Python<'_>, elevations: Vec<Option<f64>>) -> Bound<'_, PyArray1<f64>> {
+    elevations
+        .into_iter()
+        .map(|elevation| elevation.unwrap_or(f64::NAN))
+        .collect::<Vec<f64>>()
+        .into_pyarray(py)
+}
@@ -0,0 +1,313 @@
+//! Contains the export of the terrain as
+//! [quantized-mesh](https://github.com/CesiumGS/quantized-mesh) tiles, the
+//! terrain format streamed by Cesium to 3D globes.
+//!
+//! The mesh is a regular grid of vertices over a lat/lon extent, two triangles
+//! per square, with the vertex positions quantized to 0-32767 over the extent
+//! and the heights between the minimum and maximum elevations. Void (or not
+//! covered) vertices are set to the minimum elevation, as the mesh can not
+//! have holes. Tiles are written uncompressed, usually served gzipped.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dataset::DTEDDataset;
+use crate::dted::DTEDData;
+use crate::geodesy::{to_ecef, WGS84_A, WGS84_B};
+use crate::primitives::AxisElement;
+use crate::source::ElevationSource;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Largest quantized coordinate
+const QUANTIZED_MAX: f64 = 32767.0;
+/// Number of vertices above which indices take 32 bits
+const U16_INDEX_LIMIT: usize = 65536;
+
+/// Encode a quantized-mesh tile
+///
+/// # Arguments
+///
+/// * `min` - south west lat/lon of the tile
+/// * `max` - north east lat/lon of the tile
+/// * `size` - number of vertices per side (at least 2)
+/// * `elevation` - elevation at a lat/lon
+///
+/// # Returns
+///
+/// * Bytes of the tile
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::AxisElement;
+/// use dted2::quantized_mesh;
+///
+/// let min = AxisElement::new(42.0, 15.0);
+/// let max = AxisElement::new(43.0, 16.0);
+/// let tile = quantized_mesh::encode(min, max, 3, |lat, _| Some(lat * 10.0));
+/// // header, then 9 vertices, 8 triangles, and 3 vertices per edge
+/// assert_eq!(tile.len(), 88 + 4 + 9 * 6 + 4 + 8 * 3 * 2 + 4 * (4 + 3 * 2));
+/// // minimum and maximum heights
+/// assert_eq!(tile[24..28], 420f32.to_le_bytes());
+/// assert_eq!(tile[28..32], 430f32.to_le_bytes());
+/// ```
+pub fn encode<F>(min: AxisElement<f64>, max: AxisElement<f64>, size: usize, elevation: F) -> Vec<u8>
+where
+    F: Fn(f64, f64) -> Option<f64>,
+{
+    let size = size.max(2);
+    let step = |idx: usize| idx as f64 / (size - 1) as f64;
+    // --------------------------------------------------
+    // grid of vertices, from south to north then west to east
+    // --------------------------------------------------
+    let positions: Vec<(f64, f64)> = (0..size)
+        .flat_map(|row| (0..size).map(move |col| (row, col)))
+        .map(|(row, col)| {
+            (
+                min.lat + step(row) * (max.lat - min.lat),
+                min.lon + step(col) * (max.lon - min.lon),
+            )
+        })
+        .collect();
+    let elevations: Vec<Option<f64>> = positions
+        .iter()
+        .map(|(lat, lon)| elevation(*lat, *lon))
+        .collect();
+    let known = elevations.iter().flatten().copied();
+    let (min_height, max_height) = known.fold((f64::INFINITY, f64::NEG_INFINITY), |acc, h| {
+        (acc.0.min(h), acc.1.max(h))
+    });
+    let (min_height, max_height) = match min_height <= max_height {
+        true => (min_height, max_height),
+        false => (0.0, 0.0),
+    };
+    let heights: Vec<f64> = elevations
+        .iter()
+        .map(|elev| elev.unwrap_or(min_height))
+        .collect();
+    // --------------------------------------------------
+    // triangles, counter-clockwise seen from above, with the vertices
+    // renumbered by first use (for the high water mark encoding)
+    // --------------------------------------------------
+    let grid = |row: usize, col: usize| row * size + col;
+    let triangles: Vec<usize> = (0..size - 1)
+        .flat_map(|row| (0..size - 1).map(move |col| (row, col)))
+        .flat_map(|(row, col)| {
+            let (sw, se) = (grid(row, col), grid(row, col + 1));
+            let (nw, ne) = (grid(row + 1, col), grid(row + 1, col + 1));
+            [sw, se, ne, sw, ne, nw]
+        })
+        .collect();
+    let mut order = Vec::with_capacity(positions.len());
+    let mut renumbered = vec![usize::MAX; positions.len()];
+    for vertex in &triangles {
+        if renumbered[*vertex] == usize::MAX {
+            renumbered[*vertex] = order.len();
+            order.push(*vertex);
+        }
+    }
+    // --------------------------------------------------
+    // header
+    // --------------------------------------------------
+    let ecef: Vec<[f64; 3]> = order
+        .iter()
+        .map(|vertex| {
+            let (lat, lon) = positions[*vertex];
+            to_ecef(lat, lon, heights[*vertex])
+        })
+        .collect();
+    let center = to_ecef(
+        (min.lat + max.lat) / 2.0,
+        (min.lon + max.lon) / 2.0,
+        (min_height + max_height) / 2.0,
+    );
+    let (sphere_center, radius) = bounding_sphere(&ecef);
+    let occlusion = horizon_occlusion_point(&ecef, sphere_center);
+    let mut bytes = Vec::new();
+    for value in center {
+        bytes.extend(value.to_le_bytes());
+    }
+    bytes.extend((min_height as f32).to_le_bytes());
+    bytes.extend((max_height as f32).to_le_bytes());
+    for value in sphere_center.into_iter().chain([radius]).chain(occlusion) {
+        bytes.extend(value.to_le_bytes());
+    }
+    // --------------------------------------------------
+    // vertices, zig-zag delta encoded
+    // --------------------------------------------------
+    let quantize = |value: f64, min: f64, max: f64| match max > min {
+        true => ((value - min) / (max - min) * QUANTIZED_MAX).round() as i32,
+        false => 0,
+    };
+    let u: Vec<i32> = order
+        .iter()
+        .map(|v| quantize(positions[*v].1, min.lon, max.lon))
+        .collect();
+    let v: Vec<i32> = order
+        .iter()
+        .map(|v| quantize(positions[*v].0, min.lat, max.lat))
+        .collect();
+    let h: Vec<i32> = order
+        .iter()
+        .map(|v| quantize(heights[*v], min_height, max_height))
+        .collect();
+    bytes.extend((order.len() as u32).to_le_bytes());
+    for values in [&u, &v, &h] {
+        let mut previous = 0;
+        for value in values.iter() {
+            let delta = value - previous;
+            bytes.extend((((delta << 1) ^ (delta >> 31)) as u16).to_le_bytes());
+            previous = *value;
+        }
+    }
+    // --------------------------------------------------
+    // triangle indices (high water mark encoded), then edge indices
+    // --------------------------------------------------
+    let wide = order.len() > U16_INDEX_LIMIT;
+    let index = |bytes: &mut Vec<u8>, index: usize| match wide {
+        true => bytes.extend((index as u32).to_le_bytes()),
+        false => bytes.extend((index as u16).to_le_bytes()),
+    };
+    if wide {
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+    }
+    bytes.extend(((triangles.len() / 3) as u32).to_le_bytes());
+    let mut highest = 0;
+    for vertex in &triangles {
+        let vertex = renumbered[*vertex];
+        index(&mut bytes, highest - vertex);
+        if vertex == highest {
+            highest += 1;
+        }
+    }
+    let edges: [Vec<usize>; 4] = [
+        (0..size).map(|row| grid(row, 0)).collect(),
+        (0..size).map(|col| grid(0, col)).collect(),
+        (0..size).map(|row| grid(row, size - 1)).collect(),
+        (0..size).map(|col| grid(size - 1, col)).collect(),
+    ];
+    for edge in edges {
+        bytes.extend((edge.len() as u32).to_le_bytes());
+        for vertex in edge {
+            index(&mut bytes, renumbered[vertex]);
+        }
+    }
+    bytes
+}
+
+/// Sphere around the center of the bounding box of points
+///
+/// # Returns
+///
+/// * Center and radius of the sphere
+fn bounding_sphere(points: &[[f64; 3]]) -> ([f64; 3], f64) {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for point in points {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
+        }
+    }
+    let center = [0, 1, 2].map(|axis| (min[axis] + max[axis]) / 2.0);
+    let radius = points
+        .iter()
+        .map(|point| norm([0, 1, 2].map(|axis| point[axis] - center[axis])))
+        .fold(0.0, f64::max);
+    (center, radius)
+}
+
+/// Point in the ellipsoid-scaled frame which, once below the horizon of a
+/// viewer, hides all of the points
+///
+/// # Arguments
+///
+/// * `points` - ECEF points
+/// * `direction` - ECEF direction of the occlusion point from the center of
+///   the earth, usually the center of the bounding sphere
+fn horizon_occlusion_point(points: &[[f64; 3]], direction: [f64; 3]) -> [f64; 3] {
+    let scale = |point: [f64; 3]| [point[0] / WGS84_A, point[1] / WGS84_A, point[2] / WGS84_B];
+    let direction = scale(direction);
+    let length = norm(direction);
+    if length == 0.0 {
+        return [0.0; 3];
+    }
+    let direction = direction.map(|value| value / length);
+    let magnitude = points
+        .iter()
+        .map(|point| {
+            let point = scale(*point);
+            let magnitude = norm(point).max(1.0);
+            let to_point = point.map(|value| value / norm(point));
+            let cos_alpha = dot(to_point, direction);
+            let sin_alpha = norm(cross(to_point, direction));
+            let cos_beta = 1.0 / magnitude;
+            let sin_beta = (magnitude * magnitude - 1.0).sqrt() * cos_beta;
+            1.0 / (cos_alpha * cos_beta - sin_alpha * sin_beta)
+        })
+        .fold(0.0, f64::max);
+    direction.map(|value| value * magnitude)
+}
+
+/// Dot product of two vectors
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Cross product of two vectors
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Length of a vector
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+impl DTEDData {
+    /// Encode the cell as a quantized-mesh tile, see [encode]
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - number of vertices per side (at least 2), e.g. 65 for the
+    ///   usual Cesium tiles, or the number of latitude points to keep every post
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the tile
+    pub fn quantized_mesh(&self, size: usize) -> Vec<u8> {
+        encode(self.min, self.max, size, |lat, lon| {
+            self.elevation_at_interpolated(lat, lon)
+        })
+    }
+}
+
+impl<S: ElevationSource> DTEDDataset<S> {
+    /// Encode a window of the dataset as a quantized-mesh tile, see [encode]
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - south west lat/lon of the window
+    /// * `max` - north east lat/lon of the window
+    /// * `size` - number of vertices per side (at least 2)
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the tile
+    pub fn quantized_mesh(
+        &self,
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+        size: usize,
+    ) -> Vec<u8> {
+        encode(min, max, size, |lat, lon| {
+            self.elevation_at_interpolated(lat, lon)
+        })
+    }
+}
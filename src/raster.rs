@@ -0,0 +1,388 @@
+//! Contains [Raster], a georeferenced grid of values derived from DTED posts
+//! (slopes, shaded relief, resampled elevations, ...).
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::interpolation::Interpolation;
+use crate::primitives::AxisElement;
+use crate::source::ElevationSource;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Fractional indices beyond the first or last post by at most this value
+/// are considered on that post, absorbing the rounding of the lat/lon
+const RASTER_EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Georeferenced grid of values, one per post
+///
+/// The values are stored as the DTED data records: column-major, one
+/// longitude line after the other (from west to east), each from south to north
+///
+/// # Fields
+///
+/// * `origin` - lat/lon of the first (south west) post
+/// * `interval` - lat/lon interval between posts, in degrees
+/// * `count` - number of latitude points and longitude lines
+/// * `values` - values, at index `lon_idx * count.lat + lat_idx`
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::AxisElement;
+/// use dted2::raster::Raster;
+///
+/// let raster = Raster::from_fn(
+///     AxisElement::new(10.0, 20.0),
+///     AxisElement::new(0.5, 0.25),
+///     AxisElement::new(3, 5),
+///     |lat_idx, lon_idx| 10 * lat_idx + lon_idx,
+/// );
+/// assert_eq!(raster.get(2, 4), Some(&24));
+/// assert_eq!(raster.get(3, 0), None);
+/// assert_eq!(raster.column(1), Some(&[1, 11, 21][..]));
+/// assert_eq!(raster.position(2, 4), AxisElement::new(11.0, 21.0));
+/// assert_eq!(raster.map(|value| value * 2).get(2, 4), Some(&48));
+/// ```
+pub struct Raster<T> {
+    pub origin: AxisElement<f64>,
+    pub interval: AxisElement<f64>,
+    pub count: AxisElement<usize>,
+    pub values: Vec<T>,
+}
+impl<T> Raster<T> {
+    /// Create a raster from the value of each post
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - lat/lon of the first (south west) post
+    /// * `interval` - lat/lon interval between posts, in degrees
+    /// * `count` - number of latitude points and longitude lines
+    /// * `value` - value of the post at `(lat_idx, lon_idx)`
+    ///
+    /// # Returns
+    ///
+    /// * [Raster]: raster of the values
+    pub fn from_fn<F>(
+        origin: AxisElement<f64>,
+        interval: AxisElement<f64>,
+        count: AxisElement<usize>,
+        value: F,
+    ) -> Raster<T>
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        let values = (0..count.lon)
+            .flat_map(|lon_idx| (0..count.lat).map(move |lat_idx| (lat_idx, lon_idx)))
+            .map(|(lat_idx, lon_idx)| value(lat_idx, lon_idx))
+            .collect();
+        Raster {
+            origin,
+            interval,
+            count,
+            values,
+        }
+    }
+
+    /// Get the value of a post
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Value or None if out of bounds
+    pub fn get(&self, lat_idx: usize, lon_idx: usize) -> Option<&T> {
+        match lat_idx < self.count.lat && lon_idx < self.count.lon {
+            true => self.values.get(lon_idx * self.count.lat + lat_idx),
+            false => None,
+        }
+    }
+
+    /// Get the values of a longitude line
+    ///
+    /// # Arguments
+    ///
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Values from south to north, or None if out of bounds
+    pub fn column(&self, lon_idx: usize) -> Option<&[T]> {
+        match lon_idx < self.count.lon {
+            true => self
+                .values
+                .get(lon_idx * self.count.lat..(lon_idx + 1) * self.count.lat),
+            false => None,
+        }
+    }
+
+    /// Get the lat/lon of a post
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    pub fn position(&self, lat_idx: usize, lon_idx: usize) -> AxisElement<f64> {
+        AxisElement::new(
+            self.origin.lat + lat_idx as f64 * self.interval.lat,
+            self.origin.lon + lon_idx as f64 * self.interval.lon,
+        )
+    }
+
+    /// Map the values into a new raster, over the same posts
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - mapping of each value
+    pub fn map<U, F>(&self, f: F) -> Raster<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Raster {
+            origin: self.origin,
+            interval: self.interval,
+            count: self.count,
+            values: self.values.iter().map(f).collect(),
+        }
+    }
+
+    /// Get the values row by row, from north to south, each from west to
+    /// east, as the pixels of an image
+    pub fn image_rows(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.count.lat).rev().flat_map(move |lat_idx| {
+            (0..self.count.lon).map(move |lon_idx| &self.values[lon_idx * self.count.lat + lat_idx])
+        })
+    }
+}
+impl Raster<Option<f64>> {
+    /// Create a raster of the elevations of a source, to export them
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - any [ElevationSource]
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the elevations, None where void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::raster::Raster;
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let raster = Raster::from_source(&data);
+    /// assert_eq!(raster.origin, data.min);
+    /// assert_eq!(raster.position(3600, 3600), data.max);
+    /// assert_eq!(raster.get(5, 3), Some(&data.data.get(5, 3).map(f64::from)));
+    /// ```
+    pub fn from_source<S: ElevationSource>(source: &S) -> Raster<Option<f64>> {
+        let metadata = source.metadata();
+        Raster::from_fn(
+            metadata.origin,
+            metadata.interval,
+            AxisElement::new(metadata.count.lat as usize, metadata.count.lon as usize),
+            |lat_idx, lon_idx| source.elevation(lat_idx, lon_idx).map(f64::from),
+        )
+    }
+
+    /// Get the value at a lat/lon between posts
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `interpolation` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * Interpolated value, or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::interpolation::Interpolation;
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::raster::Raster;
+    ///
+    /// let raster = Raster::from_fn(
+    ///     AxisElement::new(10.0, 20.0),
+    ///     AxisElement::new(0.5, 0.5),
+    ///     AxisElement::new(3, 3),
+    ///     |lat_idx, lon_idx| Some((10 * lat_idx + lon_idx) as f64),
+    /// );
+    /// assert_eq!(raster.value_at(10.25, 20.75, Interpolation::Bilinear), Some(6.5));
+    /// assert_eq!(raster.value_at(11.0, 21.0, Interpolation::Nearest), Some(22.0));
+    /// assert_eq!(raster.value_at(11.5, 21.0, Interpolation::Bilinear), None);
+    /// assert_eq!(raster.value_at(11.0, 21.1, Interpolation::Nearest), None);
+    /// ```
+    pub fn value_at(&self, lat: f64, lon: f64, interpolation: Interpolation) -> Option<f64> {
+        // fractional index within the posts, up to rounding errors
+        let index = |value: f64, origin: f64, interval: f64, count: usize| {
+            let idx = (value - origin) / interval;
+            let last = count.checked_sub(1)? as f64;
+            match (-RASTER_EPSILON..=last + RASTER_EPSILON).contains(&idx) {
+                true => Some(idx.clamp(0.0, last)),
+                false => None,
+            }
+        };
+        let lat_idx = index(lat, self.origin.lat, self.interval.lat, self.count.lat)?;
+        let lon_idx = index(lon, self.origin.lon, self.interval.lon, self.count.lon)?;
+        interpolation.interpolate(lat_idx, lon_idx, |lat_idx, lon_idx| {
+            match lat_idx >= 0 && lon_idx >= 0 {
+                true => *self.get(lat_idx as usize, lon_idx as usize)?,
+                false => None,
+            }
+        })
+    }
+
+    /// Encode the values as a 16-bit grayscale PNG heightmap (requires the
+    /// `png` feature), for game engines and 3D tools
+    ///
+    /// The values are scaled from black at the lowest value to white at the
+    /// highest, rows from north to south. Voids are black.
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the PNG image, or an [crate::Error::InvalidInput] if it
+    ///   could not be encoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::raster::Raster;
+    ///
+    /// let raster = Raster::from_fn(
+    ///     AxisElement::new(42.0, 15.0),
+    ///     AxisElement::new(0.5, 0.5),
+    ///     AxisElement::new(3, 3),
+    ///     |lat_idx, _| Some(lat_idx as f64 * 100.0),
+    /// );
+    /// let png = raster.to_heightmap_png().unwrap();
+    /// assert_eq!(png[1..4], *b"PNG");
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn to_heightmap_png(&self) -> Result<Vec<u8>, crate::Error> {
+        let (min, max) = self
+            .values
+            .iter()
+            .flatten()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        let level = |value: &Option<f64>| match (value, max > min) {
+            (Some(value), true) => {
+                ((value - min) / (max - min) * f64::from(u16::MAX)).round() as u16
+            }
+            _ => 0,
+        };
+        let pixels: Vec<u8> = self
+            .image_rows()
+            .flat_map(|value| level(value).to_be_bytes())
+            .collect();
+        encode_png(
+            self,
+            &pixels,
+            png::ColorType::Grayscale,
+            png::BitDepth::Sixteen,
+        )
+    }
+}
+impl Raster<u8> {
+    /// Encode the raster as an 8-bit grayscale PNG image (requires the `png`
+    /// feature), such as a [crate::terrain::hillshade]
+    ///
+    /// Rows are from north to south.
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the PNG image, or an [crate::Error::InvalidInput] if it
+    ///   could not be encoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let png = data.hillshade(315.0, 45.0, 1.0).to_png().unwrap();
+    /// assert_eq!(png[1..4], *b"PNG");
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>, crate::Error> {
+        let pixels: Vec<u8> = self.image_rows().copied().collect();
+        encode_png(
+            self,
+            &pixels,
+            png::ColorType::Grayscale,
+            png::BitDepth::Eight,
+        )
+    }
+}
+impl Raster<Option<[u8; 3]>> {
+    /// Encode the raster as an RGBA PNG image (requires the `png` feature),
+    /// such as a [crate::terrain::color_relief]
+    ///
+    /// Rows are from north to south, None being transparent.
+    ///
+    /// # Returns
+    ///
+    /// * Bytes of the PNG image, or an [crate::Error::InvalidInput] if it
+    ///   could not be encoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::terrain::Palette;
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let png = data.color_relief(Palette::Terrain).to_png().unwrap();
+    /// assert_eq!(png[1..4], *b"PNG");
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>, crate::Error> {
+        let pixels: Vec<u8> = self
+            .image_rows()
+            .flat_map(|color| match color {
+                Some([r, g, b]) => [*r, *g, *b, u8::MAX],
+                None => [0; 4],
+            })
+            .collect();
+        encode_png(self, &pixels, png::ColorType::Rgba, png::BitDepth::Eight)
+    }
+}
+
+/// Encode the pixels of a raster as a PNG image
+///
+/// # Arguments
+///
+/// * `raster` - [Raster] of the pixels, for the dimensions of the image
+/// * `pixels` - pixels, see [Raster::image_rows]
+/// * `color` - color type of the pixels
+/// * `depth` - bit depth of the pixels
+#[cfg(feature = "png")]
+fn encode_png<T>(
+    raster: &Raster<T>,
+    pixels: &[u8],
+    color: png::ColorType,
+    depth: png::BitDepth,
+) -> Result<Vec<u8>, crate::Error> {
+    let invalid = |err: png::EncodingError| crate::Error::InvalidInput(format!("PNG: {}", err));
+    let mut bytes = Vec::new();
+    let mut encoder =
+        png::Encoder::new(&mut bytes, raster.count.lon as u32, raster.count.lat as u32);
+    encoder.set_color(color);
+    encoder.set_depth(depth);
+    let mut writer = encoder.write_header().map_err(invalid)?;
+    writer.write_image_data(pixels).map_err(invalid)?;
+    writer.finish().map_err(invalid)?;
+    Ok(bytes)
+}
@@ -0,0 +1,184 @@
+//! Contains [DTEDReader], for random access to the posts of a DTED file by seeking.
+//!
+//! Only the header records are held in memory, with the byte offsets of
+//! the posts computed from them, such that even huge files are queried
+//! with constant memory.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::cell::RefCell;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Range;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+/// Seeking DTED reader
+///
+/// Queries through [ElevationSource] report I/O errors as None, use
+/// [DTEDReader::read_post] or [DTEDReader::read_window] to get them.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDData, ElevationSource};
+/// use dted2::reader::DTEDReader;
+///
+/// let file = std::fs::File::open("tests/test_data.dt2").unwrap();
+/// let reader = DTEDReader::new(file).unwrap();
+/// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+/// assert_eq!(reader.elevation_at(42.52, 15.75), data.elevation_at(42.52, 15.75));
+/// assert_eq!(reader.read_post(10, 20).unwrap(), data.data.get(10, 20));
+/// ```
+pub struct DTEDReader<R: Read + Seek> {
+    reader: RefCell<R>,
+    metadata: DTEDMetadata,
+    record_len: usize,
+}
+impl<R: Read + Seek> DTEDReader<R> {
+    /// Create a seeking reader, parsing the header records
+    ///
+    /// The length of the stream is checked to hold all of the data records,
+    /// which are otherwise not read
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - seekable stream to read the DTED from
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDReader]: seeking reader
+    pub fn new(mut reader: R) -> Result<DTEDReader<R>, DTEDError> {
+        let mut content = vec![0; DTED_DATA_OFFSET];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut content)?;
+        let (_, (header, dsi_record, acc_record)) = parsers::dted_headers_parser(&content)
+            .map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+        let record_len = dted_record_length(header.count.lat);
+        let end = (DTED_DATA_OFFSET + header.count.lon as usize * record_len) as u64;
+        let len = reader.seek(SeekFrom::End(0))?;
+        if len < end {
+            return Err(DTEDError::locate(
+                nom::Err::Incomplete(nom::Needed::new((end - len) as usize)),
+                &[],
+                len as usize,
+                Some(header.count.lat),
+            ));
+        }
+        Ok(DTEDReader {
+            reader: RefCell::new(reader),
+            metadata: DTEDMetadata::from_records(&header, dsi_record, acc_record, ""),
+            record_len,
+        })
+    }
+
+    /// Get the [DTEDMetadata] parsed from the header records
+    ///
+    /// The filename is empty, as the DTED is read from a stream
+    pub fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    /// Unwrap the underlying stream
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+
+    /// Read the raw elevation (in meters) of a post, void posts included
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation or None if out of bounds
+    pub fn read_post(&self, lat_idx: usize, lon_idx: usize) -> Result<Option<i16>, DTEDError> {
+        if lat_idx >= self.metadata.count.lat as usize
+            || lon_idx >= self.metadata.count.lon as usize
+        {
+            return Ok(None);
+        }
+        let mut bytes = [0; 2];
+        self.read_at(self.post_offset(lat_idx, lon_idx), &mut bytes)?;
+        Ok(Some(parsers::to_i16(u16::from_be_bytes(bytes))))
+    }
+
+    /// Read the raw elevations (in meters) of a window of posts, void posts
+    /// included, with a single read per longitude line
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_range` - latitude indices, from south to north
+    /// * `lon_range` - longitude indices, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevations, one [Vec] per longitude line (from south to north)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::reader::DTEDReader;
+    ///
+    /// let file = std::fs::File::open("tests/test_data.dt2").unwrap();
+    /// let reader = DTEDReader::new(file).unwrap();
+    /// let window = reader.read_window(100..104, 7..9).unwrap();
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert_eq!(window[1], data.data.column(8).unwrap()[100..104]);
+    /// assert!(reader.read_window(3600..3602, 0..1).is_err());
+    /// ```
+    pub fn read_window(
+        &self,
+        lat_range: Range<usize>,
+        lon_range: Range<usize>,
+    ) -> Result<Vec<Vec<i16>>, DTEDError> {
+        if lat_range.end > self.metadata.count.lat as usize
+            || lon_range.end > self.metadata.count.lon as usize
+        {
+            return Err(DTEDError::InvalidInput(format!(
+                "window {:?} x {:?} out of bounds of the {} x {} posts",
+                lat_range, lon_range, self.metadata.count.lat, self.metadata.count.lon
+            )));
+        }
+        let mut bytes = vec![0; lat_range.len() * 2];
+        lon_range
+            .map(|lon_idx| {
+                self.read_at(self.post_offset(lat_range.start, lon_idx), &mut bytes)?;
+                let mut column = vec![0; lat_range.len()];
+                parsers::to_i16_slice(&bytes, &mut column);
+                Ok(column)
+            })
+            .collect()
+    }
+
+    /// Byte offset of a post within the file
+    fn post_offset(&self, lat_idx: usize, lon_idx: usize) -> u64 {
+        (DTED_DATA_OFFSET + lon_idx * self.record_len + DTED_RECORD_HEADER_LENGTH + lat_idx * 2)
+            as u64
+    }
+
+    /// Seek to an offset and fill the buffer from there
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        let mut reader = self.reader.borrow_mut();
+        reader.seek(SeekFrom::Start(offset))?;
+        reader.read_exact(buf)
+    }
+}
+impl<R: Read + Seek> ElevationSource for DTEDReader<R> {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.read_post(lat_idx, lon_idx).ok()?
+    }
+}
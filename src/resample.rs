@@ -0,0 +1,257 @@
+//! Contains the resampling of cells: downsampling a cell into a valid cell
+//! of a lower [DTEDLevel], aggregating the posts of the higher level with
+//! an [Aggregation] method, or regridding it at any post spacing with an
+//! [Interpolation] method ([resample]).
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::builder::DTEDBuilder;
+use crate::dted::{DTEDData, DTEDLevel, RawDTEDFile, DTED_VOID_ELEVATION};
+use crate::interpolation::Interpolation;
+use crate::primitives::AxisElement;
+use crate::raster::Raster;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Fractional post indices within this distance of a post are on that post,
+/// absorbing the rounding of the spacing
+const RESAMPLE_EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+/// Method aggregating the posts of a higher level into a post of a lower level
+///
+/// * Nearest - the post of the higher level at the same lat/lon
+/// * Mean - mean of the non-void posts within half an interval of the lower
+///   level, rounded
+/// * Max - highest of the non-void posts within half an interval of the lower
+///   level, keeping the peaks (e.g. for obstacle clearance)
+pub enum Aggregation {
+    #[default]
+    Nearest,
+    Mean,
+    Max,
+}
+
+/// Regrid the elevations of a cell at any post spacing
+///
+/// The grid starts at the origin of the cell and covers it up to its north
+/// east corner (the last post being at most one spacing short of it). Where
+/// the posts of the grid line up with the posts of the cell, they hold the
+/// elevations of the cell as they are.
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `spacing` - lat/lon spacing of the posts of the grid, in degrees
+/// * `method` - [Interpolation] method, e.g. bilinear or bicubic
+///
+/// # Returns
+///
+/// * [Raster] of the elevations, None where void, or an
+///   [DTEDError::InvalidInput] if the spacing is not positive, or too fine
+///   for the number of posts to be counted
+///
+/// # Examples
+///
+/// ```
+/// use dted2::interpolation::Interpolation;
+/// use dted2::primitives::AxisElement;
+/// use dted2::resample;
+/// use dted2::{DTEDData, ElevationSource};
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// // a 20 arc-second grid, every third post lining up with every other post
+/// let spacing = AxisElement::new(20.0 / 3600.0, 20.0 / 3600.0);
+/// let grid = resample::resample(&data, spacing, Interpolation::Bicubic).unwrap();
+/// assert_eq!(grid.count, AxisElement::new(181, 181));
+/// assert_eq!(grid.get(3, 6), Some(&data.data.get(2, 4).map(f64::from)));
+/// assert_eq!(
+///     *grid.get(1, 1).unwrap(),
+///     data.elevation_at_with(-30.0 + 20.0 / 3600.0, -178.0 + 20.0 / 3600.0, Interpolation::Bicubic)
+/// );
+/// assert!(resample::resample(&data, AxisElement::new(0.0, 0.1), Interpolation::Bicubic).is_err());
+/// ```
+pub fn resample<S: ElevationSource>(
+    source: &S,
+    spacing: AxisElement<f64>,
+    method: Interpolation,
+) -> Result<Raster<Option<f64>>, DTEDError> {
+    let metadata = source.metadata();
+    let count = |count: u16, interval: f64, spacing: f64| {
+        if !(spacing.is_finite() && spacing > 0.0) {
+            return Err(DTEDError::InvalidInput(format!(
+                "the spacing {} is not positive",
+                spacing
+            )));
+        }
+        let extent = f64::from(count.max(1) - 1) * interval;
+        let posts = (extent / spacing + RESAMPLE_EPSILON).floor();
+        match posts < f64::from(u32::MAX) {
+            true => Ok(posts as usize + 1),
+            false => Err(DTEDError::InvalidInput(format!(
+                "the spacing {} is too fine for {} posts",
+                spacing, posts
+            ))),
+        }
+    };
+    let count = AxisElement::new(
+        count(metadata.count.lat, metadata.interval.lat, spacing.lat)?,
+        count(metadata.count.lon, metadata.interval.lon, spacing.lon)?,
+    );
+    // fractional post index of the cell, on the post up to rounding
+    let index = |idx: usize, interval: f64, spacing: f64| {
+        let index = idx as f64 * spacing / interval;
+        match (index - index.round()).abs() < RESAMPLE_EPSILON {
+            true => index.round(),
+            false => index,
+        }
+    };
+    Ok(Raster::from_fn(
+        metadata.origin,
+        spacing,
+        count,
+        |lat_idx, lon_idx| {
+            method.interpolate(
+                index(lat_idx, metadata.interval.lat, spacing.lat),
+                index(lon_idx, metadata.interval.lon, spacing.lon),
+                |lat, lon| {
+                    let lat = usize::try_from(lat).ok()?;
+                    let lon = usize::try_from(lon).ok()?;
+                    source.elevation(lat, lon).map(f64::from)
+                },
+            )
+        },
+    ))
+}
+
+impl DTEDData {
+    /// Regrid the elevations at any post spacing, see [resample]
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - lat/lon spacing of the posts of the grid, in degrees
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the elevations, None where void, or an
+    ///   [DTEDError::InvalidInput] if the spacing is not positive
+    pub fn resample(
+        &self,
+        spacing: AxisElement<f64>,
+        method: Interpolation,
+    ) -> Result<Raster<Option<f64>>, DTEDError> {
+        resample(self, spacing, method)
+    }
+
+    /// Downsample a complete cell into a cell of a lower level
+    ///
+    /// The lower level cell has the origin, accuracy, security
+    /// classification, producer and edition of the cell, with the intervals
+    /// and counts of the lower level. A post of the lower level is void if
+    /// all the posts it aggregates are void.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - lower [DTEDLevel]
+    /// * `aggregation` - [Aggregation] method
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDFile] of the lower level, which can be written with
+    ///   [RawDTEDFile::write], or an [DTEDError::InvalidInput] if the level is
+    ///   not lower, or the cell is not a complete cell of its level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::resample::Aggregation;
+    /// use dted2::{DTEDData, DTEDLevel};
+    ///
+    /// let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let file = data.downsample(DTEDLevel::Level1, Aggregation::Nearest).unwrap();
+    /// assert_eq!(file.level(), Some(DTEDLevel::Level1));
+    /// assert_eq!(file.header.count, AxisElement::new(1201, 1201));
+    /// assert_eq!(file.data.get(100, 200), data.data.get(300, 600));
+    ///
+    /// let file = data.downsample(DTEDLevel::Level0, Aggregation::Max).unwrap();
+    /// assert_eq!(file.header.count, AxisElement::new(121, 121));
+    /// assert!(data.downsample(DTEDLevel::Level2, Aggregation::Mean).is_err());
+    /// ```
+    pub fn downsample(
+        &self,
+        level: DTEDLevel,
+        aggregation: Aggregation,
+    ) -> Result<RawDTEDFile, DTEDError> {
+        let metadata = &self.metadata;
+        let source = metadata.level().ok_or_else(|| {
+            DTEDError::InvalidInput("the level of the cell is unknown".to_string())
+        })?;
+        if level >= source {
+            return Err(DTEDError::InvalidInput(format!(
+                "can not downsample {} to {}, which is not a lower level",
+                source, level
+            )));
+        }
+        let zone = metadata.zone();
+        if metadata.count != AxisElement::new(source.lat_count(), source.lon_count(zone)) {
+            return Err(DTEDError::InvalidInput(format!(
+                "{}x{} posts are not a complete {} cell",
+                metadata.count.lat, metadata.count.lon, source
+            )));
+        }
+        // same ratio of intervals along both axes, the longitude intervals of
+        // both levels being multiplied alike by the latitude zone
+        let ratio = usize::from(level.lat_interval_secs_x_10() / source.lat_interval_secs_x_10());
+        let radius = ratio / 2;
+        let count = AxisElement::new(
+            usize::from(metadata.count.lat),
+            usize::from(metadata.count.lon),
+        );
+        let post = |lat_idx: usize, lon_idx: usize| -> i16 {
+            let (lat_idx, lon_idx) = (lat_idx * ratio, lon_idx * ratio);
+            let window = || {
+                let lats = lat_idx.saturating_sub(radius)..(lat_idx + radius + 1).min(count.lat);
+                let lons = lon_idx.saturating_sub(radius)..(lon_idx + radius + 1).min(count.lon);
+                lons.flat_map(move |lon| lats.clone().map(move |lat| (lat, lon)))
+                    .filter_map(|(lat, lon)| self.elevation(lat, lon))
+            };
+            let elevation = match aggregation {
+                Aggregation::Nearest => self.elevation(lat_idx, lon_idx),
+                Aggregation::Mean => {
+                    let (sum, n) =
+                        window().fold((0.0, 0), |(sum, n), elev| (sum + f64::from(elev), n + 1));
+                    (n > 0).then(|| (sum / f64::from(n)).round() as i16)
+                }
+                Aggregation::Max => window().max(),
+            };
+            elevation.unwrap_or(DTED_VOID_ELEVATION)
+        };
+        let elevations = (0..usize::from(level.lon_count(zone)))
+            .map(|lon_idx| {
+                (0..usize::from(level.lat_count()))
+                    .map(|lat_idx| post(lat_idx, lon_idx))
+                    .collect()
+            })
+            .collect();
+        let mut builder =
+            DTEDBuilder::for_level(metadata.origin_angle, level).elevations(elevations);
+        if let Some(accuracy) = metadata.accuracy {
+            builder = builder.accuracy(accuracy);
+        }
+        if let Some(dsi) = &metadata.dsi_record {
+            builder = builder
+                .security_classification(dsi.security_classification)
+                .producer_code(dsi.producer_code.trim());
+            if let Some(edition) = dsi.edition {
+                builder = builder.edition(edition);
+            }
+        }
+        builder.build()
+    }
+}
@@ -0,0 +1,543 @@
+//! Contains the [ElevationSource] trait, providing elevation queries over any
+//! storage of DTED posts (in memory, memory-mapped, ...).
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDMetadata, Post};
+#[cfg(feature = "std")]
+use crate::geodesy::LineType;
+#[cfg(feature = "egm96")]
+use crate::geoid::Geoid;
+use crate::interpolation::Interpolation;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::primitives::Elevation;
+#[cfg(feature = "std")]
+use crate::profile::{self, ProfileSample};
+#[cfg(feature = "std")]
+use crate::visibility::{self, LineOfSight};
+
+/// A source of DTED posts, described by its [DTEDMetadata]
+///
+/// Implementors only provide access to the raw posts, and get the
+/// elevation queries by geographic coordinate for free.
+pub trait ElevationSource {
+    /// Get the [DTEDMetadata] describing the posts
+    fn metadata(&self) -> &DTEDMetadata;
+
+    /// Get the raw elevation (in meters) of a post, void posts included
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Raw elevation or None if out of bounds
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16>;
+
+    /// Get the bounds of the posts, see [DTEDMetadata::bounds]
+    ///
+    /// # Returns
+    ///
+    /// * `(south, west, north, east)`, in degrees
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert_eq!(dted_data.bounds(), (42.0, 15.0, 43.0, 16.0));
+    /// ```
+    fn bounds(&self) -> (f64, f64, f64, f64) {
+        self.metadata().bounds()
+    }
+
+    /// Whether a lat/lon is within the bounds of the posts (edges included),
+    /// such that [ElevationSource::elevation_at] does not return None for
+    /// being out of bounds
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.contains(42.52, 15.75));
+    /// assert!(dted_data.contains(43.0, 16.0));
+    /// assert!(!dted_data.contains(43.0, 16.001));
+    /// ```
+    fn contains<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> bool {
+        self.metadata().contains(lat, lon)
+    }
+
+    /// Get the indices of the post nearest to a lat/lon,
+    /// see [DTEDMetadata::index_of]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(lat_index, lon_index)` or None if out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let (lat_idx, lon_idx) = dted_data.index_of(-29.5, -177.25).unwrap();
+    /// assert_eq!((lat_idx, lon_idx), (60, 90));
+    /// assert_eq!(dted_data.position_of(lat_idx, lon_idx), (-29.5, -177.25));
+    /// assert_eq!(dted_data.elevation(lat_idx, lon_idx), dted_data.elevation_at(-29.5, -177.25));
+    /// ```
+    fn index_of<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(usize, usize)> {
+        self.metadata().index_of(lat, lon)
+    }
+
+    /// Get the lat/lon of a post, see [DTEDMetadata::position_of]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * `(lat, lon)` of the post, in degrees
+    fn position_of(&self, lat_idx: usize, lon_idx: usize) -> (f64, f64) {
+        self.metadata().position_of(lat_idx, lon_idx)
+    }
+
+    /// Get a [Post], telling void posts apart from elevations
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * [Post] or None if out of bounds
+    fn post(&self, lat_idx: usize, lon_idx: usize) -> Option<Post> {
+        self.raw_elevation(lat_idx, lon_idx).map(Post::from_raw)
+    }
+
+    /// Get the elevation (in meters) of a post
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index, from south to north
+    /// * `lon_idx` - longitude index, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Elevation or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert_eq!(dted_data.elevation(10, 20), dted_data.data.get(10, 20));
+    /// assert_eq!(dted_data.elevation(3601, 0), None);
+    /// ```
+    fn elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.post(lat_idx, lon_idx)?.elevation()
+    }
+
+    /// Get the elevation of the post nearest to a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) of the nearest post, or None if out of bounds
+    ///   or the nearest post is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.elevation_at(42.52, 15.75).is_some());
+    /// assert!(dted_data.elevation_at(0.0, 0.0).is_none());
+    /// // on a post, the nearest post and the interpolated elevation agree
+    /// assert_eq!(
+    ///     dted_data.elevation_at(42.5, 15.5).map(f64::from),
+    ///     dted_data.get_elevation(42.5, 15.5),
+    /// );
+    /// ```
+    fn elevation_at<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<i16> {
+        let (lat_idx, lon_idx) = self.index_of(lat, lon)?;
+        self.elevation(lat_idx, lon_idx)
+    }
+
+    /// Get the elevation of the post nearest to a lat/lon, along with the
+    /// absolute vertical accuracy applicable there, see
+    /// [DTEDMetadata::vertical_accuracy_at], for error budgets
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) of the nearest post and its accuracy (in meters,
+    ///   or None if not available), or None if out of bounds or the nearest post is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let (elevation, accuracy) = dted_data.elevation_with_accuracy(42.52, 15.75).unwrap();
+    /// assert_eq!(Some(elevation), dted_data.elevation_at(42.52, 15.75));
+    /// assert_eq!(accuracy, Some(5));
+    /// assert!(dted_data.elevation_with_accuracy(0.0, 0.0).is_none());
+    /// ```
+    fn elevation_with_accuracy<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+    ) -> Option<(i16, Option<u16>)> {
+        let (lat, lon) = (lat.into(), lon.into());
+        let elevation = self.elevation_at(lat, lon)?;
+        Some((elevation, self.metadata().vertical_accuracy_at(lat, lon)))
+    }
+
+    /// Get the elevations of the posts nearest to many lat/lons,
+    /// see [ElevationSource::elevation_at]
+    ///
+    /// The posts are queried in the order they are stored (longitude line by
+    /// longitude line), rather than in the order of the lat/lons, for locality
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - `(lat, lon)` pairs
+    ///
+    /// # Returns
+    ///
+    /// * Elevations (in meters) in the order of the lat/lons, each None if out
+    ///   of bounds or the nearest post is void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let elevations = dted_data.elevations_at(&[(42.52, 15.75), (0.0, 0.0), (42.1, 15.2)]);
+    /// assert_eq!(elevations[0], dted_data.elevation_at(42.52, 15.75));
+    /// assert_eq!(elevations[1], None);
+    /// assert_eq!(elevations[2], dted_data.elevation_at(42.1, 15.2));
+    /// ```
+    fn elevations_at(&self, points: &[(f64, f64)]) -> Vec<Option<i16>> {
+        let metadata = self.metadata();
+        let mut posts: Vec<(usize, usize, usize)> = points
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (lat, lon))| {
+                let (lat_idx, lon_idx) = metadata.index_of(*lat, *lon)?;
+                Some((lon_idx, lat_idx, idx))
+            })
+            .collect();
+        posts.sort_unstable();
+        let mut elevations = vec![None; points.len()];
+        for (lon_idx, lat_idx, idx) in posts {
+            elevations[idx] = self.elevation(lat_idx, lon_idx);
+        }
+        elevations
+    }
+
+    /// Get the bilinearly interpolated elevation at a lat/lon, from the
+    /// four surrounding posts
+    ///
+    /// Void posts are excluded from the interpolation, with the weights of
+    /// the remaining valid posts renormalized. If all of the posts with a
+    /// non-zero weight are void, the elevation is void
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.elevation_at_interpolated(42.52, 15.75).is_some());
+    /// assert!(dted_data.elevation_at_interpolated(0.0, 0.0).is_none());
+    /// ```
+    fn elevation_at_interpolated<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        self.elevation_at_with(lat, lon, Interpolation::Bilinear)
+    }
+
+    /// Get the elevation at a lat/lon, using the given [Interpolation] method
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// use dted2::interpolation::Interpolation;
+    ///
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.elevation_at_with(42.52, 15.75, Interpolation::Lanczos).is_some());
+    /// assert_eq!(
+    ///     dted_data.elevation_at_with(42.52, 15.75, Interpolation::Nearest),
+    ///     dted_data.elevation_at(42.52, 15.75).map(f64::from),
+    /// );
+    /// ```
+    fn elevation_at_with<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+        method: Interpolation,
+    ) -> Option<f64> {
+        let (lat_idx, lon_idx) = self.metadata().get_indices(lat, lon)?;
+        method.interpolate(lat_idx, lon_idx, |lat, lon| {
+            let lat = usize::try_from(lat).ok()?;
+            let lon = usize::try_from(lon).ok()?;
+            self.elevation(lat, lon).map(f64::from)
+        })
+    }
+
+    /// Get the elevation at a lat/lon as an [Elevation], to be read in
+    /// meters or feet, see [ElevationSource::elevation_at_with]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * [Elevation] or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::interpolation::Interpolation;
+    /// use dted2::primitives::ElevationUnit;
+    /// use dted2::{DTEDData, ElevationSource};
+    ///
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let meters = dted_data.elevation_at(42.5, 15.5).unwrap();
+    /// let elevation = dted_data
+    ///     .elevation_at_typed(42.5, 15.5, Interpolation::Nearest)
+    ///     .unwrap();
+    /// assert_eq!(elevation.meters(), f64::from(meters));
+    /// assert_eq!(elevation.to(ElevationUnit::Feet), f64::from(meters) / 0.3048);
+    /// ```
+    fn elevation_at_typed<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+        method: Interpolation,
+    ) -> Option<Elevation> {
+        self.elevation_at_with(lat, lon, method)
+            .map(Elevation::from_meters)
+    }
+
+    /// Get the height above the WGS84 ellipsoid (as used by GNSS) at a
+    /// lat/lon, the bilinearly interpolated elevation above mean sea level
+    /// corrected by the undulation of a [Geoid] (requires the `egm96`
+    /// feature)
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `geoid` - [Geoid], e.g. the EGM96 grid read with [Geoid::read]
+    ///
+    /// # Returns
+    ///
+    /// * Height (in meters) or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::geoid::Geoid;
+    /// use dted2::{DTEDData, ElevationSource};
+    ///
+    /// // a geoid 40 m above the ellipsoid everywhere
+    /// let geoid = Geoid::new(90.0, vec![40.0; 12]).unwrap();
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert_eq!(
+    ///     dted_data.elevation_at_ellipsoidal(42.52, 15.75, &geoid),
+    ///     dted_data.elevation_at_interpolated(42.52, 15.75).map(|elev| elev + 40.0)
+    /// );
+    /// ```
+    #[cfg(feature = "egm96")]
+    fn elevation_at_ellipsoidal(&self, lat: f64, lon: f64, geoid: &Geoid) -> Option<f64> {
+        let elevation = self.elevation_at_interpolated(lat, lon)?;
+        Some(geoid.to_ellipsoidal(lat, lon, elevation))
+    }
+
+    /// Get the elevation profile along the great circle between two lat/lons,
+    /// bilinearly interpolated
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - `(lat, lon)` of the first sample
+    /// * `end` - `(lat, lon)` of the last sample
+    /// * `num_samples` - number of samples, including both ends
+    ///
+    /// # Returns
+    ///
+    /// * [ProfileSample]s, from the start to the end
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let profile = dted_data.profile((42.1, 15.1), (42.9, 15.9), 100);
+    /// assert_eq!(profile.len(), 100);
+    /// assert_eq!(profile[0].elevation, dted_data.elevation_at_interpolated(42.1, 15.1));
+    /// assert!(profile.windows(2).all(|s| s[0].distance < s[1].distance));
+    /// ```
+    #[cfg(feature = "std")]
+    fn profile(
+        &self,
+        start: (f64, f64),
+        end: (f64, f64),
+        num_samples: usize,
+    ) -> Vec<ProfileSample> {
+        self.profile_with(
+            start,
+            end,
+            num_samples,
+            LineType::GreatCircle,
+            Interpolation::Bilinear,
+        )
+    }
+
+    /// Get the elevation profile along a line between two lat/lons
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - `(lat, lon)` of the first sample
+    /// * `end` - `(lat, lon)` of the last sample
+    /// * `num_samples` - number of samples, including both ends
+    /// * `line` - [LineType] of the line
+    /// * `method` - [Interpolation] method
+    ///
+    /// # Returns
+    ///
+    /// * [ProfileSample]s, from the start to the end
+    #[cfg(feature = "std")]
+    fn profile_with(
+        &self,
+        start: (f64, f64),
+        end: (f64, f64),
+        num_samples: usize,
+        line: LineType,
+        method: Interpolation,
+    ) -> Vec<ProfileSample> {
+        profile::sample(start, end, num_samples, line, |lat, lon| {
+            self.elevation_at_with(lat, lon, method)
+        })
+    }
+
+    /// Get the line of sight between an observer and a target, see [LineOfSight]
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - `(lat, lon)` of the observer
+    /// * `observer_height` - height of the observer above the terrain, in meters
+    /// * `target` - `(lat, lon)` of the target
+    /// * `target_height` - height of the target above the terrain, in meters
+    ///
+    /// # Returns
+    ///
+    /// * [LineOfSight], or None if the elevation of the observer or target is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let los = dted_data.line_of_sight((42.1, 15.1), 2.0, (42.2, 15.2), 2.0).unwrap();
+    /// assert!(los.is_visible() == (los.clearance >= 0.0));
+    /// // looking down from high enough, nothing is in the way
+    /// assert!(dted_data.line_of_sight((42.1, 15.1), 10000.0, (42.2, 15.2), 0.0).unwrap().is_visible());
+    /// assert!(dted_data.line_of_sight((0.0, 0.0), 2.0, (42.2, 15.2), 2.0).is_none());
+    /// ```
+    #[cfg(feature = "std")]
+    fn line_of_sight(
+        &self,
+        observer: (f64, f64),
+        observer_height: f64,
+        target: (f64, f64),
+        target_height: f64,
+    ) -> Option<LineOfSight> {
+        let distance = LineType::GreatCircle.distance(observer, target);
+        let num_samples = visibility::num_samples(self.metadata(), distance);
+        let profile = self.profile(observer, target, num_samples);
+        LineOfSight::from_profile(&profile, observer_height, target_height)
+    }
+
+    /// Get the horizon around a point on the terrain, see [visibility::horizon]
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - `(lat, lon)` of the observer, on the terrain
+    /// * `azimuth_step` - angle between azimuths, in degrees
+    /// * `max_range` - range of the terrain masking the horizon, in meters
+    ///
+    /// # Returns
+    ///
+    /// * Masking angle (in degrees above the horizontal) per azimuth, clockwise
+    ///   from north, None if the elevation of the observer or of the terrain
+    ///   in that azimuth is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{DTEDData, ElevationSource};
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// let horizon = dted_data.horizon_profile((42.5, 15.5), 10.0, 1000.0);
+    /// assert_eq!(horizon.len(), 36);
+    /// assert!(horizon.iter().all(|angle| angle.is_some()));
+    /// ```
+    #[cfg(feature = "std")]
+    fn horizon_profile(
+        &self,
+        point: (f64, f64),
+        azimuth_step: f64,
+        max_range: f64,
+    ) -> Vec<Option<f64>> {
+        let spacing = visibility::sample_spacing(self.metadata());
+        visibility::horizon(point, azimuth_step, max_range, spacing, |lat, lon| {
+            self.elevation_at_interpolated(lat, lon)
+        })
+    }
+}
@@ -0,0 +1,454 @@
+//! Contains the [Statistics] of the elevations of a cell, computed in a
+//! single pass over its posts (in parallel with the `rayon` feature), for
+//! catalogs and quality assurance, along with their [Histogram] and
+//! percentiles.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+#[cfg(feature = "geo-types")]
+use geo_types::Polygon;
+use std::ops::Range;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDData, DTED_VOID_ELEVATION};
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Number of posts accumulated by each task when in parallel
+#[cfg(feature = "rayon")]
+const STATISTICS_CHUNK_LEN: usize = 1 << 16;
+/// Number of distinct 16-bit elevations
+const ELEVATION_VALUES: usize = 1 << 16;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Statistics of the elevations of a cell
+///
+/// # Fields
+///
+/// * `count` - number of (non-void) elevations
+/// * `void_count` - number of void posts
+/// * `min` - lowest elevation (in meters), None if there are none
+/// * `max` - highest elevation (in meters), None if there are none
+/// * `mean` - mean elevation (in meters), None if there are none
+/// * `std_dev` - (population) standard deviation of the elevations (in
+///   meters), None if there are none
+pub struct Statistics {
+    pub count: usize,
+    pub void_count: usize,
+    pub min: Option<i16>,
+    pub max: Option<i16>,
+    pub mean: Option<f64>,
+    pub std_dev: Option<f64>,
+}
+impl Statistics {
+    /// Compute the statistics of elevations
+    ///
+    /// # Arguments
+    ///
+    /// * `elevations` - raw elevations, void posts included
+    ///
+    /// # Returns
+    ///
+    /// * [Statistics] of the elevations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::DTED_VOID_ELEVATION;
+    /// use dted2::statistics::Statistics;
+    ///
+    /// let stats = Statistics::of(&[10, DTED_VOID_ELEVATION, 20, 30, 40]);
+    /// assert_eq!((stats.count, stats.void_count), (4, 1));
+    /// assert_eq!((stats.min, stats.max), (Some(10), Some(40)));
+    /// assert_eq!(stats.mean, Some(25.0));
+    /// assert_eq!(stats.std_dev, Some(125.0_f64.sqrt()));
+    /// assert_eq!(Statistics::of(&[DTED_VOID_ELEVATION]).mean, None);
+    /// ```
+    pub fn of(elevations: &[i16]) -> Statistics {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            elevations
+                .par_chunks(STATISTICS_CHUNK_LEN)
+                .map(|chunk| Accumulator::of(chunk.iter().copied()))
+                .reduce(Accumulator::default, Accumulator::merge)
+                .statistics()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            Accumulator::of(elevations.iter().copied()).statistics()
+        }
+    }
+
+    /// Get the fraction (from 0 to 1) of the posts which are void
+    ///
+    /// # Returns
+    ///
+    /// * Fraction of void posts, 0 if there are no posts at all
+    pub fn void_fraction(&self) -> f64 {
+        match self.count + self.void_count {
+            0 => 0.0,
+            len => self.void_count as f64 / len as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Histogram of elevations, in bins of equal width aligned on multiples of
+/// the width (e.g. 0 to 100 m, 100 to 200 m, ...)
+///
+/// # Fields
+///
+/// * `bin_width` - width of the bins, in meters
+/// * `start` - lower edge of the first bin, in meters
+/// * `counts` - number of elevations in each bin, from the lowest to the
+///   highest, empty if there are no elevations
+pub struct Histogram {
+    pub bin_width: u16,
+    pub start: i32,
+    pub counts: Vec<usize>,
+}
+impl Histogram {
+    /// Compute the histogram of elevations, skipping voids
+    ///
+    /// # Arguments
+    ///
+    /// * `elevations` - raw elevations, void posts included
+    /// * `bin_width` - width of the bins, in meters
+    ///
+    /// # Returns
+    ///
+    /// * [Histogram] of the elevations
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `bin_width` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::DTED_VOID_ELEVATION;
+    /// use dted2::statistics::Histogram;
+    ///
+    /// let histogram = Histogram::of(&[-5, 12, DTED_VOID_ELEVATION, 19, 20, 45], 10);
+    /// assert_eq!(histogram.start, -10);
+    /// assert_eq!(histogram.counts, vec![1, 0, 2, 1, 0, 1]);
+    /// let bins: Vec<_> = histogram.bins().collect();
+    /// assert_eq!(bins[2], (10..20, 2));
+    /// assert_eq!(histogram.total(), 5);
+    /// ```
+    pub fn of(elevations: &[i16], bin_width: u16) -> Histogram {
+        assert!(bin_width > 0, "the bin width of a histogram must not be 0");
+        let width = i32::from(bin_width);
+        let counts = value_counts(elevations);
+        let bin = |value: usize| (value as i32 + i32::from(i16::MIN)).div_euclid(width);
+        let mut values = (0..ELEVATION_VALUES).filter(|&value| counts[value] > 0);
+        let Some(first) = values.next() else {
+            return Histogram {
+                bin_width,
+                start: 0,
+                counts: Vec::new(),
+            };
+        };
+        let last = values.next_back().unwrap_or(first);
+        let (first_bin, last_bin) = (bin(first), bin(last));
+        let mut bins = vec![0; (last_bin - first_bin + 1) as usize];
+        for value in first..=last {
+            bins[(bin(value) - first_bin) as usize] += counts[value];
+        }
+        Histogram {
+            bin_width,
+            start: first_bin * width,
+            counts: bins,
+        }
+    }
+
+    /// Iterate over the bins
+    ///
+    /// # Returns
+    ///
+    /// * `(range, count)` of each bin, the range of elevations (in meters)
+    ///   excluding its upper edge
+    pub fn bins(&self) -> impl Iterator<Item = (Range<i32>, usize)> + '_ {
+        let width = i32::from(self.bin_width);
+        self.counts.iter().enumerate().map(move |(idx, &count)| {
+            let lower = self.start + idx as i32 * width;
+            (lower..lower + width, count)
+        })
+    }
+
+    /// Get the number of elevations in all of the bins
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+/// Get the percentiles of elevations, skipping voids, linearly interpolated
+/// between the closest ranks (as numpy's default)
+///
+/// # Arguments
+///
+/// * `elevations` - raw elevations, void posts included
+/// * `percents` - percentiles, from 0 to 100
+///
+/// # Returns
+///
+/// * Elevation (in meters) at each percentile, None if there are no
+///   elevations or the percentile is not between 0 and 100
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTED_VOID_ELEVATION;
+/// use dted2::statistics;
+///
+/// let elevations = [40, 10, DTED_VOID_ELEVATION, 30, 20];
+/// assert_eq!(
+///     statistics::percentiles(&elevations, &[0.0, 50.0, 75.0, 100.0, 101.0]),
+///     vec![Some(10.0), Some(25.0), Some(32.5), Some(40.0), None]
+/// );
+/// ```
+pub fn percentiles(elevations: &[i16], percents: &[f64]) -> Vec<Option<f64>> {
+    let counts = value_counts(elevations);
+    let total: usize = counts.iter().sum();
+    // the elevation of a rank, from 0, walking the cumulative counts
+    let ranked = |rank: usize| {
+        let mut seen = 0;
+        let value = counts.iter().position(|&count| {
+            seen += count;
+            seen > rank
+        })?;
+        Some(f64::from(value as i32 + i32::from(i16::MIN)))
+    };
+    percents
+        .iter()
+        .map(|&percent| {
+            if total == 0 || !(0.0..=100.0).contains(&percent) {
+                return None;
+            }
+            let rank = percent / 100.0 * (total - 1) as f64;
+            let (lower, upper) = (
+                ranked(rank.floor() as usize)?,
+                ranked(rank.ceil() as usize)?,
+            );
+            Some(lower + (upper - lower) * rank.fract())
+        })
+        .collect()
+}
+
+/// Count each distinct elevation, skipping voids, indexed by the elevation
+/// offset by 32768
+fn value_counts(elevations: &[i16]) -> Vec<usize> {
+    let mut counts = vec![0; ELEVATION_VALUES];
+    for &elev in elevations {
+        if elev != DTED_VOID_ELEVATION {
+            counts[(i32::from(elev) - i32::from(i16::MIN)) as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// Running sums of elevations, exact (being integers) and mergeable
+#[derive(Default)]
+struct Accumulator {
+    count: usize,
+    void_count: usize,
+    min: Option<i16>,
+    max: Option<i16>,
+    sum: i64,
+    sum_sq: i64,
+}
+impl Accumulator {
+    /// Accumulate elevations
+    fn of(elevations: impl IntoIterator<Item = i16>) -> Accumulator {
+        let mut acc = Accumulator::default();
+        for elev in elevations {
+            if elev == DTED_VOID_ELEVATION {
+                acc.void_count += 1;
+                continue;
+            }
+            acc.count += 1;
+            acc.min = Some(acc.min.map_or(elev, |min| min.min(elev)));
+            acc.max = Some(acc.max.map_or(elev, |max| max.max(elev)));
+            acc.sum += i64::from(elev);
+            acc.sum_sq += i64::from(elev) * i64::from(elev);
+        }
+        acc
+    }
+
+    /// Merge the sums of two disjoint sets of elevations
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    fn merge(self, other: Accumulator) -> Accumulator {
+        Accumulator {
+            count: self.count + other.count,
+            void_count: self.void_count + other.void_count,
+            min: self.min.into_iter().chain(other.min).min(),
+            max: self.max.into_iter().chain(other.max).max(),
+            sum: self.sum + other.sum,
+            sum_sq: self.sum_sq + other.sum_sq,
+        }
+    }
+
+    /// Get the statistics of the sums
+    fn statistics(self) -> Statistics {
+        let (mean, std_dev) = match self.count {
+            0 => (None, None),
+            count => {
+                // the variance of integers, computed exactly before dividing
+                let (n, sum) = (count as i128, i128::from(self.sum));
+                let spread = n * i128::from(self.sum_sq) - sum * sum;
+                let variance = spread as f64 / (n * n) as f64;
+                (Some(self.sum as f64 / count as f64), Some(variance.sqrt()))
+            }
+        };
+        Statistics {
+            count: self.count,
+            void_count: self.void_count,
+            min: self.min,
+            max: self.max,
+            mean,
+            std_dev,
+        }
+    }
+}
+
+impl DTEDData {
+    /// Compute the [Statistics] of the elevations in a single pass
+    ///
+    /// # Returns
+    ///
+    /// * [Statistics] of the elevations, voids counted apart
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let stats = data.statistics();
+    /// assert_eq!(stats.count + stats.void_count, 121 * 121);
+    /// assert_eq!(stats.void_count, data.void_count());
+    /// assert!(stats.min <= stats.max);
+    /// ```
+    pub fn statistics(&self) -> Statistics {
+        Statistics::of(&self.data.elevations)
+    }
+
+    /// Compute the [Statistics] of the elevations of the posts within a
+    /// polygon (requires the `geo-types` feature), rasterized with
+    /// [DTEDData::polygon_mask], e.g. to summarize an area of interest
+    ///
+    /// # Arguments
+    ///
+    /// * `polygon` - [Polygon] of `x` = longitude, `y` = latitude coordinates
+    ///
+    /// # Returns
+    ///
+    /// * [Statistics] of the elevations within the polygon, voids counted
+    ///   apart (see [Statistics::void_fraction])
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use geo_types::polygon;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let polygon = polygon![
+    ///     (x: -178.0, y: -30.0),
+    ///     (x: -177.5, y: -30.0),
+    ///     (x: -177.5, y: -29.5),
+    /// ];
+    /// let stats = data.statistics_in_polygon(&polygon);
+    /// // the south west post, on the boundary, is within
+    /// assert!(stats.count > 0 && stats.min <= data.data.get(0, 0));
+    /// assert_eq!(stats.void_fraction(), 0.0);
+    /// ```
+    #[cfg(feature = "geo-types")]
+    pub fn statistics_in_polygon(&self, polygon: &Polygon<f64>) -> Statistics {
+        let mask = self.polygon_mask(polygon);
+        Accumulator::of(
+            self.data
+                .elevations
+                .iter()
+                .zip(mask)
+                .filter_map(|(&elev, inside)| inside.then_some(elev)),
+        )
+        .statistics()
+    }
+
+    /// Compute the [Histogram] of the elevations, skipping voids
+    ///
+    /// # Arguments
+    ///
+    /// * `bin_width` - width of the bins, in meters
+    ///
+    /// # Returns
+    ///
+    /// * [Histogram] of the elevations
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `bin_width` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let histogram = data.histogram(100);
+    /// assert_eq!(histogram.total(), 121 * 121);
+    /// assert_eq!(histogram.start % 100, 0);
+    /// ```
+    pub fn histogram(&self, bin_width: u16) -> Histogram {
+        Histogram::of(&self.data.elevations, bin_width)
+    }
+
+    /// Get the elevation at a percentile, skipping voids, see [percentiles]
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - percentile, from 0 to 100
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters), or None if there are no elevations or the
+    ///   percentile is not between 0 and 100
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let stats = data.statistics();
+    /// assert_eq!(data.percentile(0.0), stats.min.map(f64::from));
+    /// assert_eq!(data.percentile(100.0), stats.max.map(f64::from));
+    /// assert!(data.percentile(50.0) <= data.percentile(95.0));
+    /// ```
+    pub fn percentile(&self, percent: f64) -> Option<f64> {
+        percentiles(&self.data.elevations, &[percent])[0]
+    }
+
+    /// Get the elevations at many percentiles, counting the elevations once,
+    /// see [percentiles]
+    ///
+    /// # Arguments
+    ///
+    /// * `percents` - percentiles, from 0 to 100
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) at each percentile, None if there are no
+    ///   elevations or the percentile is not between 0 and 100
+    pub fn percentiles(&self, percents: &[f64]) -> Vec<Option<f64>> {
+        percentiles(&self.data.elevations, percents)
+    }
+}
@@ -0,0 +1,273 @@
+//! Contains [DTEDStreamReader], for incrementally parsing DTED from any [io::Read].
+//!
+//! The stream is read in chunks and parsed section by section, such that
+//! DTED can be parsed from pipes, sockets, compressed streams, etc.
+//! without buffering the whole file. With the `flate2` feature, gzip
+//! compressed streams are recognized and decompressed, see [crate::gzip].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::io;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers;
+use crate::Error as DTEDError;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Minimum number of bytes read from the stream at once
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Stream read by a [DTEDStreamReader], decompressed if need be
+#[cfg(feature = "flate2")]
+type Input<R> = crate::gzip::MaybeGzDecoder<R>;
+/// Stream read by a [DTEDStreamReader]
+#[cfg(not(feature = "flate2"))]
+type Input<R> = R;
+
+/// Streaming DTED reader
+///
+/// The header records are parsed when creating the reader, with the data
+/// records then parsed (and their checksums validated) one at a time by
+/// iterating over the reader.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::stream::DTEDStreamReader;
+///
+/// let file = std::fs::File::open("tests/test_data.dt2").unwrap();
+/// let mut reader = DTEDStreamReader::new(file).unwrap();
+/// assert_eq!(reader.metadata().count.lon, 3601);
+/// let first = reader.next().unwrap().unwrap();
+/// assert_eq!(first.elevations.len(), 3601);
+/// assert_eq!(reader.count(), 3600);
+/// ```
+pub struct DTEDStreamReader<R: io::Read> {
+    stream: StreamBuffer<R>,
+    metadata: DTEDMetadata,
+    remaining: usize,
+}
+impl<R: io::Read> DTEDStreamReader<R> {
+    /// Create a streaming reader, parsing the header records from the stream
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - stream to read the DTED from, possibly gzip compressed
+    ///   (with the `flate2` feature)
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDStreamReader]: streaming reader, positioned at the first data record
+    pub fn new(reader: R) -> Result<DTEDStreamReader<R>, DTEDError> {
+        #[cfg(feature = "flate2")]
+        let reader = crate::gzip::MaybeGzDecoder::new(reader)?;
+        let mut stream = StreamBuffer {
+            reader,
+            buffer: ParseBuffer::default(),
+        };
+        let (header, dsi_record, acc_record) = stream.parse(parsers::fixed_length(
+            DTED_DATA_OFFSET,
+            parsers::dted_headers_parser,
+        ))?;
+        stream.buffer.set_lat_count(header.count.lat);
+        Ok(DTEDStreamReader {
+            stream,
+            metadata: DTEDMetadata::from_records(&header, dsi_record, acc_record, ""),
+            remaining: header.count.lon as usize,
+        })
+    }
+
+    /// Get the [DTEDMetadata] parsed from the header records
+    ///
+    /// The filename is empty, as the DTED is read from a stream
+    pub fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    /// Read all of the (remaining) data records into [DTEDData]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::stream::DTEDStreamReader;
+    ///
+    /// let file = std::fs::File::open("tests/test_data.dt2").unwrap();
+    /// let data = DTEDStreamReader::new(file).unwrap().read_data().unwrap();
+    /// let expected = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(data.data == expected.data);
+    /// ```
+    pub fn read_data(mut self) -> Result<DTEDData, DTEDError> {
+        let mut data =
+            RawDTEDRecords::with_capacity(self.metadata.count.lat as usize, self.remaining);
+        for record in self.by_ref() {
+            data.push(record?)?;
+        }
+        Ok(DTEDData::new(self.metadata, data))
+    }
+}
+impl<R: io::Read> Iterator for DTEDStreamReader<R> {
+    type Item = Result<RawDTEDRecord, DTEDError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let lat_count = self.metadata.count.lat;
+        let record = self.stream.parse(parsers::fixed_length(
+            dted_record_length(lat_count),
+            |input| parsers::parse_dted_record(input, lat_count as usize),
+        ));
+        let index = self.metadata.count.lon as usize - self.remaining;
+        let record = record.and_then(|record| match record.compute_checksum() {
+            computed if computed == record.checksum => Ok(record),
+            computed => Err(DTEDError::BadChecksum {
+                record: index,
+                stored: record.checksum,
+                computed,
+            }),
+        });
+        // stop at the first error, as when reading the whole file
+        self.remaining = match record.is_ok() {
+            true => self.remaining - 1,
+            false => 0,
+        };
+        Some(record)
+    }
+}
+
+/// Buffer over a stream, holding the bytes read but not parsed yet
+///
+/// # Fields
+///
+/// * `reader` - stream
+/// * `buffer` - bytes read from the stream
+struct StreamBuffer<R: io::Read> {
+    reader: Input<R>,
+    buffer: ParseBuffer,
+}
+impl<R: io::Read> StreamBuffer<R> {
+    /// Parse the next section of the stream, reading more of it until
+    /// the parser no longer returns [nom::Err::Incomplete]
+    fn parse<O, F>(&mut self, parser: F) -> Result<O, DTEDError>
+    where
+        F: Fn(&[u8]) -> nom::IResult<&[u8], O>,
+    {
+        loop {
+            match self.buffer.try_parse(&parser)? {
+                Parsed::Done(output) => return Ok(output),
+                Parsed::Needs(needed) => {
+                    let required = self.buffer.reserve(needed);
+                    while !self.buffer.is_filled(required) {
+                        match io::Read::read(&mut self.reader, self.buffer.spare()) {
+                            Ok(n) => self.buffer.advance(n),
+                            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                            Err(e) => return Err(e.into()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Outcome of parsing from a [ParseBuffer]
+pub(crate) enum Parsed<O> {
+    /// The section was parsed
+    Done(O),
+    /// At least this many more bytes are needed to parse the section
+    Needs(usize),
+}
+
+/// Buffer of bytes read from a stream, parsed section by section
+///
+/// # Fields
+///
+/// * `buffer` - bytes read from the stream (and spare room to read into)
+/// * `start` - start of the bytes not parsed yet
+/// * `end` - end of the bytes read
+/// * `eof` - whether the end of the stream was reached
+/// * `offset` - offset of the buffer within the stream
+/// * `lat_count` - number of latitude points per data record, once known
+#[derive(Default)]
+pub(crate) struct ParseBuffer {
+    buffer: Vec<u8>,
+    start: usize,
+    end: usize,
+    eof: bool,
+    offset: usize,
+    lat_count: Option<u16>,
+}
+impl ParseBuffer {
+    /// Set the number of latitude points per data record, for locating errors
+    pub(crate) fn set_lat_count(&mut self, lat_count: u16) {
+        self.lat_count = Some(lat_count);
+    }
+
+    /// Try to parse the next section from the bytes read so far
+    ///
+    /// # Returns
+    ///
+    /// * The parsed section, or the number of bytes needed to parse it.
+    ///   An incomplete section at the end of the stream is an error
+    pub(crate) fn try_parse<O, F>(&mut self, parser: F) -> Result<Parsed<O>, DTEDError>
+    where
+        F: Fn(&[u8]) -> nom::IResult<&[u8], O>,
+    {
+        let input = &self.buffer[self.start..self.end];
+        let offset = self.offset + self.start;
+        match parser(input) {
+            Ok((rest, output)) => {
+                self.start = self.end - rest.len();
+                Ok(Parsed::Done(output))
+            }
+            Err(nom::Err::Incomplete(needed)) if !self.eof => Ok(Parsed::Needs(match needed {
+                nom::Needed::Size(size) => size.get(),
+                nom::Needed::Unknown => 1,
+            })),
+            Err(e) => Err(DTEDError::locate(e, input, offset, self.lat_count)),
+        }
+    }
+
+    /// Discard the parsed bytes, and make room to read at least `needed` more
+    ///
+    /// # Returns
+    ///
+    /// * The end of the bytes read once the needed bytes are read,
+    ///   see [ParseBuffer::is_filled]
+    pub(crate) fn reserve(&mut self, needed: usize) -> usize {
+        self.buffer.copy_within(self.start..self.end, 0);
+        self.offset += self.start;
+        self.end -= self.start;
+        self.start = 0;
+        self.buffer
+            .resize(self.end + needed.max(STREAM_CHUNK_SIZE), 0);
+        self.end + needed
+    }
+
+    /// Whether the bytes read reach `required` (or the stream ended)
+    pub(crate) fn is_filled(&self, required: usize) -> bool {
+        self.eof || self.end >= required
+    }
+
+    /// Spare room to read into
+    pub(crate) fn spare(&mut self) -> &mut [u8] {
+        &mut self.buffer[self.end..]
+    }
+
+    /// Commit `n` bytes read into the spare room, 0 meaning the stream ended
+    pub(crate) fn advance(&mut self, n: usize) {
+        self.end += n;
+        self.eof |= n == 0;
+    }
+}
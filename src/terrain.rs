@@ -0,0 +1,501 @@
+//! Contains the terrain analyses deriving a [Raster] from the elevations of
+//! a cell, such as the [slope], [aspect] and [hillshade], or the
+//! [ruggedness] and [topographic_position] indices, or the [color_relief]
+//! coloring the elevations with a [Palette].
+//!
+//! Derivatives are computed over the 3x3 neighborhood of each post, with the
+//! angular post spacing converted to meters at the latitude of the post. Along
+//! the edges of the cell, the missing neighbors are replaced by one-sided
+//! differences, while any void post in the neighborhood voids the result.
+//!
+//! Indices are computed over a square window of posts around each post,
+//! cut by the edges of the cell, from the non-void neighbors in the window.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::geodesy::EARTH_RADIUS_M;
+use crate::primitives::AxisElement;
+use crate::raster::Raster;
+use crate::source::ElevationSource;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+/// Units of a slope
+///
+/// * Degrees - angle from the horizontal, 0 to 90
+/// * Percent - rise over run, times 100
+pub enum SlopeUnits {
+    #[default]
+    Degrees,
+    Percent,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+/// Color ramp of a [color_relief], from the lowest to the highest elevation
+///
+/// * Terrain - green lowlands, through yellow and brown, to white peaks
+/// * Grayscale - black to white
+/// * Viridis - the perceptually uniform dark blue to yellow ramp
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain::Palette;
+///
+/// assert_eq!(Palette::Grayscale.color(0.0), [0, 0, 0]);
+/// assert_eq!(Palette::Grayscale.color(0.5), [128, 128, 128]);
+/// assert_eq!(Palette::Terrain.color(1.0), [255, 255, 255]);
+/// assert_eq!(Palette::Viridis.color(2.0), Palette::Viridis.color(1.0));
+/// ```
+pub enum Palette {
+    #[default]
+    Terrain,
+    Grayscale,
+    Viridis,
+}
+impl Palette {
+    /// Get the color of a fraction of the ramp, linearly interpolated
+    /// between the stops of the palette
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - position along the ramp, clamped to 0 (lowest) to 1
+    ///   (highest)
+    ///
+    /// # Returns
+    ///
+    /// * RGB color
+    pub fn color(self, fraction: f64) -> [u8; 3] {
+        let stops = self.stops();
+        let fraction = fraction.clamp(0.0, 1.0);
+        let upper = stops
+            .iter()
+            .position(|(stop, _)| *stop >= fraction)
+            .unwrap_or(stops.len() - 1)
+            .max(1);
+        let ((low, low_color), (high, high_color)) = (stops[upper - 1], stops[upper]);
+        let t = (fraction - low) / (high - low);
+        [0, 1, 2].map(|channel| {
+            let (low, high) = (
+                f64::from(low_color[channel]),
+                f64::from(high_color[channel]),
+            );
+            (low + t * (high - low)).round() as u8
+        })
+    }
+
+    /// Get the stops of the palette: fractions of the ramp and their colors
+    fn stops(self) -> &'static [(f64, [u8; 3])] {
+        match self {
+            Palette::Terrain => &[
+                (0.0, [0, 97, 71]),
+                (0.2, [16, 122, 47]),
+                (0.4, [232, 215, 125]),
+                (0.6, [161, 67, 0]),
+                (0.8, [130, 30, 30]),
+                (1.0, [255, 255, 255]),
+            ],
+            Palette::Grayscale => &[(0.0, [0, 0, 0]), (1.0, [255, 255, 255])],
+            Palette::Viridis => &[
+                (0.0, [68, 1, 84]),
+                (0.25, [59, 82, 139]),
+                (0.5, [33, 145, 140]),
+                (0.75, [94, 201, 98]),
+                (1.0, [253, 231, 37]),
+            ],
+        }
+    }
+}
+
+/// Get the slope of every post, using Horn's method
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `units` - [SlopeUnits]
+///
+/// # Returns
+///
+/// * [Raster] of the slopes, None where the neighborhood is void
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain::{self, SlopeUnits};
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let slope = terrain::slope(&data, SlopeUnits::Degrees);
+/// assert_eq!(slope.count.lat, 121);
+/// assert!(slope.values.iter().flatten().all(|slope| (0.0..=90.0).contains(slope)));
+/// ```
+pub fn slope<S: ElevationSource>(source: &S, units: SlopeUnits) -> Raster<Option<f64>> {
+    gradients(source).map(|gradient| {
+        let (dz_dx, dz_dy) = (*gradient)?;
+        let rise = dz_dx.hypot(dz_dy);
+        Some(match units {
+            SlopeUnits::Degrees => rise.atan().to_degrees(),
+            SlopeUnits::Percent => rise * 100.0,
+        })
+    })
+}
+
+/// Get the aspect of every post, the downslope direction using Horn's method
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+///
+/// # Returns
+///
+/// * [Raster] of the aspects, in degrees clockwise from north (0 to 360),
+///   None where the neighborhood is void or flat
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let aspect = terrain::aspect(&data);
+/// assert_eq!(aspect.count.lon, 121);
+/// assert!(aspect.values.iter().flatten().all(|aspect| (0.0..360.0).contains(aspect)));
+/// ```
+pub fn aspect<S: ElevationSource>(source: &S) -> Raster<Option<f64>> {
+    gradients(source).map(|gradient| {
+        let (dz_dx, dz_dy) = (*gradient)?;
+        match dz_dx == 0.0 && dz_dy == 0.0 {
+            true => None,
+            // the gradient points upslope
+            false => Some((-dz_dx).atan2(-dz_dy).to_degrees().rem_euclid(360.0)),
+        }
+    })
+}
+
+/// Get the shaded relief of every post, lit by a distant light source
+///
+/// The shades are those of a matte surface, from 1 (facing away from the
+/// light) to 255 (facing the light), leaving 0 for void neighborhoods as in
+/// the usual GIS tools
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `azimuth` - direction of the light, in degrees clockwise from north
+/// * `altitude` - angle of the light above the horizon, in degrees
+/// * `z_factor` - exaggeration of the elevations
+///
+/// # Returns
+///
+/// * [Raster] of the shades
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let shades = terrain::hillshade(&data, 315.0, 45.0, 1.0);
+/// assert_eq!(shades.values.len(), 121 * 121);
+/// assert!(shades.values.iter().all(|shade| *shade > 0));
+/// ```
+pub fn hillshade<S: ElevationSource>(
+    source: &S,
+    azimuth: f64,
+    altitude: f64,
+    z_factor: f64,
+) -> Raster<u8> {
+    let (azimuth, altitude) = (azimuth.to_radians(), altitude.to_radians());
+    // (east, north, up) unit vector towards the light
+    let light = (
+        azimuth.sin() * altitude.cos(),
+        azimuth.cos() * altitude.cos(),
+        altitude.sin(),
+    );
+    gradients(source).map(|gradient| match gradient {
+        Some((dz_dx, dz_dy)) => {
+            let (nx, ny) = (-z_factor * dz_dx, -z_factor * dz_dy);
+            let norm = nx.hypot(ny).hypot(1.0);
+            let illumination = (nx * light.0 + ny * light.1 + light.2) / norm;
+            1 + (254.0 * illumination.max(0.0)).round() as u8
+        }
+        None => 0,
+    })
+}
+
+/// Get the terrain ruggedness index (TRI) of every post: the mean absolute
+/// elevation difference to its neighbors
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `radius` - number of posts between the post and the edge of the window,
+///   1 for the usual 3x3 window
+///
+/// # Returns
+///
+/// * [Raster] of the indices, in meters, None where the post is void or has
+///   no non-void neighbors
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let tri = terrain::ruggedness(&data, 1);
+/// assert_eq!(tri.values.len(), 121 * 121);
+/// assert!(tri.values.iter().flatten().all(|tri| *tri >= 0.0));
+/// ```
+pub fn ruggedness<S: ElevationSource>(source: &S, radius: usize) -> Raster<Option<f64>> {
+    window(source, radius, |center, neighbors| {
+        neighbors.iter().map(|z| (z - center).abs()).sum::<f64>() / neighbors.len() as f64
+    })
+}
+
+/// Get the topographic position index (TPI) of every post: its elevation
+/// above the mean elevation of its neighbors
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `radius` - number of posts between the post and the edge of the window,
+///   1 for the usual 3x3 window
+///
+/// # Returns
+///
+/// * [Raster] of the indices, in meters (positive on ridges, negative in
+///   valleys), None where the post is void or has no non-void neighbors
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain;
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let tpi = terrain::topographic_position(&data, 5);
+/// assert_eq!(tpi.count.lat, 121);
+/// assert!(tpi.values.iter().all(|tpi| tpi.is_some()));
+/// ```
+pub fn topographic_position<S: ElevationSource>(source: &S, radius: usize) -> Raster<Option<f64>> {
+    window(source, radius, |center, neighbors| {
+        center - neighbors.iter().sum::<f64>() / neighbors.len() as f64
+    })
+}
+
+/// Get an index of every post from the elevations in a window around it
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `radius` - number of posts between the post and the edge of the window
+/// * `index` - index from the elevation of the post and of its (non-void,
+///   at least one) neighbors
+fn window<S, F>(source: &S, radius: usize, index: F) -> Raster<Option<f64>>
+where
+    S: ElevationSource,
+    F: Fn(f64, &[f64]) -> f64,
+{
+    let metadata = source.metadata();
+    let (lat_count, lon_count) = (metadata.count.lat as usize, metadata.count.lon as usize);
+    Raster::from_fn(
+        metadata.origin,
+        metadata.interval,
+        AxisElement::new(lat_count, lon_count),
+        |lat_idx, lon_idx| {
+            let center = f64::from(source.elevation(lat_idx, lon_idx)?);
+            let lats = lat_idx.saturating_sub(radius)..(lat_idx + radius + 1).min(lat_count);
+            let lons = lon_idx.saturating_sub(radius)..(lon_idx + radius + 1).min(lon_count);
+            let neighbors: Vec<f64> = lons
+                .flat_map(|lon| lats.clone().map(move |lat| (lat, lon)))
+                .filter(|post| *post != (lat_idx, lon_idx))
+                .filter_map(|(lat, lon)| source.elevation(lat, lon).map(f64::from))
+                .collect();
+            match neighbors.is_empty() {
+                true => None,
+                false => Some(index(center, &neighbors)),
+            }
+        },
+    )
+}
+
+/// Color the elevation of every post, from the lowest elevation of the cell
+/// (the start of the palette) to the highest (its end)
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `palette` - [Palette] of the colors
+///
+/// # Returns
+///
+/// * [Raster] of the RGB colors, None where the post is void
+///
+/// # Examples
+///
+/// ```
+/// use dted2::terrain::{self, Palette};
+/// use dted2::DTEDData;
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let relief = terrain::color_relief(&data, Palette::Grayscale);
+/// assert_eq!(relief.values.len(), 121 * 121);
+/// assert!(relief.values.contains(&Some([0, 0, 0])));
+/// assert!(relief.values.contains(&Some([255, 255, 255])));
+/// ```
+pub fn color_relief<S: ElevationSource>(source: &S, palette: Palette) -> Raster<Option<[u8; 3]>> {
+    let elevations = Raster::from_source(source);
+    let (min, max) = elevations
+        .values
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), elev| {
+            (min.min(*elev), max.max(*elev))
+        });
+    elevations.map(|elev| {
+        let elev = (*elev)?;
+        Some(palette.color(match max > min {
+            true => (elev - min) / (max - min),
+            false => 0.0,
+        }))
+    })
+}
+
+/// Get the gradient of every post, using Horn's method
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+///
+/// # Returns
+///
+/// * [Raster] of the `(east, north)` gradients, in meters per meter,
+///   None where the neighborhood is void
+pub fn gradients<S: ElevationSource>(source: &S) -> Raster<Option<(f64, f64)>> {
+    let metadata = source.metadata();
+    let count = metadata.count;
+    let (lat_count, lon_count) = (count.lat as usize, count.lon as usize);
+    let dy = metadata.interval.lat.to_radians() * EARTH_RADIUS_M;
+    let dx = metadata.interval.lon.to_radians() * EARTH_RADIUS_M;
+    Raster::from_fn(
+        metadata.origin,
+        metadata.interval,
+        AxisElement::new(lat_count, lon_count),
+        |lat_idx, lon_idx| {
+            // neighbors, clamped to the cell
+            let south = lat_idx.saturating_sub(1);
+            let north = (lat_idx + 1).min(lat_count - 1);
+            let west = lon_idx.saturating_sub(1);
+            let east = (lon_idx + 1).min(lon_count - 1);
+            let z =
+                |lat_idx: usize, lon_idx: usize| source.elevation(lat_idx, lon_idx).map(f64::from);
+            z(lat_idx, lon_idx)?;
+            // Horn's weighted sums of the rows and columns
+            let column = |lon_idx| -> Option<f64> {
+                Some(z(south, lon_idx)? + 2.0 * z(lat_idx, lon_idx)? + z(north, lon_idx)?)
+            };
+            let row = |lat_idx| -> Option<f64> {
+                Some(z(lat_idx, west)? + 2.0 * z(lat_idx, lon_idx)? + z(lat_idx, east)?)
+            };
+            let lat = metadata.origin.lat + lat_idx as f64 * metadata.interval.lat;
+            let run_x = (east - west) as f64 * dx * lat.to_radians().cos();
+            let run_y = (north - south) as f64 * dy;
+            let dz_dx = match east > west {
+                true => (column(east)? - column(west)?) / (4.0 * run_x),
+                false => 0.0,
+            };
+            let dz_dy = match north > south {
+                true => (row(north)? - row(south)?) / (4.0 * run_y),
+                false => 0.0,
+            };
+            Some((dz_dx, dz_dy))
+        },
+    )
+}
+
+impl DTEDData {
+    /// Get the slope of every post, see [slope]
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - [SlopeUnits]
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the slopes, None where the neighborhood is void
+    pub fn slope(&self, units: SlopeUnits) -> Raster<Option<f64>> {
+        slope(self, units)
+    }
+
+    /// Get the aspect of every post, see [aspect]
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the aspects, in degrees clockwise from north (0 to 360),
+    ///   None where the neighborhood is void or flat
+    pub fn aspect(&self) -> Raster<Option<f64>> {
+        aspect(self)
+    }
+
+    /// Get the shaded relief of every post, see [hillshade]
+    ///
+    /// # Arguments
+    ///
+    /// * `azimuth` - direction of the light, in degrees clockwise from north
+    /// * `altitude` - angle of the light above the horizon, in degrees
+    /// * `z_factor` - exaggeration of the elevations
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the shades, from 1 to 255, 0 where the neighborhood is void
+    pub fn hillshade(&self, azimuth: f64, altitude: f64, z_factor: f64) -> Raster<u8> {
+        hillshade(self, azimuth, altitude, z_factor)
+    }
+
+    /// Color the elevation of every post, see [color_relief]
+    ///
+    /// # Arguments
+    ///
+    /// * `palette` - [Palette] of the colors
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the RGB colors, None where the post is void
+    pub fn color_relief(&self, palette: Palette) -> Raster<Option<[u8; 3]>> {
+        color_relief(self, palette)
+    }
+
+    /// Get the terrain ruggedness index (TRI) of every post, see [ruggedness]
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - number of posts between the post and the edge of the window
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the indices, in meters, None where the post is void or
+    ///   has no non-void neighbors
+    pub fn ruggedness(&self, radius: usize) -> Raster<Option<f64>> {
+        ruggedness(self, radius)
+    }
+
+    /// Get the topographic position index (TPI) of every post, see
+    /// [topographic_position]
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - number of posts between the post and the edge of the window
+    ///
+    /// # Returns
+    ///
+    /// * [Raster] of the indices, in meters, None where the post is void or
+    ///   has no non-void neighbors
+    pub fn topographic_position(&self, radius: usize) -> Raster<Option<f64>> {
+        topographic_position(self, radius)
+    }
+}
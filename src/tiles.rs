@@ -0,0 +1,240 @@
+//! Contains the encoding of the elevations into
+//! [Terrain-RGB](https://docs.mapbox.com/data/tilesets/reference/mapbox-terrain-rgb-v1/)
+//! web map tiles, as used by Mapbox and MapLibre for 3D terrain and hillshading.
+//!
+//! Tiles are the `z/x/y` tiles of the Web Mercator projection, [TILE_SIZE]
+//! pixels wide, each pixel sampled (bilinearly) at its center. Elevations are
+//! packed into the red, green and blue channels as
+//! `elevation = -10000 + (r * 65536 + g * 256 + b) * 0.1`, pixels without an
+//! elevation (void or not covered) being transparent. With the `png` feature,
+//! the tiles can be encoded as PNG images.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::f64::consts::PI;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dataset::DTEDDataset;
+use crate::dted::DTEDData;
+use crate::primitives::AxisElement;
+use crate::source::ElevationSource;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Width (and height) of a tile, in pixels
+pub const TILE_SIZE: usize = 256;
+/// Elevation encoded as black, in meters
+const TERRAIN_RGB_BASE: f64 = -10_000.0;
+/// Elevation step of the encoding, in meters
+const TERRAIN_RGB_STEP: f64 = 0.1;
+
+/// Get the extent of a Web Mercator tile
+///
+/// # Arguments
+///
+/// * `z` - zoom level
+/// * `x` - column, from west to east
+/// * `y` - row, from north to south
+///
+/// # Returns
+///
+/// * `(min, max)` lat/lon of the tile, or None if not a tile of the zoom level
+///
+/// # Examples
+///
+/// ```
+/// use dted2::tiles::tile_bounds;
+///
+/// let (min, max) = tile_bounds(1, 1, 0).unwrap();
+/// assert_eq!((min.lat, min.lon, max.lon), (0.0, 0.0, 180.0));
+/// assert!((max.lat - 85.0511).abs() < 1e-4);
+/// assert!(tile_bounds(1, 2, 0).is_none());
+/// ```
+pub fn tile_bounds(z: u8, x: u32, y: u32) -> Option<(AxisElement<f64>, AxisElement<f64>)> {
+    let tiles = 1u64.checked_shl(u32::from(z))?;
+    if u64::from(x) >= tiles || u64::from(y) >= tiles {
+        return None;
+    }
+    let (x, y, tiles) = (f64::from(x), f64::from(y), tiles as f64);
+    Some((
+        AxisElement::new(mercator_lat(y + 1.0, tiles), mercator_lon(x, tiles)),
+        AxisElement::new(mercator_lat(y, tiles), mercator_lon(x + 1.0, tiles)),
+    ))
+}
+
+/// Encode an elevation as a Terrain-RGB color
+///
+/// # Arguments
+///
+/// * `elevation` - elevation in meters, clamped to the encodable range
+///
+/// # Examples
+///
+/// ```
+/// use dted2::tiles::{decode_terrain_rgb, encode_terrain_rgb};
+///
+/// assert_eq!(encode_terrain_rgb(0.0), [1, 134, 160]);
+/// assert_eq!(decode_terrain_rgb(encode_terrain_rgb(1234.5)), 1234.5);
+/// assert_eq!(encode_terrain_rgb(-20_000.0), [0, 0, 0]);
+/// ```
+pub fn encode_terrain_rgb(elevation: f64) -> [u8; 3] {
+    let value = ((elevation - TERRAIN_RGB_BASE) / TERRAIN_RGB_STEP)
+        .round()
+        .clamp(0.0, f64::from(0xff_ffff)) as u32;
+    [(value >> 16) as u8, (value >> 8) as u8, value as u8]
+}
+
+/// Decode the elevation of a Terrain-RGB color
+///
+/// # Arguments
+///
+/// * `rgb` - red, green and blue channels
+///
+/// # Returns
+///
+/// * Elevation in meters, to the decimeter
+pub fn decode_terrain_rgb(rgb: [u8; 3]) -> f64 {
+    let value = u32::from_be_bytes([0, rgb[0], rgb[1], rgb[2]]);
+    // rounded to the decimeter, as the step is not exact in floating point
+    ((TERRAIN_RGB_BASE + f64::from(value) * TERRAIN_RGB_STEP) * 10.0).round() / 10.0
+}
+
+/// Render a Terrain-RGB tile
+///
+/// # Arguments
+///
+/// * `z` - zoom level
+/// * `x` - column, from west to east
+/// * `y` - row, from north to south
+/// * `elevation` - elevation at a lat/lon
+///
+/// # Returns
+///
+/// * RGBA pixels, row by row from north to south (each from west to east),
+///   or None if not a tile of the zoom level
+///
+/// # Examples
+///
+/// ```
+/// use dted2::tiles::{self, TILE_SIZE};
+///
+/// let pixels = tiles::terrain_rgb(2, 1, 1, |lat, _| match lat > 40.0 {
+///     true => Some(100.0),
+///     false => None,
+/// })
+/// .unwrap();
+/// assert_eq!(pixels.len(), TILE_SIZE * TILE_SIZE * 4);
+/// assert_eq!(pixels[..4], [1, 138, 136, 255]);
+/// // the southernmost row, just north of the equator, is not covered
+/// assert_eq!(pixels[pixels.len() - 4..], [0, 0, 0, 0]);
+/// ```
+pub fn terrain_rgb<F>(z: u8, x: u32, y: u32, elevation: F) -> Option<Vec<u8>>
+where
+    F: Fn(f64, f64) -> Option<f64>,
+{
+    tile_bounds(z, x, y)?;
+    let tiles = (1u64 << z) as f64;
+    let pixel = |idx: usize| (idx as f64 + 0.5) / TILE_SIZE as f64;
+    let lons: Vec<f64> = (0..TILE_SIZE)
+        .map(|col| mercator_lon(f64::from(x) + pixel(col), tiles))
+        .collect();
+    let pixels = (0..TILE_SIZE)
+        .flat_map(|row| {
+            let lat = mercator_lat(f64::from(y) + pixel(row), tiles);
+            lons.iter().map(move |lon| (lat, *lon))
+        })
+        .flat_map(|(lat, lon)| match elevation(lat, lon) {
+            Some(elev) => {
+                let [r, g, b] = encode_terrain_rgb(elev);
+                [r, g, b, u8::MAX]
+            }
+            None => [0; 4],
+        })
+        .collect();
+    Some(pixels)
+}
+
+/// Encode RGBA pixels as a PNG image (requires the `png` feature)
+///
+/// # Arguments
+///
+/// * `pixels` - RGBA pixels, row by row, such as a [terrain_rgb] tile
+/// * `width` - number of pixels per row
+/// * `height` - number of rows
+///
+/// # Returns
+///
+/// * Bytes of the PNG image, or an [crate::Error::InvalidInput] if the
+///   pixels do not match the dimensions
+///
+/// # Examples
+///
+/// ```
+/// use dted2::tiles::{self, TILE_SIZE};
+///
+/// let pixels = tiles::terrain_rgb(0, 0, 0, |_, _| Some(0.0)).unwrap();
+/// let png = tiles::encode_png(&pixels, TILE_SIZE as u32, TILE_SIZE as u32).unwrap();
+/// assert_eq!(png[1..4], *b"PNG");
+/// ```
+#[cfg(feature = "png")]
+pub fn encode_png(pixels: &[u8], width: u32, height: u32) -> Result<Vec<u8>, crate::Error> {
+    let invalid = |err: png::EncodingError| crate::Error::InvalidInput(format!("PNG: {}", err));
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(invalid)?;
+    writer.write_image_data(pixels).map_err(invalid)?;
+    writer.finish().map_err(invalid)?;
+    Ok(bytes)
+}
+
+/// Longitude of a fractional tile column
+fn mercator_lon(x: f64, tiles: f64) -> f64 {
+    x / tiles * 360.0 - 180.0
+}
+
+/// Latitude of a fractional tile row
+fn mercator_lat(y: f64, tiles: f64) -> f64 {
+    (PI * (1.0 - 2.0 * y / tiles)).sinh().atan().to_degrees()
+}
+
+impl DTEDData {
+    /// Render a Terrain-RGB tile of the cell, see [terrain_rgb]
+    ///
+    /// # Arguments
+    ///
+    /// * `z` - zoom level
+    /// * `x` - column, from west to east
+    /// * `y` - row, from north to south
+    ///
+    /// # Returns
+    ///
+    /// * RGBA pixels, transparent outside of the cell, or None if not a tile
+    ///   of the zoom level
+    pub fn terrain_rgb(&self, z: u8, x: u32, y: u32) -> Option<Vec<u8>> {
+        terrain_rgb(z, x, y, |lat, lon| self.elevation_at_interpolated(lat, lon))
+    }
+}
+
+impl<S: ElevationSource> DTEDDataset<S> {
+    /// Render a Terrain-RGB tile of the dataset, see [terrain_rgb]
+    ///
+    /// # Arguments
+    ///
+    /// * `z` - zoom level
+    /// * `x` - column, from west to east
+    /// * `y` - row, from north to south
+    ///
+    /// # Returns
+    ///
+    /// * RGBA pixels, transparent outside of the cells, or None if not a tile
+    ///   of the zoom level
+    pub fn terrain_rgb(&self, z: u8, x: u32, y: u32) -> Option<Vec<u8>> {
+        terrain_rgb(z, x, y, |lat, lon| self.elevation_at_interpolated(lat, lon))
+    }
+}
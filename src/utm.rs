@@ -0,0 +1,412 @@
+//! Contains the reprojection of cells into the Universal Transverse Mercator
+//! (UTM) projection (requires the `proj` feature), warping the geographic
+//! grid of the posts into a north-up [UtmRaster] of square pixels.
+//!
+//! The transverse Mercator projection is computed on the WGS84 ellipsoid with
+//! the 4th order Krüger series, accurate to well under a millimeter within the
+//! zone (and a few millimeters several degrees beyond it). Each pixel is
+//! sampled at its center, from the lat/lon of the center projected back onto
+//! the cell; pixels outside of the cell, or over voids, hold no data (None).
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDData;
+use crate::geodesy::{WGS84_A, WGS84_B};
+use crate::interpolation::Interpolation;
+use crate::source::ElevationSource;
+
+// --------------------------------------------------
+// constants
+// --------------------------------------------------
+/// Scale factor on the central meridian of a zone
+const UTM_SCALE_FACTOR: f64 = 0.9996;
+/// False easting of the central meridian, in meters
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+/// False northing of the equator in the southern hemisphere, in meters
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+/// Number of points projected along each edge of a cell to find its extent
+const UTM_EDGE_SAMPLES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// UTM zone
+///
+/// # Fields
+///
+/// * `zone` - zone number, 1 to 60, each 6 degrees of longitude wide from 180
+///   degrees west
+/// * `north` - whether the zone is of the northern hemisphere (no false
+///   northing), rather than of the southern one
+///
+/// # Examples
+///
+/// ```
+/// use dted2::utm::UtmZone;
+///
+/// let zone = UtmZone::of(42.5, 15.5);
+/// assert_eq!(zone, UtmZone { zone: 33, north: true });
+/// assert_eq!(zone.central_meridian(), 15.0);
+/// assert_eq!(zone.epsg(), 32633);
+/// assert_eq!(UtmZone::of(-29.5, -177.5).epsg(), 32701);
+/// ```
+pub struct UtmZone {
+    pub zone: u8,
+    pub north: bool,
+}
+impl UtmZone {
+    /// Get the zone of a lat/lon, ignoring the exceptions of the zones
+    /// around Norway and Svalbard
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    pub fn of(lat: f64, lon: f64) -> UtmZone {
+        UtmZone {
+            zone: (((lon + 180.0) / 6.0).floor() as i32).rem_euclid(60) as u8 + 1,
+            north: lat >= 0.0,
+        }
+    }
+
+    /// Get the longitude of the central meridian of the zone, in degrees
+    pub fn central_meridian(&self) -> f64 {
+        f64::from(self.zone) * 6.0 - 183.0
+    }
+
+    /// Get the EPSG code of the zone on WGS84 (326xx in the north, 327xx in
+    /// the south)
+    pub fn epsg(&self) -> u16 {
+        match self.north {
+            true => 32600 + u16::from(self.zone),
+            false => 32700 + u16::from(self.zone),
+        }
+    }
+
+    /// Project a lat/lon into the zone
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(easting, northing)` in meters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::utm::UtmZone;
+    ///
+    /// let zone = UtmZone { zone: 31, north: true };
+    /// let (easting, northing) = zone.project(0.0, 3.0);
+    /// assert!((easting - 500_000.0).abs() < 1e-6 && northing.abs() < 1e-6);
+    /// let (easting, northing) = zone.project(45.0, 5.0);
+    /// let (lat, lon) = zone.unproject(easting, northing);
+    /// assert!((lat - 45.0).abs() < 1e-9 && (lon - 5.0).abs() < 1e-9);
+    /// ```
+    pub fn project(&self, lat: f64, lon: f64) -> (f64, f64) {
+        let series = KrugerSeries::wgs84();
+        let (lat, dlon) = (
+            lat.to_radians(),
+            (lon - self.central_meridian()).to_radians(),
+        );
+        let conformal = 2.0 * series.n.sqrt() / (1.0 + series.n);
+        let t = (lat.sin().atanh() - conformal * (conformal * lat.sin()).atanh()).sinh();
+        let xi_prime = t.atan2(dlon.cos());
+        let eta_prime = (dlon.sin() / (1.0 + t * t).sqrt()).atanh();
+        let (mut xi, mut eta) = (xi_prime, eta_prime);
+        for (j, alpha) in series.alpha.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi += alpha * (k * xi_prime).sin() * (k * eta_prime).cosh();
+            eta += alpha * (k * xi_prime).cos() * (k * eta_prime).sinh();
+        }
+        (
+            UTM_FALSE_EASTING + UTM_SCALE_FACTOR * series.rectifying_radius * eta,
+            self.false_northing() + UTM_SCALE_FACTOR * series.rectifying_radius * xi,
+        )
+    }
+
+    /// Project a point of the zone back to its lat/lon, see
+    /// [UtmZone::project]
+    ///
+    /// # Arguments
+    ///
+    /// * `easting` - easting, in meters
+    /// * `northing` - northing, in meters
+    ///
+    /// # Returns
+    ///
+    /// * `(lat, lon)` in degrees
+    pub fn unproject(&self, easting: f64, northing: f64) -> (f64, f64) {
+        let series = KrugerSeries::wgs84();
+        let scale = UTM_SCALE_FACTOR * series.rectifying_radius;
+        let xi = (northing - self.false_northing()) / scale;
+        let eta = (easting - UTM_FALSE_EASTING) / scale;
+        let (mut xi_prime, mut eta_prime) = (xi, eta);
+        for (j, beta) in series.beta.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi_prime -= beta * (k * xi).sin() * (k * eta).cosh();
+            eta_prime -= beta * (k * xi).cos() * (k * eta).sinh();
+        }
+        let chi = (xi_prime.sin() / eta_prime.cosh()).asin();
+        let lat = series
+            .delta
+            .iter()
+            .enumerate()
+            .fold(chi, |lat, (j, delta)| {
+                lat + delta * (2.0 * (j + 1) as f64 * chi).sin()
+            });
+        let dlon = eta_prime.sinh().atan2(xi_prime.cos());
+        (
+            lat.to_degrees(),
+            self.central_meridian() + dlon.to_degrees(),
+        )
+    }
+
+    /// Get the false northing of the zone, in meters
+    fn false_northing(&self) -> f64 {
+        match self.north {
+            true => 0.0,
+            false => UTM_FALSE_NORTHING_SOUTH,
+        }
+    }
+}
+
+/// Coefficients of the Krüger series of the transverse Mercator projection,
+/// to the 4th order of the third flattening
+///
+/// # Fields
+///
+/// * `n` - third flattening of the ellipsoid
+/// * `rectifying_radius` - radius of the sphere of the same meridian length
+/// * `alpha` - coefficients of the forward projection
+/// * `beta` - coefficients of the inverse projection
+/// * `delta` - coefficients of the latitude from the conformal latitude
+struct KrugerSeries {
+    n: f64,
+    rectifying_radius: f64,
+    alpha: [f64; 4],
+    beta: [f64; 4],
+    delta: [f64; 4],
+}
+impl KrugerSeries {
+    /// Get the coefficients of the WGS84 ellipsoid
+    fn wgs84() -> KrugerSeries {
+        let n = (WGS84_A - WGS84_B) / (WGS84_A + WGS84_B);
+        let (n2, n3, n4) = (n * n, n * n * n, n * n * n * n);
+        KrugerSeries {
+            n,
+            rectifying_radius: WGS84_A / (1.0 + n) * (1.0 + n2 / 4.0 + n4 / 64.0),
+            alpha: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0 + 41.0 * n4 / 180.0,
+                13.0 * n2 / 48.0 - 3.0 * n3 / 5.0 + 557.0 * n4 / 1440.0,
+                61.0 * n3 / 240.0 - 103.0 * n4 / 140.0,
+                49561.0 * n4 / 161280.0,
+            ],
+            beta: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0 - n4 / 360.0,
+                n2 / 48.0 + n3 / 15.0 - 437.0 * n4 / 1440.0,
+                17.0 * n3 / 480.0 - 37.0 * n4 / 840.0,
+                4397.0 * n4 / 161280.0,
+            ],
+            delta: [
+                2.0 * n - 2.0 * n2 / 3.0 - 2.0 * n3 + 116.0 * n4 / 45.0,
+                7.0 * n2 / 3.0 - 8.0 * n3 / 5.0 - 227.0 * n4 / 45.0,
+                56.0 * n3 / 15.0 - 136.0 * n4 / 35.0,
+                4279.0 * n4 / 630.0,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// North-up raster of square pixels in a UTM zone
+///
+/// # Fields
+///
+/// * `zone` - [UtmZone] of the raster
+/// * `west` - easting of the west edge of the raster, in meters
+/// * `north` - northing of the north edge of the raster, in meters
+/// * `pixel_size` - width (and height) of a pixel, in meters
+/// * `width` - number of pixels per row
+/// * `height` - number of rows
+/// * `values` - values, row by row from north to south, each from west to
+///   east, at index `row * width + col`, None where there is no data
+pub struct UtmRaster {
+    pub zone: UtmZone,
+    pub west: f64,
+    pub north: f64,
+    pub pixel_size: f64,
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<Option<f64>>,
+}
+impl UtmRaster {
+    /// Get the value of a pixel
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - row, from north to south
+    /// * `col` - column, from west to east
+    ///
+    /// # Returns
+    ///
+    /// * Value (None if no data), or None if out of bounds
+    pub fn get(&self, row: usize, col: usize) -> Option<&Option<f64>> {
+        match row < self.height && col < self.width {
+            true => self.values.get(row * self.width + col),
+            false => None,
+        }
+    }
+
+    /// Get the `(easting, northing)` of the center of a pixel, in meters
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - row, from north to south
+    /// * `col` - column, from west to east
+    pub fn center(&self, row: usize, col: usize) -> (f64, f64) {
+        (
+            self.west + (col as f64 + 0.5) * self.pixel_size,
+            self.north - (row as f64 + 0.5) * self.pixel_size,
+        )
+    }
+}
+
+/// Reproject the elevations of a cell into a UTM zone
+///
+/// The raster covers the projected extent of the cell, its edges snapped
+/// outward to multiples of the pixel size.
+///
+/// # Arguments
+///
+/// * `source` - any [ElevationSource]
+/// * `zone` - [UtmZone] to project into
+/// * `pixel_size` - width (and height) of a pixel, in meters
+/// * `method` - [Interpolation] method sampling the elevations
+///
+/// # Returns
+///
+/// * [UtmRaster] of the elevations, None outside of the cell and over voids
+///
+/// # Examples
+///
+/// ```
+/// use dted2::interpolation::Interpolation;
+/// use dted2::utm::{self, UtmZone};
+/// use dted2::{DTEDData, ElevationSource};
+///
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let zone = UtmZone::of(-29.5, -177.5);
+/// let raster = utm::reproject(&data, zone, 1000.0, Interpolation::Bilinear);
+/// assert_eq!(raster.west % 1000.0, 0.0);
+/// // about 111 km north to south, 97 km west to east, in a skewed footprint
+/// assert!((110..=114).contains(&raster.height) && (96..=100).contains(&raster.width));
+/// assert_eq!(raster.get(0, 0), Some(&None));
+/// let (easting, northing) = raster.center(50, 50);
+/// let (lat, lon) = zone.unproject(easting, northing);
+/// assert_eq!(
+///     *raster.get(50, 50).unwrap(),
+///     data.elevation_at_with(lat, lon, Interpolation::Bilinear)
+/// );
+/// ```
+pub fn reproject<S: ElevationSource>(
+    source: &S,
+    zone: UtmZone,
+    pixel_size: f64,
+    method: Interpolation,
+) -> UtmRaster {
+    let metadata = source.metadata();
+    let extent = CellExtent::of(metadata);
+    // the projected edges of the cell, the lines of latitude being curved
+    let (mut west, mut south) = (f64::INFINITY, f64::INFINITY);
+    let (mut east, mut north) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for idx in 0..=UTM_EDGE_SAMPLES {
+        let fraction = idx as f64 / UTM_EDGE_SAMPLES as f64;
+        let lat = extent.south + fraction * (extent.north - extent.south);
+        let lon = extent.west + fraction * (extent.east - extent.west);
+        for (lat, lon) in [
+            (lat, extent.west),
+            (lat, extent.east),
+            (extent.south, lon),
+            (extent.north, lon),
+        ] {
+            let (easting, northing) = zone.project(lat, lon);
+            west = west.min(easting);
+            east = east.max(easting);
+            south = south.min(northing);
+            north = north.max(northing);
+        }
+    }
+    let (west, east) = (
+        (west / pixel_size).floor() * pixel_size,
+        (east / pixel_size).ceil() * pixel_size,
+    );
+    let (south, north) = (
+        (south / pixel_size).floor() * pixel_size,
+        (north / pixel_size).ceil() * pixel_size,
+    );
+    let width = ((east - west) / pixel_size).round() as usize;
+    let height = ((north - south) / pixel_size).round() as usize;
+    let mut raster = UtmRaster {
+        zone,
+        west,
+        north,
+        pixel_size,
+        width,
+        height,
+        values: Vec::with_capacity(width * height),
+    };
+    for row in 0..height {
+        for col in 0..width {
+            let (easting, northing) = raster.center(row, col);
+            let (lat, lon) = zone.unproject(easting, northing);
+            raster
+                .values
+                .push(source.elevation_at_with(lat, lon, method));
+        }
+    }
+    raster
+}
+
+/// Lat/lon extent of the posts of a cell
+struct CellExtent {
+    south: f64,
+    north: f64,
+    west: f64,
+    east: f64,
+}
+impl CellExtent {
+    /// Get the extent of the posts of a cell
+    fn of(metadata: &crate::dted::DTEDMetadata) -> CellExtent {
+        let last = |count: u16| f64::from(count.max(1) - 1);
+        CellExtent {
+            south: metadata.origin.lat,
+            north: metadata.origin.lat + last(metadata.count.lat) * metadata.interval.lat,
+            west: metadata.origin.lon,
+            east: metadata.origin.lon + last(metadata.count.lon) * metadata.interval.lon,
+        }
+    }
+}
+
+impl DTEDData {
+    /// Reproject the elevations into the UTM zone of the center of the cell,
+    /// see [reproject]
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel_size` - width (and height) of a pixel, in meters
+    /// * `method` - [Interpolation] method sampling the elevations
+    ///
+    /// # Returns
+    ///
+    /// * [UtmRaster] of the elevations, None outside of the cell and over voids
+    pub fn to_utm(&self, pixel_size: f64, method: Interpolation) -> UtmRaster {
+        let zone = UtmZone::of(
+            (self.min.lat + self.max.lat) / 2.0,
+            (self.min.lon + self.max.lon) / 2.0,
+        );
+        reproject(self, zone, pixel_size, method)
+    }
+}
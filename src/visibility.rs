@@ -0,0 +1,187 @@
+//! Contains the [LineOfSight] between two points above the terrain
+//! (see [crate::ElevationSource::line_of_sight]), and the [horizon] masking
+//! angles around a point (see [crate::ElevationSource::horizon_profile]).
+//!
+//! The terrain is sampled every half post, raised by the curvature of the
+//! earth (without atmospheric refraction) along the way.
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::DTEDMetadata;
+use crate::geodesy::{self, EARTH_RADIUS_M};
+use crate::profile::ProfileSample;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Line of sight between an observer and a target
+///
+/// # Fields
+///
+/// * `obstruction` - first profile sample (from the observer) where the
+///   terrain rises above the sight line, None if the target is visible
+/// * `clearance` - smallest height (in meters) of the sight line above the
+///   terrain between the observer and the target, negative if obstructed
+pub struct LineOfSight {
+    pub obstruction: Option<ProfileSample>,
+    pub clearance: f64,
+}
+impl LineOfSight {
+    /// Check the line of sight along an elevation profile, from its first
+    /// sample (observer) to its last (target)
+    ///
+    /// Samples without an elevation (not covered or void) are skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - elevation profile, see [crate::ElevationSource::profile]
+    /// * `observer_height` - height of the observer above the terrain, in meters
+    /// * `target_height` - height of the target above the terrain, in meters
+    ///
+    /// # Returns
+    ///
+    /// * [LineOfSight], or None if the elevation of the observer or target is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::profile::ProfileSample;
+    /// use dted2::visibility::LineOfSight;
+    ///
+    /// // a 50 m ridge halfway along a short profile
+    /// let profile: Vec<_> = [0.0, 50.0, 0.0]
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(idx, elev)| ProfileSample {
+    ///         distance: idx as f64 * 100.0,
+    ///         lat: 0.0,
+    ///         lon: 0.0,
+    ///         elevation: Some(*elev),
+    ///     })
+    ///     .collect();
+    /// let los = LineOfSight::from_profile(&profile, 10.0, 10.0).unwrap();
+    /// assert!(!los.is_visible());
+    /// assert_eq!(los.obstruction.unwrap().distance, 100.0);
+    /// assert!(LineOfSight::from_profile(&profile, 60.0, 60.0).unwrap().is_visible());
+    /// ```
+    pub fn from_profile(
+        profile: &[ProfileSample],
+        observer_height: f64,
+        target_height: f64,
+    ) -> Option<LineOfSight> {
+        let (observer, target) = (profile.first()?, profile.last()?);
+        let start = observer.elevation? + observer_height;
+        let end = target.elevation? + target_height;
+        let length = target.distance - observer.distance;
+        let mut los = LineOfSight {
+            obstruction: None,
+            clearance: f64::INFINITY,
+        };
+        let between = profile.get(1..profile.len() - 1).unwrap_or_default();
+        for sample in between {
+            let Some(elevation) = sample.elevation else {
+                continue;
+            };
+            let distance = sample.distance - observer.distance;
+            let sight = start + (end - start) * distance / length;
+            let bulge = distance * (length - distance) / (2.0 * EARTH_RADIUS_M);
+            let clearance = sight - (elevation + bulge);
+            if clearance < 0.0 && los.obstruction.is_none() {
+                los.obstruction = Some(*sample);
+            }
+            los.clearance = los.clearance.min(clearance);
+        }
+        Some(los)
+    }
+
+    /// Whether the target is visible from the observer
+    pub fn is_visible(&self) -> bool {
+        self.obstruction.is_none()
+    }
+}
+
+/// Number of profile samples to check a line of sight, every half post
+///
+/// # Arguments
+///
+/// * `metadata` - [DTEDMetadata] of the cell the line starts in
+/// * `distance` - length of the line, in meters
+pub(crate) fn num_samples(metadata: &DTEDMetadata, distance: f64) -> usize {
+    (distance / sample_spacing(metadata)).ceil() as usize + 1
+}
+
+/// Distance between samples of the terrain, half a post
+///
+/// # Arguments
+///
+/// * `metadata` - [DTEDMetadata] of the cell the samples start in
+pub(crate) fn sample_spacing(metadata: &DTEDMetadata) -> f64 {
+    metadata.interval.lat.to_radians() * EARTH_RADIUS_M / 2.0
+}
+
+/// Get the horizon around a point on the terrain: the largest elevation angle
+/// of the terrain along rays cast in each azimuth
+///
+/// # Arguments
+///
+/// * `point` - `(lat, lon)` of the observer, on the terrain
+/// * `azimuth_step` - angle between rays, in degrees
+/// * `max_range` - length of the rays, in meters
+/// * `spacing` - distance between samples along the rays, in meters
+/// * `elevation` - elevation at a lat/lon
+///
+/// # Returns
+///
+/// * Masking angle (in degrees above the horizontal) per azimuth, clockwise
+///   from north, None if the elevation of the observer or of the terrain
+///   along the ray is unknown
+///
+/// # Examples
+///
+/// ```
+/// use dted2::visibility::horizon;
+///
+/// // a 100 m high plateau, starting 1 km east of the observer
+/// let angles = horizon((0.0, 0.0), 90.0, 5000.0, 10.0, |_, lon| match lon > 0.009 {
+///     true => Some(100.0),
+///     false => Some(0.0),
+/// });
+/// assert_eq!(angles.len(), 4);
+/// assert!((angles[1].unwrap() - 100f64.atan2(1000.0).to_degrees()).abs() < 0.1);
+/// assert!(angles[3].unwrap() < 0.0);
+/// ```
+pub fn horizon<F>(
+    point: (f64, f64),
+    azimuth_step: f64,
+    max_range: f64,
+    spacing: f64,
+    elevation: F,
+) -> Vec<Option<f64>>
+where
+    F: Fn(f64, f64) -> Option<f64>,
+{
+    if azimuth_step <= 0.0 {
+        return Vec::new();
+    }
+    let num_azimuths = (360.0 / azimuth_step).ceil() as usize;
+    let Some(height) = elevation(point.0, point.1) else {
+        return vec![None; num_azimuths];
+    };
+    let num_samples = match spacing > 0.0 {
+        true => (max_range / spacing).floor() as usize,
+        false => 0,
+    };
+    (0..num_azimuths)
+        .map(|idx| {
+            let azimuth = idx as f64 * azimuth_step;
+            (1..=num_samples)
+                .filter_map(|sample| {
+                    let distance = sample as f64 * spacing;
+                    let (lat, lon) = geodesy::destination(point, azimuth, distance);
+                    let drop = distance * distance / (2.0 * EARTH_RADIUS_M);
+                    let rise = elevation(lat, lon)? - height - drop;
+                    Some(rise.atan2(distance).to_degrees())
+                })
+                .reduce(f64::max)
+        })
+        .collect()
+}
@@ -0,0 +1,405 @@
+//! Contains writers that serialize the components of a DTED file into bytes,
+//! the inverse of the [crate::parsers].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::io::{self, Write};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers::to_signed_mag;
+use crate::primitives::{Angle, AxisElement};
+
+// --------------------------------------------------
+// field helpers
+// --------------------------------------------------
+/// Hemisphere characters for latitudes (positive, negative)
+const LAT_HEMISPHERES: [u8; 2] = [b'N', b'S'];
+/// Hemisphere characters for longitudes (positive, negative)
+const LON_HEMISPHERES: [u8; 2] = [b'E', b'W'];
+
+/// Creates an [io::Error] for a field that can not be represented
+fn invalid_field(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+/// Pushes `value` as a zero-padded ASCII unsigned integer of `width` bytes
+fn push_uint(buf: &mut Vec<u8>, value: u32, width: usize) -> io::Result<()> {
+    let text = format!("{:0width$}", value, width = width);
+    if text.len() > width {
+        return Err(invalid_field(format!(
+            "{} does not fit in a {} byte field",
+            value, width
+        )));
+    }
+    buf.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+/// Pushes `value` as a zero-padded ASCII unsigned integer of `width` bytes,
+/// or blanks if `value` is [None]
+fn push_opt_uint<U: Into<u32>>(
+    buf: &mut Vec<u8>,
+    value: Option<U>,
+    width: usize,
+) -> io::Result<()> {
+    match value {
+        Some(value) => push_uint(buf, value.into(), width),
+        None => push_blank(buf, width),
+    }
+}
+
+/// Pushes an accuracy of `width` bytes, using the `NA` sentinel if [None]
+fn push_accuracy(buf: &mut Vec<u8>, value: Option<u16>, width: usize) -> io::Result<()> {
+    match value {
+        Some(value) => push_uint(buf, value as u32, width),
+        None => push_text(buf, "NA$$", width),
+    }
+}
+
+/// Pushes `text`, left justified and padded with spaces to `width` bytes
+fn push_text(buf: &mut Vec<u8>, text: &str, width: usize) -> io::Result<()> {
+    if text.len() > width {
+        return Err(invalid_field(format!(
+            "\"{}\" does not fit in a {} byte field",
+            text, width
+        )));
+    }
+    buf.extend_from_slice(text.as_bytes());
+    push_blank(buf, width - text.len())
+}
+
+/// Pushes `width` blank bytes (spaces)
+fn push_blank(buf: &mut Vec<u8>, width: usize) -> io::Result<()> {
+    buf.resize(buf.len() + width, b' ');
+    Ok(())
+}
+
+/// Pads `buf` with blank bytes (spaces) up to `len` bytes
+fn pad_to(buf: &mut Vec<u8>, len: usize) {
+    buf.resize(len.max(buf.len()), b' ');
+}
+
+/// Pushes an [Angle] formatted as `D..DMMSS[.S..S][H]`
+///
+/// # Arguments
+///
+/// * `buf` - The buffer to push to
+/// * `angle` - The angle
+/// * `num_deg` - The number of bytes for degrees
+/// * `num_frac` - The number of bytes for the fraction of seconds (0 for none)
+/// * `hemispheres` - The hemisphere characters (positive, negative), if any
+fn push_angle(
+    buf: &mut Vec<u8>,
+    angle: &Angle,
+    num_deg: usize,
+    num_frac: usize,
+    hemispheres: Option<[u8; 2]>,
+) -> io::Result<()> {
+    let scale = 10u64.pow(num_frac as u32);
+    let total = (angle.total_secs().abs() * scale as f64).round() as u64;
+    let (secs, frac) = (total / scale, total % scale);
+    push_uint(buf, (secs / 3600) as u32, num_deg)?;
+    push_uint(buf, ((secs % 3600) / 60) as u32, 2)?;
+    push_uint(buf, (secs % 60) as u32, 2)?;
+    if num_frac > 0 {
+        buf.push(b'.');
+        push_uint(buf, frac as u32, num_frac)?;
+    }
+    if let Some(hemispheres) = hemispheres {
+        buf.push(hemispheres[angle.is_negative() as usize]);
+    }
+    Ok(())
+}
+
+/// Pushes a latitude/longitude pair formatted as `DDMMSS[.S..S]H` / `DDDMMSS[.S..S]H`
+fn push_position(buf: &mut Vec<u8>, pos: &AxisElement<Angle>, num_frac: usize) -> io::Result<()> {
+    push_angle(buf, &pos.lat, 2, num_frac, Some(LAT_HEMISPHERES))?;
+    push_angle(buf, &pos.lon, 3, num_frac, Some(LON_HEMISPHERES))
+}
+
+// --------------------------------------------------
+// record writers
+// --------------------------------------------------
+/// Serializes a [RawDTEDHeader] into the 80 byte User Header Label
+///
+/// # Arguments
+///
+/// * `header` - The [RawDTEDHeader] to serialize
+///
+/// # Returns
+///
+/// The bytes of the UHL, or an error if a field can not be represented
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::dted_uhl_parser;
+/// use dted2::writers::dted_uhl_to_bytes;
+///
+/// let input = b"UHL10150000E0420000N001000100005U  F02 045     360136010                        ";
+/// let (_, header) = dted_uhl_parser(input).unwrap();
+/// assert_eq!(dted_uhl_to_bytes(&header).unwrap(), input.to_vec());
+/// ```
+pub fn dted_uhl_to_bytes(header: &RawDTEDHeader) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(DTED_UHL_LENGTH as usize);
+    buf.extend_from_slice(RecognitionSentinel::UHL.value());
+    push_angle(&mut buf, &header.origin.lon, 3, 0, Some(LON_HEMISPHERES))?;
+    push_angle(&mut buf, &header.origin.lat, 3, 0, Some(LAT_HEMISPHERES))?;
+    push_uint(&mut buf, header.interval_secs_x_10.lon as u32, 4)?;
+    push_uint(&mut buf, header.interval_secs_x_10.lat as u32, 4)?;
+    push_accuracy(&mut buf, header.accuracy, 4)?;
+    push_text(&mut buf, &header.security_code, 3)?;
+    push_text(&mut buf, &header.unique_ref, 12)?;
+    push_uint(&mut buf, header.count.lon as u32, 4)?;
+    push_uint(&mut buf, header.count.lat as u32, 4)?;
+    buf.push(if header.multiple_accuracy { b'1' } else { b'0' });
+    push_blank(&mut buf, 24)?;
+    Ok(buf)
+}
+
+/// Serializes a [RawDTEDDSIRecord] into the 648 byte Data Set Identification record
+///
+/// # Arguments
+///
+/// * `dsi` - The [RawDTEDDSIRecord] to serialize
+///
+/// # Returns
+///
+/// The bytes of the DSI record, or an error if a field can not be represented
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{DTED_UHL_LENGTH, DTED_DSI_RECORD_LENGTH};
+/// use dted2::parsers::dted_dsi_parser;
+/// use dted2::writers::dted_dsi_to_bytes;
+///
+/// let content = std::fs::read("tests/test_data.dt2").unwrap();
+/// let (_, dsi) = dted_dsi_parser(&content[DTED_UHL_LENGTH as usize..]).unwrap();
+/// let bytes = dted_dsi_to_bytes(&dsi).unwrap();
+/// assert_eq!(bytes.len(), DTED_DSI_RECORD_LENGTH);
+/// assert_eq!(dted_dsi_parser(&bytes).unwrap().1, dsi);
+/// ```
+pub fn dted_dsi_to_bytes(dsi: &RawDTEDDSIRecord) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(DTED_DSI_RECORD_LENGTH);
+    // --------------------------------------------------
+    // security + product identification
+    // --------------------------------------------------
+    buf.extend_from_slice(RecognitionSentinel::DSI.value());
+    buf.push(dsi.security_classification.code() as u8);
+    push_text(&mut buf, dsi.security_control.as_deref().unwrap_or(""), 2)?;
+    push_text(&mut buf, dsi.security_handling.as_deref().unwrap_or(""), 27)?;
+    push_blank(&mut buf, 26)?;
+    push_text(&mut buf, &dsi.product_level, 5)?;
+    push_text(&mut buf, &dsi.unique_ref, 15)?;
+    push_blank(&mut buf, 8)?;
+    push_opt_uint(&mut buf, dsi.edition, 2)?;
+    buf.push(dsi.match_merge_version as u8);
+    push_opt_uint(&mut buf, dsi.maintenance_date, 4)?;
+    push_opt_uint(&mut buf, dsi.match_merge_date, 4)?;
+    push_opt_uint(&mut buf, dsi.maintenance_code, 4)?;
+    push_text(&mut buf, &dsi.producer_code, 8)?;
+    push_blank(&mut buf, 16)?;
+    // --------------------------------------------------
+    // product specification + datums
+    // --------------------------------------------------
+    push_text(&mut buf, &dsi.product_spec, 9)?;
+    push_opt_uint(&mut buf, dsi.product_spec_amendment, 2)?;
+    push_opt_uint(&mut buf, dsi.product_spec_date, 4)?;
+    push_text(&mut buf, &dsi.vertical_datum, 3)?;
+    push_text(&mut buf, &dsi.horizontal_datum, 5)?;
+    push_text(&mut buf, &dsi.collection_system, 10)?;
+    push_opt_uint(&mut buf, dsi.compilation_date, 4)?;
+    push_blank(&mut buf, 22)?;
+    // --------------------------------------------------
+    // geographic extents
+    // --------------------------------------------------
+    push_position(&mut buf, &dsi.origin, 1)?;
+    push_position(&mut buf, &dsi.sw_corner, 0)?;
+    push_position(&mut buf, &dsi.nw_corner, 0)?;
+    push_position(&mut buf, &dsi.ne_corner, 0)?;
+    push_position(&mut buf, &dsi.se_corner, 0)?;
+    push_angle(&mut buf, &dsi.orientation, 3, 1, None)?;
+    push_uint(&mut buf, dsi.interval_secs_x_10.lat as u32, 4)?;
+    push_uint(&mut buf, dsi.interval_secs_x_10.lon as u32, 4)?;
+    push_uint(&mut buf, dsi.count.lat as u32, 4)?;
+    push_uint(&mut buf, dsi.count.lon as u32, 4)?;
+    push_opt_uint(&mut buf, dsi.partial_cell, 2)?;
+    pad_to(&mut buf, DTED_DSI_RECORD_LENGTH);
+    Ok(buf)
+}
+
+/// Serializes a [RawDTEDAccRecord] into the 2700 byte Accuracy Description record
+///
+/// # Arguments
+///
+/// * `acc` - The [RawDTEDAccRecord] to serialize
+///
+/// # Returns
+///
+/// The bytes of the ACC record, or an error if a field can not be represented
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{DTED_ACC_RECORD_LENGTH, RawDTEDAccRecord};
+/// use dted2::parsers::dted_acc_parser;
+/// use dted2::writers::dted_acc_to_bytes;
+///
+/// let acc = RawDTEDAccRecord {
+///     abs_horizontal: Some(9),
+///     abs_vertical: Some(5),
+///     rel_horizontal: None,
+///     rel_vertical: Some(5),
+///     multiple_accuracy: 0,
+///     subregions: vec![],
+/// };
+/// let bytes = dted_acc_to_bytes(&acc).unwrap();
+/// assert_eq!(&bytes[..19], b"ACC00090005NA$$0005");
+/// assert_eq!(bytes.len(), DTED_ACC_RECORD_LENGTH);
+/// assert_eq!(dted_acc_parser(&bytes).unwrap().1, acc);
+/// ```
+pub fn dted_acc_to_bytes(acc: &RawDTEDAccRecord) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(DTED_ACC_RECORD_LENGTH);
+    // --------------------------------------------------
+    // accuracies
+    // --------------------------------------------------
+    buf.extend_from_slice(RecognitionSentinel::ACC.value());
+    push_accuracy(&mut buf, acc.abs_horizontal, 4)?;
+    push_accuracy(&mut buf, acc.abs_vertical, 4)?;
+    push_accuracy(&mut buf, acc.rel_horizontal, 4)?;
+    push_accuracy(&mut buf, acc.rel_vertical, 4)?;
+    push_blank(&mut buf, 36)?;
+    push_uint(&mut buf, acc.multiple_accuracy as u32, 2)?;
+    // --------------------------------------------------
+    // sub-regions
+    // --------------------------------------------------
+    if acc.subregions.len() > DTED_ACC_MAX_SUBREGIONS {
+        return Err(invalid_field(format!(
+            "at most {} accuracy sub-regions are allowed",
+            DTED_ACC_MAX_SUBREGIONS
+        )));
+    }
+    for subregion in acc.subregions.iter() {
+        let outline = &subregion.outline;
+        if outline.len() > DTED_ACC_MAX_SUBREGION_COORDS {
+            return Err(invalid_field(format!(
+                "at most {} coordinates are allowed in an accuracy sub-region",
+                DTED_ACC_MAX_SUBREGION_COORDS
+            )));
+        }
+        push_uint(&mut buf, outline.len() as u32, 2)?;
+        for vertex in outline.iter() {
+            push_position(&mut buf, vertex, 1)?;
+        }
+        push_blank(
+            &mut buf,
+            (DTED_ACC_MAX_SUBREGION_COORDS - outline.len()) * 19,
+        )?;
+        push_accuracy(&mut buf, subregion.abs_horizontal, 4)?;
+        push_accuracy(&mut buf, subregion.abs_vertical, 4)?;
+        push_accuracy(&mut buf, subregion.rel_horizontal, 4)?;
+        push_accuracy(&mut buf, subregion.rel_vertical, 4)?;
+    }
+    pad_to(&mut buf, DTED_ACC_RECORD_LENGTH);
+    Ok(buf)
+}
+
+/// Serializes a [RawDTEDRecord] into a data record
+///
+/// The elevations are encoded as signed magnitude integers, and the
+/// checksum is computed from the record contents (the stored
+/// [RawDTEDRecord::checksum] is ignored)
+///
+/// # Arguments
+///
+/// * `record` - The [RawDTEDRecord] to serialize
+///
+/// # Returns
+///
+/// The bytes of the data record
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::RawDTEDRecord;
+/// use dted2::writers::dted_record_to_bytes;
+///
+/// let record = RawDTEDRecord {
+///     blk_count: 1,
+///     lon_count: 1,
+///     lat_count: 0,
+///     elevations: vec![3, -3],
+///     checksum: 0,
+/// };
+/// assert_eq!(
+///     dted_record_to_bytes(&record),
+///     vec![0xAA, 0, 0, 1, 0, 1, 0, 0, 0x00, 0x03, 0x80, 0x03, 0, 0, 0x01, 0x32],
+/// );
+/// ```
+pub fn dted_record_to_bytes(record: &RawDTEDRecord) -> Vec<u8> {
+    dted_record_parts_to_bytes(&record.info(), &record.elevations)
+}
+
+/// Serializes the header and elevations of a record into a data record,
+/// see [dted_record_to_bytes]
+///
+/// # Arguments
+///
+/// * `info` - The [RawDTEDRecordInfo] of the record
+/// * `elevations` - The elevations of the record, from south to north
+///
+/// # Returns
+///
+/// The bytes of the data record
+pub fn dted_record_parts_to_bytes(info: &RawDTEDRecordInfo, elevations: &[i16]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(
+        DTED_RECORD_HEADER_LENGTH + elevations.len() * 2 + DTED_RECORD_CHECKSUM_LENGTH,
+    );
+    buf.extend_from_slice(RecognitionSentinel::DATA.value());
+    buf.extend_from_slice(&info.blk_count.to_be_bytes()[1..]);
+    buf.extend_from_slice(&info.lon_count.to_be_bytes());
+    buf.extend_from_slice(&info.lat_count.to_be_bytes());
+    for elev in elevations.iter() {
+        buf.extend_from_slice(&to_signed_mag(*elev).to_be_bytes());
+    }
+    buf.extend_from_slice(&info.compute_checksum(elevations).to_be_bytes());
+    buf
+}
+
+/// Writes a [RawDTEDFile] (UHL, DSI, ACC and data records) to any [io::Write]
+///
+/// A missing DSI or ACC record is derived from the UHL,
+/// see [RawDTEDDSIRecord::from_header] and [RawDTEDAccRecord::from_header]
+///
+/// # Arguments
+///
+/// * `writer` - The destination
+/// * `file` - The [RawDTEDFile] to write
+///
+/// # Returns
+///
+/// An error if writing fails or a field can not be represented
+pub fn write_dted_file<W: Write>(writer: &mut W, file: &RawDTEDFile) -> io::Result<()> {
+    writer.write_all(&dted_uhl_to_bytes(&file.header)?)?;
+    match &file.dsi_record {
+        Some(dsi) => writer.write_all(&dted_dsi_to_bytes(dsi)?)?,
+        None => writer.write_all(&dted_dsi_to_bytes(&RawDTEDDSIRecord::from_header(
+            &file.header,
+        ))?)?,
+    }
+    match &file.acc_record {
+        Some(acc) => writer.write_all(&dted_acc_to_bytes(acc)?)?,
+        None => writer.write_all(&dted_acc_to_bytes(&RawDTEDAccRecord::from_header(
+            &file.header,
+        ))?)?,
+    }
+    for (info, elevations) in file.data.records.iter().zip(file.data.columns()) {
+        writer.write_all(&dted_record_parts_to_bytes(info, elevations))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Contains the reading of DTED cells straight out of ZIP archives (requires
+//! the `zip` feature), as distributed by the NGA, into a [DTEDDataset] of
+//! [ZipDTEDCell]s ([DTEDDataset::from_zip]), without unpacking them to disk.
+//!
+//! The archive is indexed upfront, reading the header records of the entries
+//! named after their cells (see [cell_origin]), with an entry only extracted
+//! (in memory) on the first query of its cell, its data records then being
+//! decoded on demand as by [LazyDTEDData].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::fs::File;
+use std::io::{self, Read, Seek};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use zip::ZipArchive;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dataset::{cell_origin, DTEDDataset};
+use crate::dted::{dted_record_length, DTEDMetadata, DTED_DATA_OFFSET};
+use crate::lazy::LazyDTEDData;
+use crate::parsers;
+use crate::source::ElevationSource;
+use crate::Error as DTEDError;
+
+/// DTED cell within a ZIP archive, whose header records are read upfront,
+/// with the entry only extracted on the first query
+///
+/// The metadata filename is the name of the entry within the archive.
+/// Queries through [ElevationSource] report I/O errors as None.
+///
+/// # Examples
+///
+/// ```
+/// use dted2::{DTEDData, DTEDDataset, ElevationSource};
+/// use std::io::{Cursor, Write};
+/// use zip::write::{SimpleFileOptions, ZipWriter};
+///
+/// // an archive with the test cell named after its origin
+/// let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+/// writer.start_file("dted/w178/s30.dt0", SimpleFileOptions::default()).unwrap();
+/// writer.write_all(&std::fs::read("tests/test_data_negative.dt2").unwrap()).unwrap();
+/// let archive = writer.finish().unwrap();
+///
+/// let dataset = DTEDDataset::from_zip_reader(archive).unwrap();
+/// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+/// let cell = &dataset.cells()[0];
+/// assert_eq!(cell.metadata().filename, "dted/w178/s30.dt0");
+/// assert!(!cell.is_extracted());
+/// assert_eq!(dataset.elevation_at(-29.5, -177.5), data.elevation_at(-29.5, -177.5));
+/// assert!(cell.is_extracted());
+/// ```
+pub struct ZipDTEDCell<R: Read + Seek = File> {
+    metadata: DTEDMetadata,
+    archive: Arc<Mutex<ZipArchive<R>>>,
+    index: usize,
+    cell: OnceLock<Option<LazyDTEDData>>,
+}
+impl<R: Read + Seek> ZipDTEDCell<R> {
+    /// Whether the entry was extracted (by a query)
+    pub fn is_extracted(&self) -> bool {
+        self.cell.get().is_some()
+    }
+
+    /// Get the extracted cell, extracting the entry on first access
+    ///
+    /// Only the length of the cell (from its header records) is read, whatever
+    /// the size the archive declares for the entry.
+    fn cell(&self) -> Option<&LazyDTEDData> {
+        self.cell
+            .get_or_init(|| {
+                let count = self.metadata.count;
+                let len = DTED_DATA_OFFSET + usize::from(count.lon) * dted_record_length(count.lat);
+                let mut archive = self.archive.lock().ok()?;
+                let entry = archive.by_index(self.index).ok()?;
+                let mut content = Vec::with_capacity(len.min(entry.size() as usize));
+                entry.take(len as u64).read_to_end(&mut content).ok()?;
+                LazyDTEDData::from_content(content, &self.metadata.filename).ok()
+            })
+            .as_ref()
+    }
+}
+impl<R: Read + Seek> ElevationSource for ZipDTEDCell<R> {
+    fn metadata(&self) -> &DTEDMetadata {
+        &self.metadata
+    }
+
+    fn raw_elevation(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        self.cell()?.raw_elevation(lat_idx, lon_idx)
+    }
+}
+
+impl DTEDDataset<ZipDTEDCell> {
+    /// Index the DTED cells of a ZIP archive, reading their header records
+    /// only, see [ZipDTEDCell]
+    ///
+    /// Entries are recognized by their names (see [cell_origin]), either
+    /// `n34_w119.dt2` or the classic `w119/n34.dt2` layout, others being ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `path` (str): Path to the ZIP archive
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the cells, sorted by entry name
+    pub fn from_zip(path: &str) -> Result<DTEDDataset<ZipDTEDCell>, DTEDError> {
+        DTEDDataset::from_zip_reader(File::open(path)?)
+    }
+}
+impl<R: Read + Seek> DTEDDataset<ZipDTEDCell<R>> {
+    /// Index the DTED cells of a ZIP archive read from any seekable reader,
+    /// see [DTEDDataset::from_zip]
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - ZIP archive
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDDataset]: dataset of the cells, sorted by entry name
+    pub fn from_zip_reader(reader: R) -> Result<DTEDDataset<ZipDTEDCell<R>>, DTEDError> {
+        let mut archive = ZipArchive::new(reader).map_err(io::Error::from)?;
+        let mut entries: Vec<(String, usize)> = (0..archive.len())
+            .filter_map(|index| {
+                let name = archive.name_for_index(index)?;
+                cell_origin(Path::new(name)).map(|_| (name.to_string(), index))
+            })
+            .collect();
+        entries.sort();
+        let mut metadata = Vec::with_capacity(entries.len());
+        for (name, index) in entries.iter() {
+            let mut content = Vec::with_capacity(DTED_DATA_OFFSET);
+            archive
+                .by_index(*index)
+                .map_err(io::Error::from)?
+                .take(DTED_DATA_OFFSET as u64)
+                .read_to_end(&mut content)?;
+            let (_, (header, dsi_record, acc_record)) = parsers::dted_headers_parser(&content)
+                .map_err(|e| DTEDError::locate(e, &content, 0, None))?;
+            metadata.push(DTEDMetadata::from_records(
+                &header, dsi_record, acc_record, name,
+            ));
+        }
+        let archive = Arc::new(Mutex::new(archive));
+        Ok(DTEDDataset::from_cells(
+            metadata
+                .into_iter()
+                .zip(entries)
+                .map(|(metadata, (_, index))| ZipDTEDCell {
+                    metadata,
+                    archive: Arc::clone(&archive),
+                    index,
+                    cell: OnceLock::new(),
+                })
+                .collect(),
+        ))
+    }
+}
@@ -1,5 +1,46 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use dted2::DTEDData;
 
+/// Writes a 121x121 Level 0 cell with its origin at 10S 20E to a temp file,
+/// with elevations given by `elev(lat_idx, lon_idx)`, returning its path
+fn write_test_file(name: &str, elev: impl Fn(usize, usize) -> i16) -> String {
+    write_test_cell(name, (-10, 20), elev)
+}
+
+/// Writes a Level 0 test cell at an origin (in whole degrees) to a temp file,
+/// with the elevation of each post given by `elev(lat_idx, lon_idx)`
+fn write_test_cell(name: &str, origin: (i16, i16), elev: impl Fn(usize, usize) -> i16) -> String {
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::DTEDBuilder;
+
+    let angle = |deg: i16| Angle::new(deg.unsigned_abs(), 0, 0.0, deg < 0);
+    let origin = AxisElement::new(angle(origin.0), angle(origin.1));
+    let grid: Vec<Vec<i16>> = (0..121)
+        .map(|lon| (0..121).map(|lat| elev(lat, lon)).collect())
+        .collect();
+    let file = DTEDBuilder::new(
+        origin,
+        AxisElement::new(300, 300),
+        AxisElement::new(121, 121),
+    )
+    .elevations(grid)
+    .level(dted2::DTEDLevel::Level0)
+    .build()
+    .unwrap();
+    let path = std::env::temp_dir().join(format!("dted2_test_{}.dt0", name));
+    std::fs::write(&path, file.to_bytes().unwrap()).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+/// Builds a test cell (see [write_test_file]) and reads it back
+fn build_test_data(name: &str, elev: impl Fn(usize, usize) -> i16) -> DTEDData {
+    let path = write_test_file(name, elev);
+    let data = DTEDData::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    data
+}
+
 #[test]
 fn test_input_data() {
     let data = DTEDData::read("tests/test_data.dt2").unwrap();
@@ -38,6 +79,21 @@ fn test_input_data_negative() {
     assert_eq!(data.metadata.count.lon, 121);
 }
 
+#[test]
+fn test_right_justified_na() {
+    use dted2::parsers::dted_uhl_parser;
+
+    // the USGS producer of this file right justifies the NA accuracy of the UHL
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(&content[28..32], b"00NA");
+    let (_, header) = dted_uhl_parser(&content).unwrap();
+    assert_eq!(header.accuracy, None);
+    // NA among digits is malformed
+    let mut malformed = content.clone();
+    malformed[28..32].copy_from_slice(b"1NA2");
+    assert!(dted_uhl_parser(&malformed).is_err());
+}
+
 #[test]
 fn test_read_header_only() {
     let header = DTEDData::read_header("tests/test_data.dt2").unwrap();
@@ -54,3 +110,4367 @@ fn test_read_header_only() {
     assert_eq!(header.count.lat, 3601);
     assert_eq!(header.count.lon, 3601);
 }
+
+#[test]
+fn test_dsi_record() {
+    use dted2::dted::SecurityClassification;
+
+    let content = std::fs::read("tests/test_data.dt2").unwrap();
+    let (_, file) = dted2::parsers::dted_file_parser(&content).unwrap();
+    let dsi = file.dsi_record.unwrap();
+    assert_eq!(
+        dsi.security_classification,
+        SecurityClassification::Unclassified
+    );
+    assert_eq!(dsi.product_level, "DTED2");
+    assert_eq!(dsi.edition, Some(1));
+    assert_eq!(dsi.match_merge_version, 'A');
+    assert_eq!(dsi.producer_code, "USCNIMA");
+    assert_eq!(dsi.product_spec, "PRF89020B");
+    assert_eq!(dsi.vertical_datum, "E96");
+    assert_eq!(dsi.horizontal_datum, "WGS84");
+    assert_eq!(dsi.collection_system, "SRTM");
+    assert_eq!(dsi.compilation_date, Some(2));
+    assert_eq!(dsi.origin.lat.deg(), 42);
+    assert_eq!(dsi.origin.lon.deg(), 15);
+    assert_eq!(dsi.ne_corner.lat.deg(), 43);
+    assert_eq!(dsi.ne_corner.lon.deg(), 16);
+    assert_eq!(dsi.interval_secs_x_10.lat, 10);
+    assert_eq!(dsi.count.lat, 3601);
+    assert_eq!(dsi.count.lon, 3601);
+    assert_eq!(dsi.partial_cell, Some(0));
+}
+
+#[test]
+fn test_dsi_corners() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::ElevationSource;
+
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let corners = data.metadata.corners().unwrap();
+    let (south, west, north, east) = data.bounds();
+    assert_eq!(corners.sw, AxisElement::new(south, west));
+    assert_eq!(corners.nw, AxisElement::new(north, west));
+    assert_eq!(corners.ne, AxisElement::new(north, east));
+    assert_eq!(corners.se, AxisElement::new(south, east));
+    let dsi = data.metadata.dsi_record.as_ref().unwrap();
+    assert!(dsi.origin_matches(&data.metadata.origin_angle));
+
+    // a skewed cell, written and read back
+    let mut file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    let dsi = file.dsi_record.as_mut().unwrap();
+    dsi.nw_corner.lon = Angle::new(177, 59, 30.0, true);
+    dsi.ne_corner.lon = Angle::new(176, 59, 30.0, true);
+    dsi.origin.lon = Angle::new(178, 0, 0.3, true);
+    let read = DTEDData::from_bytes(&file.to_bytes().unwrap()).unwrap();
+    let corners = read.metadata.corners().unwrap();
+    assert_eq!(corners.nw, AxisElement::new(-29.0, -(177.0 + 59.5 / 60.0)));
+    assert_eq!(corners.ne, AxisElement::new(-29.0, -(176.0 + 59.5 / 60.0)));
+    assert_eq!(corners.se, AxisElement::new(-30.0, -177.0));
+    let dsi = read.metadata.dsi_record.as_ref().unwrap();
+    assert!(dsi.origin_matches(&read.metadata.origin_angle));
+    assert!(!dsi.origin_matches(&AxisElement::new(
+        Angle::new(30, 0, 0.0, true),
+        Angle::new(178, 0, 1.0, true),
+    )));
+
+    // no corners without a DSI record
+    let metadata = dted2::DTEDMetadata {
+        dsi_record: None,
+        ..data.metadata.clone()
+    };
+    assert_eq!(metadata.corners(), None);
+}
+
+#[test]
+fn test_cross_validate() {
+    use dted2::dted::{RawDTEDFile, RecordDiscrepancy};
+    use dted2::primitives::{Angle, AxisElement};
+
+    let mut file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    assert!(file.cross_validate().is_empty());
+    let dsi = file.dsi_record.as_mut().unwrap();
+    dsi.origin.lat = Angle::new(29, 59, 59.0, true);
+    // within the rounding of the UHL origin to whole seconds
+    dsi.origin.lon = Angle::new(178, 0, 0.4, true);
+    dsi.interval_secs_x_10.lon = 600;
+    dsi.count = AxisElement::new(121, 61);
+
+    // same discrepancies from the file and from its parsed metadata
+    let discrepancies = file.cross_validate();
+    let read = DTEDData::from_bytes(&file.to_bytes().unwrap()).unwrap();
+    assert_eq!(read.metadata.cross_validate(), discrepancies);
+    assert_eq!(
+        discrepancies,
+        vec![
+            RecordDiscrepancy::Origin {
+                uhl: file.header.origin,
+                dsi: AxisElement::new(
+                    Angle::new(29, 59, 59.0, true),
+                    Angle::new(178, 0, 0.4, true)
+                ),
+            },
+            RecordDiscrepancy::Interval {
+                uhl: AxisElement::new(300, 300),
+                dsi: AxisElement::new(300, 600),
+            },
+            RecordDiscrepancy::Count {
+                uhl: AxisElement::new(121, 121),
+                dsi: AxisElement::new(121, 61),
+            },
+        ]
+    );
+    assert_eq!(
+        discrepancies[0].to_string(),
+        "DSI origin 29°59'59\"S, 178°0'0.4\"W differs from the UHL origin 30°0'0\"S, 178°0'0\"W"
+    );
+    assert_eq!(
+        discrepancies[1].to_string(),
+        "DSI intervals 30\" x 60\" differ from the UHL intervals 30\" x 30\""
+    );
+
+    // nothing to compare without a DSI record
+    file.dsi_record = None;
+    assert!(file.cross_validate().is_empty());
+}
+
+#[test]
+fn test_acc_record() {
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let (_, file) = dted2::parsers::dted_file_parser(&content).unwrap();
+    let acc = file.acc_record.unwrap();
+    assert_eq!(acc.abs_horizontal, None);
+    assert_eq!(acc.abs_vertical, None);
+    assert_eq!(acc.rel_horizontal, None);
+    assert_eq!(acc.rel_vertical, None);
+    assert_eq!(acc.multiple_accuracy, 0);
+    assert!(acc.subregions.is_empty());
+}
+
+#[test]
+fn test_acc_subregions() {
+    use dted2::dted::{AccuracySubregion, RawDTEDAccRecord};
+    use dted2::primitives::{Angle, AxisElement};
+
+    let vertex = |lat: f64, lon: f64| {
+        AxisElement::new(
+            Angle::from_decimal_degrees(lat),
+            Angle::from_decimal_degrees(lon),
+        )
+    };
+    let subregion = |west: f64, east: f64, abs_vertical| AccuracySubregion {
+        outline: vec![
+            vertex(42.0, west),
+            vertex(43.0, west),
+            vertex(43.0, east),
+            vertex(42.0, east),
+        ],
+        abs_horizontal: Some(20),
+        abs_vertical: Some(abs_vertical),
+        rel_horizontal: None,
+        rel_vertical: Some(3),
+    };
+    let acc = RawDTEDAccRecord {
+        abs_horizontal: Some(20),
+        abs_vertical: Some(12),
+        rel_horizontal: None,
+        rel_vertical: Some(3),
+        multiple_accuracy: 2,
+        subregions: vec![subregion(15.0, 15.5, 5), subregion(15.5, 16.0, 12)],
+    };
+    let bytes = dted2::writers::dted_acc_to_bytes(&acc).unwrap();
+    let (_, parsed) = dted2::parsers::dted_acc_parser(&bytes).unwrap();
+    assert_eq!(parsed, acc);
+    assert_eq!(
+        parsed.subregions[1].outline_degrees()[2],
+        AxisElement::new(43.0, 16.0)
+    );
+
+    assert_eq!(
+        parsed.subregion_at(42.5, 15.2).unwrap().abs_vertical,
+        Some(5)
+    );
+    assert_eq!(
+        parsed.subregion_at(42.5, 15.8).unwrap().abs_vertical,
+        Some(12)
+    );
+    // on the shared edge, the first sub-region
+    assert_eq!(
+        parsed.subregion_at(42.5, 15.5).unwrap().abs_vertical,
+        Some(5)
+    );
+    assert!(parsed.subregion_at(41.5, 15.2).is_none());
+}
+
+#[test]
+fn test_elevation_with_accuracy() {
+    use dted2::dted::{AccuracySubregion, RawDTEDFile};
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // the western half of the cell (-10,20 to -9,21) is more accurate
+    let path = write_test_file("elevation_with_accuracy", |lat, lon| (lat + lon) as i16);
+    let mut file = RawDTEDFile::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let vertex = |lat: f64, lon: f64| {
+        AxisElement::new(
+            Angle::from_decimal_degrees(lat),
+            Angle::from_decimal_degrees(lon),
+        )
+    };
+    let acc = file.acc_record.as_mut().unwrap();
+    acc.abs_vertical = Some(30);
+    acc.multiple_accuracy = 2;
+    acc.subregions = vec![
+        AccuracySubregion {
+            outline: vec![
+                vertex(-10.0, 20.0),
+                vertex(-9.0, 20.0),
+                vertex(-9.0, 20.5),
+                vertex(-10.0, 20.5),
+            ],
+            abs_horizontal: None,
+            abs_vertical: Some(10),
+            rel_horizontal: None,
+            rel_vertical: None,
+        },
+        AccuracySubregion {
+            outline: vec![
+                vertex(-10.0, 20.5),
+                vertex(-9.0, 20.5),
+                vertex(-9.0, 21.0),
+                vertex(-10.0, 21.0),
+            ],
+            abs_horizontal: None,
+            abs_vertical: None,
+            rel_horizontal: None,
+            rel_vertical: None,
+        },
+    ];
+    let data = DTEDData::from_bytes(&file.to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        data.elevation_with_accuracy(-9.5, 20.25),
+        Some((90, Some(10)))
+    );
+    // not available in the sub-region, that of the record
+    assert_eq!(
+        data.elevation_with_accuracy(-9.5, 20.75),
+        Some((150, Some(30)))
+    );
+    assert_eq!(data.elevation_with_accuracy(-11.0, 20.25), None);
+
+    let dataset = DTEDDataset::from_cells(vec![data]);
+    assert_eq!(
+        dataset.elevation_with_accuracy(-9.5, 20.25),
+        Some((90, Some(10)))
+    );
+}
+
+#[test]
+fn test_posts() {
+    use dted2::ElevationSource;
+
+    let data = build_test_data("posts", |lat, lon| match (lat, lon) {
+        (60, 60) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (lat + 2 * lon) as i16,
+    });
+    let posts: Vec<_> = data.posts().collect();
+    assert_eq!(posts.len(), 121 * 121);
+    // longitude line by longitude line, from south to north
+    assert_eq!(posts[0], (-10.0, 20.0, Some(0)));
+    assert_eq!(posts[120], (-9.0, 20.0, Some(120)));
+    assert_eq!(posts[121].2, Some(2));
+    assert_eq!(posts[121 * 121 - 1], (-9.0, 21.0, Some(360)));
+    // each post agrees with the elevation queries
+    let (lat, lon, elevation) = posts[61 * 121 + 30];
+    assert_eq!(elevation, data.elevation_at(lat, lon));
+    let voids: Vec<_> = posts.iter().filter(|post| post.2.is_none()).collect();
+    assert_eq!(voids, vec![&(-9.5, 20.5, None)]);
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        let sum: i64 = data
+            .par_posts()
+            .filter_map(|(_, _, elevation)| elevation.map(i64::from))
+            .sum();
+        assert_eq!(
+            sum,
+            posts
+                .iter()
+                .filter_map(|post| post.2.map(i64::from))
+                .sum::<i64>()
+        );
+    }
+}
+
+#[test]
+fn test_grid_views() {
+    use dted2::primitives::AxisElement;
+
+    let data = build_test_data("grid_views", |lat, lon| (lat + 2 * lon) as i16);
+    let rows: Vec<Vec<i16>> = data.rows().map(|row| row.collect()).collect();
+    assert_eq!(rows.len(), 121);
+    assert_eq!(rows[7][..3], [7, 9, 11]);
+    let columns: Vec<&[i16]> = data.columns().collect();
+    assert_eq!(columns.len(), 121);
+    assert_eq!(columns[3][..3], [6, 7, 8]);
+
+    // the window of the north east quarter
+    let window = data
+        .window_in(AxisElement::new(-9.5, 20.5), AxisElement::new(-9.0, 21.0))
+        .unwrap();
+    assert_eq!(window.lat_range(), 60..121);
+    assert_eq!(window.lon_range(), 60..121);
+    assert_eq!(window.get(0, 0), Some(180));
+    assert_eq!(window.get(61, 0), None);
+    let row: Vec<i16> = window.rows().nth(1).unwrap().collect();
+    assert_eq!(row.len(), 61);
+    assert_eq!(row[..2], [181, 183]);
+    assert_eq!(window.columns().last().unwrap()[60], 120 + 240);
+
+    // tiles of 40x40 posts cover the cell once
+    let mut total = 0;
+    for lat in (0..121).step_by(40) {
+        for lon in (0..121).step_by(40) {
+            let tile = data
+                .window(lat..(lat + 40).min(121), lon..(lon + 40).min(121))
+                .unwrap();
+            total += tile.columns().map(|column| column.len()).sum::<usize>();
+        }
+    }
+    assert_eq!(total, 121 * 121);
+    assert_eq!(window[(1, 1)], 183);
+    assert!(data.window(0..0, 0..10).is_none());
+    assert!(data.window(0..10, 100..122).is_none());
+    assert!(data
+        .window_in(AxisElement::new(0.0, 0.0), AxisElement::new(1.0, 1.0))
+        .is_none());
+}
+
+#[test]
+fn test_grid_index() {
+    let mut data = build_test_data("grid_index", |lat, lon| (lat + 2 * lon) as i16);
+    // the row (latitude index) first, then the column (longitude index)
+    assert_eq!(data.data[(10, 3)], 16);
+    assert_eq!(data.data.get(10, 3), Some(16));
+    assert_eq!(data.data.get(121, 0), None);
+    data.data[(10, 3)] = 500;
+    *data.data.get_mut(11, 3).unwrap() += 1;
+    assert_eq!(data.data.column(3).unwrap()[10..12], [500, 18]);
+    assert!(data.data.get_mut(0, 121).is_none());
+    assert_eq!(data.data.recompute_checksums(), 1);
+    let out_of_bounds = std::panic::catch_unwind(|| data.data[(0, 121)]);
+    assert!(out_of_bounds.is_err());
+}
+
+#[test]
+fn test_to_i16_slice() {
+    use dted2::parsers::{to_i16, to_i16_slice};
+
+    // every word, with a length that is not a multiple of the lane count
+    let words: Vec<u16> = (0..=u16::MAX).chain(0..7).collect();
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+    let mut out = vec![1; words.len()];
+    to_i16_slice(&bytes, &mut out);
+    assert!(words
+        .iter()
+        .zip(&out)
+        .all(|(word, elev)| to_i16(*word) == *elev));
+    // only as many as fit
+    let mut out = [1; 3];
+    to_i16_slice(&bytes[..4], &mut out);
+    assert_eq!(out, [0, 1, 1]);
+}
+
+#[test]
+fn test_parse_dted_record_into() {
+    use dted2::dted::{dted_record_length, DTED_DATA_OFFSET};
+    use dted2::parsers::parse_dted_record_into;
+    use nom::bytes::complete::{tag, take};
+    use nom::number::complete::{be_u16, be_u32};
+    use nom::sequence::tuple;
+
+    // the combinators the record parser behaves as
+    let reference = |input| {
+        tuple((
+            tag([0xAA]),
+            take(1_usize),
+            be_u16,
+            be_u16,
+            be_u16,
+            take(242_usize),
+            be_u32,
+        ))(input)
+        .map(|(rest, _)| rest)
+    };
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let record = &content[DTED_DATA_OFFSET..DTED_DATA_OFFSET + dted_record_length(121) + 3];
+    let mut bad_sentinel = record.to_vec();
+    bad_sentinel[0] = 0xAB;
+    let mut out = [0; 121];
+    for input in (0..=record.len())
+        .map(|len| &record[..len])
+        .chain([&bad_sentinel[..]])
+    {
+        let parsed = parse_dted_record_into(input, &mut out).map(|(rest, _)| rest);
+        assert_eq!(parsed, reference(input));
+    }
+}
+
+#[test]
+fn test_raw_file_ref() {
+    use dted2::borrowed::RawDTEDFileRef;
+    use dted2::dted::RawDTEDFile;
+    use std::borrow::Cow;
+
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let file = RawDTEDFileRef::from_bytes(&content).unwrap();
+    let owned = RawDTEDFile::from_bytes(&content).unwrap();
+    // the text fields borrow from the content
+    assert!(matches!(file.header.unique_ref, Cow::Borrowed(_)));
+    assert_eq!(file.header.clone().into_owned(), owned.header);
+    assert_eq!(
+        file.acc_record().unwrap(),
+        owned.acc_record.clone().unwrap()
+    );
+    assert_eq!(file.records().count(), 121);
+    for (lon_idx, record) in file.records().enumerate() {
+        assert_eq!(record.info(), owned.data.records[lon_idx]);
+        assert!(record
+            .iter()
+            .eq(owned.data.column(lon_idx).unwrap().iter().copied()));
+    }
+    let record = file.record(4).unwrap();
+    assert_eq!(record.get(7), owned.data.get(7, 4));
+    assert_eq!(record.get(121), None);
+    assert_eq!(record.to_record().elevations, owned.data.column(4).unwrap());
+    assert!(file.record(121).is_none());
+    let converted = file.to_raw_file().unwrap();
+    assert_eq!(converted.data, owned.data);
+    assert_eq!(converted.dsi_record, owned.dsi_record);
+}
+
+#[test]
+fn test_checksums() {
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let (_, mut file) = dted2::parsers::dted_file_parser(&content).unwrap();
+    assert!((0..file.data.len()).all(|idx| file.data.is_checksum_valid(idx)));
+    assert_eq!(file.recompute_checksums(), 0);
+    file.data.records[3].checksum += 1;
+    assert!(!file.data.is_checksum_valid(3));
+    assert_eq!(file.recompute_checksums(), 1);
+    assert!(file.data.is_checksum_valid(3));
+}
+
+#[test]
+fn test_repair_checksums() {
+    let original = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let path = std::env::temp_dir().join("dted2_test_repair_checksums.dt2");
+    let path = path.to_str().unwrap();
+    // corrupt the checksum (last 4 bytes) of the final record
+    let mut corrupted = original.clone();
+    let len = corrupted.len();
+    corrupted[len - 1] ^= 0xFF;
+    std::fs::write(path, &corrupted).unwrap();
+    assert_eq!(dted2::dted::repair_checksums(path).unwrap(), 1);
+    assert_eq!(std::fs::read(path).unwrap(), original);
+    assert_eq!(dted2::dted::repair_checksums(path).unwrap(), 0);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_write_round_trip() {
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let (_, mut file) = dted2::parsers::dted_file_parser(&content).unwrap();
+    // unmodified data records are written byte for byte
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(bytes.len(), content.len());
+    assert_eq!(
+        bytes[dted2::dted::DTED_DATA_OFFSET..],
+        content[dted2::dted::DTED_DATA_OFFSET..]
+    );
+    // modifications survive the round trip with valid checksums
+    file.data.column_mut(10).unwrap()[20] = -1234;
+    let mut bytes = Vec::new();
+    file.write(&mut bytes).unwrap();
+    let (_, written) = dted2::parsers::dted_file_parser(&bytes).unwrap();
+    assert_eq!(written.data.get(20, 10), Some(-1234));
+    assert!(written.data.is_checksum_valid(10));
+    assert_eq!(written.header, file.header);
+    assert_eq!(written.dsi_record, file.dsi_record);
+    assert_eq!(written.acc_record, file.acc_record);
+}
+
+#[test]
+fn test_builder() {
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::DTEDBuilder;
+
+    let origin = AxisElement::new(Angle::new(10, 0, 0.0, true), Angle::new(20, 0, 0.0, false));
+    let grid: Vec<Vec<i16>> = (0..121)
+        .map(|lon| (0..121).map(|lat| lat - lon).collect())
+        .collect();
+    let file = DTEDBuilder::new(
+        origin,
+        AxisElement::new(300, 300),
+        AxisElement::new(121, 121),
+    )
+    .elevations(grid)
+    .level(dted2::DTEDLevel::Level0)
+    .accuracy(30)
+    .build()
+    .unwrap();
+    let path = std::env::temp_dir().join("dted2_test_builder.dt0");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, file.to_bytes().unwrap()).unwrap();
+    let data = DTEDData::read(path).unwrap();
+    assert_eq!(data.metadata.count.lat, 121);
+    assert_eq!(data.metadata.accuracy, Some(30));
+    assert_eq!(data.metadata.interval_secs.lat, 30.0);
+    assert_eq!(data.get_elevation(-10.0, 20.0), Some(0.0));
+    assert_eq!(data.get_elevation(-9.0, 20.0), Some(120.0));
+    assert_eq!(data.get_elevation(-10.0, 21.0), Some(-120.0));
+    std::fs::remove_file(path).unwrap();
+
+    // level validation
+    let wrong_level = DTEDBuilder::new(
+        origin,
+        AxisElement::new(300, 300),
+        AxisElement::new(121, 121),
+    )
+    .elevations(vec![vec![0; 121]; 121])
+    .level(dted2::DTEDLevel::Level2)
+    .build();
+    assert!(wrong_level.is_err());
+}
+
+#[test]
+fn test_producer() {
+    use dted2::producer::Producer;
+
+    let metadata = DTEDData::read("tests/test_data.dt2").unwrap().metadata;
+    assert_eq!(metadata.producer_code(), Some("USCNIMA"));
+    assert_eq!(metadata.edition(), Some(1));
+    assert_eq!(
+        metadata.producer().unwrap().to_string(),
+        "National Imagery and Mapping Agency (United States)"
+    );
+    let unique_ref = metadata.unique_ref().unwrap();
+    assert_eq!(unique_ref, metadata.dsi_record.as_ref().unwrap().unique_ref);
+
+    // an unknown agency, or no DSI record
+    let metadata = DTEDData::read("tests/test_data_negative.dt2")
+        .unwrap()
+        .metadata;
+    assert_eq!(metadata.producer_code(), Some("USNIM"));
+    assert_eq!(metadata.producer().unwrap().agency, None);
+    let metadata = DTEDData::read_header("tests/test_data.dt2").unwrap();
+    assert_eq!(metadata.producer_code(), None);
+    assert_eq!(metadata.unique_ref(), None);
+
+    assert_eq!(Producer::lookup("CAXYZ").unwrap().to_string(), "Canada");
+    assert_eq!(Producer::lookup(""), None);
+}
+
+#[test]
+fn test_security_classification() {
+    use dted2::dted::SecurityClassification;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::DTEDBuilder;
+
+    assert!(SecurityClassification::Unclassified < SecurityClassification::Restricted);
+    assert!(SecurityClassification::Confidential < SecurityClassification::Secret);
+    assert!(SecurityClassification::Secret < SecurityClassification::Other('X'));
+    assert_eq!(SecurityClassification::Secret.to_string(), "SECRET");
+    assert!(!SecurityClassification::from_code(' ').is_known());
+
+    let origin = AxisElement::new(Angle::new(10, 0, 0.0, true), Angle::new(20, 0, 0.0, false));
+    let builder = |classification| {
+        DTEDBuilder::new(
+            origin,
+            AxisElement::new(300, 300),
+            AxisElement::new(121, 121),
+        )
+        .elevations(vec![vec![0; 121]; 121])
+        .security_classification(classification)
+        .build()
+    };
+    let file = builder(SecurityClassification::Confidential).unwrap();
+    let (_, parsed) = dted2::parsers::dted_file_parser(&file.to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        parsed.header.security_classification(),
+        SecurityClassification::Confidential
+    );
+    assert_eq!(
+        parsed.dsi_record.unwrap().security_classification,
+        SecurityClassification::Confidential
+    );
+    assert!(builder(SecurityClassification::Other('X')).is_err());
+}
+
+#[test]
+fn test_elevation_at() {
+    use dted2::ElevationSource;
+    let data = build_test_data("elevation_at", |lat, lon| match (lat, lon) {
+        (60, 60) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (lat * 2 + lon) as i16,
+    });
+    assert_eq!(data.elevation_at(-10.0, 20.0), Some(0));
+    // 0.2 of a post north, 0.4 of a post east -> nearest is the origin
+    assert_eq!(
+        data.elevation_at(-10.0 + 0.2 / 120.0, 20.0 + 0.4 / 120.0),
+        Some(0)
+    );
+    // 0.6 of a post north -> next latitude point
+    assert_eq!(data.elevation_at(-10.0 + 0.6 / 120.0, 20.0), Some(2));
+    assert_eq!(data.elevation_at(-9.0, 21.0), Some(360));
+    assert_eq!(data.elevation_at(-9.5, 20.5), None);
+    assert_eq!(data.elevation_at(-8.9, 20.5), None);
+}
+
+#[test]
+fn test_elevation_at_interpolated() {
+    use dted2::ElevationSource;
+    let void = dted2::dted::DTED_VOID_ELEVATION;
+    let data = build_test_data("interpolated", |lat, lon| match (lat, lon) {
+        (61, 60) => void,
+        (100, 100) | (100, 101) | (101, 100) | (101, 101) => void,
+        _ => (lat * 2 + lon) as i16,
+    });
+    let post = 1.0 / 120.0;
+    let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-6;
+    assert!(close(data.elevation_at_interpolated(-10.0, 20.0), 0.0));
+    assert!(close(
+        data.elevation_at_interpolated(-10.0 + 0.5 * post, 20.0 + 0.25 * post),
+        1.25
+    ));
+    assert!(close(data.elevation_at_interpolated(-9.0, 21.0), 360.0));
+    // the void post (61, 60) is excluded, leaving (60, 60), (60, 61) and (61, 61)
+    let (lat, lon) = (-9.5 + 0.5 * post, 20.5 + 0.5 * post);
+    assert!(close(
+        data.elevation_at_interpolated(lat, lon),
+        (180.0 + 181.0 + 183.0) / 3.0
+    ));
+    // exactly on a void post, or surrounded by void posts
+    assert_eq!(data.elevation_at_interpolated(-9.5 + post, 20.5), None);
+    let (lat, lon) = (-10.0 + 100.5 * post, 20.0 + 100.5 * post);
+    assert_eq!(data.elevation_at_interpolated(lat, lon), None);
+    assert_eq!(data.elevation_at_interpolated(0.0, 0.0), None);
+}
+
+#[test]
+fn test_elevation_at_with() {
+    use dted2::interpolation::Interpolation;
+    use dted2::ElevationSource;
+
+    let data = build_test_data("elevation_at_with", |lat, lon| match (lat, lon) {
+        (80, 80) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (lat * 2 + lon) as i16,
+    });
+    let post = 1.0 / 120.0;
+    let (lat, lon) = (-10.0 + 40.3 * post, 20.0 + 50.6 * post);
+    // bilinear and bicubic reproduce a linear surface, lanczos approximately
+    for (method, tolerance) in [
+        (Interpolation::Bilinear, 1e-6),
+        (Interpolation::Bicubic, 1e-6),
+        (Interpolation::Lanczos, 0.05),
+    ] {
+        let elev = data.elevation_at_with(lat, lon, method).unwrap();
+        assert!((elev - 131.2).abs() < tolerance, "{:?}: {}", method, elev);
+    }
+    assert_eq!(
+        data.elevation_at_with(lat, lon, Interpolation::Nearest),
+        Some(131.0)
+    );
+    // near a void post (and at the edges) the higher order methods fall back to bilinear
+    for (lat, lon) in [
+        (-10.0 + 79.5 * post, 20.0 + 78.5 * post),
+        (-10.0 + 0.5 * post, 20.0 + 0.5 * post),
+    ] {
+        assert_eq!(
+            data.elevation_at_with(lat, lon, Interpolation::Lanczos),
+            data.elevation_at_with(lat, lon, Interpolation::Bilinear),
+        );
+    }
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap() {
+    use dted2::mmap::MmapDTEDData;
+    use dted2::ElevationSource;
+
+    let elev = |lat, lon| match (lat, lon) {
+        (3, 4) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => lat as i16 - lon as i16,
+    };
+    let path = write_test_file("mmap", elev);
+    let data = DTEDData::read(&path).unwrap();
+    let mapped = MmapDTEDData::open(&path).unwrap();
+    assert_eq!(mapped.metadata.count, data.metadata.count);
+    for (lat_idx, lon_idx) in [(0, 0), (3, 4), (120, 7), (55, 120)] {
+        assert_eq!(
+            mapped.raw_elevation(lat_idx, lon_idx),
+            data.raw_elevation(lat_idx, lon_idx)
+        );
+    }
+    assert_eq!(mapped.raw_elevation(121, 0), None);
+    assert_eq!(mapped.raw_elevation(0, 121), None);
+    // the records viewed in place agree with the decoded ones
+    assert_eq!(mapped.columns().count(), 121);
+    for (column, decoded) in mapped.columns().zip(data.columns()) {
+        assert!(column.iter().eq(decoded.iter().copied()));
+    }
+    let column = mapped.column(4).unwrap();
+    assert_eq!(column.post(3), Some(dted2::Post::Void));
+    assert_eq!(column.get(5), Some(1));
+    assert_eq!(column.words()[5], 1u16.to_be());
+    assert_eq!(column.get(121), None);
+    let post = 1.0 / 120.0;
+    assert_eq!(
+        mapped.elevation_at(-10.0 + 3.0 * post, 20.0 + 4.0 * post),
+        None
+    );
+    assert_eq!(
+        mapped.elevation_at_interpolated(-10.0 + 10.5 * post, 20.0 + 2.5 * post),
+        data.elevation_at_interpolated(-10.0 + 10.5 * post, 20.0 + 2.5 * post)
+    );
+    // a truncated file is rejected
+    let content = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &content[..content.len() - 1]).unwrap();
+    assert!(MmapDTEDData::open(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_read_lazy() {
+    use dted2::ElevationSource;
+
+    let path = write_test_file("lazy", |lat, lon| (lat * lon) as i16);
+    let data = DTEDData::read(&path).unwrap();
+    let lazy = DTEDData::read_lazy(&path).unwrap();
+    assert_eq!(lazy.decoded_records(), 0);
+    assert_eq!(lazy.raw_elevation(7, 9), Some(63));
+    assert_eq!(lazy.raw_elevation(8, 9), Some(72));
+    assert_eq!(lazy.decoded_records(), 1);
+    assert_eq!(lazy.record(9).cloned(), data.data.record(9));
+    assert_eq!(lazy.raw_elevation(0, 121), None);
+    let post = 1.0 / 120.0;
+    assert_eq!(
+        lazy.elevation_at_interpolated(-10.0 + 10.5 * post, 20.0 + 2.5 * post),
+        data.elevation_at_interpolated(-10.0 + 10.5 * post, 20.0 + 2.5 * post)
+    );
+    assert_eq!(lazy.decoded_records(), 3);
+    // a truncated file is rejected up front
+    let content = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &content[..content.len() - 1]).unwrap();
+    assert!(DTEDData::read_lazy(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_read_metadata() {
+    let path = write_test_file("metadata", |_, _| 0);
+    let full = DTEDData::read_metadata(&path).unwrap();
+    // only the header records are needed
+    let content = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &content[..dted2::dted::DTED_DATA_OFFSET]).unwrap();
+    let metadata = DTEDData::read_metadata(&path).unwrap();
+    assert!(DTEDData::read(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(metadata.count.lat, 121);
+    assert_eq!(metadata.interval_secs.lat, 30.0);
+    assert_eq!(metadata.origin.lat, -10.0);
+    assert_eq!(metadata.dsi_record, full.dsi_record);
+    assert_eq!(metadata.acc_record, full.acc_record);
+    assert_eq!(metadata.dsi_record.unwrap().product_level, "DTED0");
+    assert!(DTEDData::read_header("tests/test_data.dt2")
+        .unwrap()
+        .dsi_record
+        .is_none());
+}
+
+#[test]
+fn test_read_from_stream() {
+    use dted2::stream::DTEDStreamReader;
+
+    /// Stream returning at most 7 bytes per read
+    struct Trickle<'a>(&'a [u8]);
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(7).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    let path = write_test_file("stream", |lat, lon| lat as i16 - 2 * lon as i16);
+    let content = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let data = DTEDData::read_from(Trickle(&content)).unwrap();
+    assert_eq!(data.metadata.count.lon, 121);
+    assert_eq!(data.data.len(), 121);
+    assert_eq!(data.data.get(9, 5), Some(-1));
+    assert_eq!(data.max.lat, -9.0);
+    // records are yielded one at a time, up to the first error
+    let truncated = &content[..content.len() - 10];
+    let reader = DTEDStreamReader::new(Trickle(truncated)).unwrap();
+    let records: Vec<_> = reader.collect();
+    assert_eq!(records.len(), 121);
+    assert!(records[..120].iter().all(|r| r.is_ok()));
+    assert!(records[120].is_err());
+    assert!(DTEDData::read_from(&content[..100]).is_err());
+}
+
+#[test]
+fn test_seek_reader() {
+    use dted2::reader::DTEDReader;
+    use dted2::ElevationSource;
+
+    let path = write_test_file("seek", |lat, lon| match (lat, lon) {
+        (1, 2) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => 3 * lat as i16 - lon as i16,
+    });
+    let content = std::fs::read(&path).unwrap();
+    let data = DTEDData::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let reader = DTEDReader::new(std::io::Cursor::new(&content)).unwrap();
+    assert_eq!(reader.metadata().count, data.metadata.count);
+    assert_eq!(reader.read_post(120, 120).unwrap(), Some(240));
+    assert_eq!(reader.read_post(121, 0).unwrap(), None);
+    assert_eq!(
+        reader.raw_elevation(1, 2),
+        Some(dted2::dted::DTED_VOID_ELEVATION)
+    );
+    let window = reader.read_window(10..20, 30..33).unwrap();
+    assert_eq!(window.len(), 3);
+    for (i, column) in window.iter().enumerate() {
+        assert_eq!(column[..], data.data.column(30 + i).unwrap()[10..20]);
+    }
+    assert!(reader.read_window(0..1, 120..122).is_err());
+    let post = 1.0 / 120.0;
+    assert_eq!(
+        reader.elevation_at_interpolated(-10.0 + 0.5 * post, 20.0 + 1.5 * post),
+        data.elevation_at_interpolated(-10.0 + 0.5 * post, 20.0 + 1.5 * post)
+    );
+    // a truncated file is rejected up front
+    let truncated = std::io::Cursor::new(&content[..content.len() - 1]);
+    assert!(DTEDReader::new(truncated).is_err());
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_read_async() {
+    use dted2::async_stream::AsyncDTEDStreamReader;
+
+    let path = write_test_file("async", |lat, lon| (lat + lon) as i16);
+    let expected = DTEDData::read(&path).unwrap();
+    let data = DTEDData::read_async(&path).await.unwrap();
+    assert_eq!(data.metadata.filename, path);
+    assert!(data.data == expected.data);
+    assert_eq!(data.metadata.dsi_record, expected.metadata.dsi_record);
+    // records are yielded one at a time, up to the first error
+    let content = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut reader = AsyncDTEDStreamReader::new(&content[..content.len() - 1])
+        .await
+        .unwrap();
+    let mut count = 0;
+    while let Some(record) = reader.next_record().await {
+        assert_eq!(record.is_ok(), count < 120);
+        count += 1;
+    }
+    assert_eq!(count, 121);
+    assert!(DTEDData::read_from_async(&content[..100]).await.is_err());
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_ndarray() {
+    let mut data = build_test_data("ndarray", |lat, lon| match (lat, lon) {
+        (4, 6) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (10 * lat + lon) as i16,
+    });
+    let view = data.data.as_array();
+    assert_eq!(view.dim(), (121, 121));
+    assert_eq!(view[[7, 2]], 72);
+    assert_eq!(
+        view.row(1).iter().take(3).collect::<Vec<_>>(),
+        [&10, &11, &12]
+    );
+    let array = data.to_array();
+    assert!(array[[4, 6]].is_nan());
+    assert_eq!(array[[120, 120]], 1320.0);
+    let mask = data.void_mask_array();
+    assert!(mask[[4, 6]]);
+    assert!(!mask[[6, 4]]);
+    assert_eq!(mask.iter().filter(|void| **void).count(), 1);
+    data.data.as_array_mut()[[0, 1]] = -5;
+    assert_eq!(data.data.get(0, 1), Some(-5));
+    assert!(!data.data.is_checksum_valid(1));
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_nalgebra() {
+    let mut data = build_test_data("nalgebra", |lat, lon| match (lat, lon) {
+        (4, 6) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (10 * lat + lon) as i16,
+    });
+    let view = data.data.as_matrix();
+    assert_eq!(view.shape(), (121, 121));
+    assert_eq!(view[(7, 2)], 72);
+    assert_eq!(
+        view.row(1).iter().take(3).collect::<Vec<_>>(),
+        [&10, &11, &12]
+    );
+    let matrix = data.to_dmatrix();
+    assert!(matrix[(4, 6)].is_nan());
+    assert_eq!(matrix[(120, 120)], 1320.0);
+    // linear algebra over the posts, e.g. the mean of a column
+    let column = matrix.column(2);
+    assert_eq!(
+        column.mean(),
+        (0..121).map(|lat| 10.0 * lat as f64 + 2.0).sum::<f64>() / 121.0
+    );
+    data.data.as_matrix_mut()[(0, 1)] = -5;
+    assert_eq!(data.data.get(0, 1), Some(-5));
+    assert!(!data.data.is_checksum_valid(1));
+}
+
+#[test]
+fn test_errors() {
+    use dted2::Error;
+
+    assert!(matches!(
+        DTEDData::read("tests/does_not_exist.dt2"),
+        Err(Error::Io(_))
+    ));
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let path = std::env::temp_dir().join("dted2_test_errors.dt0");
+    let path = path.to_str().unwrap();
+    // truncated
+    std::fs::write(path, &content[..content.len() - 3]).unwrap();
+    assert!(matches!(
+        DTEDData::read(path),
+        Err(Error::TruncatedFile { .. })
+    ));
+    // bad sentinel of the third data record
+    let mut corrupted = content.clone();
+    corrupted[dted2::dted::DTED_DATA_OFFSET + 2 * dted2::dted::dted_record_length(121)] = 0;
+    std::fs::write(path, &corrupted).unwrap();
+    assert!(matches!(
+        DTEDData::read(path),
+        Err(Error::InvalidSentinel { .. })
+    ));
+    // bad checksum of the last data record
+    let mut corrupted = content.clone();
+    *corrupted.last_mut().unwrap() ^= 0x01;
+    std::fs::write(path, &corrupted).unwrap();
+    let err = DTEDData::read(path).err().unwrap();
+    assert!(matches!(err, Error::BadChecksum { record: 120, .. }));
+    assert!(err
+        .to_string()
+        .starts_with("bad checksum for data record 120"));
+    assert!(DTEDData::read_from(&corrupted[..]).is_err());
+    // the raw file is still readable, and repairable
+    assert!(dted2::dted::RawDTEDFile::read(path).is_ok());
+    assert_eq!(dted2::dted::repair_checksums(path).unwrap(), 1);
+    assert!(DTEDData::read(path).is_ok());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_error_context() {
+    use dted2::dted::{dted_record_length, RawDTEDFile, DTED_DATA_OFFSET};
+    use dted2::{Error, Section};
+
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    // corrupt DSI sentinel
+    let mut corrupted = content.clone();
+    corrupted[80] = b'X';
+    for err in [
+        RawDTEDFile::from_bytes(&corrupted).err().unwrap(),
+        DTEDData::read_from(&corrupted[..]).err().unwrap(),
+    ] {
+        assert!(matches!(err, Error::InvalidSentinel { .. }));
+        let context = err.context().unwrap();
+        assert_eq!(context.section, Section::Dsi);
+        assert_eq!(context.offset, 80);
+        assert_eq!(&context.bytes[..3], b"XSI");
+    }
+    // corrupt sentinel of the third data record
+    let mut corrupted = content.clone();
+    let offset = DTED_DATA_OFFSET + 2 * dted_record_length(121);
+    corrupted[offset] = 0x55;
+    for err in [
+        RawDTEDFile::from_bytes(&corrupted).err().unwrap(),
+        DTEDData::read_from(&corrupted[..]).err().unwrap(),
+    ] {
+        let context = err.context().unwrap();
+        assert_eq!(context.section, Section::Record(Some(2)));
+        assert_eq!(context.offset, offset);
+        assert_eq!(context.bytes.len(), 16);
+        assert_eq!(context.bytes[0], 0x55);
+        assert!(err
+            .to_string()
+            .starts_with("invalid recognition sentinel in data record 2 at byte offset"));
+    }
+    // truncated in the last data record
+    let truncated = &content[..content.len() - 3];
+    for err in [
+        RawDTEDFile::from_bytes(truncated).err().unwrap(),
+        DTEDData::read_from(truncated).err().unwrap(),
+    ] {
+        assert!(matches!(err, Error::TruncatedFile { .. }));
+        assert_eq!(err.context().unwrap().section, Section::Record(Some(120)));
+    }
+}
+
+#[test]
+fn test_parse_options() {
+    use dted2::dted::{dted_record_length, RawDTEDFile, DTED_DATA_OFFSET, DTED_VOID_ELEVATION};
+    use dted2::{Error, ParseOptions};
+
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let strict = ParseOptions::strict();
+    let lenient = ParseOptions::lenient();
+    // bad checksum of the last data record
+    let mut corrupted = content.clone();
+    *corrupted.last_mut().unwrap() ^= 0x01;
+    assert!(matches!(
+        RawDTEDFile::from_bytes_with(&corrupted, &strict),
+        Err(Error::BadChecksum { record: 120, .. })
+    ));
+    assert!(RawDTEDFile::from_bytes_with(&corrupted, &lenient).is_ok());
+    // malformed DSI record
+    let mut corrupted = content.clone();
+    // decimal point of the latitude of origin
+    corrupted[80 + 191] = b'X';
+    assert!(RawDTEDFile::from_bytes_with(&corrupted, &strict).is_err());
+    let file = RawDTEDFile::from_bytes_with(&corrupted, &lenient).unwrap();
+    assert!(file.dsi_record.is_none());
+    assert!(file.acc_record.is_some());
+    assert_eq!(file.data.len(), 121);
+    // missing ACC record
+    let mut missing = content[..DTED_DATA_OFFSET - 2700].to_vec();
+    missing.extend_from_slice(&content[DTED_DATA_OFFSET..]);
+    assert!(RawDTEDFile::from_bytes_with(&missing, &strict).is_err());
+    let file = RawDTEDFile::from_bytes_with(&missing, &lenient).unwrap();
+    assert!(file.dsi_record.is_some());
+    assert!(file.acc_record.is_none());
+    assert_eq!(file.data, RawDTEDFile::from_bytes(&content).unwrap().data);
+    // truncated in the middle of the sixth to last data record
+    let truncated = &content[..content.len() - 5 * dted_record_length(121) - 10];
+    assert!(matches!(
+        RawDTEDFile::from_bytes_with(truncated, &strict),
+        Err(Error::TruncatedFile { .. })
+    ));
+    let file = RawDTEDFile::from_bytes_with(truncated, &lenient).unwrap();
+    assert_eq!(file.data.len(), 121);
+    assert!(file
+        .data
+        .column(115)
+        .unwrap()
+        .iter()
+        .all(|&e| e == DTED_VOID_ELEVATION));
+    assert_ne!(file.data.get(0, 114), Some(DTED_VOID_ELEVATION));
+    // maximum number of data records
+    let capped = ParseOptions {
+        max_records: Some(120),
+        ..ParseOptions::default()
+    };
+    assert!(matches!(
+        RawDTEDFile::from_bytes_with(&content, &capped),
+        Err(Error::ParseError { .. })
+    ));
+    // reading a file with options
+    let path = std::env::temp_dir().join("dted2_test_parse_options.dt0");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, truncated).unwrap();
+    assert!(DTEDData::read(path).is_err());
+    let data = DTEDData::read_with(path, &lenient).unwrap();
+    assert_eq!(data.metadata.count.lon, 121);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_parse_progress() {
+    use dted2::dted::{dted_record_length, RawDTEDFile, DTED_DATA_OFFSET};
+    use dted2::{ParseOptions, ParseProgress};
+    use std::sync::{Arc, Mutex};
+
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let record_len = dted_record_length(121);
+    let reports = Arc::new(Mutex::new(Vec::<ParseProgress>::new()));
+    let sink = reports.clone();
+    let options = ParseOptions::default()
+        .on_progress(40, move |progress| sink.lock().unwrap().push(*progress));
+    assert_eq!(options.clone(), options);
+    assert_ne!(options, ParseOptions::default());
+    let file = RawDTEDFile::from_bytes_with(&content, &options).unwrap();
+    assert_eq!(file.data, RawDTEDFile::from_bytes(&content).unwrap().data);
+    let expected: Vec<_> = [40, 80, 120, 121]
+        .into_iter()
+        .map(|records| ParseProgress {
+            records,
+            total_records: 121,
+            bytes: DTED_DATA_OFFSET + records * record_len,
+            total_bytes: content.len(),
+        })
+        .collect();
+    assert_eq!(*reports.lock().unwrap(), expected);
+    assert_eq!(expected[3].fraction(), 1.0);
+    // recovering reports the same progress
+    reports.lock().unwrap().clear();
+    RawDTEDFile::recover_from_bytes(&content, &options).unwrap();
+    assert_eq!(*reports.lock().unwrap(), expected);
+    // errors still locate the offending record
+    reports.lock().unwrap().clear();
+    let mut corrupted = content.clone();
+    corrupted[DTED_DATA_OFFSET + 50 * record_len] = 0;
+    let err = RawDTEDFile::from_bytes_with(&corrupted, &options).unwrap_err();
+    assert_eq!(
+        err.context().unwrap().offset,
+        DTED_DATA_OFFSET + 50 * record_len
+    );
+    assert_eq!(reports.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_read_recovering() {
+    use dted2::dted::{dted_record_length, RawDTEDFile, DTED_DATA_OFFSET, DTED_VOID_ELEVATION};
+    use dted2::{Error, ParseOptions, Section};
+
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let expected = RawDTEDFile::from_bytes(&content).unwrap();
+    let record_len = dted_record_length(121);
+    // bad sentinel of the third data record, bad checksum of the tenth
+    let mut corrupted = content.clone();
+    corrupted[DTED_DATA_OFFSET + 2 * record_len] = 0;
+    corrupted[DTED_DATA_OFFSET + 10 * record_len - 1] ^= 0x01;
+    let (file, diagnostics) =
+        RawDTEDFile::recover_from_bytes(&corrupted, &ParseOptions::default()).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].section, Section::Record(Some(2)));
+    assert!(matches!(
+        diagnostics[0].error,
+        Error::InvalidSentinel { .. }
+    ));
+    assert_eq!(
+        diagnostics[0].error.context().unwrap().offset,
+        DTED_DATA_OFFSET + 2 * record_len
+    );
+    assert_eq!(diagnostics[1].section, Section::Record(Some(9)));
+    assert!(matches!(
+        diagnostics[1].error,
+        Error::BadChecksum { record: 9, .. }
+    ));
+    assert!(diagnostics[0]
+        .to_string()
+        .starts_with("skipped data record 2: invalid recognition sentinel"));
+    for lon_idx in 0..121 {
+        match lon_idx {
+            2 | 9 => assert!(file
+                .data
+                .column(lon_idx)
+                .unwrap()
+                .iter()
+                .all(|&e| e == DTED_VOID_ELEVATION)),
+            _ => assert_eq!(file.data.column(lon_idx), expected.data.column(lon_idx)),
+        }
+    }
+    assert!(file.data.validate_checksums().is_ok());
+    // without verifying checksums, only the third data record is skipped
+    let options = ParseOptions {
+        verify_checksums: false,
+        ..ParseOptions::default()
+    };
+    let (_, diagnostics) = RawDTEDFile::recover_from_bytes(&corrupted, &options).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    // missing records are only recovered when tolerated
+    let truncated = &corrupted[..corrupted.len() - record_len - 1];
+    assert!(matches!(
+        RawDTEDFile::recover_from_bytes(truncated, &ParseOptions::default()),
+        Err(Error::TruncatedFile { .. })
+    ));
+    let (file, diagnostics) =
+        RawDTEDFile::recover_from_bytes(truncated, &ParseOptions::lenient()).unwrap();
+    assert_eq!(file.data.len(), 121);
+    let sections: Vec<_> = diagnostics.iter().map(|d| d.section).collect();
+    assert_eq!(
+        sections,
+        vec![
+            Section::Record(Some(2)),
+            Section::Record(Some(119)),
+            Section::Record(Some(120))
+        ]
+    );
+    // reading a file
+    let path = std::env::temp_dir().join("dted2_test_read_recovering.dt0");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, &corrupted).unwrap();
+    assert!(DTEDData::read(path).is_err());
+    let (data, diagnostics) = DTEDData::read_recovering(path, &ParseOptions::default()).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(data.data.get(0, 2), Some(DTED_VOID_ELEVATION));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_malformed_fields() {
+    use dted2::dted::{RawDTEDFile, DTED_DATA_OFFSET, DTED_UHL_LENGTH};
+    use dted2::parsers::dted_headers_parser;
+    use dted2::{Error, Section};
+
+    let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    // garbage in any header byte fails (or not) without panicking
+    for idx in 0..DTED_DATA_OFFSET {
+        for garbage in [b'/', b':', b'9', b'X', 0x00, 0xFF] {
+            let mut corrupted = content[..DTED_DATA_OFFSET].to_vec();
+            corrupted[idx] = garbage;
+            let _ = dted_headers_parser(&corrupted);
+        }
+    }
+    // non-digit in the UHL longitude count
+    let mut corrupted = content.clone();
+    corrupted[47] = b'x';
+    let err = RawDTEDFile::from_bytes(&corrupted).err().unwrap();
+    assert!(matches!(err, Error::ParseError { .. }));
+    assert_eq!(err.context().unwrap().section, Section::Uhl);
+    assert_eq!(err.context().unwrap().offset, 47);
+    // out of range minutes of the UHL longitude of origin
+    let mut corrupted = content.clone();
+    corrupted[7] = b'6';
+    assert!(RawDTEDFile::from_bytes(&corrupted).is_err());
+    // out of range UHL latitude and longitude of origin
+    for (offset, degrees) in [(12, b"091"), (4, b"181")] {
+        let mut corrupted = content.clone();
+        corrupted[offset..offset + 3].copy_from_slice(degrees);
+        let err = RawDTEDFile::from_bytes(&corrupted).err().unwrap();
+        assert_eq!(err.context().unwrap().section, Section::Uhl);
+    }
+    // out of range DSI latitude of origin
+    let mut corrupted = content.clone();
+    let offset = DTED_UHL_LENGTH as usize + 185;
+    corrupted[offset..offset + 2].copy_from_slice(b"95");
+    let err = RawDTEDFile::from_bytes(&corrupted).err().unwrap();
+    assert_eq!(err.context().unwrap().section, Section::Dsi);
+    // no data records
+    let mut corrupted = content[..DTED_DATA_OFFSET].to_vec();
+    corrupted[47..51].copy_from_slice(b"0000");
+    let file = RawDTEDFile::from_bytes(&corrupted).unwrap();
+    assert!(file.data.is_empty());
+    let metadata = dted2::DTEDMetadata::from_header(&file.header, "");
+    assert_eq!(
+        DTEDData::new(metadata, file.data).get_elevation(-30.0, -178.0),
+        None
+    );
+}
+
+#[test]
+fn test_level() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::DTEDLevel;
+
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    assert_eq!(data.metadata.level(), Some(DTEDLevel::Level2));
+    let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(file.level(), Some(DTEDLevel::Level0));
+    // the level follows the latitude interval, then the count, then the DSI record
+    assert_eq!(DTEDLevel::infer(30, 0, None), Some(DTEDLevel::Level1));
+    assert_eq!(DTEDLevel::infer(0, 3601, None), Some(DTEDLevel::Level2));
+    assert_eq!(
+        DTEDLevel::infer(0, 0, Some("DTED1")),
+        Some(DTEDLevel::Level1)
+    );
+    assert_eq!(DTEDLevel::infer(0, 0, Some("SRTM ")), None);
+    // the builder writes the level into the DSI record
+    let data = build_test_data("level", |_, _| 0);
+    assert_eq!(
+        data.metadata.dsi_record.as_ref().unwrap().product_level,
+        "DTED0"
+    );
+    assert_eq!(data.metadata.level(), Some(DTEDLevel::Level0));
+    assert!(DTEDLevel::Level0 < DTEDLevel::Level2);
+    assert_eq!(DTEDLevel::Level1.number(), 1);
+}
+
+#[test]
+fn test_latitude_zones() {
+    use dted2::dted::LatitudeZone;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::{DTEDBuilder, DTEDLevel, ElevationSource};
+
+    for (lat, south, zone, lon_count) in [
+        (49, false, LatitudeZone::I, 121),
+        (50, false, LatitudeZone::II, 61),
+        (70, true, LatitudeZone::II, 61),
+        (72, false, LatitudeZone::III, 41),
+        (76, true, LatitudeZone::IV, 31),
+        (85, false, LatitudeZone::V, 21),
+    ] {
+        let origin = AxisElement::new(
+            Angle::new(lat, 0, 0.0, south),
+            Angle::new(15, 0, 0.0, false),
+        );
+        let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+            .elevations((0..lon_count).map(|lon| vec![lon as i16; 121]).collect())
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("dted2_test_zone_{}.dt0", lat));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, file.to_bytes().unwrap()).unwrap();
+        let data = DTEDData::read(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(data.metadata.zone(), zone);
+        assert_eq!(data.metadata.count.lon, lon_count);
+        // the cell spans 1 degree of longitude, whatever the number of posts
+        let origin_lat: f64 = origin.lat.into();
+        assert_eq!(data.max.lon, 16.0);
+        assert_eq!(
+            data.elevation_at(origin_lat + 0.5, 16.0),
+            Some(lon_count as i16 - 1)
+        );
+        assert_eq!(
+            data.elevation_at(origin_lat + 0.5, 15.5),
+            Some((lon_count as i16 - 1) / 2)
+        );
+        assert_eq!(data.elevation_at(origin_lat + 0.5, 16.01), None);
+        // the post indices follow the longitude interval of the zone
+        let last = lon_count as usize - 1;
+        assert_eq!(data.index_of(origin_lat + 0.5, 15.5), Some((60, last / 2)));
+        assert_eq!(data.index_of(origin_lat + 1.0, 16.0), Some((120, last)));
+        let (lat, lon) = data.position_of(60, last);
+        assert!((lat - (origin_lat + 0.5)).abs() < 1e-9);
+        assert!((lon - 16.0).abs() < 1e-9);
+        let (_, lon) = data.position_of(0, 1);
+        assert!((lon - 15.0 - 1.0 / last as f64).abs() < 1e-9);
+    }
+    // a square grid is rejected above 50 degrees
+    let origin = AxisElement::new(Angle::new(60, 0, 0.0, false), Angle::new(15, 0, 0.0, false));
+    let square = DTEDBuilder::new(
+        origin,
+        AxisElement::new(300, 300),
+        AxisElement::new(121, 121),
+    )
+    .elevations(vec![vec![0; 121]; 121])
+    .level(DTEDLevel::Level0)
+    .build();
+    assert!(square.is_err());
+}
+
+#[test]
+fn test_all_levels() {
+    use dted2::dted::{dted_record_length, RawDTEDFile, DTED_DATA_OFFSET};
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::reader::DTEDReader;
+    use dted2::{DTEDBuilder, DTEDLevel, ElevationSource};
+
+    // one cell per level, in different latitude zones to keep them small
+    for (level, lat, count) in [
+        (DTEDLevel::Level0, 10, AxisElement::new(121, 121)),
+        (DTEDLevel::Level1, 55, AxisElement::new(1201, 601)),
+        (DTEDLevel::Level2, 85, AxisElement::new(3601, 601)),
+    ] {
+        let origin = AxisElement::new(Angle::new(lat, 0, 0.0, false), Angle::new(20, 0, 0.0, true));
+        let elev = |lat_idx: usize, lon_idx: usize| (lat_idx % 1000) as i16 - lon_idx as i16;
+        let grid = (0..count.lon as usize)
+            .map(|lon_idx| {
+                (0..count.lat as usize)
+                    .map(|lat_idx| elev(lat_idx, lon_idx))
+                    .collect()
+            })
+            .collect();
+        let bytes = DTEDBuilder::for_level(origin, level)
+            .elevations(grid)
+            .build()
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert_eq!(
+            bytes.len(),
+            DTED_DATA_OFFSET + count.lon as usize * dted_record_length(count.lat)
+        );
+        let file = RawDTEDFile::from_bytes(&bytes).unwrap();
+        assert_eq!(file.level(), Some(level));
+        assert_eq!(file.header.count, count);
+        let data = DTEDData::read_from(&bytes[..]).unwrap();
+        let reader = DTEDReader::new(std::io::Cursor::new(&bytes)).unwrap();
+        assert_eq!(data.metadata.level(), Some(level));
+        assert_eq!(data.data, file.data);
+        let last = AxisElement::new(count.lat as usize - 1, count.lon as usize - 1);
+        for (lat_idx, lon_idx) in [
+            (0, 0),
+            (last.lat, 0),
+            (0, last.lon),
+            (last.lat, last.lon),
+            (7, 3),
+        ] {
+            assert_eq!(
+                data.raw_elevation(lat_idx, lon_idx),
+                Some(elev(lat_idx, lon_idx))
+            );
+            assert_eq!(
+                reader.read_post(lat_idx, lon_idx).unwrap(),
+                Some(elev(lat_idx, lon_idx))
+            );
+        }
+        // corners of the 1 degree cell
+        let (lat, lon) = (lat as f64, -20.0);
+        assert_eq!(data.elevation_at(lat, lon), Some(0));
+        assert_eq!(
+            data.elevation_at(lat + 1.0, lon + 1.0),
+            Some(elev(last.lat, last.lon))
+        );
+    }
+}
+
+#[test]
+fn test_partial_cell() {
+    use dted2::dted::{dted_record_length, Coverage, RawDTEDFile, DTED_DATA_OFFSET};
+    use dted2::{ParseOptions, Section};
+
+    let path = write_test_file("partial_cell", |lat, lon| (lat + 2 * lon) as i16);
+    let content = std::fs::read(&path).unwrap();
+    let expected = DTEDData::read(&path).unwrap();
+    assert!(!expected.coverage.is_partial());
+    let record_len = dted_record_length(121);
+    let lenient = ParseOptions::lenient();
+    // only the western data records
+    let west = &content[..DTED_DATA_OFFSET + 60 * record_len];
+    std::fs::write(&path, west).unwrap();
+    assert!(DTEDData::read(&path).is_err());
+    let data = DTEDData::read_with(&path, &lenient).unwrap();
+    assert_eq!(data.coverage.lon_range, 0..60);
+    assert!(data.coverage.is_partial());
+    assert_eq!(data.data.len(), 121);
+    assert_eq!(data.data.get(5, 59), expected.data.get(5, 59));
+    assert_eq!(data.data.get(5, 60), Some(dted2::dted::DTED_VOID_ELEVATION));
+    let (min, max) = data.covered_extent().unwrap();
+    assert_eq!((min.lat, min.lon), (-10.0, 20.0));
+    assert_eq!((max.lat, max.lon), (-9.0, 20.0 + 59.0 / 120.0));
+    // only the eastern data records, placed by their longitude count
+    let mut east = content[..DTED_DATA_OFFSET].to_vec();
+    east.extend_from_slice(&content[DTED_DATA_OFFSET + 61 * record_len..]);
+    let (file, diagnostics) = RawDTEDFile::recover_from_bytes(&east, &lenient).unwrap();
+    assert_eq!(
+        file.coverage,
+        Coverage {
+            lon_range: 61..121,
+            lon_count: 121
+        }
+    );
+    assert_eq!(diagnostics.len(), 61);
+    assert_eq!(diagnostics[0].section, Section::Record(Some(0)));
+    assert_eq!(diagnostics[60].section, Section::Record(Some(60)));
+    assert_eq!(file.data.column(61), expected.data.column(61));
+    assert_eq!(file.data.column(120), expected.data.column(120));
+    assert!(file.data.validate_checksums().is_ok());
+    // no data records at all
+    let file = RawDTEDFile::from_bytes_with(&content[..DTED_DATA_OFFSET], &lenient).unwrap();
+    assert!(file.coverage.lon_range.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_void_posts() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+    use dted2::{ElevationSource, Post};
+
+    let data = build_test_data("void_posts", |lat, lon| match (lat, lon) {
+        (10, 20) => DTED_VOID_ELEVATION,
+        _ => 100,
+    });
+    assert_eq!(data.post(10, 20), Some(Post::Void));
+    assert_eq!(data.post(10, 21), Some(Post::Elevation(100)));
+    assert_eq!(data.post(121, 0), None);
+    assert_eq!(data.data.post(10, 20), Some(Post::Void));
+    // the raw value is still reachable
+    assert_eq!(data.raw_elevation(10, 20), Some(DTED_VOID_ELEVATION));
+    assert_eq!(data.post(10, 20).unwrap().raw(), DTED_VOID_ELEVATION);
+    assert_eq!(data.elevation(10, 20), None);
+    assert_eq!(data.elevation(10, 21), Some(100));
+    // voids never leak into the elevations at a lat/lon
+    let post = 1.0 / 120.0;
+    let (lat, lon) = (-10.0 + 10.0 * post, 20.0 + 20.0 * post);
+    assert_eq!(data.elevation_at(lat, lon), None);
+    assert_eq!(data.get_elevation(lat + 0.5 * post, lon + 0.5 * post), None);
+    assert_eq!(data.get_elevation(lat - 0.5 * post, lon - 0.5 * post), None);
+    assert_eq!(
+        data.get_elevation(lat + 1.5 * post, lon + 1.5 * post),
+        Some(100.0)
+    );
+}
+
+#[test]
+fn test_void_stats() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // the western quarter of the cell, plus one post, is void
+    let data = build_test_data("void_stats", |lat, lon| match (lat, lon) {
+        (_, 0..=29) | (7, 100) => DTED_VOID_ELEVATION,
+        _ => 0,
+    });
+    assert_eq!(data.void_count(), 30 * 121 + 1);
+    assert_eq!(data.void_fraction(), (30.0 * 121.0 + 1.0) / (121.0 * 121.0));
+    let mask = data.void_mask();
+    assert_eq!(mask.len(), 121 * 121);
+    assert!(mask[29 * 121 + 120]);
+    assert!(!mask[30 * 121]);
+    assert!(mask[100 * 121 + 7]);
+    assert_eq!(mask.iter().filter(|void| **void).count(), data.void_count());
+}
+
+#[test]
+fn test_fill_voids() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+    use dted2::fill::VoidFill;
+    use dted2::ElevationSource;
+
+    // elevations rise 10 m per post eastward, with a void hole
+    // and the whole northern line void
+    let data = build_test_data("fill_voids", |lat, lon| match (lat, lon) {
+        (50..=52, 59..=61) | (120, _) => DTED_VOID_ELEVATION,
+        _ => 10 * lon as i16,
+    });
+    assert_eq!(data.void_count(), 9 + 121);
+
+    let nearest = data.fill_voids(VoidFill::Nearest);
+    assert_eq!(nearest.void_count(), 0);
+    assert_eq!(nearest.elevation(51, 59), Some(580));
+    assert_eq!(nearest.elevation(51, 61), Some(620));
+    assert_eq!(nearest.elevation(120, 7), Some(70));
+
+    let bilinear = data.fill_voids(VoidFill::Bilinear);
+    // the northern line only has valid posts to its south
+    assert_eq!(bilinear.elevation(51, 60), Some(600));
+    assert_eq!(bilinear.elevation(50, 61), Some(610));
+    assert_eq!(bilinear.elevation(120, 7), None);
+
+    let idw = data.fill_voids(VoidFill::InverseDistance {
+        power: 2.0,
+        radius: 1,
+    });
+    // the center of the hole is beyond the radius of any valid post
+    assert_eq!(idw.elevation(51, 60), None);
+    assert_eq!(idw.elevation(50, 60), Some(600));
+    assert_eq!(idw.void_count(), 1);
+    let idw = data.fill_voids(VoidFill::InverseDistance {
+        power: 2.0,
+        radius: 2,
+    });
+    assert_eq!(idw.void_count(), 0);
+
+    // valid posts, metadata and checksums are untouched
+    assert_eq!(bilinear.elevation(10, 10), data.elevation(10, 10));
+    assert_eq!(bilinear.metadata.origin, data.metadata.origin);
+    let mut checked = bilinear.data.clone();
+    assert_eq!(checked.recompute_checksums(), 0);
+}
+
+#[test]
+fn test_dataset() {
+    use dted2::interpolation::Interpolation;
+    use dted2::primitives::AxisElement;
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // two adjacent cells, elevations telling them apart
+    let west = write_test_cell("dataset_west", (-10, 20), |_, _| 100);
+    let east = write_test_cell("dataset_east", (-10, 21), |_, _| 200);
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    let lazy = DTEDDataset::read_lazy(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(
+        dataset.bounds(),
+        Some((AxisElement::new(-10.0, 20.0), AxisElement::new(-9.0, 22.0)))
+    );
+    assert_eq!(dataset.elevation_at(-9.5, 20.5), Some(100));
+    assert_eq!(dataset.elevation_at(-9.5, 21.5), Some(200));
+    assert_eq!(dataset.elevation_at_interpolated(-9.5, 21.5), Some(200.0));
+    assert_eq!(
+        dataset.elevation_at_with(-9.5, 21.5, Interpolation::Bicubic),
+        Some(200.0)
+    );
+    // the shared edge is answered by the first cell
+    assert_eq!(dataset.elevation_at(-9.5, 21.0), Some(100));
+    assert_eq!(
+        dataset.cell_at(-9.5, 21.0).unwrap().metadata().origin.lon,
+        20.0
+    );
+    assert!(dataset.elevation_at(-8.5, 20.5).is_none());
+    assert!(dataset.cell_at(-8.5, 20.5).is_none());
+    // lazily decoded cells answer the same
+    assert_eq!(lazy.elevation_at(-9.5, 21.5), Some(200));
+    assert_eq!(lazy.bounds(), dataset.bounds());
+
+    let mut empty = DTEDDataset::<dted2::DTEDData>::new();
+    assert!(empty.is_empty());
+    assert!(empty.bounds().is_none());
+    empty.push(DTEDData::read("tests/test_data_negative.dt2").unwrap());
+    assert_eq!(empty.len(), 1);
+    assert!(empty.elevation_at(-29.5, -177.5).is_some());
+}
+
+#[test]
+fn test_dataset_from_dir() {
+    use dted2::DTEDDataset;
+
+    // one cell of each naming convention, plus files to be ignored
+    let dir = std::env::temp_dir().join("dted2_test_dataset_dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("dted/e020")).unwrap();
+    let cell = write_test_cell("dir_classic", (-10, 20), |_, _| 100);
+    std::fs::rename(&cell, dir.join("dted/e020/s10.dt0")).unwrap();
+    let cell = write_test_cell("dir_flat", (-10, 21), |_, _| 200);
+    std::fs::rename(&cell, dir.join("S10_E021.DT0")).unwrap();
+    std::fs::write(dir.join("readme.txt"), "not a cell").unwrap();
+    std::fs::write(dir.join("dted/e020/corrupt.dt0"), "not a cell").unwrap();
+
+    let dataset = DTEDDataset::from_dir(dir.to_str().unwrap()).unwrap();
+    assert_eq!(dataset.len(), 2);
+    assert!(dataset.cells().iter().all(|cell| !cell.is_opened()));
+    assert_eq!(dataset.elevation_at(-9.5, 20.5), Some(100));
+    assert_eq!(dataset.elevation_at(-9.5, 21.5), Some(200));
+    assert_eq!(
+        dataset
+            .cells()
+            .iter()
+            .filter(|cell| cell.is_opened())
+            .count(),
+        2
+    );
+    assert!(dataset.elevation_at(-8.5, 20.5).is_none());
+
+    // a symbolic link cycle is not followed
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink("..", dir.join("dted/e020/back")).unwrap();
+        let dataset = DTEDDataset::from_dir(dir.to_str().unwrap()).unwrap();
+        assert_eq!(dataset.len(), 2);
+    }
+
+    // a file named after a cell must be a valid DTED file
+    std::fs::write(dir.join("n01_e001.dt2"), "not a cell").unwrap();
+    assert!(DTEDDataset::from_dir(dir.to_str().unwrap()).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_dataset_index() {
+    use dted2::primitives::AxisElement;
+    use dted2::DTEDDataset;
+
+    // 5x5 cells, the elevations telling them apart
+    let mut dataset = DTEDDataset::new();
+    for lat in -2..3 {
+        for lon in 40..45 {
+            let name = format!("index_{}_{}", lat, lon);
+            let path = write_test_cell(&name, (lat, lon), |_, _| 100 * lat + lon);
+            dataset.push(DTEDData::read(&path).unwrap());
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+    assert_eq!(dataset.len(), 25);
+    assert_eq!(dataset.elevation_at(-1.5, 42.5), Some(-200 + 42));
+    assert_eq!(dataset.elevation_at(2.99, 44.99), Some(200 + 44));
+    assert_eq!(dataset.elevation_at(-2.0, 40.0), Some(-200 + 40));
+    assert_eq!(dataset.elevation_at(3.0, 45.0), Some(200 + 44));
+    assert!(dataset.elevation_at(3.01, 45.0).is_none());
+    assert!(dataset.elevation_at(0.5, 39.99).is_none());
+
+    // a box strictly within 2x2 cells, then one along their edges
+    let cells = dataset.cells_in(AxisElement::new(-0.5, 41.5), AxisElement::new(0.5, 42.5));
+    assert_eq!(cells.len(), 4);
+    let cells = dataset.cells_in(AxisElement::new(0.0, 42.0), AxisElement::new(1.0, 43.0));
+    assert_eq!(cells.len(), 9);
+    let origins: Vec<_> = cells
+        .iter()
+        .map(|cell| (cell.metadata.origin.lat, cell.metadata.origin.lon))
+        .collect();
+    assert_eq!(origins[0], (-1.0, 41.0));
+    assert_eq!(origins[8], (1.0, 43.0));
+    assert_eq!(
+        dataset
+            .cells_in(
+                AxisElement::new(-90.0, -180.0),
+                AxisElement::new(90.0, 180.0)
+            )
+            .len(),
+        25
+    );
+    assert!(dataset
+        .cells_in(AxisElement::new(1.0, 1.0), AxisElement::new(0.0, 0.0))
+        .is_empty());
+}
+
+#[test]
+fn test_dataset_seams() {
+    use dted2::interpolation::Interpolation;
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // a parabola along longitude, with its minimum on the shared edge
+    let west = write_test_cell("seams_west", (-10, 20), |_, lon| (120 - lon as i16).pow(2));
+    let east = write_test_cell("seams_east", (-10, 21), |_, lon| (lon as i16).pow(2));
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+
+    // half a post west of the edge
+    let (lat, lon) = (-9.5, 21.0 - 0.5 / 120.0);
+    let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9;
+    assert!(close(dataset.elevation_at_interpolated(lat, lon), 0.5));
+    // the cubic kernel reproduces the parabola, with posts from both cells
+    assert!(close(
+        dataset.elevation_at_with(lat, lon, Interpolation::Bicubic),
+        0.25
+    ));
+    // while a lone cell falls back to bilinear at its edge
+    let west = &dataset.cells()[0];
+    assert!(close(
+        west.elevation_at_with(lat, lon, Interpolation::Bicubic),
+        0.5
+    ));
+    // on the east side, the posts come from the west cell
+    assert!(close(
+        dataset.elevation_at_with(lat, 21.0 + 0.5 / 120.0, Interpolation::Bicubic),
+        0.25
+    ));
+    assert!(close(
+        dataset.elevation_at_with(lat, 21.0, Interpolation::Lanczos),
+        0.0
+    ));
+}
+
+#[test]
+fn test_bulk_queries() {
+    use dted2::lazy::LazyDTEDData;
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // a scattered trajectory, partly outside of the cells
+    let points: Vec<(f64, f64)> = (0..1000)
+        .map(|i| {
+            let t = i as f64 * 0.618_034;
+            (42.0 + (t * 7.0).fract() * 1.2, 15.0 + t.fract() * 1.2)
+        })
+        .collect();
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    let expected: Vec<_> = points
+        .iter()
+        .map(|(lat, lon)| data.elevation_at(*lat, *lon))
+        .collect();
+    assert!(expected.iter().any(|elev| elev.is_none()));
+    assert!(expected.iter().any(|elev| elev.is_some()));
+    assert_eq!(data.elevations_at(&points), expected);
+    let lazy = LazyDTEDData::read("tests/test_data.dt2").unwrap();
+    assert_eq!(lazy.elevations_at(&points), expected);
+
+    let dataset =
+        DTEDDataset::read(&["tests/test_data_negative.dt2", "tests/test_data.dt2"]).unwrap();
+    let mut mixed = points.clone();
+    mixed.extend([(-29.5, -177.5), (-30.0, -178.0), (0.0, 0.0)]);
+    let expected: Vec<_> = mixed
+        .iter()
+        .map(|(lat, lon)| dataset.elevation_at(*lat, *lon))
+        .collect();
+    assert_eq!(dataset.elevations_at(&mixed), expected);
+    assert!(dataset.elevations_at(&[]).is_empty());
+}
+
+#[test]
+fn test_profile() {
+    use dted2::geodesy::{LineType, EARTH_RADIUS_M};
+    use dted2::interpolation::Interpolation;
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // elevations rising 1 m per post eastward, across two cells
+    let west = write_test_cell("profile_west", (-10, 20), |_, lon| lon as i16);
+    let east = write_test_cell("profile_east", (-10, 21), |_, lon| 120 + lon as i16);
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+
+    // along a parallel, the great circle bulges poleward, the rhumb line does not
+    let (start, end) = ((-9.5, 20.25), (-9.5, 21.75));
+    let profile = dataset.profile(start, end, 7);
+    assert_eq!(profile.len(), 7);
+    assert_eq!((profile[0].lat, profile[0].lon), start);
+    assert_eq!((profile[6].lat, profile[6].lon), end);
+    assert!(profile[3].lat < -9.5);
+    let rhumb = dataset.profile_with(start, end, 7, LineType::Rhumb, Interpolation::Bilinear);
+    for (idx, sample) in rhumb.iter().enumerate() {
+        assert!((sample.lat + 9.5).abs() < 1e-9);
+        assert!((sample.lon - (20.25 + 0.25 * idx as f64)).abs() < 1e-9);
+        let expected = 120.0 * (sample.lon - 20.0);
+        assert!((sample.elevation.unwrap() - expected).abs() < 1e-6);
+    }
+    let length = 1.5f64.to_radians() * EARTH_RADIUS_M * 9.5f64.to_radians().cos();
+    assert!((rhumb[6].distance - length).abs() < 1e-6);
+    assert!(profile[6].distance < rhumb[6].distance);
+
+    // samples off the dataset are None, a single sample is the start
+    let off = dataset.profile((-9.5, 21.5), (-9.5, 22.5), 3);
+    assert!(off[0].elevation.is_some());
+    assert!(off[2].elevation.is_none());
+    assert_eq!(dataset.profile(start, end, 1)[0].lon, start.1);
+    assert!(dataset.profile(start, end, 0).is_empty());
+    // a single cell samples the same
+    let cell = &dataset.cells()[0];
+    assert_eq!(
+        cell.profile(start, (-9.5, 20.75), 5),
+        dataset.profile(start, (-9.5, 20.75), 5)
+    );
+}
+
+#[test]
+fn test_line_of_sight() {
+    use dted2::profile::ProfileSample;
+    use dted2::visibility::LineOfSight;
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // a 100 m north-south wall along the shared edge of two flat cells
+    let west = write_test_cell("los_west", (-10, 20), |_, lon| match lon {
+        120 => 100,
+        _ => 0,
+    });
+    let east = write_test_cell("los_east", (-10, 21), |_, lon| match lon {
+        0 => 100,
+        _ => 0,
+    });
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+
+    let (observer, target) = ((-9.5, 20.98), (-9.5, 21.02));
+    let los = dataset.line_of_sight(observer, 2.0, target, 2.0).unwrap();
+    assert!(!los.is_visible());
+    assert!(los.clearance < -90.0);
+    let obstruction = los.obstruction.unwrap();
+    assert!((obstruction.lon - 21.0).abs() < 1.0 / 120.0);
+    assert!(obstruction.elevation.unwrap() > 0.0);
+    // over the wall
+    let los = dataset
+        .line_of_sight(observer, 150.0, target, 150.0)
+        .unwrap();
+    assert!(los.is_visible());
+    assert!(los.clearance > 40.0 && los.clearance < 50.0);
+    // within a cell, away from the wall
+    let cell = &dataset.cells()[0];
+    assert!(cell
+        .line_of_sight((-9.5, 20.5), 2.0, (-9.5, 20.55), 2.0)
+        .unwrap()
+        .is_visible());
+    // the curvature of the earth hides the ground 20 km away from 2 m
+    assert!(!cell
+        .line_of_sight((-9.9, 20.05), 2.0, (-9.9, 20.25), 0.0)
+        .unwrap()
+        .is_visible());
+    assert!(dataset
+        .line_of_sight((-9.5, 23.0), 2.0, target, 2.0)
+        .is_none());
+
+    // degenerate profiles
+    let sample = ProfileSample {
+        distance: 0.0,
+        lat: 0.0,
+        lon: 0.0,
+        elevation: Some(0.0),
+    };
+    assert!(LineOfSight::from_profile(&[], 2.0, 2.0).is_none());
+    assert!(LineOfSight::from_profile(&[sample], 2.0, 2.0)
+        .unwrap()
+        .is_visible());
+}
+
+#[test]
+fn test_horizon_profile() {
+    use dted2::{DTEDDataset, ElevationSource};
+
+    // flat cells, with a 500 m high, 4 post wide wall along the shared edge
+    let west = write_test_cell("horizon_west", (-10, 20), |_, lon| match lon {
+        118..=120 => 500,
+        _ => 0,
+    });
+    let east = write_test_cell("horizon_east", (-10, 21), |_, lon| match lon {
+        0..=2 => 500,
+        _ => 0,
+    });
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+
+    // 0.1 degree west of the wall (about 11 km), sampled every ~460 m
+    let point = (-9.5, 20.9 - 2.0 / 120.0);
+    let horizon = dataset.horizon_profile(point, 45.0, 20_000.0);
+    assert_eq!(horizon.len(), 8);
+    let east = horizon[2].unwrap();
+    let distance = 0.1f64.to_radians() * 6_371_008.8 * 9.5f64.to_radians().cos();
+    let drop = distance * distance / (2.0 * 6_371_008.8);
+    assert!((east - (500.0 - drop).atan2(distance).to_degrees()).abs() < 0.15);
+    // northeast, the wall is further away
+    assert!(horizon[1].unwrap() < east);
+    // west, only the curvature of the earth
+    let west = horizon[6].unwrap();
+    assert!(west < 0.0 && west > -0.2);
+    // beyond the wall, the east cell is sampled too
+    let beyond = dataset.horizon_profile((-9.5, 21.1), 90.0, 20_000.0);
+    assert!(beyond[3].unwrap() > 2.0);
+    assert_eq!(
+        beyond[3],
+        dataset.cells()[1].horizon_profile((-9.5, 21.1), 90.0, 20_000.0)[3]
+    );
+    // off the dataset
+    assert_eq!(
+        dataset.horizon_profile((0.0, 0.0), 90.0, 1000.0),
+        vec![None; 4]
+    );
+    assert!(dataset.horizon_profile(point, 0.0, 1000.0).is_empty());
+}
+
+#[test]
+fn test_slope() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+    use dted2::terrain::SlopeUnits;
+
+    // a plane rising 10 m per post eastward and 5 m per post northward
+    let data = build_test_data("slope", |lat, lon| match (lat, lon) {
+        (60, 60) => DTED_VOID_ELEVATION,
+        _ => (10 * lon + 5 * lat) as i16,
+    });
+    let post = (30.0f64 / 3600.0).to_radians() * 6_371_008.8;
+    let expected = |lat_idx: usize| {
+        let lat = (-10.0 + lat_idx as f64 / 120.0).to_radians();
+        let dz_dx = 10.0 / (post * lat.cos());
+        let dz_dy = 5.0 / post;
+        dz_dx.hypot(dz_dy)
+    };
+    let close = |a: Option<&Option<f64>>, b: f64| (a.unwrap().unwrap() - b).abs() < 1e-9;
+
+    let percent = data.slope(SlopeUnits::Percent);
+    assert_eq!((percent.count.lat, percent.count.lon), (121, 121));
+    assert_eq!(percent.origin, data.metadata.origin);
+    assert!(close(percent.get(10, 10), 100.0 * expected(10)));
+    // the edges and corners, with one-sided differences
+    assert!(close(percent.get(0, 50), 100.0 * expected(0)));
+    assert!(close(percent.get(120, 120), 100.0 * expected(120)));
+    let degrees = data.slope(SlopeUnits::Degrees);
+    assert!(close(degrees.get(10, 10), expected(10).atan().to_degrees()));
+    // steeper towards the (south) pole, as the posts get closer
+    assert!(degrees.get(0, 10).unwrap().unwrap() > degrees.get(120, 10).unwrap().unwrap());
+
+    // the void post and its neighbors have no slope
+    assert_eq!(degrees.get(60, 60), Some(&None));
+    assert_eq!(degrees.get(61, 61), Some(&None));
+    assert_eq!(degrees.get(60, 59), Some(&None));
+    assert!(degrees.get(62, 60).unwrap().is_some());
+    assert_eq!(
+        degrees
+            .values
+            .iter()
+            .filter(|slope| slope.is_none())
+            .count(),
+        9
+    );
+}
+
+#[test]
+fn test_aspect() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // flat in the south, rising northward in the north, falling eastward in
+    // the east, with a void post
+    let data = build_test_data("aspect", |lat, lon| match (lat, lon) {
+        (100, 20) => DTED_VOID_ELEVATION,
+        (_, 90..) => (2000 - 10 * lon) as i16,
+        (..60, _) => 100,
+        _ => (100 + 5 * (lat - 60)) as i16,
+    });
+    let aspect = data.aspect();
+    assert_eq!((aspect.count.lat, aspect.count.lon), (121, 121));
+    let close = |a: Option<&Option<f64>>, b: f64| (a.unwrap().unwrap() - b).abs() < 1e-9;
+
+    // flat posts have no aspect
+    assert_eq!(aspect.get(10, 10), Some(&None));
+    // downslope to the south and east
+    assert!(close(aspect.get(100, 50), 180.0));
+    assert!(close(aspect.get(120, 50), 180.0));
+    assert!(close(aspect.get(10, 100), 90.0));
+    assert!(close(aspect.get(10, 120), 90.0));
+    // void
+    assert_eq!(aspect.get(100, 20), Some(&None));
+    assert_eq!(aspect.get(101, 21), Some(&None));
+
+    // downslope to the north and west, between the cardinal directions
+    let data = build_test_data("aspect_diagonal", |lat, lon| {
+        (1000 + 3 * lon - 2 * lat) as i16
+    });
+    let aspect = dted2::terrain::aspect(&data);
+    let value = aspect.get(60, 60).unwrap().unwrap();
+    assert!(value > 270.0 && value < 360.0);
+    let slope = data.slope(dted2::terrain::SlopeUnits::Degrees);
+    assert!(aspect
+        .values
+        .iter()
+        .zip(&slope.values)
+        .all(|(aspect, slope)| aspect.is_some() == slope.is_some()));
+}
+
+#[test]
+fn test_hillshade() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // flat in the west, a steep slope facing east in the east, with a void post
+    let data = build_test_data("hillshade", |lat, lon| match (lat, lon) {
+        (100, 20) => DTED_VOID_ELEVATION,
+        (_, ..60) => 1000,
+        _ => 1000 - 50 * (lon as i16 - 60),
+    });
+
+    // flat terrain is lit by the sine of the altitude
+    let shades = data.hillshade(90.0, 30.0, 1.0);
+    assert_eq!(shades.get(10, 10), Some(&128));
+    assert_eq!(shades.get(100, 20), Some(&0));
+    assert_eq!(shades.get(101, 19), Some(&0));
+    // the east facing slope is brighter in the morning than in the evening
+    let morning = *shades.get(10, 100).unwrap();
+    let evening = *data.hillshade(270.0, 30.0, 1.0).get(10, 100).unwrap();
+    assert!(morning > 128 && evening < 128 && evening > 0);
+    // and darker still when exaggerated
+    let exaggerated = *data.hillshade(270.0, 30.0, 10.0).get(10, 100).unwrap();
+    assert!(exaggerated < evening && exaggerated > 0);
+    // lit from straight above, only flat terrain is at full brightness (the
+    // slope barely dims it unless exaggerated)
+    let zenith = data.hillshade(0.0, 90.0, 10.0);
+    assert_eq!(zenith.get(10, 10), Some(&255));
+    assert!(*zenith.get(10, 100).unwrap() < 255);
+}
+
+#[test]
+fn test_terrain_indices() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // flat, with a 100 m spike, a 100 m pit and a void post
+    let data = build_test_data("terrain_indices", |lat, lon| match (lat, lon) {
+        (30, 30) => 1100,
+        (90, 90) => 900,
+        (60, 60) => DTED_VOID_ELEVATION,
+        _ => 1000,
+    });
+
+    let tri = data.ruggedness(1);
+    assert_eq!((tri.count.lat, tri.count.lon), (121, 121));
+    assert_eq!(tri.get(10, 10), Some(&Some(0.0)));
+    assert_eq!(tri.get(30, 30), Some(&Some(100.0)));
+    assert_eq!(tri.get(31, 29), Some(&Some(100.0 / 8.0)));
+    assert_eq!(tri.get(90, 90), Some(&Some(100.0)));
+    // void posts are skipped in the window
+    assert_eq!(tri.get(60, 60), Some(&None));
+    assert_eq!(tri.get(61, 61), Some(&Some(0.0)));
+    // wider windows dilute the spike
+    let wide = data.ruggedness(2);
+    assert_eq!(wide.get(30, 30), Some(&Some(100.0)));
+    assert_eq!(wide.get(32, 32), Some(&Some(100.0 / 24.0)));
+    assert_eq!(wide.get(33, 33), Some(&Some(0.0)));
+
+    let tpi = data.topographic_position(1);
+    assert_eq!(tpi.get(30, 30), Some(&Some(100.0)));
+    assert_eq!(tpi.get(90, 90), Some(&Some(-100.0)));
+    assert_eq!(tpi.get(89, 90), Some(&Some(100.0 / 8.0)));
+    assert_eq!(tpi.get(60, 60), Some(&None));
+    // the window is cut by the edges of the cell
+    let edge = build_test_data("terrain_indices_edge", |lat, lon| match (lat, lon) {
+        (0, 0) => 1030,
+        _ => 1000,
+    });
+    assert_eq!(edge.topographic_position(1).get(0, 0), Some(&Some(30.0)));
+    assert_eq!(
+        edge.topographic_position(1).get(1, 1),
+        Some(&Some(-30.0 / 8.0))
+    );
+    assert_eq!(edge.ruggedness(1).get(0, 1), Some(&Some(30.0 / 5.0)));
+}
+
+#[test]
+fn test_contours() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // a plane rising 10 m per post eastward, with a void post
+    let data = build_test_data("contours_plane", |lat, lon| match (lat, lon) {
+        (60, 45) => DTED_VOID_ELEVATION,
+        _ => (10 * lon) as i16,
+    });
+    let contours = data.contours(250.0);
+    let levels: Vec<f64> = contours.iter().map(|c| c.elevation).collect();
+    assert_eq!(levels, vec![0.0, 250.0, 500.0, 750.0, 1000.0]);
+    // each level crosses the cell from south to north, along a meridian
+    let line = &contours[1].lines;
+    assert_eq!(line.len(), 1);
+    assert_eq!(line[0].len(), 121);
+    let lon = 20.0 + 25.0 / 120.0;
+    assert!(line[0].iter().all(|(_, l)| (l - lon).abs() < 1e-9));
+    let mut lats: Vec<f64> = line[0].iter().map(|(lat, _)| *lat).collect();
+    lats.sort_by(f64::total_cmp);
+    assert!((lats[0] + 10.0).abs() < 1e-9 && (lats[120] + 9.0).abs() < 1e-9);
+    // the squares around the void post break the line next to it in two, of
+    // 59 segments each
+    let broken = data.contours(445.0);
+    assert_eq!(broken[1].elevation, 445.0);
+    assert_eq!(broken[1].lines.len(), 2);
+    assert_eq!(broken[1].lines.iter().map(Vec::len).sum::<usize>(), 120);
+
+    // a cone of 10 m per post around the center
+    let data = build_test_data("contours_cone", |lat, lon| {
+        let distance = (lat as f64 - 60.0).hypot(lon as f64 - 60.0);
+        (2000.0 - 10.0 * distance).round() as i16
+    });
+    let contours = data.contours(100.0);
+    assert_eq!(contours.last().unwrap().elevation, 2000.0);
+    let ring = &contours
+        .iter()
+        .find(|c| c.elevation == 1700.0)
+        .unwrap()
+        .lines;
+    assert_eq!(ring.len(), 1);
+    // closed, around the center
+    assert_eq!(ring[0].first(), ring[0].last());
+    let center = (-10.0 + 60.0 / 120.0, 20.0 + 60.0 / 120.0);
+    assert!(ring[0].iter().all(|(lat, lon)| {
+        let distance = (lat - center.0).hypot(lon - center.1) * 120.0;
+        (distance - 30.0).abs() < 1.0
+    }));
+
+    // no levels without a positive interval
+    assert!(data.contours(0.0).is_empty());
+    assert!(data.contours(-10.0).is_empty());
+}
+
+#[cfg(feature = "geo-types")]
+#[test]
+fn test_contours_geo_types() {
+    use geo_types::MultiLineString;
+
+    let data = build_test_data("contours_geo_types", |_, lon| (10 * lon) as i16);
+    let contour = &data.contours(500.0)[1];
+    let lines: MultiLineString<f64> = contour.into();
+    assert_eq!(lines.0.len(), 1);
+    assert_eq!(lines.0[0].0.len(), 121);
+    assert!(lines.0[0]
+        .0
+        .iter()
+        .all(|c| (c.x - (20.0 + 50.0 / 120.0)).abs() < 1e-9));
+    assert_eq!(contour.to_multi_line_string(), lines);
+}
+
+#[cfg(feature = "geojson")]
+#[test]
+fn test_geojson() {
+    use dted2::{geojson, DTEDDataset};
+
+    let west = write_test_cell("geojson_west", (-10, 20), |_, lon| (10 * lon) as i16);
+    let east = write_test_cell("geojson_east", (-10, 21), |_, _| 100);
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+
+    // contours, written to a file and read back
+    let contours = geojson::contours(&dataset.cells()[0].contours(500.0));
+    let path = std::env::temp_dir().join("dted2_test_geojson.geojson");
+    let path = path.to_str().unwrap();
+    geojson::write(path, &contours).unwrap();
+    let written = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(written, contours.to_string());
+    let read: serde_json::Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(read["type"], "FeatureCollection");
+    let features = read["features"].as_array().unwrap();
+    assert_eq!(features.len(), 3);
+    assert_eq!(features[1]["properties"]["elevation"], 500.0);
+    let line = features[1]["geometry"]["coordinates"][0]
+        .as_array()
+        .unwrap();
+    assert_eq!(line.len(), 121);
+    let lon = line[0][0].as_f64().unwrap();
+    assert!((lon - (20.0 + 50.0 / 120.0)).abs() < 1e-9);
+
+    // coverage, one polygon per cell
+    let coverage = geojson::coverage(dataset.cells());
+    let features = coverage["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[1]["geometry"]["type"], "Polygon");
+    assert_eq!(
+        features[1]["geometry"]["coordinates"],
+        serde_json::json!([[
+            [21.0, -10.0],
+            [22.0, -10.0],
+            [22.0, -9.0],
+            [21.0, -9.0],
+            [21.0, -10.0]
+        ]])
+    );
+    assert_eq!(features[1]["properties"]["filename"], east.as_str());
+    assert_eq!(features[1]["properties"]["level"], 0);
+}
+
+#[cfg(feature = "geotiff")]
+#[test]
+fn test_geotiff() {
+    use dted2::interpolation::Interpolation;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::{geotiff, DTEDBuilder, DTEDLevel};
+    use tiff::encoder::{colortype::GrayI16, TiffEncoder};
+    use tiff::tags::Tag;
+
+    // a 1 arc-minute grid over the cell at 10S 20E, pixels as areas, rising
+    // 60 m per pixel eastward, with a no-data pixel in the north west
+    let path = std::env::temp_dir().join("dted2_test_geotiff.tif");
+    let path = path.to_str().unwrap();
+    let pixels: Vec<i16> = (0..60 * 60)
+        .map(|idx| match idx {
+            0 => -9999,
+            idx => (60 * (idx % 60)) as i16,
+        })
+        .collect();
+    {
+        let mut file = std::fs::File::create(path).unwrap();
+        let mut tiff = TiffEncoder::new(&mut file).unwrap();
+        let mut image = tiff.new_image::<GrayI16>(60, 60).unwrap();
+        let encoder = image.encoder();
+        encoder
+            .write_tag(Tag::ModelPixelScaleTag, &[1.0 / 60.0, 1.0 / 60.0, 0.0][..])
+            .unwrap();
+        encoder
+            .write_tag(Tag::ModelTiepointTag, &[0.0, 0.0, 0.0, 20.0, -9.0, 0.0][..])
+            .unwrap();
+        encoder
+            .write_tag(
+                Tag::GeoKeyDirectoryTag,
+                &[1u16, 1, 0, 2, 1024, 0, 1, 2, 1025, 0, 1, 1][..],
+            )
+            .unwrap();
+        encoder.write_tag(Tag::GdalNodata, "-9999").unwrap();
+        image.write_data(&pixels).unwrap();
+    }
+    let raster = geotiff::read(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    // pixel centers, from south to north
+    assert_eq!(raster.count, AxisElement::new(60, 60));
+    assert!((raster.origin.lat - (-10.0 + 1.0 / 120.0)).abs() < 1e-12);
+    assert!((raster.origin.lon - (20.0 + 1.0 / 120.0)).abs() < 1e-12);
+    assert_eq!(raster.get(0, 59), Some(&Some(3540.0)));
+    assert_eq!(raster.get(59, 0), Some(&None));
+    assert_eq!(raster.get(58, 1), Some(&Some(60.0)));
+
+    // into a Level 0 cell, void along the edges outside of the pixel centers
+    let origin = AxisElement::new(Angle::new(10, 0, 0.0, true), Angle::new(20, 0, 0.0, false));
+    let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+        .resample(&raster, Interpolation::Bilinear)
+        .build()
+        .unwrap();
+    assert_eq!(file.data.get(0, 10), Some(-32767));
+    assert_eq!(file.data.get(120, 10), Some(-32767));
+    assert_eq!(file.data.get(10, 0), Some(-32767));
+    // halfway between pixel centers 4 and 5
+    assert_eq!(file.data.get(60, 10), Some(270));
+    // on the last pixel center
+    assert_eq!(file.data.get(60, 119), Some(3540));
+
+    // missing georeferencing
+    assert!(matches!(
+        geotiff::read_from(std::io::Cursor::new(b"not a tiff".to_vec())),
+        Err(dted2::Error::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_hgt() {
+    use dted2::hgt;
+    use dted2::primitives::AxisElement;
+    use dted2::{DTEDLevel, ElevationSource};
+
+    // SRTM3 tiles, rows from north to south, rising 1 m per post eastward
+    // and 2 m per post southward, with a void post
+    let dir = std::env::temp_dir().join("dted2_test_hgt");
+    std::fs::create_dir_all(&dir).unwrap();
+    let tile = |name: &str| {
+        let bytes: Vec<u8> = (0..1201 * 1201)
+            .map(|idx| match idx {
+                1000 => hgt::HGT_VOID_ELEVATION,
+                idx => (idx % 1201 + 2 * (idx / 1201)) as i16,
+            })
+            .flat_map(|post| post.to_be_bytes())
+            .collect();
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        path.to_str().unwrap().to_string()
+    };
+    let south = tile("S34W119.hgt");
+    let north = tile("N60E010.hgt");
+
+    let raster = hgt::read(&south).unwrap();
+    assert_eq!(raster.origin, AxisElement::new(-34.0, -119.0));
+    assert_eq!(raster.count, AxisElement::new(1201, 1201));
+    assert_eq!(raster.get(1200, 0), Some(&Some(0.0)));
+    assert_eq!(raster.get(0, 1200), Some(&Some(3600.0)));
+    assert_eq!(raster.get(1200, 1000), Some(&None));
+
+    // Level 1, post for post
+    let file = hgt::read_dted(&south).unwrap();
+    assert_eq!(file.level(), Some(DTEDLevel::Level1));
+    assert_eq!(file.data.get(1200, 0), Some(0));
+    assert_eq!(file.data.get(0, 1200), Some(3600));
+    assert_eq!(file.data.get(1200, 1000), Some(-32767));
+    assert_eq!(file.data.get(600, 300), Some(300 + 1200));
+    let data = hgt::read_data(&south).unwrap();
+    assert_eq!(data.metadata.filename, south);
+    assert_eq!(data.elevation(600, 300), Some(1500));
+
+    // above 50 degrees, every other post in longitude
+    let file = hgt::read_dted(&north).unwrap();
+    assert_eq!(file.header.count, AxisElement::new(1201, 601));
+    assert_eq!(file.data.get(0, 600), Some(3600));
+    assert_eq!(file.data.get(1200, 300), Some(600));
+
+    // not named after a tile, or not a square grid
+    let other = dir.join("N34W119.hgt");
+    std::fs::write(&other, [0u8; 10]).unwrap();
+    assert!(matches!(
+        hgt::read(other.to_str().unwrap()),
+        Err(dted2::Error::InvalidInput(_))
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(
+        hgt::read("tests/test_data.dt2"),
+        Err(dted2::Error::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_hgt_export() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+    use dted2::hgt;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::{DTEDBuilder, DTEDLevel};
+
+    // round trip, post for post, voids included
+    let data = build_test_data("hgt_export", |lat, lon| match (lat, lon) {
+        (7, 9) => DTED_VOID_ELEVATION,
+        _ => (3 * lon + lat) as i16 - 100,
+    });
+    let path = std::env::temp_dir().join(hgt::tile_name(AxisElement::new(-10, 20)));
+    assert!(path.ends_with("S10E020.hgt"));
+    let path = path.to_str().unwrap();
+    data.write_hgt(path).unwrap();
+    let bytes = std::fs::read(path).unwrap();
+    let raster = hgt::read(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(bytes.len(), 2 * 121 * 121);
+    // big-endian, the first row being the northernmost
+    assert_eq!(i16::from_be_bytes([bytes[2], bytes[3]]), 3 + 120 - 100);
+    assert_eq!(raster.get(7, 9), Some(&None));
+    assert!((0..121)
+        .flat_map(|lon| (0..121).map(move |lat| (lat, lon)))
+        .filter(|post| *post != (7, 9))
+        .all(|(lat, lon)| raster.get(lat, lon) == Some(&data.data.get(lat, lon).map(f64::from))));
+
+    // above 50 degrees, the longitude lines are resampled into a square grid
+    let origin = AxisElement::new(Angle::new(60, 0, 0.0, false), Angle::new(15, 0, 0.0, false));
+    let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+        .elevations((0..61).map(|lon| vec![10 * lon as i16; 121]).collect())
+        .build()
+        .unwrap();
+    let path = std::env::temp_dir().join("dted2_test_hgt_export.dt0");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, file.to_bytes().unwrap()).unwrap();
+    let data = dted2::DTEDData::read(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    let mut bytes = Vec::new();
+    hgt::write(&mut bytes, &data).unwrap();
+    let raster = hgt::read_from(&bytes[..], AxisElement::new(60, 15)).unwrap();
+    assert_eq!(raster.count, AxisElement::new(121, 121));
+    assert_eq!(raster.get(50, 0), Some(&Some(0.0)));
+    assert_eq!(raster.get(50, 1), Some(&Some(5.0)));
+    assert_eq!(raster.get(50, 120), Some(&Some(600.0)));
+}
+
+#[test]
+fn test_asc() {
+    use dted2::asc;
+    use dted2::interpolation::Interpolation;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::{DTEDBuilder, DTEDLevel};
+
+    // a 1 arc-minute grid of centers over the cell at 10S 20E, rising 6 m per
+    // column eastward, with a no-data value in the north west
+    let mut grid = String::from(
+        "NCOLS 61\nNROWS 61\nXLLCENTER 20\nYLLCENTER -10\nDX 0.016666666666666666\n\
+         DY 0.016666666666666666\nNODATA_VALUE -9999\n",
+    );
+    for row in 0..61 {
+        let values: Vec<String> = (0..61)
+            .map(|col| match (row, col) {
+                (0, 0) => "-9999".to_string(),
+                _ => format!("{}", 6 * col),
+            })
+            .collect();
+        grid.push_str(&values.join(" "));
+        grid.push('\n');
+    }
+    let path = std::env::temp_dir().join("dted2_test_asc.asc");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, &grid).unwrap();
+    let raster = asc::read(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(raster.origin, AxisElement::new(-10.0, 20.0));
+    assert_eq!(raster.count, AxisElement::new(61, 61));
+    assert_eq!(raster.get(60, 0), Some(&None));
+    assert_eq!(raster.get(0, 60), Some(&Some(360.0)));
+
+    // resampled into a Level 0 cell, twice as dense
+    let origin = AxisElement::new(Angle::new(10, 0, 0.0, true), Angle::new(20, 0, 0.0, false));
+    let file = DTEDBuilder::for_level(origin, DTEDLevel::Level0)
+        .resample(&raster, Interpolation::Bilinear)
+        .build()
+        .unwrap();
+    assert_eq!(file.data.get(0, 0), Some(0));
+    assert_eq!(file.data.get(0, 1), Some(3));
+    assert_eq!(file.data.get(50, 120), Some(360));
+    // around the no-data value, from the remaining values
+    assert_eq!(file.data.get(120, 0), Some(-32767));
+    assert_eq!(file.data.get(120, 1), Some(6));
+
+    // malformed grids
+    for grid in [
+        "ncols 2\nnrows 1\nxllcorner 0\nyllcorner 0\n1 2\n",
+        "ncols 2\nnrows 1\nxllcorner 0\nyllcorner 0\ncellsize 1\n1\n",
+        "ncols 2\nnrows 1\nxllcorner 0\ncellsize 1\n1 2\n",
+        "ncols 2\nnrows 1\nxllcorner 0\nyllcorner 0\ncellsize 1\n1 x\n",
+        "ncols two\nnrows 1\n",
+    ] {
+        assert!(matches!(
+            asc::read_from(grid.as_bytes()),
+            Err(dted2::Error::InvalidInput(_))
+        ));
+    }
+}
+
+#[test]
+fn test_terrain_rgb() {
+    use dted2::tiles::{self, decode_terrain_rgb, TILE_SIZE};
+    use dted2::DTEDDataset;
+
+    // two adjacent cells at 10S 20E and 10S 21E, in the tile 8/142/134
+    let west = write_test_cell("terrain_rgb_west", (-10, 20), |lat, _| lat as i16);
+    let east = write_test_cell("terrain_rgb_east", (-10, 21), |_, _| 2000);
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+    let (min, max) = tiles::tile_bounds(8, 142, 134).unwrap();
+    // the tile spans both cells in longitude, and the north of the cells
+    assert!(min.lon < 20.0 && max.lon > 21.0 && min.lat > -10.0);
+    assert!(min.lat < -9.0 && max.lat > -9.0);
+
+    let pixels = dataset.terrain_rgb(8, 142, 134).unwrap();
+    assert_eq!(pixels.len(), TILE_SIZE * TILE_SIZE * 4);
+    let pixel = |pixels: &[u8], row: usize, col: usize| {
+        let idx = 4 * (row * TILE_SIZE + col);
+        (
+            decode_terrain_rgb([pixels[idx], pixels[idx + 1], pixels[idx + 2]]),
+            pixels[idx + 3],
+        )
+    };
+    let lon = |col: usize| min.lon + (col as f64 + 0.5) * (max.lon - min.lon) / TILE_SIZE as f64;
+    // west and north of the cells, transparent
+    assert_eq!(pixel(&pixels, 200, 10), (-10_000.0, 0));
+    assert!(lon(10) < 20.0);
+    assert_eq!(pixel(&pixels, 50, 100), (-10_000.0, 0));
+    // in the east cell
+    assert_eq!(pixel(&pixels, 200, 250), (2000.0, 255));
+    assert!(lon(250) > 21.0);
+    // in the west cell, rising northward by 1 m per post, to the decimeter
+    let (south, _) = pixel(&pixels, 250, 100);
+    let (north, _) = pixel(&pixels, 120, 100);
+    assert!(north > south && south > 0.0);
+    assert_eq!(south, (south * 10.0).round() / 10.0);
+
+    // a cell alone only covers its own extent
+    let data = &dataset.cells()[0];
+    let pixels = data.terrain_rgb(8, 142, 134).unwrap();
+    assert_eq!(pixel(&pixels, 200, 250).1, 0);
+    assert_eq!(pixel(&pixels, 250, 100), (south, 255));
+
+    // not a tile
+    assert!(dataset.terrain_rgb(8, 256, 0).is_none());
+}
+
+#[test]
+fn test_quantized_mesh() {
+    use dted2::primitives::AxisElement;
+    use dted2::quantized_mesh;
+
+    let data = build_test_data("test_quantized_mesh.dt0", |lat, lon| {
+        (lat * 10 + lon) as i16
+    });
+    let tile = data.quantized_mesh(5);
+    let f32_at = |idx: usize| f32::from_le_bytes(tile[idx..idx + 4].try_into().unwrap());
+    let u32_at = |idx: usize| u32::from_le_bytes(tile[idx..idx + 4].try_into().unwrap()) as usize;
+    let u16_at = |idx: usize| u16::from_le_bytes(tile[idx..idx + 2].try_into().unwrap()) as usize;
+    // minimum and maximum heights, at the south west and north east posts
+    assert_eq!((f32_at(24), f32_at(28)), (0.0, 1320.0));
+
+    // vertices, zig-zag delta decoded
+    assert_eq!(u32_at(88), 25);
+    let decode = |start: usize| {
+        (0..25)
+            .scan(0i32, |value, idx| {
+                let zigzag = u16_at(start + 2 * idx) as i32;
+                *value += (zigzag >> 1) ^ -(zigzag & 1);
+                Some(*value)
+            })
+            .collect::<Vec<i32>>()
+    };
+    let (u, v, h) = (decode(92), decode(92 + 50), decode(92 + 100));
+    assert!(u
+        .iter()
+        .chain(&v)
+        .chain(&h)
+        .all(|q| (0..=32767).contains(q)));
+    // the heights follow the elevations
+    let vertex = |u_q: i32, v_q: i32| {
+        (0..25)
+            .find(|idx| u[*idx] == u_q && v[*idx] == v_q)
+            .unwrap()
+    };
+    assert_eq!(h[vertex(0, 0)], 0);
+    assert_eq!(h[vertex(32767, 32767)], 32767);
+    assert_eq!(
+        h[vertex(32767, 0)],
+        (120.0 / 1320.0 * 32767.0f64).round() as i32
+    );
+
+    // triangles, high water mark decoded
+    let mut idx = 92 + 150;
+    assert_eq!(u32_at(idx), 32);
+    idx += 4;
+    let mut highest = 0;
+    let mut triangles = Vec::new();
+    for _ in 0..32 * 3 {
+        let vertex = highest - u16_at(idx);
+        if u16_at(idx) == 0 {
+            highest += 1;
+        }
+        triangles.push(vertex);
+        idx += 2;
+    }
+    assert_eq!(highest, 25);
+    // counter-clockwise, seen from above
+    for triangle in triangles.chunks(3) {
+        let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+        let cross = (u[b] - u[a]) as i64 * (v[c] - v[a]) as i64
+            - (v[b] - v[a]) as i64 * (u[c] - u[a]) as i64;
+        assert!(cross > 0);
+    }
+
+    // edges: west, south, east then north
+    for (edge, on_edge) in [
+        (
+            0,
+            Box::new(|idx: usize| u[idx] == 0) as Box<dyn Fn(usize) -> bool>,
+        ),
+        (1, Box::new(|idx: usize| v[idx] == 0)),
+        (2, Box::new(|idx: usize| u[idx] == 32767)),
+        (3, Box::new(|idx: usize| v[idx] == 32767)),
+    ] {
+        assert_eq!(u32_at(idx), 5, "edge {}", edge);
+        idx += 4;
+        let vertices: Vec<usize> = (0..5).map(|n| u16_at(idx + 2 * n)).collect();
+        assert!(
+            vertices.iter().all(|vertex| on_edge(*vertex)),
+            "edge {}",
+            edge
+        );
+        idx += 10;
+    }
+    assert_eq!(idx, tile.len());
+
+    // a window of the dataset, partly not covered
+    let dataset = dted2::DTEDDataset::from_cells(vec![data]);
+    let tile = dataset.quantized_mesh(
+        AxisElement::new(-10.5, 19.5),
+        AxisElement::new(-9.5, 20.5),
+        3,
+    );
+    let f32_at = |idx: usize| f32::from_le_bytes(tile[idx..idx + 4].try_into().unwrap());
+    assert_eq!((f32_at(24), f32_at(28)), (0.0, 660.0));
+    assert_eq!(
+        tile.len(),
+        quantized_mesh::encode(
+            AxisElement::new(0.0, 0.0),
+            AxisElement::new(1.0, 1.0),
+            3,
+            |_, _| None
+        )
+        .len()
+    );
+}
+
+#[test]
+fn test_mesh() {
+    use dted2::geodesy::EARTH_RADIUS_M;
+
+    let data = build_test_data("test_mesh.dt0", |lat, lon| match (lat, lon) {
+        (60, 60) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (lat + lon) as i16,
+    });
+    let mesh = data.mesh(1);
+    assert_eq!(mesh.vertices.len(), 121 * 121 - 1);
+    // the void post is in 6 triangles
+    assert_eq!(mesh.triangles.len(), 2 * 120 * 120 - 6);
+    // all of them facing up
+    for [a, b, c] in &mesh.triangles {
+        let (a, b, c) = (
+            mesh.vertices[*a as usize],
+            mesh.vertices[*b as usize],
+            mesh.vertices[*c as usize],
+        );
+        let cross = (b[0] - a[0]) * -(c[2] - a[2]) - -(b[2] - a[2]) * (c[0] - a[0]);
+        assert!(cross > 0.0);
+    }
+    // the south east post, a degree east of the origin at 10 degrees south
+    // (after the void post)
+    let south_east = mesh.vertices[120 * 121 - 1];
+    let degree = 1f64.to_radians() * EARTH_RADIUS_M;
+    assert!((south_east[0] - degree * 10f64.to_radians().cos()).abs() < 1e-6);
+    assert_eq!((south_east[1], south_east[2]), (120.0, 0.0));
+    // the north west post, a degree north
+    assert!((mesh.vertices[120][2] + degree).abs() < 1e-6);
+
+    // decimated, keeping the last posts
+    let decimated = data.mesh(50);
+    assert_eq!(decimated.vertices.len(), 4 * 4);
+    assert_eq!(decimated.triangles.len(), 2 * 3 * 3);
+    assert_eq!(decimated.vertices[15][1], 240.0);
+
+    // OBJ, with a line per vertex and triangle
+    let path = std::env::temp_dir().join("test_mesh.obj");
+    let path = path.to_str().unwrap();
+    decimated.write_obj(path).unwrap();
+    let obj = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("v ")).count(),
+        16
+    );
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("f ")).count(),
+        18
+    );
+    assert!(obj.lines().any(|line| line == "f 1 5 6"));
+
+    // glTF, with the chunk lengths adding up
+    let glb = decimated.to_glb();
+    let u32_at = |idx: usize| u32::from_le_bytes(glb[idx..idx + 4].try_into().unwrap()) as usize;
+    assert_eq!(glb[..4], *b"glTF");
+    assert_eq!((u32_at(4), u32_at(8)), (2, glb.len()));
+    let json_len = u32_at(12);
+    assert_eq!(glb[16..20], *b"JSON");
+    let json = std::str::from_utf8(&glb[20..20 + json_len]).unwrap();
+    assert!(json.contains(r#""count":16,"type":"VEC3""#));
+    assert!(json.contains(r#""count":54,"type":"SCALAR""#));
+    let bin_len = u32_at(20 + json_len);
+    assert_eq!(glb[24 + json_len..28 + json_len], *b"BIN\0");
+    assert_eq!(bin_len, 16 * 12 + 54 * 4);
+    assert_eq!(28 + json_len + bin_len, glb.len());
+}
+
+#[cfg(feature = "las")]
+#[test]
+fn test_las() {
+    let data = build_test_data("test_las.dt0", |lat, lon| match (lat, lon) {
+        (60, 60) => dted2::dted::DTED_VOID_ELEVATION,
+        _ => (lat * 10 + lon) as i16,
+    });
+    let path = std::env::temp_dir().join("test_las.las");
+    let path = path.to_str().unwrap();
+    data.write_las(path).unwrap();
+    let mut reader = las::Reader::from_path(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    // the void post is left out
+    let header = reader.header().clone();
+    assert_eq!(header.number_of_points(), 121 * 121 - 1);
+    let bounds = header.bounds();
+    assert_eq!(
+        (bounds.min.x, bounds.min.y, bounds.min.z),
+        (20.0, -10.0, 0.0)
+    );
+    assert_eq!(
+        (bounds.max.x, bounds.max.y, bounds.max.z),
+        (21.0, -9.0, 1320.0)
+    );
+    // in degrees on WGS84
+    let vlr = &header.vlrs()[0];
+    assert_eq!(
+        (vlr.user_id.as_str(), vlr.record_id),
+        ("LASF_Projection", 34735)
+    );
+
+    let points: Vec<las::Point> = reader
+        .read_all()
+        .unwrap()
+        .points()
+        .map(|p| p.unwrap())
+        .collect();
+    // along the first longitude line, then the next ones, to 1e-7 degree
+    let close = |point: &las::Point, x: f64, y: f64, z: f64| {
+        (point.x - x).abs() <= 1e-7 && (point.y - y).abs() <= 1e-7 && point.z == z
+    };
+    assert!(close(&points[1], 20.0, -10.0 + 1.0 / 120.0, 10.0));
+    assert!(close(&points[121], 20.0 + 1.0 / 120.0, -10.0, 1.0));
+    assert!(points
+        .iter()
+        .all(|p| p.classification == las::point::Classification::Ground));
+    assert!(!points.iter().any(|p| (p.x, p.y) == (20.5, -9.5)));
+}
+
+#[cfg(feature = "laz")]
+#[test]
+fn test_laz() {
+    let data = build_test_data("test_laz.dt0", |lat, lon| (lat * 10 + lon) as i16);
+    let (las_path, laz_path) = (
+        std::env::temp_dir().join("test_laz.las"),
+        std::env::temp_dir().join("test_laz.laz"),
+    );
+    let (las_path, laz_path) = (las_path.to_str().unwrap(), laz_path.to_str().unwrap());
+    data.write_las(las_path).unwrap();
+    data.write_las(laz_path).unwrap();
+    let read = |path: &str| {
+        let mut reader = las::Reader::from_path(path).unwrap();
+        let points: Vec<las::Point> = reader
+            .read_all()
+            .unwrap()
+            .points()
+            .map(|p| p.unwrap())
+            .collect();
+        (reader.header().point_format().is_compressed, points)
+    };
+    let (las, laz) = (read(las_path), read(laz_path));
+    // compressed, with the same points
+    assert!(
+        std::fs::metadata(laz_path).unwrap().len() < std::fs::metadata(las_path).unwrap().len()
+    );
+    std::fs::remove_file(las_path).unwrap();
+    std::fs::remove_file(laz_path).unwrap();
+    assert_eq!((las.0, laz.0), (false, true));
+    assert_eq!(las.1, laz.1);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_metadata_json() {
+    use dted2::dted::RawDTEDFile;
+
+    let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    let json = file.metadata_json();
+    // every field of the records
+    assert_eq!(json["uhl"].as_object().unwrap().len(), 7);
+    assert_eq!(json["dsi"].as_object().unwrap().len(), 27);
+    assert_eq!(json["acc"].as_object().unwrap().len(), 6);
+    // with the angles in degrees, minutes and seconds
+    let origin = &json["uhl"]["origin"];
+    let angle = file.header.origin.lon;
+    assert_eq!(origin["lon"]["deg"], angle.deg());
+    assert_eq!(origin["lon"]["negative"], angle.is_negative());
+    assert_eq!(json["dsi"]["sw_corner"], json["dsi"]["origin"]);
+    // and the blank values as null
+    let dsi = file.dsi_record.as_ref().unwrap();
+    assert_eq!(json["dsi"]["edition"].is_null(), dsi.edition.is_none());
+    assert_eq!(json["uhl"]["unique_ref"], file.header.unique_ref);
+
+    // the metadata of the data, with the same records
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let metadata = data.metadata_json();
+    assert_eq!(metadata["dsi_record"], json["dsi"]);
+    assert_eq!(metadata["acc_record"], json["acc"]);
+    assert_eq!(metadata["count"], json["uhl"]["count"]);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_info() {
+    use std::process::Command;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args([
+            "info",
+            "tests/test_data.dt2",
+            "tests/test_data_negative.dt2",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("tests/test_data.dt2\n"));
+    assert!(stdout.contains("\ntests/test_data_negative.dt2\n"));
+    assert!(stdout.contains("  level:      DTED2\n"));
+    assert!(stdout.contains("  counts:     3601 latitude points x 3601 longitude lines\n"));
+    assert!(stdout.contains("  voids:      0.00%\n"));
+    assert!(stdout
+        .contains("  producer:   USCNIMA - National Imagery and Mapping Agency (United States)\n"));
+    assert!(stdout.contains("  producer:   USNIM - United States\n"));
+
+    // the other files are still printed, but the status is a failure
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args(["info", "tests/missing.dt2", "tests/test_data.dt2"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("tests/test_data.dt2"));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("tests/missing.dt2: "));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_convert() {
+    use dted2::{asc, hgt};
+    use std::process::Command;
+
+    let convert = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_dted2"))
+            .arg("convert")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let temp = |name: &str| {
+        std::env::temp_dir()
+            .join(name)
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+    let (tif, dt0, asc_path, hgt_path, png) = (
+        temp("dted2_cli.tif"),
+        temp("dted2_cli.dt0"),
+        temp("dted2_cli.asc"),
+        temp("S30W178.hgt"),
+        temp("dted2_cli.png"),
+    );
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+
+    // through a GeoTIFF, back into the same posts
+    assert!(convert(&["tests/test_data_negative.dt2", &tif])
+        .status
+        .success());
+    assert!(convert(&[&tif, &dt0]).status.success());
+    let round_trip = DTEDData::read(&dt0).unwrap();
+    assert_eq!(round_trip.metadata.origin, data.metadata.origin);
+    assert_eq!(round_trip.data, data.data);
+
+    // an ESRI ASCII grid of the posts
+    assert!(convert(&[&dt0, &asc_path]).status.success());
+    let raster = asc::read(&asc_path).unwrap();
+    assert_eq!(raster.get(5, 3), Some(&data.data.get(5, 3).map(f64::from)));
+
+    // a Level 1 HGT tile, interpolated between the posts
+    assert!(convert(&[
+        &asc_path,
+        &hgt_path,
+        "--level",
+        "1",
+        "--interpolation",
+        "nearest"
+    ])
+    .status
+    .success());
+    let raster = hgt::read(&hgt_path).unwrap();
+    assert_eq!(raster.count.lat, 1201);
+    assert_eq!(
+        raster.get(10 * 5, 10 * 3),
+        Some(&data.data.get(5, 3).map(f64::from))
+    );
+
+    // a heightmap, which can not be converted back
+    assert!(convert(&[&dt0, &png]).status.success());
+    assert_eq!(std::fs::read(&png).unwrap()[1..4], *b"PNG");
+    let output = convert(&[&png, &dt0]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("not georeferenced"));
+
+    for path in [tif, dt0, asc_path, hgt_path, png] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_sample() {
+    use dted2::interpolation::Interpolation;
+    use dted2::ElevationSource;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    let expected = |lat: f64, lon: f64, method: Interpolation| {
+        let elevation = data.elevation_at_with(lat, lon, method).unwrap();
+        format!("{},{},{}", lat, lon, elevation)
+    };
+
+    // points as arguments, one not covered
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args([
+            "sample",
+            "--interpolation",
+            "nearest",
+            "tests/test_data.dt2",
+        ])
+        .args(["42.5,15.5", "42.1234,15.9876", "-10,20"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "lat,lon,elevation");
+    assert_eq!(lines[1], expected(42.5, 15.5, Interpolation::Nearest));
+    assert_eq!(lines[2], expected(42.1234, 15.9876, Interpolation::Nearest));
+    assert_eq!(lines[3], "-10,20,");
+
+    // points in DMS notation
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args([
+            "sample",
+            "--interpolation",
+            "nearest",
+            "tests/test_data.dt2",
+        ])
+        .args(["42°30'N,15°30'E", "42 45 00 N, 15 15 00 E"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], expected(42.5, 15.5, Interpolation::Nearest));
+    assert_eq!(lines[2], expected(42.75, 15.25, Interpolation::Nearest));
+
+    // CSV on stdin, with a header, bilinearly interpolated
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args(["sample", "tests/test_data.dt2"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"lat,lon,name\n42.1234,15.9876,a\n\n42.75,15.25,b\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[1],
+        expected(42.1234, 15.9876, Interpolation::Bilinear)
+    );
+    assert_eq!(lines[2], expected(42.75, 15.25, Interpolation::Bilinear));
+
+    // a malformed point
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args(["sample", "tests/test_data.dt2", "42.5"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_validate() {
+    use dted2::dted::{dted_record_length, DTED_DATA_OFFSET};
+    use std::process::Command;
+
+    let validate = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_dted2"))
+            .arg("validate")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    // a directory of valid files
+    let output = validate(&["tests"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("tests/test_data.dt2: ok"));
+    assert!(stdout.contains("tests/test_data_negative.dt2: ok"));
+
+    // a corrupt data record
+    let dir = std::env::temp_dir().join("dted2_cli_validate");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("corrupt.dt0");
+    let mut content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    content[DTED_DATA_OFFSET + 7 * dted_record_length(121) + 20] ^= 1;
+    std::fs::write(&path, content).unwrap();
+    let output = validate(&["--json", dir.to_str().unwrap(), "tests/test_data.dt2"]);
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["valid"], 1);
+    assert_eq!(json["invalid"], 1);
+    assert_eq!(json["files"][0]["file"], path.to_str().unwrap());
+    assert_eq!(json["files"][0]["valid"], false);
+    assert!(json["files"][0]["problems"][0]
+        .as_str()
+        .unwrap()
+        .contains("checksum"));
+    assert_eq!(json["files"][1]["problems"], serde_json::json!([]));
+
+    // a symbolic link cycle is not followed
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink("..", dir.join("back")).unwrap();
+        let output = validate(&["--json", dir.to_str().unwrap()]);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["invalid"], 1);
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_render() {
+    use std::process::Command;
+
+    let render = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_dted2"))
+            .arg("render")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let path = std::env::temp_dir().join("dted2_cli_render.png");
+    let png = path.to_str().unwrap();
+    // width, height, bit depth and color type of the IHDR chunk
+    let header = || {
+        let bytes = std::fs::read(png).unwrap();
+        assert_eq!(bytes[1..4], *b"PNG");
+        (
+            u32::from_be_bytes(bytes[16..20].try_into().unwrap()),
+            u32::from_be_bytes(bytes[20..24].try_into().unwrap()),
+            bytes[24],
+            bytes[25],
+        )
+    };
+
+    assert!(
+        render(&["tests/test_data_negative.dt2", png, "--azimuth", "270"])
+            .status
+            .success()
+    );
+    assert_eq!(header(), (121, 121, 8, 0));
+
+    assert!(render(&[
+        "tests/test_data_negative.dt2",
+        png,
+        "--mode",
+        "color-relief",
+        "--palette",
+        "viridis"
+    ])
+    .status
+    .success());
+    assert_eq!(header(), (121, 121, 8, 6));
+
+    assert!(!render(&["tests/missing.dt2", png]).status.success());
+    assert!(
+        !render(&["tests/test_data_negative.dt2", png, "--palette", "rainbow"])
+            .status
+            .success()
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_cli_profile() {
+    use dted2::geodesy::LineType;
+    use dted2::interpolation::Interpolation;
+    use dted2::ElevationSource;
+    use std::process::Command;
+
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    let expected = data.profile_with(
+        (42.1, 14.9),
+        (42.9, 15.9),
+        11,
+        LineType::Rhumb,
+        Interpolation::Bilinear,
+    );
+
+    // CSV, not covered at the start
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args(["profile", "--samples", "11", "--line", "rhumb"])
+        .args(["tests/test_data.dt2", "42.1,14.9", "42.9,15.9"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[0], "distance,lat,lon,elevation");
+    for (line, sample) in lines[1..].iter().zip(&expected) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let distance: f64 = fields[0].parse().unwrap();
+        assert!((distance - sample.distance).abs() < 1e-3);
+        assert_eq!(
+            fields[3],
+            sample
+                .elevation
+                .map(|elevation| elevation.to_string())
+                .unwrap_or_default()
+        );
+    }
+    assert!(lines[1].ends_with(','));
+
+    // SVG chart
+    let path = std::env::temp_dir().join("dted2_cli_profile.svg");
+    let output = Command::new(env!("CARGO_BIN_EXE_dted2"))
+        .args(["profile", "--svg", path.to_str().unwrap()])
+        .args(["tests/test_data.dt2", "42.1,14.9", "42.9,15.9"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let svg = std::fs::read_to_string(&path).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("<polyline"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_crop() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::primitives::AxisElement;
+
+    let data = build_test_data("crop", |lat_idx, lon_idx| {
+        (lat_idx * 3 + lon_idx * 7) as i16
+    });
+    let (min, max) = (AxisElement::new(-9.91, 20.2), AxisElement::new(-9.5, 20.25));
+    let crop = data.crop(min, max).unwrap();
+    // posts 11..=60 by 24..=30, snapped inward
+    assert_eq!(crop.metadata.count, AxisElement::new(50, 7));
+    assert!((crop.min.lat - (-10.0 + 11.0 / 120.0)).abs() < 1e-9);
+    assert!((crop.max.lon - 20.25).abs() < 1e-9);
+    for (lat_idx, lon_idx) in [(0, 0), (49, 6), (17, 3)] {
+        assert_eq!(
+            crop.data.get(lat_idx, lon_idx),
+            data.data.get(lat_idx + 11, lon_idx + 24)
+        );
+    }
+    let dsi = crop.metadata.dsi_record.as_ref().unwrap();
+    assert_eq!(dsi.count, crop.metadata.count);
+    assert_eq!(dsi.origin, crop.metadata.origin_angle);
+    assert!(crop.data.validate_checksums().is_ok());
+
+    // a written cell, read back
+    let path = std::env::temp_dir().join("dted2_crop.dt0");
+    let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    let cropped = file.crop(
+        min - AxisElement::new(20.0, 198.0),
+        max - AxisElement::new(20.0, 198.0),
+    );
+    std::fs::write(&path, cropped.unwrap().to_bytes().unwrap()).unwrap();
+    let read = DTEDData::read(path.to_str().unwrap()).unwrap();
+    assert_eq!(read.metadata.count, AxisElement::new(50, 7));
+    assert_eq!(read.data.get(0, 0), file.data.get(11, 24));
+    std::fs::remove_file(path).unwrap();
+
+    // outside of the cell
+    assert!(data
+        .crop(AxisElement::new(0.0, 0.0), AxisElement::new(1.0, 1.0))
+        .is_none());
+}
+
+#[test]
+fn test_crop_single_line() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::primitives::AxisElement;
+
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    let expected = data.get_elevation(42.5, 15.25);
+    assert!(expected.is_some());
+    // a single post
+    let point = AxisElement::new(42.5, 15.25);
+    let crop = data.crop(point, point).unwrap();
+    assert_eq!(crop.metadata.count, AxisElement::new(1, 1));
+    assert_eq!(crop.get_elevation(42.5, 15.25), expected);
+    // a single latitude line
+    let crop = data
+        .crop(AxisElement::new(42.5, 15.2), AxisElement::new(42.5, 15.3))
+        .unwrap();
+    assert_eq!(crop.metadata.count.lat, 1);
+    assert_eq!(crop.get_elevation(42.5, 15.25), expected);
+    assert!(crop.get_elevation(42.5, 15.27).is_some());
+
+    // a single post, written and read back
+    let file = RawDTEDFile::read("tests/test_data.dt2").unwrap();
+    let bytes = file.crop(point, point).unwrap().to_bytes().unwrap();
+    let read = DTEDData::from_bytes(&bytes).unwrap();
+    assert_eq!(read.get_elevation(42.5, 15.25), expected);
+}
+
+#[test]
+fn test_mosaic() {
+    use dted2::primitives::AxisElement;
+    use dted2::DTEDDataset;
+
+    // two adjacent cells, the eastern one disagreeing on the shared edge
+    let west = write_test_cell("mosaic_west", (-10, 20), |lat, lon| (lat + 2 * lon) as i16);
+    let east = write_test_cell("mosaic_east", (-10, 21), |lat, lon| {
+        (lat + 2 * (lon + 120)) as i16 + i16::from(lon == 0)
+    });
+    let dataset = DTEDDataset::read(&[&west, &east]).unwrap();
+    let interval = AxisElement::new(1.0 / 120.0, 1.0 / 120.0);
+
+    // the posts of both cells, once
+    let mosaic = dataset.mosaic(
+        AxisElement::new(-10.0, 20.0),
+        AxisElement::new(-9.0, 22.0),
+        interval,
+    );
+    assert_eq!(mosaic.count, AxisElement::new(121, 241));
+    for (lat_idx, lon_idx) in [(0, 0), (5, 119), (5, 120), (5, 121), (120, 240)] {
+        assert_eq!(
+            mosaic.get(lat_idx, lon_idx),
+            Some(&Some((lat_idx + 2 * lon_idx) as f64))
+        );
+    }
+
+    // coarser, beyond the dataset
+    let mosaic = dataset.mosaic(
+        AxisElement::new(-10.5, 20.0),
+        AxisElement::new(-9.0, 22.0),
+        AxisElement::new(0.25, 0.5),
+    );
+    assert_eq!(mosaic.count, AxisElement::new(7, 5));
+    assert_eq!(mosaic.get(0, 0), Some(&None));
+    assert_eq!(mosaic.get(2, 2), Some(&Some(240.0)));
+
+    for path in [west, east] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_downsample() {
+    use dted2::primitives::AxisElement;
+    use dted2::resample::Aggregation;
+    use dted2::DTEDLevel;
+
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    // posts of level 2 within half a level 1 interval of a level 1 post
+    let window = |lat_idx: usize, lon_idx: usize| -> Vec<i16> {
+        (lon_idx * 3 - 1..=lon_idx * 3 + 1)
+            .flat_map(|lon| (lat_idx * 3 - 1..=lat_idx * 3 + 1).map(move |lat| (lat, lon)))
+            .map(|(lat, lon)| data.data.get(lat, lon).unwrap())
+            .collect()
+    };
+
+    let mean = data
+        .downsample(DTEDLevel::Level1, Aggregation::Mean)
+        .unwrap();
+    let max = data
+        .downsample(DTEDLevel::Level1, Aggregation::Max)
+        .unwrap();
+    for (lat_idx, lon_idx) in [(1, 1), (400, 700), (1199, 1199)] {
+        let posts = window(lat_idx, lon_idx);
+        let expected = (posts.iter().map(|elev| f64::from(*elev)).sum::<f64>() / 9.0).round();
+        assert_eq!(mean.data.get(lat_idx, lon_idx), Some(expected as i16));
+        assert_eq!(max.data.get(lat_idx, lon_idx), posts.iter().max().copied());
+    }
+    assert!(mean.data.validate_checksums().is_ok());
+
+    // written as a level 1 cell, with the metadata of the level 2 cell
+    let path = std::env::temp_dir().join("dted2_downsample.dt1");
+    std::fs::write(&path, max.to_bytes().unwrap()).unwrap();
+    let read = DTEDData::read(path.to_str().unwrap()).unwrap();
+    assert_eq!(read.metadata.level(), Some(DTEDLevel::Level1));
+    assert_eq!(read.metadata.origin, data.metadata.origin);
+    assert_eq!(read.metadata.interval_secs, AxisElement::new(3.0, 3.0));
+    assert_eq!(read.max, data.max);
+    assert_eq!(read.metadata.accuracy, data.metadata.accuracy);
+    std::fs::remove_file(path).unwrap();
+
+    // only complete cells
+    let crop = data
+        .crop(AxisElement::new(42.0, 15.0), AxisElement::new(42.5, 15.5))
+        .unwrap();
+    assert!(crop
+        .downsample(DTEDLevel::Level0, Aggregation::Nearest)
+        .is_err());
+}
+
+#[test]
+fn test_resample() {
+    use dted2::interpolation::Interpolation;
+    use dted2::primitives::AxisElement;
+
+    // a plane, which bilinear interpolation reproduces
+    let data = build_test_data("resample", |lat_idx, lon_idx| {
+        (3 * lat_idx + 2 * lon_idx) as i16
+    });
+
+    // 3 times finer
+    let spacing = AxisElement::new(10.0 / 3600.0, 10.0 / 3600.0);
+    let grid = data.resample(spacing, Interpolation::Bilinear).unwrap();
+    assert_eq!(grid.count, AxisElement::new(361, 361));
+    assert_eq!(grid.origin, data.min);
+    for (lat_idx, lon_idx) in [(0, 0), (1, 2), (200, 301), (360, 360)] {
+        let expected = lat_idx as f64 + 2.0 * lon_idx as f64 / 3.0;
+        let value = grid.get(lat_idx, lon_idx).unwrap().unwrap();
+        assert!((value - expected).abs() < 1e-9);
+    }
+
+    // coarser, not lining up with the north east corner
+    let spacing = AxisElement::new(0.3, 0.45);
+    let grid = data.resample(spacing, Interpolation::Bicubic).unwrap();
+    assert_eq!(grid.count, AxisElement::new(4, 3));
+    assert_eq!(grid.position(3, 2), AxisElement::new(-10.0 + 0.9, 20.9));
+    let value = grid.get(3, 2).unwrap().unwrap();
+    assert!((value - (3.0 * 108.0 + 2.0 * 108.0)).abs() < 1e-6);
+
+    // invalid spacings
+    for spacing in [0.0, -0.1, f64::NAN, f64::INFINITY, 1e-300] {
+        let result = data.resample(AxisElement::new(0.1, spacing), Interpolation::Bilinear);
+        assert!(matches!(result, Err(dted2::Error::InvalidInput(_))));
+    }
+}
+
+#[cfg(feature = "proj")]
+#[test]
+fn test_utm() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+    use dted2::interpolation::Interpolation;
+    use dted2::utm::UtmZone;
+    use dted2::ElevationSource;
+
+    // the meridian arc to 45N, scaled on the central meridian
+    let zone = UtmZone::of(45.0, 3.0);
+    assert_eq!(zone.epsg(), 32631);
+    let (easting, northing) = zone.project(45.0, 3.0);
+    assert!((easting - 500_000.0).abs() < 1e-6);
+    assert!((northing - 4_982_950.400).abs() < 1e-3);
+    // round trip 3 degrees off the central meridian
+    let (easting, northing) = zone.project(-12.5, 6.0);
+    let (lat, lon) = zone.unproject(easting, northing);
+    assert!((lat + 12.5).abs() < 1e-9 && (lon - 6.0).abs() < 1e-9);
+
+    // voids and the outside of the cell hold no data
+    let data = build_test_data("utm", |lat_idx, lon_idx| {
+        match lat_idx < 60 && lon_idx < 60 {
+            true => DTED_VOID_ELEVATION,
+            false => 100,
+        }
+    });
+    let raster = data.to_utm(500.0, Interpolation::Nearest);
+    assert_eq!(
+        raster.zone,
+        UtmZone {
+            zone: 34,
+            north: false
+        }
+    );
+    assert_eq!(raster.values.len(), raster.width * raster.height);
+    assert_eq!(raster.get(0, 0), Some(&None));
+    assert_eq!(raster.get(raster.height, 0), None);
+    let mut voids = 0;
+    for row in 0..raster.height {
+        for col in 0..raster.width {
+            let (easting, northing) = raster.center(row, col);
+            let (lat, lon) = raster.zone.unproject(easting, northing);
+            let expected = data.elevation_at_with(lat, lon, Interpolation::Nearest);
+            assert_eq!(*raster.get(row, col).unwrap(), expected);
+            voids += usize::from(expected.is_none());
+        }
+    }
+    assert!(voids > 0);
+    assert!(raster.values.contains(&Some(100.0)));
+}
+
+#[cfg(feature = "egm96")]
+#[test]
+fn test_geoid() {
+    use dted2::geoid::Geoid;
+    use dted2::ElevationSource;
+
+    // an EGM96 grid of the undulation (in cm) lat + lon / 5 along each row
+    let bytes: Vec<u8> = (0..721)
+        .flat_map(|row| (0..1440).map(move |col| (row, col)))
+        .flat_map(|(row, col)| {
+            let lat = 90.0 - row as f64 * 0.25;
+            let lon = col as f64 * 0.25;
+            ((100.0 * (lat + lon / 5.0)).round() as i16).to_be_bytes()
+        })
+        .collect();
+    let geoid = Geoid::from_bytes(&bytes).unwrap();
+    assert!((geoid.undulation(42.1, 15.05) - 45.11).abs() < 1e-9);
+    assert!((geoid.undulation(90.0, 0.0) - 90.0).abs() < 1e-9);
+    assert!((geoid.undulation(-90.0, 0.0) + 90.0).abs() < 1e-9);
+    // wrapping around the antimeridian, from 359.75E to 0E
+    let expected = -10.0 + (359.75 / 5.0) / 2.0;
+    assert!((geoid.undulation(-10.0, -0.125) - expected).abs() < 1e-9);
+    assert!(Geoid::from_bytes(&bytes[2..]).is_err());
+
+    // round trip of the heights
+    let height = geoid.to_ellipsoidal(42.1, 15.05, 100.0);
+    assert!((height - 145.11).abs() < 1e-9);
+    assert!((geoid.to_orthometric(42.1, 15.05, height) - 100.0).abs() < 1e-9);
+
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    let msl = data.elevation_at_interpolated(42.1, 15.05).unwrap();
+    let height = data.elevation_at_ellipsoidal(42.1, 15.05, &geoid).unwrap();
+    assert!((height - msl - 45.11).abs() < 1e-9);
+    assert_eq!(data.elevation_at_ellipsoidal(0.0, 0.0, &geoid), None);
+}
+
+#[test]
+fn test_elevation_units() {
+    use dted2::interpolation::Interpolation;
+    use dted2::primitives::{Elevation, ElevationUnit};
+    use dted2::{DTEDDataset, ElevationSource};
+
+    let data = build_test_data("units", |_, _| 1000);
+    let elevation = data
+        .elevation_at_typed(-9.5, 20.5, Interpolation::Bilinear)
+        .unwrap();
+    assert_eq!(elevation, Elevation::from(1000_i16));
+    assert!((elevation.feet() - 3280.839895).abs() < 1e-6);
+    assert_eq!(Elevation::from_feet(elevation.feet()).meters(), 1000.0);
+    assert_eq!(format!("{}", elevation), "1000 m");
+    assert!(data
+        .elevation_at_typed(0.0, 0.0, Interpolation::Bilinear)
+        .is_none());
+
+    // plain values
+    for unit in [ElevationUnit::Meters, ElevationUnit::Feet] {
+        assert!((unit.to_meters(unit.from_meters(123.4)) - 123.4).abs() < 1e-12);
+        assert_eq!(Elevation::new(50.0, unit).to(unit), 50.0);
+    }
+    assert_eq!(ElevationUnit::Feet.symbol(), "ft");
+
+    let dataset = DTEDDataset::read(&["tests/test_data.dt2"]).unwrap();
+    assert_eq!(
+        dataset
+            .elevation_at_typed(42.5, 15.5, Interpolation::Nearest)
+            .map(Elevation::meters),
+        dataset.elevation_at(42.5, 15.5).map(f64::from)
+    );
+}
+
+#[test]
+fn test_statistics() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // a column of voids, then elevations from 0 to 119 along each line
+    let data = build_test_data("statistics", |lat_idx, lon_idx| match lon_idx {
+        0 => DTED_VOID_ELEVATION,
+        _ => lat_idx.min(119) as i16,
+    });
+    let stats = data.statistics();
+    assert_eq!(stats.void_count, 121);
+    assert_eq!(stats.count, 120 * 121);
+    assert_eq!((stats.min, stats.max), (Some(0), Some(119)));
+    let values: Vec<f64> = (0..121)
+        .map(|lat_idx: usize| lat_idx.min(119) as f64)
+        .collect();
+    let mean = values.iter().sum::<f64>() / 121.0;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 121.0;
+    assert!((stats.mean.unwrap() - mean).abs() < 1e-9);
+    assert!((stats.std_dev.unwrap() - variance.sqrt()).abs() < 1e-9);
+
+    // all void
+    let data = build_test_data("statistics_void", |_, _| DTED_VOID_ELEVATION);
+    let stats = data.statistics();
+    assert_eq!((stats.count, stats.void_count), (0, 121 * 121));
+    assert_eq!((stats.min, stats.mean, stats.std_dev), (None, None, None));
+
+    // large cells, split across tasks with the rayon feature
+    let data = DTEDData::read("tests/test_data.dt2").unwrap();
+    let stats = data.statistics();
+    let elevations: Vec<i16> = data.data.columns().flatten().copied().collect();
+    assert_eq!(stats.count + stats.void_count, 3601 * 3601);
+    assert_eq!(stats.max, elevations.iter().copied().max());
+    let mean =
+        elevations.iter().map(|&elev| f64::from(elev)).sum::<f64>() / elevations.len() as f64;
+    assert!((stats.mean.unwrap() - mean).abs() < 1e-6);
+}
+
+#[test]
+fn test_histogram_percentile() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    // elevations from -20 to 99 along each line, then a void
+    let data = build_test_data("histogram", |lat_idx, _| match lat_idx {
+        120 => DTED_VOID_ELEVATION,
+        _ => lat_idx as i16 - 20,
+    });
+    let histogram = data.histogram(25);
+    assert_eq!(histogram.start, -25);
+    assert_eq!(
+        histogram.counts,
+        vec![20 * 121, 25 * 121, 25 * 121, 25 * 121, 25 * 121]
+    );
+    assert_eq!(histogram.total(), 120 * 121);
+    let (range, count) = histogram.bins().last().unwrap();
+    assert_eq!((range, count), (75..100, 25 * 121));
+
+    // every elevation appears 121 times
+    assert_eq!(data.percentile(0.0), Some(-20.0));
+    assert_eq!(data.percentile(100.0), Some(99.0));
+    assert_eq!(data.percentile(50.0), Some(39.5));
+    assert_eq!(data.percentile(-1.0), None);
+    assert_eq!(data.percentile(f64::NAN), None);
+    assert_eq!(
+        data.percentiles(&[0.0, 100.0]),
+        vec![Some(-20.0), Some(99.0)]
+    );
+
+    let data = build_test_data("histogram_void", |_, _| DTED_VOID_ELEVATION);
+    assert!(data.histogram(10).counts.is_empty());
+    assert_eq!(data.percentile(50.0), None);
+}
+
+#[cfg(feature = "geo-types")]
+#[test]
+fn test_statistics_in_polygon() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+    use geo_types::polygon;
+
+    // elevations of the latitude index, the west half of the lines void
+    let data = build_test_data("zonal", |lat_idx, lon_idx| match lon_idx < 60 {
+        true => DTED_VOID_ELEVATION,
+        false => lat_idx as i16,
+    });
+    // a triangle from the south west corner, on the posts
+    let polygon = polygon![
+        (x: 20.0, y: -10.0),
+        (x: 21.0, y: -10.0),
+        (x: 20.0, y: -9.0),
+    ];
+    let mask = data.polygon_mask(&polygon);
+    // the boundary is within, but for the east vertex, the longitude lines
+    // crossing the edges half-open
+    for lon_idx in 0..121 {
+        for lat_idx in 0..121 {
+            let within = lat_idx + lon_idx <= 120 && lon_idx < 120;
+            assert_eq!(mask[lon_idx * 121 + lat_idx], within);
+        }
+    }
+    let stats = data.statistics_in_polygon(&polygon);
+    let inside = 121 * 122 / 2 - 1;
+    let voids: usize = (0..60).map(|lon_idx| 121 - lon_idx).sum();
+    assert_eq!((stats.count, stats.void_count), (inside - voids, voids));
+    assert_eq!((stats.min, stats.max), (Some(0), Some(60)));
+    assert_eq!(stats.void_fraction(), voids as f64 / inside as f64);
+
+    // outside of the cell
+    let polygon = polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 0.0, y: 1.0)];
+    let stats = data.statistics_in_polygon(&polygon);
+    assert_eq!((stats.count, stats.void_count, stats.mean), (0, 0, None));
+    assert_eq!(stats.void_fraction(), 0.0);
+}
+
+#[test]
+fn test_diff() {
+    use dted2::dted::DTED_VOID_ELEVATION;
+
+    let data = build_test_data("diff_a", |lat_idx, lon_idx| (lat_idx + lon_idx) as i16);
+    // a new edition, raised by 3 m in a 10 x 10 block, with a void and a
+    // filled void
+    let other = build_test_data("diff_b", |lat_idx, lon_idx| match (lat_idx, lon_idx) {
+        (0, 0) => DTED_VOID_ELEVATION,
+        (lat, lon) if lat < 10 && lon < 10 => (lat + lon) as i16 + 3,
+        (lat, lon) => (lat + lon) as i16,
+    });
+    let diff = data.diff(&other).unwrap();
+    assert!(!diff.is_identical());
+    assert_eq!(diff.changed_count, 100);
+    assert_eq!(diff.void_changed_count, 1);
+    assert_eq!(diff.compared_count, 121 * 121 - 1);
+    assert_eq!(diff.max_abs_diff, Some(3));
+    let rmse = (99.0 * 9.0 / (121.0 * 121.0 - 1.0_f64)).sqrt();
+    assert!((diff.rmse.unwrap() - rmse).abs() < 1e-12);
+    assert_eq!(diff.differences.get(0, 0), Some(&None));
+    assert_eq!(diff.differences.get(5, 9), Some(&Some(3)));
+    assert_eq!(diff.differences.get(50, 50), Some(&Some(0)));
+    // the other way around
+    let back = other.diff(&data).unwrap();
+    assert_eq!(back.differences.get(5, 9), Some(&Some(-3)));
+    assert_eq!(back.rmse, diff.rmse);
+
+    // incompatible grids
+    let other = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let err = data.diff(&other).unwrap_err();
+    assert!(err.to_string().contains("origins"));
+}
+
+#[test]
+fn test_edit() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::Post;
+
+    let path = write_test_file("edit", |lat_idx, lon_idx| (lat_idx + lon_idx) as i16);
+    let mut file = RawDTEDFile::read(&path).unwrap();
+    assert_eq!(file.set_elevation(3, 4, 500), Some(Post::Elevation(7)));
+    assert_eq!(file.set_post(5, 6, Post::Void), Some(Post::Elevation(11)));
+    assert_eq!(file.set_elevation(0, 121, 0), None);
+    // clamp the cell to 100 m
+    file.apply(|post| match post {
+        Post::Elevation(elev) => Post::Elevation(elev.min(100)),
+        Post::Void => Post::Void,
+    });
+    file.write_dted(&path).unwrap();
+
+    // read back, its checksums validated
+    let data = DTEDData::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(data.data.get(3, 4), Some(100));
+    assert_eq!(data.data.post(5, 6), Some(Post::Void));
+    assert_eq!(data.data.get(10, 20), Some(30));
+    assert_eq!(data.data.get(120, 120), Some(100));
+
+    // editing the grid alone leaves the checksums stale
+    let mut data = data;
+    data.data.set_elevation(0, 0, 1);
+    assert!(data.data.validate_checksums().is_err());
+    assert_eq!(data.data.recompute_checksums(), 1);
+}
+
+#[test]
+fn test_patch() {
+    use dted2::dted::{RawDTEDFile, DTED_VOID_ELEVATION};
+    use dted2::interpolation::Interpolation;
+    use dted2::primitives::AxisElement;
+    use dted2::raster::Raster;
+    use dted2::DTEDDataset;
+
+    let mut data = build_test_data("patch", |lat_idx, lon_idx| match lat_idx < 10 {
+        true => DTED_VOID_ELEVATION,
+        false => 1000 + lon_idx as i16,
+    });
+    // a plane at half the spacing, resampled onto the posts of the cell
+    let raster = Raster::from_fn(
+        AxisElement::new(-10.0, 20.0),
+        AxisElement::new(0.5 / 120.0, 0.5 / 120.0),
+        AxisElement::new(241, 241),
+        |lat_idx, lon_idx| Some((lat_idx + 2 * lon_idx) as f64 / 2.0),
+    );
+    let filled = data.fill_voids_from(&raster, Interpolation::Bilinear);
+    assert_eq!(filled, 10 * 121);
+    assert_eq!(data.data.get(3, 5), Some(13));
+    assert_eq!(data.data.get(10, 5), Some(1005));
+    assert!(data.data.validate_checksums().is_ok());
+
+    let (min, max) = (AxisElement::new(-9.5, 20.5), AxisElement::new(-9.4, 21.0));
+    let patched = data.patch_from(&raster, min, max, Interpolation::Nearest);
+    assert_eq!(patched, 13 * 61);
+    assert_eq!(data.data.get(60, 60), Some(180));
+    assert_eq!(data.data.get(59, 60), Some(1060));
+    // nothing within the box, or no data in the source
+    assert_eq!(
+        data.patch_from(
+            &raster,
+            AxisElement::new(0.0, 0.0),
+            AxisElement::new(1.0, 1.0),
+            Interpolation::Nearest
+        ),
+        0
+    );
+    let empty = Raster::from_fn(min, max - min, AxisElement::new(2, 2), |_, _| None);
+    assert_eq!(data.patch_from(&empty, min, max, Interpolation::Nearest), 0);
+
+    // a file, patched from a dataset
+    let path = write_test_file("patch_file", |_, _| DTED_VOID_ELEVATION);
+    let mut file = RawDTEDFile::read(&path).unwrap();
+    let source = write_test_file("patch_source", |lat_idx, lon_idx| {
+        (lat_idx * lon_idx) as i16
+    });
+    let dataset = DTEDDataset::read(&[&source]).unwrap();
+    assert_eq!(
+        file.fill_voids_from(&dataset, Interpolation::Bicubic),
+        121 * 121
+    );
+    assert_eq!(file.data.get(7, 9), Some(63));
+    file.write_dted(&path).unwrap();
+    assert!(DTEDData::read(&path).is_ok());
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&source).unwrap();
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_gzip() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::lazy::LazyDTEDData;
+    use dted2::stream::DTEDStreamReader;
+    use dted2::ElevationSource;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    let path = write_test_file("gzip", |lat_idx, lon_idx| {
+        lat_idx as i16 * 3 - lon_idx as i16
+    });
+    let data = DTEDData::read(&path).unwrap();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&std::fs::read(&path).unwrap()).unwrap();
+    let compressed = encoder.finish().unwrap();
+    let gz_path = format!("{}.gz", path);
+    std::fs::write(&gz_path, &compressed).unwrap();
+
+    // read transparently from the compressed file
+    let read = DTEDData::read(&gz_path).unwrap();
+    assert!(read.data == data.data);
+    assert_eq!(read.metadata.filename, gz_path);
+    assert!(RawDTEDFile::read(&gz_path).unwrap().data == data.data);
+    assert_eq!(
+        DTEDData::read_header(&gz_path).unwrap().count,
+        data.metadata.count
+    );
+    assert_eq!(
+        DTEDData::read_metadata(&gz_path).unwrap().dsi_record,
+        data.metadata.dsi_record
+    );
+    let lazy = LazyDTEDData::read(&gz_path).unwrap();
+    assert_eq!(lazy.elevation(30, 40), data.elevation(30, 40));
+
+    // and from a compressed stream, record by record
+    let streamed = DTEDData::read_from(compressed.as_slice()).unwrap();
+    assert!(streamed.data == data.data);
+    let mut reader = DTEDStreamReader::new(std::fs::File::open(&gz_path).unwrap()).unwrap();
+    assert_eq!(reader.metadata().count, data.metadata.count);
+    assert_eq!(reader.by_ref().count(), 121);
+
+    // a truncated compressed file is an error
+    std::fs::write(&gz_path, &compressed[..compressed.len() / 2]).unwrap();
+    assert!(DTEDData::read(&gz_path).is_err());
+    assert!(DTEDData::read_from(&compressed[..compressed.len() / 2]).is_err());
+    std::fs::remove_file(&gz_path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn test_zip() {
+    use dted2::{DTEDDataset, ElevationSource};
+    use std::io::Write;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+    use zip::CompressionMethod;
+
+    let elev = |lat_idx: usize, lon_idx: usize| (lat_idx * 2 + lon_idx) as i16;
+    let south = write_test_cell("zip_south", (-10, 20), elev);
+    let north = write_test_cell("zip_north", (-9, 20), |lat_idx, lon_idx| {
+        elev(lat_idx, lon_idx) + 500
+    });
+    let path = std::env::temp_dir().join("dted2_test_zip.zip");
+    let mut writer = ZipWriter::new(std::fs::File::create(&path).unwrap());
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    writer.add_directory("DTED/E020/", stored).unwrap();
+    for (name, cell, options) in [
+        ("DTED/E020/S10.DT0", &south, deflated),
+        ("DTED/E020/S09.DT0", &north, stored),
+        ("DTED/README.TXT", &south, stored),
+    ] {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(&std::fs::read(cell).unwrap()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    // indexed by entry name, with the entries extracted on demand
+    let dataset = DTEDDataset::from_zip(path.to_str().unwrap()).unwrap();
+    assert_eq!(dataset.len(), 2);
+    let names: Vec<&str> = dataset
+        .cells()
+        .iter()
+        .map(|cell| cell.metadata().filename.as_str())
+        .collect();
+    assert_eq!(names, ["DTED/E020/S09.DT0", "DTED/E020/S10.DT0"]);
+    assert!(dataset.cells().iter().all(|cell| !cell.is_extracted()));
+    assert_eq!(dataset.elevation_at(-10.0, 20.0), Some(0));
+    assert!(!dataset.cells()[0].is_extracted());
+    assert!(dataset.cells()[1].is_extracted());
+    let south_data = DTEDData::read(&south).unwrap();
+    let north_data = DTEDData::read(&north).unwrap();
+    for (lat, lon) in [(-9.7, 20.3), (-8.2, 20.9), (-9.0, 20.5)] {
+        let expected = match lat < -9.0 {
+            true => south_data.elevation_at(lat, lon),
+            false => north_data.elevation_at(lat, lon),
+        };
+        assert_eq!(dataset.elevation_at(lat, lon), expected);
+    }
+    assert!(dataset.cells()[0].is_extracted());
+
+    // an entry declaring a bogus size (in its central directory ZIP64 extra
+    // field), only read up to its cell
+    let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .start_file("DTED/E020/S10.DT0", stored.large_file(true))
+        .unwrap();
+    writer.write_all(&std::fs::read(&south).unwrap()).unwrap();
+    let mut archive = writer.finish().unwrap().into_inner();
+    let central = archive.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+    let extra = central
+        + 46
+        + usize::from(u16::from_le_bytes([
+            archive[central + 28],
+            archive[central + 29],
+        ]));
+    assert_eq!(archive[extra..extra + 2], [1, 0]);
+    archive[extra + 4..extra + 12].copy_from_slice(&u64::MAX.to_le_bytes());
+    let dataset = DTEDDataset::from_zip_reader(std::io::Cursor::new(archive)).unwrap();
+    assert_eq!(
+        dataset.elevation_at(-9.7, 20.3),
+        south_data.elevation_at(-9.7, 20.3)
+    );
+
+    assert!(DTEDDataset::from_zip(&south).is_err());
+    assert!(DTEDDataset::from_zip("tests/missing.zip").is_err());
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&south).unwrap();
+    std::fs::remove_file(&north).unwrap();
+}
+
+/// Serves a file over HTTP on a local port, answering range requests with
+/// `206 Partial Content` if `ranges`, and with the whole file otherwise,
+/// returning the URL of the file
+#[cfg(feature = "http")]
+fn serve_http(content: Vec<u8>, ranges: bool) -> String {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/cell.dt0", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut range = None;
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(bytes) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                    let (start, end) = bytes.split_once('-').unwrap();
+                    range = Some((
+                        start.parse::<usize>().unwrap(),
+                        end.parse::<usize>().unwrap(),
+                    ));
+                }
+            }
+            let response = match range.filter(|_| ranges) {
+                Some((start, _)) if start >= content.len() => {
+                    let header = format!(
+                        "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        content.len()
+                    );
+                    header.into_bytes()
+                }
+                Some((start, end)) => {
+                    let end = end.min(content.len() - 1);
+                    let mut response = format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        start,
+                        end,
+                        content.len(),
+                        end - start + 1
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(&content[start..=end]);
+                    response
+                }
+                None => {
+                    let mut response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        content.len()
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(&content);
+                    response
+                }
+            };
+            let _ = stream.write_all(&response);
+        }
+    });
+    url
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_http_range_reader() {
+    use dted2::http::{HttpRangeReader, HTTP_READ_AHEAD};
+    use dted2::reader::DTEDReader;
+    use dted2::ElevationSource;
+    use std::io::{Read, Seek, SeekFrom};
+
+    let path = write_test_file("http", |lat_idx, lon_idx| {
+        lat_idx as i16 * 2 - lon_idx as i16
+    });
+    let content = std::fs::read(&path).unwrap();
+    let data = DTEDData::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let url = serve_http(content.clone(), true);
+
+    // the header records in a single request
+    let reader = DTEDReader::open_url(&url).unwrap();
+    assert_eq!(reader.metadata().count, data.metadata.count);
+    assert_eq!(reader.metadata().dsi_record, data.metadata.dsi_record);
+    for (lat_idx, lon_idx) in [(0, 0), (60, 60), (120, 120), (7, 99)] {
+        assert_eq!(
+            reader.read_post(lat_idx, lon_idx).unwrap(),
+            data.data.get(lat_idx, lon_idx)
+        );
+    }
+    assert_eq!(
+        reader.elevation_at_interpolated(-9.53, 20.71),
+        data.elevation_at_interpolated(-9.53, 20.71)
+    );
+    let window = reader.read_window(10..20, 30..33).unwrap();
+    assert_eq!(window[2][..], data.data.column(32).unwrap()[10..20]);
+    let remote = reader.into_inner();
+    assert_eq!(remote.url(), url);
+    assert!(remote.requests() < 20);
+
+    // reads within the last block fetched issue no request
+    let mut remote = HttpRangeReader::new(&url);
+    let mut bytes = [0; 4];
+    remote.read_exact(&mut bytes).unwrap();
+    assert_eq!(&bytes, b"UHL1");
+    remote
+        .seek(SeekFrom::Start(HTTP_READ_AHEAD as u64 - 2))
+        .unwrap();
+    remote.read_exact(&mut bytes[..2]).unwrap();
+    assert_eq!(remote.requests(), 1);
+    assert_eq!(
+        remote.seek(SeekFrom::End(-2)).unwrap(),
+        content.len() as u64 - 2
+    );
+    let mut tail = Vec::new();
+    remote.read_to_end(&mut tail).unwrap();
+    assert_eq!(tail, content[content.len() - 2..]);
+    assert!(remote
+        .seek(SeekFrom::Current(-(content.len() as i64) - 1))
+        .is_err());
+
+    // a server ignoring range requests is an error
+    let url = serve_http(content, false);
+    match DTEDReader::open_url(&url) {
+        Err(dted2::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        _ => panic!("range requests are not supported"),
+    }
+}
+
+#[cfg(feature = "object_store")]
+#[test]
+fn test_object_store() {
+    use dted2::{DTEDDataset, ElevationSource};
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::ObjectStore;
+    use std::sync::Arc;
+
+    let elev = |lat_idx: usize, lon_idx: usize| (lat_idx + lon_idx * 3) as i16;
+    let west = write_test_cell("object_store_west", (-10, 20), elev);
+    let east = write_test_cell("object_store_east", (-10, 21), |lat_idx, lon_idx| {
+        elev(lat_idx, lon_idx) + 1000
+    });
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let store = Arc::new(InMemory::new());
+    let objects = [
+        ("archive/dted0/e021/s10.dt0", &east),
+        ("archive/dted0/e020/s10.dt0", &west),
+        ("archive/dted0/readme.txt", &west),
+        ("other/dted0/e020/s10.dt0", &west),
+    ];
+    runtime.block_on(async {
+        for (location, path) in objects {
+            let content = std::fs::read(path).unwrap();
+            store
+                .put(&Path::from(location), content.into())
+                .await
+                .unwrap();
+        }
+    });
+
+    // listed under the prefix, sorted by location
+    let prefix = Path::from("archive");
+    let dataset = runtime
+        .block_on(DTEDDataset::from_object_store(store.clone(), Some(&prefix)))
+        .unwrap();
+    let locations: Vec<&str> = dataset
+        .cells()
+        .iter()
+        .map(|cell| cell.metadata().filename.as_str())
+        .collect();
+    assert_eq!(
+        locations,
+        ["archive/dted0/e020/s10.dt0", "archive/dted0/e021/s10.dt0"]
+    );
+    assert!(dataset.cells().iter().all(|cell| !cell.is_fetched()));
+
+    // fetched on the first query, outside of the runtime
+    let west_data = DTEDData::read(&west).unwrap();
+    let east_data = DTEDData::read(&east).unwrap();
+    assert_eq!(
+        dataset.elevation_at(-9.6, 20.3),
+        west_data.elevation_at(-9.6, 20.3)
+    );
+    assert!(dataset.cells()[0].is_fetched());
+    assert!(!dataset.cells()[1].is_fetched());
+
+    // or prefetched, within it
+    runtime.block_on(dataset.fetch_all()).unwrap();
+    assert!(dataset.cells()[1].is_fetched());
+    assert_eq!(
+        dataset.elevation_at(-9.2, 21.7),
+        east_data.elevation_at(-9.2, 21.7)
+    );
+
+    // an object deleted once listed has no data
+    let dataset = runtime
+        .block_on(DTEDDataset::from_object_store(store.clone(), None))
+        .unwrap();
+    assert_eq!(dataset.len(), 3);
+    runtime
+        .block_on(store.delete(&Path::from("archive/dted0/e021/s10.dt0")))
+        .unwrap();
+    assert!(runtime.block_on(dataset.fetch_all()).is_err());
+    assert_eq!(dataset.elevation_at(-9.2, 21.7), None);
+    // outside of a runtime
+    assert!(futures::executor::block_on(DTEDDataset::from_object_store(store, None)).is_err());
+
+    std::fs::remove_file(&west).unwrap();
+    std::fs::remove_file(&east).unwrap();
+}
+
+#[test]
+fn test_from_bytes() {
+    use dted2::lazy::LazyDTEDData;
+    use dted2::{ElevationSource, ParseOptions};
+
+    let path = write_test_file("from_bytes", |lat_idx, lon_idx| {
+        (lat_idx * 7 % 31) as i16 - lon_idx as i16
+    });
+    let content = std::fs::read(&path).unwrap();
+    let data = DTEDData::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let parsed = DTEDData::from_bytes(&content).unwrap();
+    assert!(parsed.data == data.data);
+    assert_eq!(parsed.metadata.dsi_record, data.metadata.dsi_record);
+    assert_eq!(parsed.metadata.filename, "");
+    assert!(DTEDData::from_vec(content.clone()).unwrap().data == data.data);
+    let lazy = LazyDTEDData::from_vec(content.clone()).unwrap();
+    assert_eq!(lazy.elevation_at(-9.5, 20.5), data.elevation_at(-9.5, 20.5));
+
+    // checksums validated as when reading a file, unless disabled
+    let mut corrupt = content;
+    let last = corrupt.len() - 1;
+    corrupt[last] ^= 1;
+    assert!(matches!(
+        DTEDData::from_bytes(&corrupt),
+        Err(dted2::Error::BadChecksum { record: 120, .. })
+    ));
+    let options = ParseOptions {
+        verify_checksums: false,
+        ..ParseOptions::default()
+    };
+    assert!(DTEDData::from_bytes_with(&corrupt, &options).is_ok());
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi() {
+    use dted2::ffi::*;
+    use dted2::ElevationSource;
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+
+    let path = write_test_file("ffi", |lat_idx, lon_idx| {
+        (lat_idx * 3 % 17) as i16 + lon_idx as i16
+    });
+    let data = DTEDData::read(&path).unwrap();
+    let content = std::fs::read(&path).unwrap();
+    let c_path = CString::new(path.clone()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    unsafe {
+        // errors reported through NULL and the last error
+        assert!(dted2_open(ptr::null()).is_null());
+        assert!(!dted2_last_error().is_null());
+        let missing = CString::new("/nonexistent/n00_e000.dt0").unwrap();
+        assert!(dted2_open(missing.as_ptr()).is_null());
+        let message = CStr::from_ptr(dted2_last_error()).to_str().unwrap();
+        assert!(!message.is_empty());
+        assert!(dted2_open_bytes(content.as_ptr(), 10).is_null());
+        // the file has been removed
+        assert!(dted2_open(c_path.as_ptr()).is_null());
+
+        let handle = dted2_open_bytes(content.as_ptr(), content.len());
+        assert!(!handle.is_null());
+
+        let mut metadata = std::mem::MaybeUninit::<CDTEDMetadata>::uninit();
+        assert_eq!(dted2_metadata(handle, metadata.as_mut_ptr()), 1);
+        let metadata = metadata.assume_init();
+        assert_eq!(metadata.origin_lat, data.metadata.origin.lat);
+        assert_eq!(metadata.origin_lon, data.metadata.origin.lon);
+        assert_eq!(metadata.interval_lat, data.metadata.interval.lat);
+        assert_eq!(metadata.lat_count, 121);
+        assert_eq!(metadata.lon_count, 121);
+        assert_eq!(metadata.level, 0);
+        assert_eq!(dted2_metadata(handle, ptr::null_mut()), 0);
+
+        let mut elevation = 0i16;
+        assert_eq!(dted2_elevation_at(handle, -9.5, 20.5, &mut elevation), 1);
+        assert_eq!(Some(elevation), data.elevation_at(-9.5, 20.5));
+        assert_eq!(dted2_elevation_at(handle, 5.0, 20.5, &mut elevation), 0);
+        let mut interpolated = 0.0;
+        assert_eq!(
+            dted2_elevation_at_interpolated(handle, -9.51, 20.52, &mut interpolated),
+            1
+        );
+        assert_eq!(
+            Some(interpolated),
+            data.elevation_at_interpolated(-9.51, 20.52)
+        );
+        assert_eq!(
+            dted2_elevation_at_interpolated(ptr::null(), -9.5, 20.5, &mut interpolated),
+            0
+        );
+
+        dted2_free(handle);
+        dted2_free(ptr::null_mut());
+    }
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python() {
+    use dted2::ElevationSource;
+    use pyo3::prelude::*;
+    use pyo3::types::{PyDict, PyModule};
+
+    let path = write_test_file("python", |lat_idx, lon_idx| {
+        (lat_idx * 5 % 23) as i16 - lon_idx as i16
+    });
+    let data = DTEDData::read(&path).unwrap();
+
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "dted2").unwrap();
+        dted2::python::dted2(&module).unwrap();
+        let locals = PyDict::new(py);
+        locals.set_item("dted2", &module).unwrap();
+        locals.set_item("path", &path).unwrap();
+        let eval = |code: &str| {
+            py.eval(&std::ffi::CString::new(code).unwrap(), None, Some(&locals))
+                .unwrap()
+        };
+
+        let cell = eval("dted2.DtedFile(path)");
+        locals.set_item("cell", &cell).unwrap();
+        let elevation: Option<i16> = eval("cell.elevation_at(-9.5, 20.5)").extract().unwrap();
+        assert_eq!(elevation, data.elevation_at(-9.5, 20.5));
+        let interpolated: Option<f64> = eval("cell.elevation_at_interpolated(-9.51, 20.52)")
+            .extract()
+            .unwrap();
+        assert_eq!(interpolated, data.elevation_at_interpolated(-9.51, 20.52));
+        assert!(eval("cell.elevation_at(5.0, 20.5) is None")
+            .is_truthy()
+            .unwrap());
+        assert!(eval("cell.metadata['origin'] == (-10.0, 20.0)")
+            .is_truthy()
+            .unwrap());
+        assert!(eval("cell.metadata['count'] == (121, 121)")
+            .is_truthy()
+            .unwrap());
+        assert!(eval("cell.metadata['level'] == 0").is_truthy().unwrap());
+
+        let dataset = eval("dted2.DtedDataset([path])");
+        locals.set_item("dataset", &dataset).unwrap();
+        assert!(eval("len(dataset) == 1").is_truthy().unwrap());
+        assert!(eval("dataset.bounds == ((-10.0, 20.0), (-9.0, 21.0))")
+            .is_truthy()
+            .unwrap());
+        assert!(
+            eval("dataset.elevation_at(-9.5, 20.5) == cell.elevation_at(-9.5, 20.5)")
+                .is_truthy()
+                .unwrap()
+        );
+
+        // errors raised as dted2.DtedError
+        let error = py
+            .eval(
+                c"dted2.DtedFile('/nonexistent/n00_e000.dt0')",
+                None,
+                Some(&locals),
+            )
+            .unwrap_err();
+        assert!(error.is_instance(py, &module.getattr("DtedError").unwrap()));
+
+        // numpy arrays, when numpy is installed
+        if py.import("numpy").is_ok() {
+            assert!(eval("cell.to_numpy().shape == (121, 121)")
+                .is_truthy()
+                .unwrap());
+            let elevations: Vec<f64> = eval("cell.elevations([-9.5, 5.0], [20.5, 20.5]).tolist()")
+                .extract()
+                .unwrap();
+            assert_eq!(elevations[0], data.elevation_at(-9.5, 20.5).unwrap() as f64);
+            assert!(elevations[1].is_nan());
+        }
+    });
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "geo-types")]
+#[test]
+fn test_geo_types_interop() {
+    use dted2::dted::RawDTEDFile;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::DTEDDataset;
+    use geo_types::{coord, Coord, Point, Rect};
+
+    // lat/lons, either way
+    let pos = AxisElement::new(-29.5, -177.25);
+    let coord = Coord::from(pos);
+    assert_eq!(coord, coord! { x: -177.25, y: -29.5 });
+    assert_eq!(AxisElement::<f64>::from(coord), pos);
+    assert_eq!(AxisElement::<f64>::from(Point::from(pos)), pos);
+    let angles: AxisElement<Angle> = coord.into();
+    assert_eq!(angles.lat, Angle::new(29, 30, 0.0, true));
+    assert_eq!(angles.lon, Angle::new(177, 15, 0.0, true));
+    assert_eq!(Point::from(angles), Point::new(-177.25, -29.5));
+
+    // bounding boxes as rects, matching the min/max APIs
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(data.bounding_rect(), Rect::new(data.min, data.max));
+    assert_eq!(data.covered_rect(), Some(data.bounding_rect()));
+    // corners given in any order
+    let rect = Rect::new(
+        coord! { x: -177.0, y: -29.9 },
+        coord! { x: -177.5, y: -29.5 },
+    );
+    let (min, max) = (
+        AxisElement::new(-29.9, -177.5),
+        AxisElement::new(-29.5, -177.0),
+    );
+    let crop = data.crop_rect(&rect).unwrap();
+    assert!(crop.data == data.crop(min, max).unwrap().data);
+    let file = RawDTEDFile::read("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(file.crop_rect(&rect), file.crop(min, max));
+    let far = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 1.0, y: 1.0 });
+    assert!(data.crop_rect(&far).is_none());
+
+    let dataset =
+        DTEDDataset::read(&["tests/test_data.dt2", "tests/test_data_negative.dt2"]).unwrap();
+    let (min, max) = dataset.bounds().unwrap();
+    assert_eq!(dataset.bounding_rect(), Some(Rect::new(min, max)));
+    assert_eq!(dataset.cells_in_rect(&rect).len(), 1);
+    assert!(dataset.cells_in_rect(&far).is_empty());
+    assert_eq!(DTEDDataset::<DTEDData>::new().bounding_rect(), None);
+}
+
+#[test]
+fn test_bounds_contains() {
+    use dted2::lazy::LazyDTEDData;
+    use dted2::ElevationSource;
+
+    let path = write_test_file("bounds_contains", |lat_idx, lon_idx| {
+        (lat_idx + lon_idx) as i16
+    });
+    let data = DTEDData::read(&path).unwrap();
+    let lazy = LazyDTEDData::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // 120 intervals of 30" from the origin
+    assert_eq!(data.bounds(), (-10.0, 20.0, -9.0, 21.0));
+    assert_eq!(lazy.bounds(), data.bounds());
+    assert_eq!(data.metadata.bounds(), data.bounds());
+    let (south, west, north, east) = data.bounds();
+    assert_eq!((data.min.lat, data.min.lon), (south, west));
+    assert_eq!((data.max.lat, data.max.lon), (north, east));
+
+    // edges included, agreeing with the queries
+    for (lat, lon) in [
+        (south, west),
+        (north, east),
+        (south, east),
+        (-9.5, 20.5),
+        (south - 1e-9, west),
+        (north, east + 1e-9),
+        (-9.5, 19.0),
+        (f64::NAN, 20.5),
+    ] {
+        let contains = data.contains(lat, lon);
+        assert_eq!(
+            contains,
+            data.elevation_at(lat, lon).is_some(),
+            "{lat}, {lon}"
+        );
+        assert_eq!(contains, lazy.contains(lat, lon));
+    }
+    assert!(data.contains(north, east));
+    assert!(!data.contains(north + 1e-9, east));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_dsi_dates() {
+    use chrono::NaiveDate;
+    use dted2::dted::RawDTEDFile;
+
+    let dsi = RawDTEDFile::read("tests/test_data.dt2")
+        .unwrap()
+        .dsi_record
+        .unwrap();
+    assert_eq!(dsi.maintenance_date, Some(0));
+    assert_eq!(dsi.maintenance_naive_date(), None);
+    assert_eq!(dsi.match_merge_naive_date(), None);
+    assert_eq!(
+        dsi.product_spec_naive_date(),
+        NaiveDate::from_ymd_opt(2000, 5, 1)
+    );
+    assert_eq!(
+        dsi.compilation_naive_date(),
+        NaiveDate::from_ymd_opt(2000, 2, 1)
+    );
+
+    // blank dates
+    let dsi = RawDTEDFile::read("tests/test_data_negative.dt2")
+        .unwrap()
+        .dsi_record
+        .unwrap();
+    assert_eq!(dsi.compilation_date, None);
+    assert_eq!(dsi.compilation_naive_date(), None);
+}